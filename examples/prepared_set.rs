@@ -0,0 +1,44 @@
+//! Time repeated membership proofs against one set, re-hashing it per call
+//! (`prove_membership`) versus preparing it once (`PreparedSet`).
+//!
+//! `cargo run --release --example prepared_set [iterations]`
+
+use libzkp::proof::set_membership::{
+    prove_membership, prove_membership_prepared, verify_membership_prepared, PreparedSet,
+};
+use libzkp::utils::error_handling::ZkpError;
+use libzkp::utils::performance::Timer;
+use std::time::Duration;
+
+fn main() -> Result<(), ZkpError> {
+    let iterations: u32 = std::env::args()
+        .nth(1)
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(10)
+        .max(1);
+    let set: Vec<u64> = (1..=64).map(|i| i * 7).collect();
+
+    // Warm up the SNARK setup so neither path pays for key generation.
+    prove_membership(7, set.clone())?;
+
+    let mut timer = Timer::new();
+    for i in 0..iterations {
+        prove_membership(set[i as usize % set.len()], set.clone())?;
+    }
+    let unprepared = timer.elapsed();
+
+    timer.reset();
+    let prepared = PreparedSet::new(set.clone())?;
+    let mut last = Vec::new();
+    for i in 0..iterations {
+        last = prove_membership_prepared(set[i as usize % set.len()], &prepared)?;
+    }
+    let with_prepared = timer.elapsed();
+    assert!(verify_membership_prepared(last, &prepared));
+
+    let per_proof = |total: Duration| total.as_secs_f64() * 1000.0 / iterations as f64;
+    println!("iterations={}", iterations);
+    println!("unprepared_avg_ms={:.3}", per_proof(unprepared));
+    println!("prepared_avg_ms={:.3}", per_proof(with_prepared));
+    Ok(())
+}
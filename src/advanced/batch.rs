@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
 
 use lazy_static::lazy_static;
@@ -17,6 +17,33 @@ use crate::utils::{
 
 lazy_static! {
     static ref BATCH_REGISTRY: Mutex<HashMap<u64, ProofBatch>> = Mutex::new(HashMap::new());
+    /// Batches taken out of the registry by an in-flight `process_batch`.
+    static ref PROCESSING_BATCHES: Mutex<HashSet<u64>> = Mutex::new(HashSet::new());
+}
+
+/// Error for a batch ID missing from the registry, distinguishing one that another caller is
+/// currently processing from an unknown ID.
+fn missing_batch_error(batch_id: u64) -> ZkpError {
+    let processing = PROCESSING_BATCHES
+        .lock()
+        .map(|set| set.contains(&batch_id))
+        .unwrap_or(false);
+    if processing {
+        ZkpError::InvalidInput(format!("batch {} is already being processed", batch_id))
+    } else {
+        ZkpError::InvalidInput(format!("Invalid batch ID: {}", batch_id))
+    }
+}
+
+/// Marks a batch as processing until dropped, so the mark is cleared even on error or panic.
+struct ProcessingGuard(u64);
+
+impl Drop for ProcessingGuard {
+    fn drop(&mut self) {
+        if let Ok(mut set) = PROCESSING_BATCHES.lock() {
+            set.remove(&self.0);
+        }
+    }
 }
 
 fn allocate_batch_id(registry: &HashMap<u64, ProofBatch>) -> u64 {
@@ -57,7 +84,7 @@ where
         .map_err(|_| ZkpError::CryptoError("batch registry lock poisoned".to_string()))?;
     let batch = registry
         .get_mut(&batch_id)
-        .ok_or_else(|| ZkpError::InvalidInput(format!("Invalid batch ID: {}", batch_id)))?;
+        .ok_or_else(|| missing_batch_error(batch_id))?;
     f(batch);
     #[cfg(feature = "batch-store")]
     {
@@ -106,15 +133,23 @@ pub fn batch_add_consistency_proof(batch_id: u64, data: Vec<u64>) -> ZkpResult<(
     with_batch_mut(batch_id, |batch| batch.add_consistency_proof(data))
 }
 
-/// Process a batch: generate all proofs in parallel and return them as byte vectors
+/// Process a batch: generate all proofs in parallel and return them as byte vectors.
+///
+/// The batch is consumed. A concurrent call for the same ID fails with
+/// "batch ... is already being processed" rather than "Invalid batch ID".
 pub fn process_batch(batch_id: u64) -> ZkpResult<Vec<Vec<u8>>> {
-    let batch = {
+    let (batch, _guard) = {
         let mut registry = BATCH_REGISTRY
             .lock()
             .map_err(|_| ZkpError::CryptoError("batch registry lock poisoned".to_string()))?;
-        registry
+        let batch = registry
             .remove(&batch_id)
-            .ok_or_else(|| ZkpError::InvalidInput(format!("Invalid batch ID: {}", batch_id)))?
+            .ok_or_else(|| missing_batch_error(batch_id))?;
+        PROCESSING_BATCHES
+            .lock()
+            .map_err(|_| ZkpError::CryptoError("batch processing lock poisoned".to_string()))?
+            .insert(batch_id);
+        (batch, ProcessingGuard(batch_id))
     };
 
     #[cfg(feature = "batch-store")]
@@ -139,6 +174,24 @@ pub fn process_batch(batch_id: u64) -> ZkpResult<Vec<Vec<u8>>> {
     }
 }
 
+/// Run each operation's input validation without proving, e.g. before an expensive
+/// `process_batch`. Returns `(index, error message)` per operation in order, with `None`
+/// for valid ones. Operations loaded from a store or file were never validated on add.
+pub fn validate_batch(batch_id: u64) -> ZkpResult<Vec<(usize, Option<String>)>> {
+    let registry = BATCH_REGISTRY
+        .lock()
+        .map_err(|_| ZkpError::CryptoError("batch registry lock poisoned".to_string()))?;
+    let batch = registry
+        .get(&batch_id)
+        .ok_or_else(|| missing_batch_error(batch_id))?;
+    Ok(batch
+        .operations()
+        .iter()
+        .enumerate()
+        .map(|(i, op)| (i, validate_batch_operation(op).err().map(|e| e.to_string())))
+        .collect())
+}
+
 /// Retrieve statistics about a batch such as counts per operation type
 pub fn get_batch_status(batch_id: u64) -> ZkpResult<HashMap<String, usize>> {
     let registry = BATCH_REGISTRY
@@ -146,7 +199,7 @@ pub fn get_batch_status(batch_id: u64) -> ZkpResult<HashMap<String, usize>> {
         .map_err(|_| ZkpError::CryptoError("batch registry lock poisoned".to_string()))?;
     let batch = registry
         .get(&batch_id)
-        .ok_or_else(|| ZkpError::InvalidInput(format!("Invalid batch ID: {}", batch_id)))?;
+        .ok_or_else(|| missing_batch_error(batch_id))?;
 
     let mut status = HashMap::new();
     status.insert("total_operations".to_string(), batch.len());
@@ -259,6 +312,28 @@ pub fn import_batch_from_file(src: impl AsRef<std::path::Path>) -> ZkpResult<u64
     Ok(batch_id)
 }
 
+fn validate_batch_operation(op: &BatchOperation) -> ZkpResult<()> {
+    match op {
+        BatchOperation::RangeProof { value, min, max } => {
+            validation::validate_range_params(*value, *min, *max)
+        }
+        BatchOperation::EqualityProof { val1, val2 } => {
+            validation::validate_equality_params(*val1, *val2)
+        }
+        BatchOperation::ThresholdProof { values, threshold } => {
+            validation::validate_threshold_params(values, *threshold).map(|_| ())
+        }
+        BatchOperation::MembershipProof { value, set } => {
+            validation::validate_membership_params(*value, set)?;
+            validation::validate_set_size(set, crate::backend::snark::MAX_SET_SIZE)
+        }
+        BatchOperation::ImprovementProof { old, new } => {
+            validation::validate_improvement_params(*old, *new).map(|_| ())
+        }
+        BatchOperation::ConsistencyProof { data } => validation::validate_consistency_params(data),
+    }
+}
+
 fn process_batch_operation(op: &BatchOperation) -> ZkpResult<Vec<u8>> {
     match op {
         BatchOperation::RangeProof { value, min, max } => {
@@ -281,3 +356,32 @@ fn process_batch_operation(op: &BatchOperation) -> ZkpResult<Vec<u8>> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_batch_flags_only_invalid_operation() {
+        let mut batch = ProofBatch::new();
+        batch.add_range_proof(5, 0, 10);
+        // Bypasses `batch_add_threshold_proof` validation, as a stored batch could.
+        batch.add_threshold_proof(vec![1, 2], 10);
+        batch.add_consistency_proof(vec![1, 2, 3]);
+        let batch_id = {
+            let mut registry = BATCH_REGISTRY.lock().unwrap();
+            let id = allocate_batch_id(&registry);
+            registry.insert(id, batch);
+            id
+        };
+
+        let results = validate_batch(batch_id).unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], (0, None));
+        assert!(matches!(&results[1], (1, Some(msg)) if msg.contains("threshold")));
+        assert_eq!(results[2], (2, None));
+
+        clear_batch(batch_id).unwrap();
+        assert!(validate_batch(batch_id).is_err());
+    }
+}
@@ -27,8 +27,7 @@ struct BatchFilePayloadV1 {
 /// Override batch store directory (created if missing). Takes precedence over `LIBZKP_BATCH_DIR`.
 pub fn set_batch_store_dir(path: impl AsRef<Path>) -> ZkpResult<()> {
     let p = path.as_ref().to_path_buf();
-    fs::create_dir_all(&p)
-        .map_err(|e| ZkpError::StorageError(format!("create batch store directory: {}", e)))?;
+    create_batch_store_dir(&p)?;
     let mut g = BATCH_STORE_OVERRIDE
         .lock()
         .map_err(|_| ZkpError::StorageError("batch store lock poisoned".to_string()))?;
@@ -36,6 +35,12 @@ pub fn set_batch_store_dir(path: impl AsRef<Path>) -> ZkpResult<()> {
     Ok(())
 }
 
+/// Create the directory [`set_batch_store_dir`] would use, without selecting it.
+pub(crate) fn create_batch_store_dir(path: &Path) -> ZkpResult<()> {
+    fs::create_dir_all(path)
+        .map_err(|e| ZkpError::StorageError(format!("create batch store directory: {}", e)))
+}
+
 /// Effective directory: explicit [`set_batch_store_dir`], else `LIBZKP_BATCH_DIR` if set.
 pub fn get_batch_store_dir() -> Option<PathBuf> {
     if let Ok(g) = BATCH_STORE_OVERRIDE.lock() {
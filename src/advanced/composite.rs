@@ -6,6 +6,9 @@ use crate::utils::{
     error_handling::{ZkpError, ZkpResult},
 };
 
+/// Metadata key holding the issue time as little-endian u64 Unix seconds.
+pub const ISSUED_AT_METADATA_KEY: &str = "issued_at";
+
 /// Create a composite proof from multiple individual proofs
 pub fn create_composite_proof(proof_list: Vec<Vec<u8>>) -> ZkpResult<Vec<u8>> {
     if proof_list.is_empty() {
@@ -36,6 +39,22 @@ pub fn verify_composite_proof_integrity_only(composite_bytes: Vec<u8>) -> ZkpRes
     Ok(composite.verify_integrity())
 }
 
+/// Return `(stored, recomputed)` composition hashes as lowercase hex. Diagnostic aid for
+/// "composition hash mismatch" errors: the blob is parsed without rejecting a mismatch.
+pub fn composite_expected_hash(composite_bytes: Vec<u8>) -> ZkpResult<(String, String)> {
+    let composite = CompositeProof::from_bytes_unverified(&composite_bytes)?;
+    let to_hex = |bytes: &[u8]| {
+        bytes
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>()
+    };
+    Ok((
+        to_hex(&composite.composition_hash),
+        to_hex(&composite.expected_composition_hash()),
+    ))
+}
+
 /// Create a proof with metadata
 pub fn create_proof_with_metadata(
     proof_data: Vec<u8>,
@@ -57,3 +76,59 @@ pub fn extract_proof_metadata(composite_bytes: Vec<u8>) -> ZkpResult<HashMap<Str
     let composite = CompositeProof::from_bytes(&composite_bytes)?;
     Ok(composite.metadata)
 }
+
+/// Verify a single range proof wrapped by [`create_proof_with_metadata`] whose
+/// [`ISSUED_AT_METADATA_KEY`] entry is at most `max_age_seconds` old. The metadata is covered by
+/// the composition hash. Returns `Ok(true)` when valid and fresh; an invalid proof fails with
+/// `VerificationFailed("range proof is invalid ...")`, a stale one with
+/// `VerificationFailed("proof expired ...")`, and a missing or future timestamp with
+/// `InvalidProofFormat`.
+pub fn verify_range_with_freshness(
+    composite_bytes: Vec<u8>,
+    min: u64,
+    max: u64,
+    max_age_seconds: u64,
+) -> ZkpResult<bool> {
+    let composite = CompositeProof::from_bytes(&composite_bytes)?;
+    let proof = match composite.proofs.as_slice() {
+        [proof] => proof,
+        proofs => {
+            return Err(ZkpError::InvalidInput(format!(
+                "expected exactly one range proof, got {}",
+                proofs.len()
+            )))
+        }
+    };
+    if !crate::proof::range_proof::verify_range(proof.to_bytes(), min, max) {
+        return Err(ZkpError::VerificationFailed(format!(
+            "range proof is invalid for [{}, {}]",
+            min, max
+        )));
+    }
+
+    let issued_at = composite
+        .metadata
+        .get(ISSUED_AT_METADATA_KEY)
+        .and_then(|v| <[u8; 8]>::try_from(v.as_slice()).ok())
+        .map(u64::from_le_bytes)
+        .ok_or_else(|| {
+            ZkpError::InvalidProofFormat(format!(
+                "missing or malformed {} metadata",
+                ISSUED_AT_METADATA_KEY
+            ))
+        })?;
+    let now = crate::utils::performance::unix_time_secs();
+    if issued_at > now {
+        return Err(ZkpError::InvalidProofFormat(
+            "issued_at is in the future".to_string(),
+        ));
+    }
+    let age = now - issued_at;
+    if age > max_age_seconds {
+        return Err(ZkpError::VerificationFailed(format!(
+            "proof expired: issued {}s ago, max age {}s",
+            age, max_age_seconds
+        )));
+    }
+    Ok(true)
+}
@@ -13,7 +13,7 @@ pub use batch_store::{
     list_batch_ids_in_store, set_batch_store_dir,
 };
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crate::proof::Proof;
 use crate::utils::{
@@ -35,23 +35,31 @@ pub fn get_cache_stats() -> ZkpResult<HashMap<String, u64>> {
     Ok(stats)
 }
 
-/// Get performance metrics from the global metrics collector
-pub fn get_performance_metrics() -> ZkpResult<HashMap<String, f64>> {
+/// Whether the `msm-accel` assembly field arithmetic for Groth16 MSMs is active in this build.
+pub fn msm_acceleration_active() -> bool {
+    crate::utils::performance::msm_acceleration().is_active()
+}
+
+/// Speedup breakdown: `field_asm` (what [`msm_acceleration_active`] reports) and, for
+/// information only, `ristretto_simd` (the AVX2 backend `curve25519-dalek` picks at runtime).
+pub fn msm_acceleration_info() -> HashMap<String, bool> {
+    let accel = crate::utils::performance::msm_acceleration();
+    HashMap::from([
+        ("field_asm".to_string(), accel.field_asm),
+        ("ristretto_simd".to_string(), accel.ristretto_simd),
+    ])
+}
+
+/// Get performance metrics from the global metrics collector, sorted by key. Per-operation
+/// entries are namespaced: `count_{operation}` is the number of recorded runs and
+/// `time_{operation}_avg_ms` their average duration; `cache_*` and `total_operations` are global.
+pub fn get_performance_metrics() -> ZkpResult<BTreeMap<String, f64>> {
     use crate::utils::performance::{get_global_cache, get_global_metrics};
 
     let cache = get_global_cache();
     let metrics_arc = get_global_metrics();
 
-    let mut result = HashMap::new();
-
-    const AVG_KEYS: &[(&str, &str)] = &[
-        ("range_proof", "avg_range_proof_time_ms"),
-        ("equality_proof", "avg_equality_proof_time_ms"),
-        ("threshold_proof", "avg_threshold_proof_time_ms"),
-        ("membership_proof", "avg_membership_proof_time_ms"),
-        ("improvement_proof", "avg_improvement_proof_time_ms"),
-        ("consistency_proof", "avg_consistency_proof_time_ms"),
-    ];
+    let mut result = BTreeMap::new();
 
     if let Ok(metrics) = metrics_arc.lock() {
         result.insert("cache_hit_rate".to_string(), metrics.get_cache_hit_rate());
@@ -59,14 +67,17 @@ pub fn get_performance_metrics() -> ZkpResult<HashMap<String, f64>> {
         result.insert("cache_hits".to_string(), metrics.cache_hits as f64);
         result.insert("cache_misses".to_string(), metrics.cache_misses as f64);
 
-        for (op, out_key) in AVG_KEYS {
-            if let Some(avg_time) = metrics.get_average_time(op) {
-                result.insert(out_key.to_string(), avg_time.as_millis() as f64);
+        for operation in metrics.operation_times.keys() {
+            if let Some(avg_time) = metrics.get_average_time(operation) {
+                result.insert(
+                    format!("time_{}_avg_ms", operation),
+                    avg_time.as_millis() as f64,
+                );
             }
         }
 
         for (operation, count) in &metrics.operation_counts {
-            result.insert(format!("{}_count", operation), *count as f64);
+            result.insert(format!("count_{}", operation), *count as f64);
         }
 
         let total_operations: u64 = metrics.operation_counts.values().sum();
@@ -79,94 +90,178 @@ pub fn get_performance_metrics() -> ZkpResult<HashMap<String, f64>> {
     Ok(result)
 }
 
-/// Numeric benchmark result (float metrics) for Rust or Python consumers
-pub fn benchmark_proof_generation_numeric(
-    proof_type: String,
-    iterations: u32,
-) -> ZkpResult<HashMap<String, f64>> {
-    let mut timer = Timer::new();
-    let mut times_ms = Vec::new();
-    let mut successful_iterations = 0u32;
+/// Export this process's performance metrics as JSON (see [`PerformanceMetrics::to_json`]).
+///
+/// [`PerformanceMetrics::to_json`]: crate::utils::performance::PerformanceMetrics::to_json
+pub fn export_metrics_json() -> ZkpResult<String> {
+    let metrics = crate::utils::performance::get_global_metrics();
+    let guard = metrics
+        .lock()
+        .map_err(|_| ZkpError::BackendError("performance metrics lock poisoned".to_string()))?;
+    Ok(guard.to_json())
+}
 
-    for _ in 0..iterations {
-        timer.reset();
-        let result = match proof_type.as_str() {
-            "range" => crate::proof::range_proof::prove_range(50, 0, 100),
-            "equality" => crate::proof::equality_proof::prove_equality(42, 42),
-            "threshold" => crate::proof::threshold_proof::prove_threshold(vec![10, 20, 30, 40], 50),
-            "membership" => {
-                crate::proof::set_membership::prove_membership(25, vec![10, 20, 25, 30, 40])
-            }
-            "improvement" => crate::proof::improvement_proof::prove_improvement(30, 50),
-            "consistency" => {
-                crate::proof::consistency_proof::prove_consistency(vec![10, 20, 30, 40, 50])
-            }
-            _ => {
-                return Err(ZkpError::InvalidInput(format!(
-                    "unsupported proof type: {}",
-                    proof_type
-                )));
-            }
-        };
+/// Merge metrics exported by another process into this process's global collector.
+pub fn import_metrics_json(json: String) -> ZkpResult<()> {
+    use crate::utils::performance::PerformanceMetrics;
 
-        if result.is_ok() {
-            let elapsed = timer.elapsed();
-            let op = match proof_type.as_str() {
-                "range" => "range_proof",
-                "equality" => "equality_proof",
-                "threshold" => "threshold_proof",
-                "membership" => "membership_proof",
-                "improvement" => "improvement_proof",
-                "consistency" => "consistency_proof",
-                _ => "unknown",
-            };
-            crate::utils::performance::record_operation_metric(op, elapsed);
+    let incoming = PerformanceMetrics::from_json(&json)?;
+    let metrics = crate::utils::performance::get_global_metrics();
+    let mut guard = metrics
+        .lock()
+        .map_err(|_| ZkpError::BackendError("performance metrics lock poisoned".to_string()))?;
+    guard.merge(&incoming);
+    Ok(())
+}
 
-            let elapsed_ms = elapsed.as_secs_f64() * 1000.0;
-            times_ms.push(elapsed_ms);
-            successful_iterations += 1;
+/// Fixed sample inputs shared by the benchmarks, keyed by proof type.
+fn prove_sample(proof_type: &str) -> ZkpResult<Vec<u8>> {
+    match proof_type {
+        "range" => crate::proof::range_proof::prove_range(50, 0, 100),
+        "equality" => crate::proof::equality_proof::prove_equality(42, 42),
+        "threshold" => crate::proof::threshold_proof::prove_threshold(vec![10, 20, 30, 40], 50),
+        "membership" => {
+            crate::proof::set_membership::prove_membership(25, vec![10, 20, 25, 30, 40])
+        }
+        "improvement" => crate::proof::improvement_proof::prove_improvement(30, 50),
+        "consistency" => {
+            crate::proof::consistency_proof::prove_consistency(vec![10, 20, 30, 40, 50])
         }
+        _ => Err(ZkpError::InvalidInput(format!(
+            "unsupported proof type: {}",
+            proof_type
+        ))),
     }
+}
 
-    if successful_iterations == 0 {
-        return Err(ZkpError::InvalidInput(
-            "no successful proof generations".to_string(),
-        ));
+/// Verify a proof from [`prove_sample`] against the same sample statement.
+fn verify_sample(proof_type: &str, proof: &[u8]) -> bool {
+    let proof = proof.to_vec();
+    match proof_type {
+        "range" => crate::proof::range_proof::verify_range(proof, 0, 100),
+        "equality" => crate::proof::equality_proof::verify_equality(proof, 42, 42),
+        "threshold" => crate::proof::threshold_proof::verify_threshold(proof, 50),
+        "membership" => {
+            crate::proof::set_membership::verify_membership(proof, vec![10, 20, 25, 30, 40])
+        }
+        "improvement" => crate::proof::improvement_proof::verify_improvement(proof, 30),
+        "consistency" => crate::proof::consistency_proof::verify_consistency(proof),
+        _ => false,
     }
+}
 
+/// Summary statistics over successful iteration timings (milliseconds).
+fn timing_stats(times_ms: &[f64], iterations: u32) -> HashMap<String, f64> {
+    let successful_iterations = times_ms.len() as f64;
     let total_time_ms: f64 = times_ms.iter().sum();
-    let avg_time_ms = total_time_ms / successful_iterations as f64;
+    let avg_time_ms = total_time_ms / successful_iterations;
     let min_time_ms = times_ms.iter().fold(f64::INFINITY, |a, &b| a.min(b));
     let max_time_ms = times_ms.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
     let variance = times_ms
         .iter()
         .map(|&x| (x - avg_time_ms).powi(2))
         .sum::<f64>()
-        / successful_iterations as f64;
+        / successful_iterations;
     let std_dev_ms = variance.sqrt();
+    let mut sorted = times_ms.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let p95_index = ((sorted.len() as f64 * 0.95).ceil() as usize).clamp(1, sorted.len()) - 1;
 
     let mut out = HashMap::new();
     out.insert("iterations".to_string(), iterations as f64);
-    out.insert(
-        "successful_iterations".to_string(),
-        successful_iterations as f64,
-    );
+    out.insert("successful_iterations".to_string(), successful_iterations);
     out.insert(
         "success_rate".to_string(),
-        (successful_iterations as f64 / iterations as f64) * 100.0,
+        (successful_iterations / iterations as f64) * 100.0,
     );
     out.insert("total_time_ms".to_string(), total_time_ms);
     out.insert("avg_time_ms".to_string(), avg_time_ms);
     out.insert("min_time_ms".to_string(), min_time_ms);
     out.insert("max_time_ms".to_string(), max_time_ms);
+    out.insert("p95_time_ms".to_string(), sorted[p95_index]);
     out.insert("std_dev_ms".to_string(), std_dev_ms);
+    out
+}
+
+/// Numeric benchmark result (float metrics) for Rust or Python consumers
+pub fn benchmark_proof_generation_numeric(
+    proof_type: String,
+    iterations: u32,
+) -> ZkpResult<HashMap<String, f64>> {
+    let op = match proof_type.as_str() {
+        "range" => "range_proof",
+        "equality" => "equality_proof",
+        "threshold" => "threshold_proof",
+        "membership" => "membership_proof",
+        "improvement" => "improvement_proof",
+        "consistency" => "consistency_proof",
+        _ => {
+            return Err(ZkpError::InvalidInput(format!(
+                "unsupported proof type: {}",
+                proof_type
+            )));
+        }
+    };
+    let mut timer = Timer::new();
+    let mut times_ms = Vec::new();
+
+    for _ in 0..iterations {
+        timer.reset();
+        if prove_sample(&proof_type).is_ok() {
+            let elapsed = timer.elapsed();
+            crate::utils::performance::record_operation_metric(op, elapsed);
+            times_ms.push(elapsed.as_secs_f64() * 1000.0);
+        }
+    }
+
+    if times_ms.is_empty() {
+        return Err(ZkpError::InvalidInput(
+            "no successful proof generations".to_string(),
+        ));
+    }
+
+    let mut out = timing_stats(&times_ms, iterations);
+    let total_time_ms = out["total_time_ms"];
+    let successful_iterations = times_ms.len() as f64;
     out.insert(
         "proofs_per_second".to_string(),
-        successful_iterations as f64 / (total_time_ms / 1000.0),
+        successful_iterations / (total_time_ms / 1000.0),
     );
     out.insert(
         "throughput_ms_per_proof".to_string(),
-        total_time_ms / successful_iterations as f64,
+        total_time_ms / successful_iterations,
+    );
+    Ok(out)
+}
+
+/// Benchmark verification: proves the sample statement once, then times `iterations`
+/// verifications of that proof. Same statistics as the generation benchmark, plus
+/// `verifications_per_second`; only successful verifications are timed.
+pub fn benchmark_proof_verification(
+    proof_type: String,
+    iterations: u32,
+) -> ZkpResult<HashMap<String, f64>> {
+    let proof = prove_sample(&proof_type)?;
+    let mut timer = Timer::new();
+    let mut times_ms = Vec::new();
+
+    for _ in 0..iterations {
+        timer.reset();
+        if verify_sample(&proof_type, &proof) {
+            times_ms.push(timer.elapsed().as_secs_f64() * 1000.0);
+        }
+    }
+
+    if times_ms.is_empty() {
+        return Err(ZkpError::InvalidInput(
+            "no successful proof verifications".to_string(),
+        ));
+    }
+
+    let mut out = timing_stats(&times_ms, iterations);
+    out.insert(
+        "verifications_per_second".to_string(),
+        times_ms.len() as f64 / (out["total_time_ms"] / 1000.0),
     );
     Ok(out)
 }
@@ -189,6 +284,42 @@ pub fn prove_range_cached(value: u64, min: u64, max: u64) -> ZkpResult<Vec<u8>>
     Ok(proof)
 }
 
+/// Range proof plus a JSON receipt summarizing what was proven, for logging or display.
+/// The receipt omits the secret value and is not itself verifiable; verify the proof bytes.
+pub fn prove_range_with_receipt(value: u64, min: u64, max: u64) -> ZkpResult<(Vec<u8>, String)> {
+    let proof = crate::proof::range_proof::prove_range(value, min, max)?;
+    let receipt = serde_json::json!({
+        "type": "range",
+        "min": min,
+        "max": max,
+        "proof_size": proof.len(),
+        "timestamp": crate::utils::performance::unix_time_secs(),
+    });
+    Ok((proof, receipt.to_string()))
+}
+
+/// Whether two proofs commit to the same value, for a holder of both commitment blindings
+/// (e.g. from [`crate::proof::range_proof::prove_range_and_greater`] or
+/// [`crate::proof::range_proof::prove_in_committed_interval`]). Only the commitments are
+/// compared; the proofs themselves are not verified.
+pub fn proofs_share_value(
+    proof_a: Vec<u8>,
+    proof_b: Vec<u8>,
+    blinding_a: &[u8; 32],
+    blinding_b: &[u8; 32],
+) -> ZkpResult<bool> {
+    use crate::backend::bulletproofs::{commitments_share_value, scalar_from_canonical};
+    use crate::utils::proof_helpers::extract_value_commitment;
+
+    let commit_a = extract_value_commitment(&proof_a)?;
+    let commit_b = extract_value_commitment(&proof_b)?;
+    let invalid_blinding = || ZkpError::InvalidInput("blinding is not a canonical scalar".into());
+    let blinding_a = scalar_from_canonical(blinding_a).ok_or_else(invalid_blinding)?;
+    let blinding_b = scalar_from_canonical(blinding_b).ok_or_else(invalid_blinding)?;
+    commitments_share_value(&commit_a, &blinding_a, &commit_b, &blinding_b)
+        .ok_or_else(|| ZkpError::InvalidProofFormat("invalid value commitment".to_string()))
+}
+
 /// Equality proof (same semantics as [`crate::proof::equality_proof::prove_equality`]).
 pub fn prove_equality_advanced(val1: u64, val2: u64) -> ZkpResult<Vec<u8>> {
     crate::proof::equality_proof::prove_equality(val1, val2)
@@ -215,7 +346,15 @@ pub fn benchmark_proof_generation(
 }
 
 /// Optimized threshold proof generation (delegates to [`crate::proof::threshold_proof::prove_threshold`]).
-pub fn prove_threshold_optimized(values: Vec<u64>, threshold: u64) -> ZkpResult<Vec<u8>> {
+/// When `max_value_per_element` is set, every value is checked against it before any proving work.
+pub fn prove_threshold_optimized(
+    values: Vec<u64>,
+    threshold: u64,
+    max_value_per_element: Option<u64>,
+) -> ZkpResult<Vec<u8>> {
+    if let Some(max_value) = max_value_per_element {
+        crate::utils::validation::validate_value_bounds(&values, max_value)?;
+    }
     crate::proof::threshold_proof::prove_threshold(values, threshold)
 }
 
@@ -234,6 +373,68 @@ pub fn validate_proof_chain(proof_chain: Vec<Vec<u8>>) -> ZkpResult<bool> {
     Ok(true)
 }
 
+/// Canonical re-encoding of a stored proof: keeps the header-declared
+/// `10 + proof_len + commitment_len` bytes and drops anything trailing (e.g. context bytes
+/// appended by older `prove_equality_advanced` builds) and re-encodes the header
+/// little-endian. Errors if the declared proof is truncated or fails strict parsing.
+/// Does not verify the proof.
+pub fn sanitize_proof(bytes: Vec<u8>) -> ZkpResult<Vec<u8>> {
+    let total = Proof::declared_len(&bytes)?;
+    let core = bytes.get(..total).ok_or_else(|| {
+        ZkpError::InvalidProofFormat(format!(
+            "proof truncated: header declares {} bytes, got {}",
+            total,
+            bytes.len()
+        ))
+    })?;
+    let proof = Proof::from_bytes_strict(core)?;
+    Ok(proof
+        .with_byte_order(crate::proof::ByteOrder::LittleEndian)
+        .to_bytes())
+}
+
+/// JSON description of every scheme in [`crate::proof::SCHEMES`]: id, name, verifier
+/// parameters, envelope length requirements and payload fields. A field's `offset` is `null`
+/// once an earlier field has a variable size.
+pub fn scheme_registry() -> ZkpResult<String> {
+    use crate::proof::{scheme_length_requirements, SCHEMES};
+
+    let schemes: Vec<serde_json::Value> = SCHEMES
+        .iter()
+        .map(|scheme| {
+            let mut offset = Some(0usize);
+            let fields: Vec<serde_json::Value> = scheme
+                .payload
+                .iter()
+                .map(|&(name, size)| {
+                    let field = serde_json::json!({
+                        "name": name,
+                        "offset": offset,
+                        "size": size,
+                    });
+                    offset = offset.zip(size.parse::<usize>().ok()).map(|(o, n)| o + n);
+                    field
+                })
+                .collect();
+            let (min_proof_len, commitment_len) =
+                scheme_length_requirements(scheme.id).unwrap_or((0, 0));
+            serde_json::json!({
+                "id": scheme.id,
+                "name": scheme.name,
+                "verify_params": scheme.verify_params,
+                "min_payload_len": min_proof_len,
+                "commitment_len": commitment_len,
+                "payload": fields,
+            })
+        })
+        .collect();
+    serde_json::to_string(&serde_json::json!({
+        "version": crate::proof::PROOF_VERSION,
+        "schemes": schemes,
+    }))
+    .map_err(|e| ZkpError::SerializationError(e.to_string()))
+}
+
 /// Extract high-level information from a proof
 pub fn get_proof_info(proof_bytes: Vec<u8>) -> ZkpResult<HashMap<String, u64>> {
     let proof = Proof::from_bytes(&proof_bytes)?;
@@ -252,6 +453,95 @@ pub fn set_snark_key_dir(path: String) -> ZkpResult<bool> {
     crate::backend::snark::set_snark_key_dir(&path).map(|_| true)
 }
 
+/// Serialize new Groth16 proofs (equality, membership, polynomial) compressed: 128 bytes
+/// instead of 256. Verification accepts both forms regardless of this setting.
+pub fn set_snark_proof_compression(enabled: bool) {
+    crate::backend::snark::set_proof_compression(enabled)
+}
+
+/// Whether new Groth16 proofs are serialized compressed.
+pub fn snark_proof_compression() -> bool {
+    crate::backend::snark::proof_compression_enabled()
+}
+
+/// Select the SNARK pairing curve, `"bn254"` (default) or `"bls12_381"`. Commitments, keys
+/// and proofs are curve-specific, so call before the first SNARK commitment or proof.
+pub fn set_snark_curve(name: String) -> ZkpResult<()> {
+    crate::backend::snark::set_snark_curve(name.parse()?)
+}
+
+/// Name of the selected SNARK curve.
+pub fn snark_curve() -> String {
+    crate::backend::snark::snark_curve().name().to_string()
+}
+
+/// Generate new keys for a SNARK circuit (named as in [`ceremony_initialize`]) and install
+/// them as its next key version; returns the version.
+pub fn rotate_snark_keys(circuit: String) -> ZkpResult<u32> {
+    crate::backend::snark::rotate_keys(circuit.parse()?)
+}
+
+/// Stop trusting an older key version of a SNARK circuit.
+pub fn retire_snark_key_version(circuit: String, version: u32) -> ZkpResult<()> {
+    crate::backend::snark::retire_key_version(circuit.parse()?, version)
+}
+
+/// Trusted key versions of a SNARK circuit, newest first.
+pub fn snark_key_versions(circuit: String) -> ZkpResult<Vec<u32>> {
+    crate::backend::snark::key_versions(circuit.parse()?)
+}
+
+/// Serialized proving and verifying key a SNARK circuit proves with.
+pub fn export_snark_keys(circuit: String) -> ZkpResult<(Vec<u8>, Vec<u8>)> {
+    crate::backend::snark::export_keys(circuit.parse()?)
+}
+
+/// Use exported keys for a SNARK circuit in this process instead of the key directory.
+pub fn import_snark_keys(circuit: String, pk_bytes: Vec<u8>, vk_bytes: Vec<u8>) -> ZkpResult<()> {
+    crate::backend::snark::import_keys(circuit.parse()?, &pk_bytes, &vk_bytes)
+}
+
+/// Start a powers of tau accumulator (setup phase 1) for circuits of up to `2^log_size`
+/// constraints on the selected SNARK curve.
+pub fn powers_of_tau_initialize(log_size: u32) -> ZkpResult<Vec<u8>> {
+    crate::backend::snark::powers_of_tau::initialize(log_size)
+}
+
+/// Add a contribution to a powers of tau file; returns the new file and the contribution hash.
+pub fn powers_of_tau_contribute(params: Vec<u8>) -> ZkpResult<(Vec<u8>, Vec<u8>)> {
+    crate::backend::snark::powers_of_tau::contribute(&params)
+        .map(|(next, hash)| (next, hash.to_vec()))
+}
+
+/// Verify a powers of tau file; returns the hash of each contribution in order.
+pub fn powers_of_tau_verify(params: Vec<u8>) -> ZkpResult<Vec<Vec<u8>>> {
+    crate::backend::snark::powers_of_tau::verify(&params)
+        .map(|hashes| hashes.iter().map(|h| h.to_vec()).collect())
+}
+
+/// Start a Groth16 setup ceremony (phase 2) for `circuit` (`"equality"`, `"equality_w{bits}"`,
+/// `"membership"`, ...) from a contributed powers of tau file, on that file's curve.
+pub fn ceremony_initialize(circuit: String, phase1: Vec<u8>) -> ZkpResult<Vec<u8>> {
+    crate::backend::snark::ceremony::initialize(circuit.parse()?, &phase1)
+}
+
+/// Add a contribution to a ceremony file; returns the new file and the contribution hash.
+pub fn ceremony_contribute(params: Vec<u8>) -> ZkpResult<(Vec<u8>, Vec<u8>)> {
+    crate::backend::snark::ceremony::contribute(&params).map(|(next, hash)| (next, hash.to_vec()))
+}
+
+/// Verify a ceremony file against its powers of tau file; returns the hash of each
+/// contribution in order.
+pub fn ceremony_verify(params: Vec<u8>, phase1: Vec<u8>) -> ZkpResult<Vec<Vec<u8>>> {
+    crate::backend::snark::ceremony::verify(&params, &phase1)
+        .map(|hashes| hashes.iter().map(|h| h.to_vec()).collect())
+}
+
+/// Verify a ceremony file and install its keys as the circuit's next key version.
+pub fn ceremony_finalize(params: Vec<u8>, phase1: Vec<u8>) -> ZkpResult<u32> {
+    crate::backend::snark::ceremony::finalize(&params, &phase1)
+}
+
 /// Return true if SNARK setups are already initialized in-memory
 pub fn is_snark_setup_initialized() -> ZkpResult<bool> {
     Ok(crate::backend::snark::is_snark_initialized())
@@ -1,13 +1,18 @@
+use super::range_batch::{self, RangeStatement};
+use super::sigma::{self, DlogProof, NonZeroProof, OrDlogProof};
 use super::ZkpBackend;
 use crate::utils::encoding::read_u64_le;
+use crate::utils::limits::{MAX_AGGREGATED_RANGE_VALUES, MAX_DISJOINT_PAIRS, MAX_U64_VEC_LEN};
 use bulletproofs::{BulletproofGens, PedersenGens, RangeProof};
 use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
 use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::Identity;
 use merlin::Transcript;
 use rand::rngs::OsRng;
 use rand::RngCore;
 use sha2::{Digest, Sha256};
 use std::sync::OnceLock;
+use zeroize::Zeroizing;
 
 /// Bulletproofs backend wire format (no ambiguous delimiters):
 /// `[u32 proof_body_len][proof_body][u32=32][32 byte commitment]`.
@@ -79,11 +84,78 @@ pub fn bp_gens_pair_bits(n_bits: usize, party_capacity: usize) -> (PedersenGens,
     }
 }
 
-fn random_blinding() -> Scalar {
+pub(crate) fn random_blinding() -> Scalar {
     let mut rng = OsRng;
-    let mut bytes = [0u8; 32];
-    rng.fill_bytes(&mut bytes);
-    Scalar::from_bytes_mod_order(bytes)
+    let mut bytes = Zeroizing::new([0u8; 32]);
+    rng.fill_bytes(bytes.as_mut());
+    Scalar::from_bytes_mod_order(*bytes)
+}
+
+/// `n` fresh blindings, wiped when the prover drops them.
+fn random_blindings(n: usize) -> Zeroizing<Vec<Scalar>> {
+    Zeroizing::new((0..n).map(|_| random_blinding()).collect())
+}
+
+/// Pedersen commitment `value * B + blinding * B_blinding` under the default generators.
+pub(crate) fn pedersen_commit(value: u64, blinding: &Scalar) -> CompressedRistretto {
+    PedersenGens::default()
+        .commit(Scalar::from(value), *blinding)
+        .compress()
+}
+
+/// Pedersen commitment to a signed value; negative values commit to `-|value| * B`.
+pub(crate) fn pedersen_commit_signed(value: i64, blinding: &Scalar) -> CompressedRistretto {
+    let magnitude = Scalar::from(value.unsigned_abs());
+    let scalar = if value < 0 { -magnitude } else { magnitude };
+    PedersenGens::default().commit(scalar, *blinding).compress()
+}
+
+/// Blinded multiset root `multiset_hash(set) + blinding * B_blinding` of set equality and
+/// shuffle proofs.
+pub(crate) fn multiset_root(set: &[u64], blinding: &Scalar) -> RistrettoPoint {
+    sigma::multiset_hash(set) + blinding * PedersenGens::default().B_blinding
+}
+
+/// The concatenated per-element commitments of a set in a disjointness proof, and their
+/// SHA-256 digest, the set's root.
+pub(crate) fn element_commitments(set: &[u64], blindings: &[Scalar]) -> (Vec<u8>, [u8; 32]) {
+    let commits: Vec<u8> = set
+        .iter()
+        .zip(blindings)
+        .flat_map(|(&v, r)| pedersen_commit(v, r).to_bytes())
+        .collect();
+    let root = Sha256::digest(&commits).into();
+    (commits, root)
+}
+
+/// Parse a caller-supplied blinding factor; rejects non-canonical scalar encodings.
+pub(crate) fn scalar_from_canonical(bytes: &[u8; 32]) -> Option<Scalar> {
+    Option::from(Scalar::from_canonical_bytes(*bytes))
+}
+
+/// [`scalar_from_canonical`] for each of a fixed number of blindings, without a heap copy.
+pub(crate) fn scalars_from_canonical<const N: usize>(
+    bytes: &[[u8; 32]; N],
+) -> Option<Zeroizing<[Scalar; N]>> {
+    let mut scalars = Zeroizing::new([Scalar::ZERO; N]);
+    for (scalar, b) in scalars.iter_mut().zip(bytes) {
+        *scalar = scalar_from_canonical(b)?;
+    }
+    Some(scalars)
+}
+
+/// Whether two Pedersen commitments hide the same value, given both blindings: compares
+/// `C_a - r_a * B_blinding` with `C_b - r_b * B_blinding`. `None` if a commitment is not a point.
+pub(crate) fn commitments_share_value(
+    commit_a: &[u8],
+    blinding_a: &Scalar,
+    commit_b: &[u8],
+    blinding_b: &Scalar,
+) -> Option<bool> {
+    let h = PedersenGens::default().B_blinding;
+    let a = parse_compressed_32(commit_a)?.decompress()? - blinding_a * h;
+    let b = parse_compressed_32(commit_b)?.decompress()? - blinding_b * h;
+    Some(a == b)
 }
 
 fn parse_compressed_32(slice: &[u8]) -> Option<CompressedRistretto> {
@@ -99,6 +171,277 @@ fn max_u64_for_bit_width(n_bits: usize) -> u64 {
     }
 }
 
+/// Aggregated 64-bit range proof that each `values[i]`, opened with `blindings[i]`, lies in
+/// `[0, 2^64)`. `prove_multiple` needs a power-of-two party count, so the list is padded with
+/// zero openings (whose commitment is the identity point).
+fn prove_aggregated_u64(
+    values: &[u64],
+    blindings: &[Scalar],
+    label: &'static [u8],
+) -> Result<(RangeProof, Vec<CompressedRistretto>), String> {
+    if values.is_empty() || values.len() != blindings.len() {
+        return Err("aggregated range proof needs one blinding per value".to_string());
+    }
+    if values.len() > MAX_AGGREGATED_RANGE_VALUES {
+        return Err(format!(
+            "aggregated range proof supports at most {} values",
+            MAX_AGGREGATED_RANGE_VALUES
+        ));
+    }
+    // Sized up front: growing a `Zeroizing` vector would free the old buffer unwiped.
+    let parties = values.len().next_power_of_two();
+    let mut padded_values = Zeroizing::new(Vec::with_capacity(parties));
+    padded_values.extend_from_slice(values);
+    padded_values.resize(parties, 0);
+    let mut padded_blindings = Zeroizing::new(Vec::with_capacity(parties));
+    padded_blindings.extend_from_slice(blindings);
+    padded_blindings.resize(parties, Scalar::ZERO);
+
+    let (pc_gens, bp_gens) = bp_gens_pair_bits(64, parties);
+    let mut transcript = Transcript::new(label);
+    let (proof, mut commitments) = RangeProof::prove_multiple(
+        &bp_gens,
+        &pc_gens,
+        &mut transcript,
+        &padded_values,
+        &padded_blindings,
+        64,
+    )
+    .map_err(|_| "aggregated range proof generation failed".to_string())?;
+    commitments.truncate(values.len());
+    Ok((proof, commitments))
+}
+
+/// Verify a proof from `prove_aggregated_u64` against the (unpadded) value commitments.
+fn verify_aggregated_u64(
+    proof: &RangeProof,
+    commitments: &[CompressedRistretto],
+    label: &'static [u8],
+) -> bool {
+    if commitments.is_empty() || commitments.len() > MAX_AGGREGATED_RANGE_VALUES {
+        return false;
+    }
+    let parties = commitments.len().next_power_of_two();
+    let mut padded = commitments.to_vec();
+    padded.resize(parties, RistrettoPoint::identity().compress());
+
+    let (pc_gens, bp_gens) = bp_gens_pair_bits(64, parties);
+    let mut transcript = Transcript::new(label);
+    proof
+        .verify_multiple(&bp_gens, &pc_gens, &mut transcript, &padded, 64)
+        .is_ok()
+}
+
+/// Read `[u32 len][bytes]` from the front of `reader` and parse it as a range proof.
+fn read_range_proof(reader: &mut &[u8]) -> Option<RangeProof> {
+    let len = u32::from_le_bytes(reader.get(0..4)?.try_into().ok()?) as usize;
+    let end = 4usize.checked_add(len)?;
+    let proof = RangeProof::from_bytes(reader.get(4..end)?).ok()?;
+    *reader = &reader[end..];
+    Some(proof)
+}
+
+fn write_range_proof(out: &mut Vec<u8>, proof: &RangeProof) {
+    let bytes = proof.to_bytes();
+    out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    out.extend_from_slice(&bytes);
+}
+
+/// Decompress the `[old, new, delta]` commitments of an exact-delta proof.
+fn decompress_delta_commitments(commitments: [&[u8]; 3]) -> Option<[RistrettoPoint; 3]> {
+    let [old, new, delta] = commitments.map(|c| {
+        if c.len() != 32 {
+            return None;
+        }
+        parse_compressed_32(c)?.decompress()
+    });
+    Some([old?, new?, delta?])
+}
+
+/// The two single range proofs inside a `prove_range_with_bounds_bits` proof, bound to
+/// `V - min*B` and `max*B - V`. `None` if the wire format or the embedded bounds and
+/// difference commitments do not match.
+fn range_statements(proof_data: &[u8], min: u64, max: u64) -> Option<[RangeStatement<'_>; 2]> {
+    let (proof_bytes, commit_slice) = decode_proof_body_and_commit(proof_data)?;
+    let value_commit = parse_compressed_32(commit_slice)?.decompress()?;
+    if read_u64_le(proof_bytes, 0)? != min || read_u64_le(proof_bytes, 8)? != max {
+        return None;
+    }
+    let n_bits = u32::from_le_bytes(proof_bytes.get(16..20)?.try_into().ok()?) as usize;
+    let mut reader = &proof_bytes[20..];
+    let mut next_blob = || {
+        let current = reader;
+        let len = u32::from_le_bytes(current.get(0..4)?.try_into().ok()?) as usize;
+        let end = 4usize.checked_add(len)?;
+        reader = current.get(end..)?;
+        current.get(4..end)
+    };
+    let proof_min = next_blob()?;
+    let proof_max = next_blob()?;
+    if reader.len() < 64 {
+        return None;
+    }
+
+    let base = PedersenGens::default().B;
+    let min_commit = value_commit - Scalar::from(min) * base;
+    let max_commit = Scalar::from(max) * base - value_commit;
+    if reader[..32] != *min_commit.compress().as_bytes()
+        || reader[32..64] != *max_commit.compress().as_bytes()
+    {
+        return None;
+    }
+    Some([
+        RangeStatement {
+            proof: proof_min,
+            commitment: min_commit,
+            label: b"libzkp_range_min",
+            n_bits,
+        },
+        RangeStatement {
+            proof: proof_max,
+            commitment: max_commit,
+            label: b"libzkp_range_max",
+            n_bits,
+        },
+    ])
+}
+
+/// `C - s * B` for each element `s` of `set`: the branches of a committed membership proof.
+fn membership_branches(commit: RistrettoPoint, set: &[u64]) -> Vec<RistrettoPoint> {
+    let base = PedersenGens::default().B;
+    set.iter()
+        .map(|&s| commit - Scalar::from(s) * base)
+        .collect()
+}
+
+/// Transcript for disjointness proofs, bound to both set roots.
+fn disjoint_transcript(root_a: &[u8; 32], root_b: &[u8; 32]) -> Transcript {
+    let mut transcript = Transcript::new(b"libzkp_disjoint");
+    transcript.append_message(b"root_a", root_a);
+    transcript.append_message(b"root_b", root_b);
+    transcript
+}
+
+/// `a - b >= margin` over the commitments `A` and `B` under `blindings`: an aggregated range
+/// proof over `[a, b, a - b - margin]`, the last committed as `A - B - margin*G`. Errors with
+/// `unmet` if `a - b` is below `margin`.
+fn prove_ordered_pair_range(
+    a: u64,
+    b: u64,
+    blindings: &[Scalar; 2],
+    margin: u64,
+    label: &'static [u8],
+    unmet: &str,
+) -> Result<RangeProof, String> {
+    let gap = a
+        .checked_sub(b)
+        .and_then(|d| d.checked_sub(margin))
+        .ok_or_else(|| unmet.to_string())?;
+    let (range_proof, _) = prove_aggregated_u64(
+        &[a, b, gap],
+        &[blindings[0], blindings[1], blindings[0] - blindings[1]],
+        label,
+    )?;
+    Ok(range_proof)
+}
+
+/// Verify a proof from `prove_ordered_pair_range` against the commitments `A` and `B`.
+fn verify_ordered_pair_range(
+    a: RistrettoPoint,
+    b: RistrettoPoint,
+    range_proof: &RangeProof,
+    margin: u64,
+    label: &'static [u8],
+) -> bool {
+    let commitments = [
+        a.compress(),
+        b.compress(),
+        (a - b - Scalar::from(margin) * PedersenGens::default().B).compress(),
+    ];
+    verify_aggregated_u64(range_proof, &commitments, label)
+}
+
+/// [`prove_ordered_pair_range`] with both commitments. Body: `[B 32][u32 len][range proof]`,
+/// commitment: `A`.
+fn prove_ordered_pair(
+    a: u64,
+    b: u64,
+    blindings: &[Scalar; 2],
+    margin: u64,
+    label: &'static [u8],
+    unmet: &str,
+) -> Result<Vec<u8>, String> {
+    let range_proof = prove_ordered_pair_range(a, b, blindings, margin, label, unmet)?;
+    let commit_a = pedersen_commit(a, &blindings[0]);
+    let commit_b = pedersen_commit(b, &blindings[1]);
+
+    let mut proof_bytes = Vec::new();
+    proof_bytes.extend_from_slice(commit_b.as_bytes());
+    write_range_proof(&mut proof_bytes, &range_proof);
+
+    encode_proof_body_with_commit(&proof_bytes, commit_a.as_bytes())
+}
+
+fn verify_ordered_pair(proof_data: &[u8], margin: u64, label: &'static [u8]) -> bool {
+    let (proof_bytes, commit_a) = match decode_proof_body_and_commit(proof_data) {
+        Some(p) => p,
+        None => return false,
+    };
+    let points: Option<Vec<RistrettoPoint>> = [commit_a.get(0..32), proof_bytes.get(0..32)]
+        .into_iter()
+        .map(|s| s.and_then(parse_compressed_32)?.decompress())
+        .collect();
+    let (a, b) = match points.as_deref() {
+        Some([a, b]) => (*a, *b),
+        _ => return false,
+    };
+    let mut reader = &proof_bytes[32..];
+    let range_proof = match read_range_proof(&mut reader) {
+        Some(rp) => rp,
+        None => return false,
+    };
+    if !reader.is_empty() {
+        return false;
+    }
+    verify_ordered_pair_range(a, b, &range_proof, margin, label)
+}
+
+/// Transcript for inequality proofs, bound to both commitments in order.
+fn inequality_transcript(commit_a: &[u8], commit_b: &[u8]) -> Transcript {
+    let mut transcript = Transcript::new(b"libzkp_inequality");
+    transcript.append_message(b"commitment_a", commit_a);
+    transcript.append_message(b"commitment_b", commit_b);
+    transcript
+}
+
+/// Transcript for exact-delta proofs, bound to all three commitments in order.
+fn delta_transcript(commitments: [&[u8]; 3]) -> Transcript {
+    let [old, new, delta] = commitments;
+    let mut transcript = Transcript::new(b"libzkp_exact_delta");
+    transcript.append_message(b"old_commitment", old);
+    transcript.append_message(b"new_commitment", new);
+    transcript.append_message(b"delta_commitment", delta);
+    transcript
+}
+
+/// Transcript for difference proofs, bound to both commitments and the public difference.
+fn difference_transcript(commit_a: &[u8], commit_b: &[u8], diff: u64) -> Transcript {
+    let mut transcript = Transcript::new(b"libzkp_difference");
+    transcript.append_message(b"commitment_a", commit_a);
+    transcript.append_message(b"commitment_b", commit_b);
+    transcript.append_u64(b"diff", diff);
+    transcript
+}
+
+/// Transcript for balance updates, bound to both balance commitments and the public amount.
+fn balance_update_transcript(old: &[u8], new: &[u8], amount: u64) -> Transcript {
+    let mut transcript = Transcript::new(b"libzkp_balance_update");
+    transcript.append_message(b"old_commitment", old);
+    transcript.append_message(b"new_commitment", new);
+    transcript.append_u64(b"amount", amount);
+    transcript
+}
+
 pub struct BulletproofsBackend;
 
 impl BulletproofsBackend {
@@ -114,6 +457,18 @@ impl BulletproofsBackend {
         min: u64,
         max: u64,
         n_bits: usize,
+    ) -> Result<Vec<u8>, String> {
+        let blinding = Zeroizing::new(random_blinding());
+        Self::prove_range_with_blinding(value, min, max, n_bits, &blinding)
+    }
+
+    /// Like `prove_range_with_bounds_bits`, but commits to `value` under a caller-chosen blinding.
+    pub fn prove_range_with_blinding(
+        value: u64,
+        min: u64,
+        max: u64,
+        n_bits: usize,
+        blinding: &Scalar,
     ) -> Result<Vec<u8>, String> {
         if value < min || value > max {
             return Err("value out of range".to_string());
@@ -129,9 +484,7 @@ impl BulletproofsBackend {
         }
 
         let (pc_gens, bp_gens) = bp_gens_pair_bits(n_bits, 2);
-        let blinding = random_blinding();
-
-        let value_commit = pc_gens.commit(Scalar::from(value), blinding).compress();
+        let value_commit = pc_gens.commit(Scalar::from(value), *blinding).compress();
 
         let diff_min_blinding = blinding;
         let mut transcript_min = Transcript::new(b"libzkp_range_min");
@@ -140,12 +493,12 @@ impl BulletproofsBackend {
             &pc_gens,
             &mut transcript_min,
             diff_min,
-            &diff_min_blinding,
+            diff_min_blinding,
             n_bits,
         )
         .map_err(|_| "min range proof generation failed".to_string())?;
 
-        let diff_max_blinding = -blinding;
+        let diff_max_blinding = Zeroizing::new(-blinding);
         let mut transcript_max = Transcript::new(b"libzkp_range_max");
         let (range_proof_max, diff_max_commit) = RangeProof::prove_single(
             &bp_gens,
@@ -300,6 +653,111 @@ impl BulletproofsBackend {
         Self::verify_range_with_bounds_bits(proof_data, min, max)
     }
 
+    /// Verify many `prove_range_with_bounds_bits` proofs at once, each against its own
+    /// `(min, max)`, with one random linear combination of all their verification equations
+    /// (see `range_batch`). Accepts exactly when every proof would pass
+    /// `verify_range_with_bounds_bits`, up to negligible probability, but does not say which
+    /// proof failed.
+    pub fn verify_range_proofs_batch(proofs: &[(&[u8], u64, u64)]) -> bool {
+        let mut statements = Vec::with_capacity(2 * proofs.len());
+        for &(proof_data, min, max) in proofs {
+            match range_statements(proof_data, min, max) {
+                Some(pair) => statements.extend(pair),
+                None => return false,
+            }
+        }
+        range_batch::verify_range_statements(&statements)
+    }
+
+    /// One aggregated proof that every value lies in `[min, max]`: a 64-bit aggregate over
+    /// `v_i - min` (blinding `r_i`) and `max - v_i` (blinding `-r_i`), both of which the
+    /// verifier derives from `V_i`. Body: `[min 8][max 8][u32 n][value commitments 32n]
+    /// [u32 len][range proof]`, commitment: SHA-256 of the value commitments.
+    pub fn prove_range_batch(values: &[u64], min: u64, max: u64) -> Result<Vec<u8>, String> {
+        if values.is_empty() {
+            return Err("values cannot be empty".to_string());
+        }
+        if values.iter().any(|&v| v < min || v > max) {
+            return Err("value out of range".to_string());
+        }
+
+        let blindings = random_blindings(values.len());
+        let mut range_values = Zeroizing::new(Vec::with_capacity(2 * values.len()));
+        let mut range_blindings = Zeroizing::new(Vec::with_capacity(2 * values.len()));
+        for (&v, r) in values.iter().zip(blindings.iter()) {
+            range_values.extend([v - min, max - v]);
+            range_blindings.extend([*r, -r]);
+        }
+        let (range_proof, _) =
+            prove_aggregated_u64(&range_values, &range_blindings, b"libzkp_range_batch")?;
+
+        let mut proof_bytes = Vec::new();
+        proof_bytes.extend_from_slice(&min.to_le_bytes());
+        proof_bytes.extend_from_slice(&max.to_le_bytes());
+        proof_bytes.extend_from_slice(&(values.len() as u32).to_le_bytes());
+        let mut hasher = Sha256::new();
+        for (&v, r) in values.iter().zip(blindings.iter()) {
+            let commit = pedersen_commit(v, r);
+            hasher.update(commit.as_bytes());
+            proof_bytes.extend_from_slice(commit.as_bytes());
+        }
+        write_range_proof(&mut proof_bytes, &range_proof);
+
+        encode_proof_body_with_commit(&proof_bytes, &hasher.finalize())
+    }
+
+    /// Verify a proof from `prove_range_batch` for the public bounds.
+    pub fn verify_range_batch(proof_data: &[u8], min: u64, max: u64) -> bool {
+        let (proof_bytes, commit_slice) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        if read_u64_le(proof_bytes, 0) != Some(min) || read_u64_le(proof_bytes, 8) != Some(max) {
+            return false;
+        }
+        let n = match proof_bytes.get(16..20).and_then(|b| b.try_into().ok()) {
+            Some(b) => u32::from_le_bytes(b) as usize,
+            None => return false,
+        };
+        if n == 0 || 2 * n > MAX_AGGREGATED_RANGE_VALUES {
+            return false;
+        }
+        let commits_end = 20 + 32 * n;
+        let commit_bytes = match proof_bytes.get(20..commits_end) {
+            Some(b) => b,
+            None => return false,
+        };
+        if Sha256::digest(commit_bytes).as_slice() != commit_slice {
+            return false;
+        }
+        let value_points: Vec<RistrettoPoint> = match commit_bytes
+            .chunks(32)
+            .map(|c| parse_compressed_32(c)?.decompress())
+            .collect()
+        {
+            Some(points) => points,
+            None => return false,
+        };
+        let mut reader = &proof_bytes[commits_end..];
+        let range_proof = match read_range_proof(&mut reader) {
+            Some(rp) => rp,
+            None => return false,
+        };
+        if !reader.is_empty() {
+            return false;
+        }
+
+        let base = PedersenGens::default().B;
+        let min_point = Scalar::from(min) * base;
+        let max_point = Scalar::from(max) * base;
+        let commitments: Vec<CompressedRistretto> = value_points
+            .iter()
+            .flat_map(|v| [(v - min_point).compress(), (max_point - v).compress()])
+            .collect();
+
+        verify_aggregated_u64(&range_proof, &commitments, b"libzkp_range_batch")
+    }
+
     /// Threshold proof with default 64-bit width. Delegates to `prove_threshold_bits`.
     pub fn prove_threshold(values: Vec<u64>, threshold: u64) -> Result<Vec<u8>, String> {
         Self::prove_threshold_bits(values, threshold, 64)
@@ -322,6 +780,13 @@ impl BulletproofsBackend {
                 .ok_or_else(|| "integer overflow in sum calculation".to_string())?;
         }
 
+        Self::prove_threshold_sum(sum, threshold, n_bits)
+    }
+
+    /// Threshold proof from an already accumulated `sum`; the wire format only depends on the
+    /// sum, so callers streaming the values never need them all at once. Single-party
+    /// generators suffice for the one range proof.
+    pub fn prove_threshold_sum(sum: u64, threshold: u64, n_bits: usize) -> Result<Vec<u8>, String> {
         if sum < threshold {
             return Err("threshold not met".to_string());
         }
@@ -335,9 +800,9 @@ impl BulletproofsBackend {
             ));
         }
 
-        let (pc_gens, bp_gens) = bp_gens_pair_bits(n_bits, values.len() + 1);
-        let sum_blinding = random_blinding();
-        let sum_commit = pc_gens.commit(Scalar::from(sum), sum_blinding).compress();
+        let (pc_gens, bp_gens) = bp_gens_pair_bits(n_bits, 1);
+        let sum_blinding = Zeroizing::new(random_blinding());
+        let sum_commit = pc_gens.commit(Scalar::from(sum), *sum_blinding).compress();
 
         let diff_blinding = sum_blinding;
         let mut transcript = Transcript::new(b"libzkp_threshold");
@@ -365,264 +830,2270 @@ impl BulletproofsBackend {
         encode_proof_body_with_commit(&proof_bytes, sum_commit.as_bytes())
     }
 
-    pub fn prove_consistency(data: Vec<u64>) -> Result<Vec<u8>, String> {
-        if data.is_empty() {
-            return Err("data cannot be empty".to_string());
+    /// Prove the mean of `values` is at least `threshold`, checked without division as
+    /// `sum >= threshold * n`. Like the threshold proof, the range proof is bound to the sum
+    /// commitment shifted by the scaled threshold, `S - threshold * n * B`.
+    /// Body: `[threshold 8][u32 n][u32 len][range proof]`, commitment: `S`.
+    pub fn prove_average_threshold(values: &[u64], threshold: u64) -> Result<Vec<u8>, String> {
+        if values.is_empty() {
+            return Err("values cannot be empty".to_string());
         }
+        let sum = values
+            .iter()
+            .try_fold(0u64, |acc, &v| acc.checked_add(v))
+            .ok_or_else(|| "integer overflow in sum calculation".to_string())?;
+        let scaled = threshold as u128 * values.len() as u128;
+        let diff = (sum as u128)
+            .checked_sub(scaled)
+            .ok_or_else(|| "average below threshold".to_string())? as u64;
+
+        let (pc_gens, bp_gens) = bp_gens_pair_bits(64, 1);
+        let blinding = Zeroizing::new(random_blinding());
+        let sum_commit = pedersen_commit(sum, &blinding);
+        let mut transcript = Transcript::new(b"libzkp_average_threshold");
+        let (range_proof, _) =
+            RangeProof::prove_single(&bp_gens, &pc_gens, &mut transcript, diff, &blinding, 64)
+                .map_err(|_| "range proof generation failed".to_string())?;
 
-        if data.windows(2).any(|w| w[0] > w[1]) {
-            return Err("data inconsistent".to_string());
-        }
+        let mut proof_bytes = Vec::new();
+        proof_bytes.extend_from_slice(&threshold.to_le_bytes());
+        proof_bytes.extend_from_slice(&(values.len() as u32).to_le_bytes());
+        write_range_proof(&mut proof_bytes, &range_proof);
 
-        let (pc_gens, bp_gens) = bp_gens_pair(data.len() * 2);
-        let mut blindings = Vec::with_capacity(data.len());
-        for _ in 0..data.len() {
-            blindings.push(random_blinding());
+        encode_proof_body_with_commit(&proof_bytes, sum_commit.as_bytes())
+    }
+
+    /// Verify a proof from `prove_average_threshold` over exactly `count` values.
+    pub fn verify_average_threshold(proof_data: &[u8], threshold: u64, count: usize) -> bool {
+        let (proof_bytes, commit_slice) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        if read_u64_le(proof_bytes, 0) != Some(threshold) {
+            return false;
         }
-        let mut commitments = Vec::with_capacity(data.len());
-        for (i, &value) in data.iter().enumerate() {
-            let commit = pc_gens.commit(Scalar::from(value), blindings[i]).compress();
-            commitments.push(commit);
+        match proof_bytes.get(8..12).and_then(|b| b.try_into().ok()) {
+            Some(b) if count > 0 && u32::from_le_bytes(b) as usize == count => {}
+            _ => return false,
+        }
+        let mut reader = &proof_bytes[12..];
+        let range_proof = match read_range_proof(&mut reader) {
+            Some(rp) => rp,
+            None => return false,
+        };
+        if !reader.is_empty() {
+            return false;
         }
+        let sum_point = match commit_slice.get(0..32).and_then(parse_compressed_32) {
+            Some(c) => match c.decompress() {
+                Some(p) => p,
+                None => return false,
+            },
+            None => return false,
+        };
 
-        let mut range_proofs = Vec::new();
-        let mut diff_commitments = Vec::new();
+        let (pc_gens, bp_gens) = bp_gens_pair_bits(64, 1);
+        let scaled = Scalar::from(threshold) * Scalar::from(count as u64);
+        let diff_commit = (sum_point - scaled * pc_gens.B).compress();
+        let mut transcript = Transcript::new(b"libzkp_average_threshold");
+        range_proof
+            .verify_single(&bp_gens, &pc_gens, &mut transcript, &diff_commit, 64)
+            .is_ok()
+    }
 
-        for i in 1..data.len() {
-            let diff = data[i] - data[i - 1];
-            let diff_blinding = blindings[i] - blindings[i - 1];
+    /// Threshold proof that additionally shows every value is `>= 1`. Each value gets a
+    /// commitment `V_i`; the verifier forms `V_i - B` and `sum V_i - threshold * B`, and one
+    /// aggregated 64-bit range proof covers all of them.
+    /// Body: `[threshold 8][u32 n][V_i 32*n][u32 len][aggregated range proof]`,
+    /// commitment: `sum V_i`.
+    pub fn prove_positive_threshold(values: &[u64], threshold: u64) -> Result<Vec<u8>, String> {
+        if values.is_empty() {
+            return Err("values cannot be empty".to_string());
+        }
+        if values.contains(&0) {
+            return Err("values must be positive".to_string());
+        }
+        let sum = values
+            .iter()
+            .try_fold(0u64, |acc, &v| acc.checked_add(v))
+            .ok_or_else(|| "integer overflow in sum calculation".to_string())?;
+        if sum < threshold {
+            return Err("threshold not met".to_string());
+        }
 
-            let mut transcript = Transcript::new(b"libzkp_consistency");
-            let (range_proof, diff_commit) = RangeProof::prove_single(
-                &bp_gens,
-                &pc_gens,
-                &mut transcript,
-                diff,
-                &diff_blinding,
-                64,
-            )
-            .map_err(|_| "range proof generation failed".to_string())?;
+        let blindings = random_blindings(values.len());
+        let sum_blinding = Zeroizing::new(blindings.iter().sum::<Scalar>());
+        let mut range_values: Vec<u64> = values.iter().map(|v| v - 1).collect();
+        range_values.push(sum - threshold);
+        let mut range_blindings = blindings.clone();
+        range_blindings.push(*sum_blinding);
 
-            range_proofs.push(range_proof);
-            diff_commitments.push(diff_commit);
-        }
+        let (range_proof, _) = prove_aggregated_u64(
+            &range_values,
+            &range_blindings,
+            b"libzkp_positive_threshold",
+        )?;
 
         let mut proof_bytes = Vec::new();
+        proof_bytes.extend_from_slice(&threshold.to_le_bytes());
+        proof_bytes.extend_from_slice(&(values.len() as u32).to_le_bytes());
+        for (&v, r) in values.iter().zip(blindings.iter()) {
+            proof_bytes.extend_from_slice(pedersen_commit(v, r).as_bytes());
+        }
+        write_range_proof(&mut proof_bytes, &range_proof);
 
-        proof_bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        let sum_commit = pedersen_commit(sum, &sum_blinding);
+        encode_proof_body_with_commit(&proof_bytes, sum_commit.as_bytes())
+    }
 
-        for commit in &commitments {
-            proof_bytes.extend_from_slice(commit.as_bytes());
+    /// Prove the values committed in the body sum to exactly `total`: with
+    /// `C = sum(C_i) = total * B + r * B_blinding`, a Schnorr proof of knowledge of `r` for
+    /// `C - total * B` shows the committed difference is zero.
+    /// Body: `[total 8][u32 n][value commitments 32n][dlog proof 64]`, commitment: `C`.
+    pub fn prove_exact_sum(values: &[u64], total: u64) -> Result<Vec<u8>, String> {
+        if values.is_empty() {
+            return Err("values cannot be empty".to_string());
         }
-
-        for range_proof in &range_proofs {
-            let rp_bytes = range_proof.to_bytes();
-            proof_bytes.extend_from_slice(&(rp_bytes.len() as u32).to_le_bytes());
-            proof_bytes.extend_from_slice(&rp_bytes);
+        let sum = values
+            .iter()
+            .try_fold(0u64, |acc, &v| acc.checked_add(v))
+            .ok_or_else(|| "integer overflow in sum calculation".to_string())?;
+        if sum != total {
+            return Err("sum does not equal total".to_string());
         }
 
-        for diff_commit in &diff_commitments {
-            proof_bytes.extend_from_slice(diff_commit.as_bytes());
-        }
+        let blindings = random_blindings(values.len());
+        let sum_blinding = Zeroizing::new(blindings.iter().sum::<Scalar>());
+        let pc_gens = PedersenGens::default();
+        let sum_point = pc_gens.commit(Scalar::from(total), *sum_blinding);
 
-        // Commit to the full commitment list with a fixed-size digest (32 bytes),
-        // so it fits our `Proof` serialization limits and can be embedded safely.
-        let mut commitment_bytes = Vec::new();
-        for commit in &commitments {
-            commitment_bytes.extend_from_slice(commit.as_bytes());
+        let mut transcript = Transcript::new(b"libzkp_exact_sum");
+        let dlog = sigma::prove_dlog(
+            &mut transcript,
+            &pc_gens.B_blinding,
+            &(sum_point - Scalar::from(total) * pc_gens.B),
+            &sum_blinding,
+        );
+
+        let mut proof_bytes = Vec::with_capacity(12 + 32 * values.len() + sigma::DLOG_PROOF_BYTES);
+        proof_bytes.extend_from_slice(&total.to_le_bytes());
+        proof_bytes.extend_from_slice(&(values.len() as u32).to_le_bytes());
+        for (&v, r) in values.iter().zip(blindings.iter()) {
+            proof_bytes.extend_from_slice(pedersen_commit(v, r).as_bytes());
         }
-        let commitment_digest: [u8; 32] = Sha256::digest(&commitment_bytes).into();
+        proof_bytes.extend_from_slice(&dlog.to_bytes());
 
-        encode_proof_body_with_commit(&proof_bytes, commitment_digest.as_slice())
+        encode_proof_body_with_commit(&proof_bytes, sum_point.compress().as_bytes())
     }
 
-    pub fn verify_consistency(proof_data: &[u8]) -> bool {
-        let (proof_bytes, commitment_hash) = match decode_proof_body_and_commit(proof_data) {
+    /// Verify a proof from `prove_exact_sum`.
+    pub fn verify_exact_sum(proof_data: &[u8], total: u64) -> bool {
+        let (proof_bytes, commit_slice) = match decode_proof_body_and_commit(proof_data) {
             Some(p) => p,
             None => return false,
         };
-        if commitment_hash.len() != 32 {
+        if read_u64_le(proof_bytes, 0) != Some(total) {
             return false;
         }
-
-        let mut reader = proof_bytes;
-
-        if reader.len() < 4 {
+        let n = match proof_bytes.get(8..12).and_then(|b| b.try_into().ok()) {
+            Some(b) => u32::from_le_bytes(b) as usize,
+            None => return false,
+        };
+        let commits_end = match n.checked_mul(32).and_then(|len| len.checked_add(12)) {
+            Some(end) if n > 0 => end,
+            _ => return false,
+        };
+        if proof_bytes.len() != commits_end + sigma::DLOG_PROOF_BYTES {
             return false;
         }
-        let num_values = match reader[0..4].try_into() {
-            Ok(arr) => u32::from_le_bytes(arr) as usize,
-            Err(_) => return false,
+        let sum_point: RistrettoPoint = match proof_bytes[12..commits_end]
+            .chunks(32)
+            .map(|c| parse_compressed_32(c)?.decompress())
+            .sum::<Option<RistrettoPoint>>()
+        {
+            Some(p) => p,
+            None => return false,
         };
-        reader = &reader[4..];
-
-        if num_values == 0 {
+        if parse_compressed_32(commit_slice) != Some(sum_point.compress()) {
             return false;
         }
+        let dlog = match DlogProof::from_bytes(&proof_bytes[commits_end..]) {
+            Some(d) => d,
+            None => return false,
+        };
 
-        if reader.len() < num_values * 32 {
-            return false;
+        let pc_gens = PedersenGens::default();
+        let mut transcript = Transcript::new(b"libzkp_exact_sum");
+        sigma::verify_dlog(
+            &mut transcript,
+            &pc_gens.B_blinding,
+            &(sum_point - Scalar::from(total) * pc_gens.B),
+            &dlog,
+        )
+    }
+
+    /// Prove `values[max_index]` is at least every other value: with per-value commitments
+    /// `V_i`, one aggregated range proof shows each `V_max - V_i` (i != max_index) opens to a
+    /// non-negative value. Body: `[u32 max_index][u32 n][V_i 32*n][u32 len][aggregated range
+    /// proof]`, commitment: `V_max`.
+    pub fn prove_is_maximum(values: &[u64], max_index: usize) -> Result<Vec<u8>, String> {
+        let max = *values
+            .get(max_index)
+            .ok_or_else(|| "max index out of range".to_string())?;
+        if values.len() < 2 {
+            return Err("at least two values are required".to_string());
         }
-        let mut commitments = Vec::with_capacity(num_values);
-        for _ in 0..num_values {
-            let commit_bytes = &reader[0..32];
-            let commit = match parse_compressed_32(commit_bytes) {
-                Some(c) => c,
-                None => return false,
-            };
-            commitments.push(commit);
-            reader = &reader[32..];
+        if values.iter().any(|&v| v > max) {
+            return Err("value at max index is not the maximum".to_string());
         }
 
-        let mut expected_commitment = Vec::new();
-        for commit in &commitments {
-            expected_commitment.extend_from_slice(commit.as_bytes());
-        }
-        let expected_digest: [u8; 32] = Sha256::digest(&expected_commitment).into();
-        if commitment_hash != expected_digest.as_slice() {
-            return false;
+        let blindings = random_blindings(values.len());
+        let (diffs, diff_blindings): (Vec<u64>, Vec<Scalar>) = values
+            .iter()
+            .zip(blindings.iter())
+            .enumerate()
+            .filter(|(i, _)| *i != max_index)
+            .map(|(_, (&v, r))| (max - v, blindings[max_index] - r))
+            .unzip();
+        let diff_blindings = Zeroizing::new(diff_blindings);
+        let (range_proof, _) = prove_aggregated_u64(&diffs, &diff_blindings, b"libzkp_is_maximum")?;
+
+        let mut proof_bytes = Vec::new();
+        proof_bytes.extend_from_slice(&(max_index as u32).to_le_bytes());
+        proof_bytes.extend_from_slice(&(values.len() as u32).to_le_bytes());
+        for (&v, r) in values.iter().zip(blindings.iter()) {
+            proof_bytes.extend_from_slice(pedersen_commit(v, r).as_bytes());
         }
+        write_range_proof(&mut proof_bytes, &range_proof);
 
-        let (pc_gens, bp_gens) = bp_gens_pair(num_values * 2);
+        let max_commit = pedersen_commit(max, &blindings[max_index]);
+        encode_proof_body_with_commit(&proof_bytes, max_commit.as_bytes())
+    }
 
-        // Read range proofs into memory
-        let mut range_proofs = Vec::with_capacity(num_values.saturating_sub(1));
-        for _i in 1..num_values {
-            if reader.len() < 4 {
-                return false;
-            }
-            let rp_len = match reader[0..4].try_into() {
-                Ok(arr) => u32::from_le_bytes(arr) as usize,
-                Err(_) => return false,
+    /// Verify a proof from `prove_is_maximum`.
+    pub fn verify_is_maximum(proof_data: &[u8]) -> bool {
+        let (proof_bytes, commit_slice) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        let read_u32 = |at: usize| {
+            proof_bytes
+                .get(at..at + 4)
+                .and_then(|b| b.try_into().ok())
+                .map(|b| u32::from_le_bytes(b) as usize)
+        };
+        let (max_index, n) = match (read_u32(0), read_u32(4)) {
+            (Some(i), Some(n)) => (i, n),
+            _ => return false,
+        };
+        if n < 2 || n - 1 > MAX_AGGREGATED_RANGE_VALUES || max_index >= n {
+            return false;
+        }
+        let commits_end = 8 + 32 * n;
+        let points: Vec<RistrettoPoint> = match proof_bytes.get(8..commits_end).and_then(|b| {
+            b.chunks(32)
+                .map(|c| parse_compressed_32(c)?.decompress())
+                .collect()
+        }) {
+            Some(points) => points,
+            None => return false,
+        };
+        let mut reader = &proof_bytes[commits_end..];
+        let range_proof = match read_range_proof(&mut reader) {
+            Some(rp) => rp,
+            None => return false,
+        };
+        if !reader.is_empty() {
+            return false;
+        }
+
+        let max_point = points[max_index];
+        if parse_compressed_32(commit_slice) != Some(max_point.compress()) {
+            return false;
+        }
+        let commitments: Vec<CompressedRistretto> = points
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != max_index)
+            .map(|(_, p)| (max_point - p).compress())
+            .collect();
+
+        verify_aggregated_u64(&range_proof, &commitments, b"libzkp_is_maximum")
+    }
+
+    /// Prove the commitments `[old, new, delta]` satisfy `new == old + delta`: the excess
+    /// `E = C_new - C_old - C_delta` must equal `(r_new - r_old - r_delta) * B_blinding`, and a
+    /// Schnorr proof of that exponent shows the committed values cancel. `blindings` are
+    /// `[old, new, delta]`. Body: `[C_old 32][C_delta 32][dlog proof 64]`, commitment: `C_new`.
+    pub fn prove_exact_delta(
+        commitments: [&[u8]; 3],
+        blindings: &[Scalar; 3],
+    ) -> Result<Vec<u8>, String> {
+        let [old, new, delta] = decompress_delta_commitments(commitments)
+            .ok_or_else(|| "invalid commitment".to_string())?;
+        let [old_blinding, new_blinding, delta_blinding] = blindings;
+        let excess_blinding = Zeroizing::new(new_blinding - old_blinding - delta_blinding);
+        let pc_gens = PedersenGens::default();
+        let excess = new - old - delta;
+        if excess != *excess_blinding * pc_gens.B_blinding {
+            return Err("commitments do not satisfy new == old + delta".to_string());
+        }
+
+        let mut transcript = delta_transcript(commitments);
+        let dlog = sigma::prove_dlog(
+            &mut transcript,
+            &pc_gens.B_blinding,
+            &excess,
+            &excess_blinding,
+        );
+
+        let mut proof_bytes = Vec::with_capacity(64 + sigma::DLOG_PROOF_BYTES);
+        proof_bytes.extend_from_slice(&commitments[0][..32]);
+        proof_bytes.extend_from_slice(&commitments[2][..32]);
+        proof_bytes.extend_from_slice(&dlog.to_bytes());
+        encode_proof_body_with_commit(&proof_bytes, &commitments[1][..32])
+    }
+
+    /// Verify a proof from `prove_exact_delta`; the commitments are read from the proof and
+    /// must be compared against the expected ones by the caller.
+    pub fn verify_exact_delta(proof_data: &[u8]) -> bool {
+        let (proof_bytes, new_commit) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        if proof_bytes.len() != 64 + sigma::DLOG_PROOF_BYTES {
+            return false;
+        }
+        let commitments = [&proof_bytes[0..32], new_commit, &proof_bytes[32..64]];
+        let [old, new, delta] = match decompress_delta_commitments(commitments) {
+            Some(points) => points,
+            None => return false,
+        };
+        let dlog = match DlogProof::from_bytes(&proof_bytes[64..]) {
+            Some(d) => d,
+            None => return false,
+        };
+
+        let mut transcript = delta_transcript(commitments);
+        sigma::verify_dlog(
+            &mut transcript,
+            &PedersenGens::default().B_blinding,
+            &(new - old - delta),
+            &dlog,
+        )
+    }
+
+    /// Prove that the values under `commitments = [a, b]` satisfy `a - b == diff`, given their
+    /// blindings: `A - B - diff*G` must be `(r_a - r_b)*H`, shown with a dlog proof. The
+    /// values are u64, so `a - b == diff` mod the group order only holds for the true
+    /// difference; `b > a` can never be proven.
+    pub fn prove_difference(
+        commitments: [&[u8]; 2],
+        diff: u64,
+        blindings: &[Scalar; 2],
+    ) -> Result<Vec<u8>, String> {
+        let [commit_a, commit_b] = commitments;
+        let decompress = |c: &[u8]| {
+            if c.len() != 32 {
+                return None;
+            }
+            parse_compressed_32(c)?.decompress()
+        };
+        let (a, b) = decompress(commit_a)
+            .zip(decompress(commit_b))
+            .ok_or_else(|| "invalid commitment".to_string())?;
+        let excess_blinding = Zeroizing::new(blindings[0] - blindings[1]);
+        let pc_gens = PedersenGens::default();
+        let excess = a - b - Scalar::from(diff) * pc_gens.B;
+        if excess != *excess_blinding * pc_gens.B_blinding {
+            return Err("commitments do not satisfy a - b == diff".to_string());
+        }
+
+        let mut transcript = difference_transcript(commit_a, commit_b, diff);
+        let dlog = sigma::prove_dlog(
+            &mut transcript,
+            &pc_gens.B_blinding,
+            &excess,
+            &excess_blinding,
+        );
+
+        let mut proof_bytes = Vec::with_capacity(40 + sigma::DLOG_PROOF_BYTES);
+        proof_bytes.extend_from_slice(commit_b);
+        proof_bytes.extend_from_slice(&diff.to_le_bytes());
+        proof_bytes.extend_from_slice(&dlog.to_bytes());
+        encode_proof_body_with_commit(&proof_bytes, commit_a)
+    }
+
+    /// Verify a proof from `prove_difference` for the public `diff`; `commitment_b` is read
+    /// from the proof and must be compared against the expected one by the caller.
+    pub fn verify_difference(proof_data: &[u8], diff: u64) -> bool {
+        let (proof_bytes, commit_a) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        if proof_bytes.len() != 40 + sigma::DLOG_PROOF_BYTES
+            || read_u64_le(proof_bytes, 32) != Some(diff)
+        {
+            return false;
+        }
+        let commit_b = &proof_bytes[0..32];
+        let points = parse_compressed_32(commit_a)
+            .and_then(|c| c.decompress())
+            .zip(parse_compressed_32(commit_b).and_then(|c| c.decompress()));
+        let (a, b) = match points {
+            Some(p) => p,
+            None => return false,
+        };
+        let dlog = match DlogProof::from_bytes(&proof_bytes[40..]) {
+            Some(d) => d,
+            None => return false,
+        };
+
+        let pc_gens = PedersenGens::default();
+        let mut transcript = difference_transcript(commit_a, commit_b, diff);
+        sigma::verify_dlog(
+            &mut transcript,
+            &pc_gens.B_blinding,
+            &(a - b - Scalar::from(diff) * pc_gens.B),
+            &dlog,
+        )
+    }
+
+    /// Prove `a != b` over the Pedersen commitments `A` and `B` under `blindings` `[r_a, r_b]`
+    /// with a non-zero proof on `A - B`. Body: `[B 32][non-zero proof 96]`, commitment: `A`.
+    pub fn prove_inequality(a: u64, b: u64, blindings: &[Scalar; 2]) -> Result<Vec<u8>, String> {
+        let commit_a = pedersen_commit(a, &blindings[0]);
+        let commit_b = pedersen_commit(b, &blindings[1]);
+        let value = Scalar::from(a) - Scalar::from(b);
+        let blinding = blindings[0] - blindings[1];
+        let diff = PedersenGens::default().commit(value, blinding);
+
+        let mut transcript = inequality_transcript(commit_a.as_bytes(), commit_b.as_bytes());
+        let proof = sigma::prove_nonzero(&mut transcript, &diff, &value, &blinding)
+            .ok_or_else(|| "values are equal".to_string())?;
+
+        let mut proof_bytes = Vec::with_capacity(32 + sigma::NONZERO_PROOF_BYTES);
+        proof_bytes.extend_from_slice(commit_b.as_bytes());
+        proof_bytes.extend_from_slice(&proof.to_bytes());
+        encode_proof_body_with_commit(&proof_bytes, commit_a.as_bytes())
+    }
+
+    /// Verify a proof from `prove_inequality`; both commitments are read from the proof and
+    /// must be compared against the expected ones by the caller.
+    pub fn verify_inequality(proof_data: &[u8]) -> bool {
+        let (proof_bytes, commit_a) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        if proof_bytes.len() != 32 + sigma::NONZERO_PROOF_BYTES {
+            return false;
+        }
+        let commit_b = &proof_bytes[..32];
+        let decompress = |c: &[u8]| parse_compressed_32(c)?.decompress();
+        let (a, b) = match (decompress(commit_a), decompress(commit_b)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return false,
+        };
+        let proof = match NonZeroProof::from_bytes(&proof_bytes[32..]) {
+            Some(p) => p,
+            None => return false,
+        };
+        let mut transcript = inequality_transcript(commit_a, commit_b);
+        sigma::verify_nonzero(&mut transcript, &(a - b), &proof)
+    }
+
+    /// Prove `a > b` over the Pedersen commitments `A` and `B` under `blindings` `[r_a, r_b]`,
+    /// revealing neither value. The verifier derives `A - B - G` (blinding `r_a - r_b`); one
+    /// aggregated 64-bit range proof covers `a`, `b` and `a - b - 1`, so the difference cannot
+    /// wrap around the group order. Body: `[B 32][u32 len][aggregated range proof]`,
+    /// commitment: `A`.
+    pub fn prove_greater_than(a: u64, b: u64, blindings: &[Scalar; 2]) -> Result<Vec<u8>, String> {
+        prove_ordered_pair(
+            a,
+            b,
+            blindings,
+            1,
+            b"libzkp_greater_than",
+            "a is not greater than b",
+        )
+    }
+
+    /// Verify a proof from `prove_greater_than`; as for `verify_inequality`, the caller compares
+    /// both commitments against the expected ones.
+    pub fn verify_greater_than(proof_data: &[u8]) -> bool {
+        verify_ordered_pair(proof_data, 1, b"libzkp_greater_than")
+    }
+
+    /// Prove `sum(assets) >= sum(liabilities)` for entries committed under `blindings` (those
+    /// of the asset and the liability entries). The totals are committed under the blinding
+    /// sums, so `A` and `L` are the sums of the entry commitments; one aggregated range proof
+    /// covers `A`, `L` and `A - L` as for `prove_greater_than`, and one per
+    /// `MAX_AGGREGATED_RANGE_VALUES` entries shows every entry is a u64, so no entry can
+    /// commit to a negative amount. Body: `[u32 n_assets][u32 n_liabilities][entry commitments
+    /// 32 each][u32 len][totals range proof]` then `[u32 len][entry range proof]` per chunk,
+    /// commitment: `A`.
+    pub fn prove_solvency(
+        assets: &[u64],
+        liabilities: &[u64],
+        blindings: [&[Scalar]; 2],
+    ) -> Result<Vec<u8>, String> {
+        if assets.is_empty()
+            || liabilities.is_empty()
+            || assets.len() + liabilities.len() > MAX_U64_VEC_LEN
+            || blindings[0].len() != assets.len()
+            || blindings[1].len() != liabilities.len()
+        {
+            return Err("solvency proof needs one blinding per entry".to_string());
+        }
+        let sum = |entries: &[u64]| {
+            entries
+                .iter()
+                .try_fold(0u64, |s, &v| s.checked_add(v))
+                .ok_or_else(|| "entry sum overflows u64".to_string())
+        };
+        let (total_assets, total_liabilities) = (sum(assets)?, sum(liabilities)?);
+        let totals = Zeroizing::new(blindings.map(|b| b.iter().sum::<Scalar>()));
+        let totals_proof = prove_ordered_pair_range(
+            total_assets,
+            total_liabilities,
+            &totals,
+            0,
+            b"libzkp_solvency",
+            "assets are less than liabilities",
+        )?;
+
+        let entries: Vec<u64> = assets.iter().chain(liabilities).copied().collect();
+        let entries = Zeroizing::new(entries);
+        let entry_blindings: Vec<Scalar> = blindings.concat();
+        let entry_blindings = Zeroizing::new(entry_blindings);
+
+        let mut proof_bytes = Vec::new();
+        proof_bytes.extend_from_slice(&(assets.len() as u32).to_le_bytes());
+        proof_bytes.extend_from_slice(&(liabilities.len() as u32).to_le_bytes());
+        for (v, r) in entries.iter().zip(entry_blindings.iter()) {
+            proof_bytes.extend_from_slice(pedersen_commit(*v, r).as_bytes());
+        }
+        write_range_proof(&mut proof_bytes, &totals_proof);
+        for (values, chunk_blindings) in entries
+            .chunks(MAX_AGGREGATED_RANGE_VALUES)
+            .zip(entry_blindings.chunks(MAX_AGGREGATED_RANGE_VALUES))
+        {
+            let (range_proof, _) =
+                prove_aggregated_u64(values, chunk_blindings, b"libzkp_solvency_entries")?;
+            write_range_proof(&mut proof_bytes, &range_proof);
+        }
+
+        let commit_assets = pedersen_commit(total_assets, &totals[0]);
+        encode_proof_body_with_commit(&proof_bytes, commit_assets.as_bytes())
+    }
+
+    /// Verify a proof from `prove_solvency` against the entry commitments it carries; the
+    /// caller compares those with the published ones.
+    pub fn verify_solvency(proof_data: &[u8]) -> bool {
+        let (proof_bytes, commit_slice) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        let read_u32 = |at: usize| {
+            proof_bytes
+                .get(at..at + 4)
+                .and_then(|b| b.try_into().ok())
+                .map(|b| u32::from_le_bytes(b) as usize)
+        };
+        let (n_assets, n_liabilities) = match (read_u32(0), read_u32(4)) {
+            (Some(a), Some(l)) => (a, l),
+            _ => return false,
+        };
+        let n = n_assets + n_liabilities;
+        if n_assets == 0 || n_liabilities == 0 || n > MAX_U64_VEC_LEN {
+            return false;
+        }
+        let commits_end = 8 + 32 * n;
+        let entries: Vec<CompressedRistretto> = match proof_bytes
+            .get(8..commits_end)
+            .and_then(|b| b.chunks(32).map(parse_compressed_32).collect())
+        {
+            Some(entries) => entries,
+            None => return false,
+        };
+        let points: Option<Vec<RistrettoPoint>> = entries.iter().map(|c| c.decompress()).collect();
+        let points = match points {
+            Some(points) => points,
+            None => return false,
+        };
+        let assets: RistrettoPoint = points[..n_assets].iter().sum();
+        let liabilities: RistrettoPoint = points[n_assets..].iter().sum();
+        if parse_compressed_32(commit_slice) != Some(assets.compress()) {
+            return false;
+        }
+
+        let mut reader = &proof_bytes[commits_end..];
+        let totals_proof = match read_range_proof(&mut reader) {
+            Some(rp) => rp,
+            None => return false,
+        };
+        if !verify_ordered_pair_range(assets, liabilities, &totals_proof, 0, b"libzkp_solvency") {
+            return false;
+        }
+        for chunk in entries.chunks(MAX_AGGREGATED_RANGE_VALUES) {
+            let range_proof = match read_range_proof(&mut reader) {
+                Some(rp) => rp,
+                None => return false,
             };
-            reader = &reader[4..];
+            if !verify_aggregated_u64(&range_proof, chunk, b"libzkp_solvency_entries") {
+                return false;
+            }
+        }
+        reader.is_empty()
+    }
+
+    /// Prove the balance `old` committed under `old_blinding` minus the public `amount` is a
+    /// u64 (so `old >= amount`), committed under a fresh blinding: a dlog proof that
+    /// `C_old - C_new - amount*G` is a multiple of `H` and a range proof on `C_new`. Body:
+    /// `[C_old 32][amount 8][dlog 64][u32 len][range proof]`, commitment: `C_new`. Returns
+    /// the proof and the new balance's blinding.
+    pub fn prove_balance_update(
+        old: u64,
+        old_blinding: &Scalar,
+        amount: u64,
+    ) -> Result<(Vec<u8>, Scalar), String> {
+        let new = old
+            .checked_sub(amount)
+            .ok_or_else(|| "amount exceeds the balance".to_string())?;
+        let new_blinding = random_blinding();
+        let (range_proof, commitments) =
+            prove_aggregated_u64(&[new], &[new_blinding], b"libzkp_balance_update_range")?;
+        let commit_old = pedersen_commit(old, old_blinding);
+        let commit_new = commitments[0];
+
+        let pc_gens = PedersenGens::default();
+        let excess_blinding = Zeroizing::new(old_blinding - new_blinding);
+        let mut transcript =
+            balance_update_transcript(commit_old.as_bytes(), commit_new.as_bytes(), amount);
+        let dlog = sigma::prove_dlog(
+            &mut transcript,
+            &pc_gens.B_blinding,
+            &(*excess_blinding * pc_gens.B_blinding),
+            &excess_blinding,
+        );
+
+        let mut proof_bytes = Vec::new();
+        proof_bytes.extend_from_slice(commit_old.as_bytes());
+        proof_bytes.extend_from_slice(&amount.to_le_bytes());
+        proof_bytes.extend_from_slice(&dlog.to_bytes());
+        write_range_proof(&mut proof_bytes, &range_proof);
+        Ok((
+            encode_proof_body_with_commit(&proof_bytes, commit_new.as_bytes())?,
+            new_blinding,
+        ))
+    }
+
+    /// Verify a proof from `prove_balance_update`; both commitments and the amount are read
+    /// from the proof and must be compared against the expected ones by the caller.
+    pub fn verify_balance_update(proof_data: &[u8]) -> bool {
+        let (proof_bytes, commit_new) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        let header = 40 + sigma::DLOG_PROOF_BYTES;
+        let amount = match read_u64_le(proof_bytes, 32) {
+            Some(a) if proof_bytes.len() > header => a,
+            _ => return false,
+        };
+        let commit_old = &proof_bytes[0..32];
+        let points: Option<Vec<RistrettoPoint>> = [commit_old, commit_new]
+            .into_iter()
+            .map(|c| parse_compressed_32(c)?.decompress())
+            .collect();
+        let (old, new) = match points.as_deref() {
+            Some([old, new]) => (*old, *new),
+            _ => return false,
+        };
+        let dlog = match DlogProof::from_bytes(&proof_bytes[40..header]) {
+            Some(d) => d,
+            None => return false,
+        };
+        let mut reader = &proof_bytes[header..];
+        let range_proof = match read_range_proof(&mut reader) {
+            Some(rp) if reader.is_empty() => rp,
+            _ => return false,
+        };
+
+        let pc_gens = PedersenGens::default();
+        let mut transcript = balance_update_transcript(commit_old, commit_new, amount);
+        sigma::verify_dlog(
+            &mut transcript,
+            &pc_gens.B_blinding,
+            &(old - new - Scalar::from(amount) * pc_gens.B),
+            &dlog,
+        ) && verify_aggregated_u64(
+            &range_proof,
+            &[new.compress()],
+            b"libzkp_balance_update_range",
+        )
+    }
+
+    /// Verify a proof from `prove_positive_threshold`.
+    pub fn verify_positive_threshold(proof_data: &[u8], threshold: u64) -> bool {
+        let (proof_bytes, commit_slice) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        if read_u64_le(proof_bytes, 0) != Some(threshold) {
+            return false;
+        }
+        let n = match proof_bytes.get(8..12).and_then(|b| b.try_into().ok()) {
+            Some(b) => u32::from_le_bytes(b) as usize,
+            None => return false,
+        };
+        if n == 0 || n + 1 > MAX_AGGREGATED_RANGE_VALUES {
+            return false;
+        }
+        let commits_end = 12 + 32 * n;
+        let value_points: Vec<RistrettoPoint> =
+            match proof_bytes.get(12..commits_end).and_then(|b| {
+                b.chunks(32)
+                    .map(|c| parse_compressed_32(c)?.decompress())
+                    .collect()
+            }) {
+                Some(points) => points,
+                None => return false,
+            };
+        let mut reader = &proof_bytes[commits_end..];
+        let range_proof = match read_range_proof(&mut reader) {
+            Some(rp) => rp,
+            None => return false,
+        };
+        if !reader.is_empty() {
+            return false;
+        }
+
+        let sum_point: RistrettoPoint = value_points.iter().sum();
+        if parse_compressed_32(commit_slice) != Some(sum_point.compress()) {
+            return false;
+        }
+        let base = PedersenGens::default().B;
+        let mut commitments: Vec<CompressedRistretto> =
+            value_points.iter().map(|p| (p - base).compress()).collect();
+        commitments.push((sum_point - Scalar::from(threshold) * base).compress());
+
+        verify_aggregated_u64(&range_proof, &commitments, b"libzkp_positive_threshold")
+    }
+
+    pub fn prove_consistency(data: Vec<u64>) -> Result<Vec<u8>, String> {
+        if data.is_empty() {
+            return Err("data cannot be empty".to_string());
+        }
+
+        if data.windows(2).any(|w| w[0] > w[1]) {
+            return Err("data inconsistent".to_string());
+        }
+
+        let (pc_gens, bp_gens) = bp_gens_pair(data.len() * 2);
+        let blindings = random_blindings(data.len());
+        let mut commitments = Vec::with_capacity(data.len());
+        for (i, &value) in data.iter().enumerate() {
+            let commit = pc_gens.commit(Scalar::from(value), blindings[i]).compress();
+            commitments.push(commit);
+        }
+
+        let mut range_proofs = Vec::new();
+        let mut diff_commitments = Vec::new();
+
+        for i in 1..data.len() {
+            let diff = data[i] - data[i - 1];
+            let diff_blinding = Zeroizing::new(blindings[i] - blindings[i - 1]);
+
+            let mut transcript = Transcript::new(b"libzkp_consistency");
+            let (range_proof, diff_commit) = RangeProof::prove_single(
+                &bp_gens,
+                &pc_gens,
+                &mut transcript,
+                diff,
+                &diff_blinding,
+                64,
+            )
+            .map_err(|_| "range proof generation failed".to_string())?;
+
+            range_proofs.push(range_proof);
+            diff_commitments.push(diff_commit);
+        }
+
+        let mut proof_bytes = Vec::new();
+
+        proof_bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+
+        for commit in &commitments {
+            proof_bytes.extend_from_slice(commit.as_bytes());
+        }
+
+        for range_proof in &range_proofs {
+            let rp_bytes = range_proof.to_bytes();
+            proof_bytes.extend_from_slice(&(rp_bytes.len() as u32).to_le_bytes());
+            proof_bytes.extend_from_slice(&rp_bytes);
+        }
+
+        for diff_commit in &diff_commitments {
+            proof_bytes.extend_from_slice(diff_commit.as_bytes());
+        }
+
+        // Commit to the full commitment list with a fixed-size digest (32 bytes),
+        // so it fits our `Proof` serialization limits and can be embedded safely.
+        let mut commitment_bytes = Vec::new();
+        for commit in &commitments {
+            commitment_bytes.extend_from_slice(commit.as_bytes());
+        }
+        let commitment_digest: [u8; 32] = Sha256::digest(&commitment_bytes).into();
+
+        encode_proof_body_with_commit(&proof_bytes, commitment_digest.as_slice())
+    }
+
+    pub fn verify_consistency(proof_data: &[u8]) -> bool {
+        Self::verify_consistency_steps(proof_data, 0, None)
+    }
+
+    /// Verify only the range proofs of steps `from_index -> from_index + 1` onward. The value
+    /// commitments `0..=from_index` must hash to `prefix_digest`, the commitment digest of an
+    /// already verified proof over that prefix; every step's difference commitment is still
+    /// checked against the value commitments.
+    pub fn verify_consistency_from(
+        proof_data: &[u8],
+        from_index: usize,
+        prefix_digest: &[u8],
+    ) -> bool {
+        Self::verify_consistency_steps(proof_data, from_index, Some(prefix_digest))
+    }
+
+    /// SHA-256 of value commitments `0..=through_index` of a consistency proof, as
+    /// [`Self::verify_consistency_from`] expects; `None` if malformed or out of range.
+    pub fn consistency_prefix_digest(proof_data: &[u8], through_index: usize) -> Option<[u8; 32]> {
+        let (proof_bytes, _) = decode_proof_body_and_commit(proof_data)?;
+        let num_values = u32::from_le_bytes(proof_bytes.get(0..4)?.try_into().ok()?) as usize;
+        if through_index >= num_values {
+            return None;
+        }
+        let prefix = proof_bytes.get(4..4 + (through_index + 1) * 32)?;
+        Some(Sha256::digest(prefix).into())
+    }
+
+    fn verify_consistency_steps(
+        proof_data: &[u8],
+        from_index: usize,
+        prefix_digest: Option<&[u8]>,
+    ) -> bool {
+        let (proof_bytes, commitment_hash) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        if commitment_hash.len() != 32 {
+            return false;
+        }
+
+        let mut reader = proof_bytes;
+
+        if reader.len() < 4 {
+            return false;
+        }
+        let num_values = match reader[0..4].try_into() {
+            Ok(arr) => u32::from_le_bytes(arr) as usize,
+            Err(_) => return false,
+        };
+        reader = &reader[4..];
+
+        if num_values == 0 || from_index >= num_values {
+            return false;
+        }
+
+        if reader.len() < num_values * 32 {
+            return false;
+        }
+        let mut commitments = Vec::with_capacity(num_values);
+        for _ in 0..num_values {
+            let commit_bytes = &reader[0..32];
+            let commit = match parse_compressed_32(commit_bytes) {
+                Some(c) => c,
+                None => return false,
+            };
+            commitments.push(commit);
+            reader = &reader[32..];
+        }
+
+        let mut expected_commitment = Vec::new();
+        for commit in &commitments {
+            expected_commitment.extend_from_slice(commit.as_bytes());
+        }
+        let expected_digest: [u8; 32] = Sha256::digest(&expected_commitment).into();
+        if commitment_hash != expected_digest.as_slice() {
+            return false;
+        }
+        if let Some(prefix_digest) = prefix_digest {
+            let prefix_len = (from_index + 1) * 32;
+            let digest: [u8; 32] = Sha256::digest(&expected_commitment[..prefix_len]).into();
+            if prefix_digest != digest.as_slice() {
+                return false;
+            }
+        }
+
+        let (pc_gens, bp_gens) = bp_gens_pair(num_values * 2);
+
+        // Read range proofs into memory
+        let mut range_proofs = Vec::with_capacity(num_values.saturating_sub(1));
+        for _i in 1..num_values {
+            if reader.len() < 4 {
+                return false;
+            }
+            let rp_len = match reader[0..4].try_into() {
+                Ok(arr) => u32::from_le_bytes(arr) as usize,
+                Err(_) => return false,
+            };
+            reader = &reader[4..];
+
+            if reader.len() < rp_len {
+                return false;
+            }
+            let rp_bytes = &reader[0..rp_len];
+            let range_proof = match RangeProof::from_bytes(rp_bytes) {
+                Ok(rp) => rp,
+                Err(_) => return false,
+            };
+            range_proofs.push(range_proof);
+            reader = &reader[rp_len..];
+        }
+
+        for i in 1..num_values {
+            if reader.len() < 32 {
+                return false;
+            }
+            let diff_commit = match parse_compressed_32(reader) {
+                Some(c) => c,
+                None => return false,
+            };
+            reader = &reader[32..];
+
+            let commit_i = commitments[i].decompress();
+            let commit_prev = commitments[i - 1].decompress();
+
+            if commit_i.is_none() || commit_prev.is_none() {
+                return false;
+            }
+
+            let expected_diff = match (commit_i, commit_prev) {
+                (Some(ci), Some(cp)) => ci - cp,
+                _ => return false,
+            };
+            if expected_diff.compress() != diff_commit {
+                return false;
+            }
+            if i <= from_index {
+                continue;
+            }
+            // Verify non-negativity of the difference via the corresponding range proof
+            let mut transcript = Transcript::new(b"libzkp_consistency");
+            if range_proofs[i - 1]
+                .verify_single(&bp_gens, &pc_gens, &mut transcript, &diff_commit, 64)
+                .is_err()
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Prove each step `data[i] - data[i-1]` lies in `[0, max_step]`: per step, an aggregated
+    /// proof that both the step and `max_step - step` are non-negative. Body:
+    /// `[max_step 8][u32 n][value commitments 32n]([u32 len][range proof])*(n-1)`; the outer
+    /// commitment is the SHA-256 digest of the value commitments, as in `prove_consistency`.
+    pub fn prove_bounded_consistency(data: &[u64], max_step: u64) -> Result<Vec<u8>, String> {
+        if data.is_empty() {
+            return Err("data cannot be empty".to_string());
+        }
+        if data
+            .windows(2)
+            .any(|w| w[0] > w[1] || w[1] - w[0] > max_step)
+        {
+            return Err("data step out of bounds".to_string());
+        }
+
+        let blindings = random_blindings(data.len());
+        let commitments: Vec<CompressedRistretto> = data
+            .iter()
+            .zip(blindings.iter())
+            .map(|(&v, r)| pedersen_commit(v, r))
+            .collect();
+
+        let mut proof_bytes = Vec::new();
+        proof_bytes.extend_from_slice(&max_step.to_le_bytes());
+        proof_bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        for commit in &commitments {
+            proof_bytes.extend_from_slice(commit.as_bytes());
+        }
+        for i in 1..data.len() {
+            let step = data[i] - data[i - 1];
+            let step_blinding = blindings[i] - blindings[i - 1];
+            let (range_proof, _) = prove_aggregated_u64(
+                &[step, max_step - step],
+                &[step_blinding, -step_blinding],
+                b"libzkp_bounded_consistency",
+            )?;
+            write_range_proof(&mut proof_bytes, &range_proof);
+        }
+
+        let commitment_digest: [u8; 32] =
+            Sha256::digest(&proof_bytes[12..12 + 32 * data.len()]).into();
+        encode_proof_body_with_commit(&proof_bytes, commitment_digest.as_slice())
+    }
+
+    /// Verify a proof from `prove_bounded_consistency`.
+    pub fn verify_bounded_consistency(proof_data: &[u8], max_step: u64) -> bool {
+        let (proof_bytes, commitment_hash) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        if read_u64_le(proof_bytes, 0) != Some(max_step) {
+            return false;
+        }
+        let n = match proof_bytes.get(8..12).and_then(|b| b.try_into().ok()) {
+            Some(b) => u32::from_le_bytes(b) as usize,
+            None => return false,
+        };
+        if n == 0 {
+            return false;
+        }
+        let commits = match n
+            .checked_mul(32)
+            .and_then(|len| proof_bytes.get(12..12 + len))
+        {
+            Some(c) => c,
+            None => return false,
+        };
+        let expected_digest: [u8; 32] = Sha256::digest(commits).into();
+        if commitment_hash != expected_digest.as_slice() {
+            return false;
+        }
+        let points: Vec<RistrettoPoint> = match commits
+            .chunks(32)
+            .map(|c| parse_compressed_32(c)?.decompress())
+            .collect()
+        {
+            Some(points) => points,
+            None => return false,
+        };
+
+        let bound = Scalar::from(max_step) * PedersenGens::default().B;
+        let mut reader = &proof_bytes[12 + commits.len()..];
+        for pair in points.windows(2) {
+            let range_proof = match read_range_proof(&mut reader) {
+                Some(rp) => rp,
+                None => return false,
+            };
+            let step = pair[1] - pair[0];
+            let step_commitments = [step.compress(), (bound - step).compress()];
+            if !verify_aggregated_u64(
+                &range_proof,
+                &step_commitments,
+                b"libzkp_bounded_consistency",
+            ) {
+                return false;
+            }
+        }
+        reader.is_empty()
+    }
+
+    /// Prove the median of `sorted` (ascending; the lower median for an even count) exceeds
+    /// `threshold`. As in `prove_consistency`, the verifier forms each step `V_i - V_{i-1}`
+    /// from adjacent value commitments; one aggregated range proof covers the steps and
+    /// `V_m - (threshold + 1)*B`. Body: `[threshold 8][u32 n][value commitments 32n][u32 len]
+    /// [aggregated range proof]`; the outer commitment is the SHA-256 digest of the value
+    /// commitments.
+    pub fn prove_median_threshold(sorted: &[u64], threshold: u64) -> Result<Vec<u8>, String> {
+        if sorted.is_empty() || sorted.len() > MAX_AGGREGATED_RANGE_VALUES {
+            return Err(format!(
+                "median threshold supports 1 to {} values",
+                MAX_AGGREGATED_RANGE_VALUES
+            ));
+        }
+        if sorted.windows(2).any(|w| w[0] > w[1]) {
+            return Err("values are not sorted".to_string());
+        }
+        let median_index = (sorted.len() - 1) / 2;
+        let gap = threshold
+            .checked_add(1)
+            .and_then(|bound| sorted[median_index].checked_sub(bound))
+            .ok_or_else(|| "median does not exceed threshold".to_string())?;
+
+        let blindings = random_blindings(sorted.len());
+        let (mut values, mut range_blindings): (Vec<u64>, Vec<Scalar>) = (1..sorted.len())
+            .map(|i| (sorted[i] - sorted[i - 1], blindings[i] - blindings[i - 1]))
+            .unzip();
+        values.push(gap);
+        range_blindings.push(blindings[median_index]);
+        let (range_proof, _) =
+            prove_aggregated_u64(&values, &range_blindings, b"libzkp_median_threshold")?;
+
+        let mut proof_bytes = Vec::new();
+        proof_bytes.extend_from_slice(&threshold.to_le_bytes());
+        proof_bytes.extend_from_slice(&(sorted.len() as u32).to_le_bytes());
+        for (&v, r) in sorted.iter().zip(blindings.iter()) {
+            proof_bytes.extend_from_slice(pedersen_commit(v, r).as_bytes());
+        }
+        write_range_proof(&mut proof_bytes, &range_proof);
+
+        let commitment_digest: [u8; 32] =
+            Sha256::digest(&proof_bytes[12..12 + 32 * sorted.len()]).into();
+        encode_proof_body_with_commit(&proof_bytes, commitment_digest.as_slice())
+    }
+
+    /// Verify a proof from `prove_median_threshold`.
+    pub fn verify_median_threshold(proof_data: &[u8], threshold: u64) -> bool {
+        let (proof_bytes, commitment_hash) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        if read_u64_le(proof_bytes, 0) != Some(threshold) {
+            return false;
+        }
+        let n = match proof_bytes.get(8..12).and_then(|b| b.try_into().ok()) {
+            Some(b) => u32::from_le_bytes(b) as usize,
+            None => return false,
+        };
+        if n == 0 || n > MAX_AGGREGATED_RANGE_VALUES {
+            return false;
+        }
+        let commits = match proof_bytes.get(12..12 + 32 * n) {
+            Some(c) => c,
+            None => return false,
+        };
+        let expected_digest: [u8; 32] = Sha256::digest(commits).into();
+        if commitment_hash != expected_digest.as_slice() {
+            return false;
+        }
+        let points: Vec<RistrettoPoint> = match commits
+            .chunks(32)
+            .map(|c| parse_compressed_32(c)?.decompress())
+            .collect()
+        {
+            Some(points) => points,
+            None => return false,
+        };
+        let mut reader = &proof_bytes[12 + commits.len()..];
+        let range_proof = match read_range_proof(&mut reader) {
+            Some(rp) if reader.is_empty() => rp,
+            _ => return false,
+        };
+
+        let bound = (Scalar::from(threshold) + Scalar::ONE) * PedersenGens::default().B;
+        let mut commitments: Vec<CompressedRistretto> = points
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).compress())
+            .collect();
+        commitments.push((points[(n - 1) / 2] - bound).compress());
+        verify_aggregated_u64(&range_proof, &commitments, b"libzkp_median_threshold")
+    }
+
+    /// Universal threshold verifier: reads n_bits from the wire format.
+    pub fn verify_threshold(proof_data: &[u8], threshold: u64) -> bool {
+        let (proof_bytes, sum_commit_slice) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+
+        let mut reader = proof_bytes;
+        if reader.len() < 12 {
+            return false;
+        }
+        let proof_threshold = match read_u64_le(reader, 0) {
+            Some(v) => v,
+            None => return false,
+        };
+        if proof_threshold != threshold {
+            return false;
+        }
+        let n_bits = u32::from_le_bytes(match reader[8..12].try_into() {
+            Ok(arr) => arr,
+            Err(_) => return false,
+        }) as usize;
+        reader = &reader[12..];
+
+        if reader.len() < 4 {
+            return false;
+        }
+        let rp_len = u32::from_le_bytes(match reader[0..4].try_into() {
+            Ok(arr) => arr,
+            Err(_) => return false,
+        }) as usize;
+        reader = &reader[4..];
+
+        if reader.len() < rp_len {
+            return false;
+        }
+        let range_proof = match RangeProof::from_bytes(&reader[0..rp_len]) {
+            Ok(rp) => rp,
+            Err(_) => return false,
+        };
+        reader = &reader[rp_len..];
+
+        if reader.len() < 32 {
+            return false;
+        }
+        let diff_commit = match parse_compressed_32(reader) {
+            Some(c) => c,
+            None => return false,
+        };
+
+        let (pc_gens, bp_gens) = bp_gens_pair_bits(n_bits, 2);
+
+        let sum_commit = match parse_compressed_32(sum_commit_slice) {
+            Some(c) => c,
+            None => return false,
+        };
+        let sum_commit_point = match sum_commit.decompress() {
+            Some(p) => p,
+            None => return false,
+        };
+        let expected_diff_commit =
+            (sum_commit_point - (Scalar::from(threshold) * pc_gens.B)).compress();
+
+        if expected_diff_commit != diff_commit {
+            return false;
+        }
+
+        let mut transcript = Transcript::new(b"libzkp_threshold");
+        range_proof
+            .verify_single(
+                &bp_gens,
+                &pc_gens,
+                &mut transcript,
+                &expected_diff_commit,
+                n_bits,
+            )
+            .is_ok()
+    }
+}
+
+impl BulletproofsBackend {
+    /// Prove that two multisets are equal without revealing them.
+    /// Each set is committed as `multiset_root(set, r)` under `blindings` `[r_a, r_b]`; when the
+    /// sets match the roots differ only by `(r_a - r_b) * B_blinding`, which is shown with a
+    /// Schnorr proof. Body: `[root_b 32][dlog proof 64]`, commitment: `root_a`.
+    pub fn prove_set_equality(
+        set_a: &[u64],
+        set_b: &[u64],
+        blindings: &[Scalar; 2],
+    ) -> Result<Vec<u8>, String> {
+        let root_a = multiset_root(set_a, &blindings[0]);
+        let root_b = multiset_root(set_b, &blindings[1]);
+        let excess_blinding = Zeroizing::new(blindings[0] - blindings[1]);
+        let b_blinding = PedersenGens::default().B_blinding;
+        if root_a - root_b != *excess_blinding * b_blinding {
+            return Err("sets are not equal".to_string());
+        }
+
+        let mut transcript = Transcript::new(b"libzkp_set_equality");
+        let dlog = sigma::prove_dlog(
+            &mut transcript,
+            &b_blinding,
+            &(root_a - root_b),
+            &excess_blinding,
+        );
+
+        let mut proof_bytes = Vec::with_capacity(32 + sigma::DLOG_PROOF_BYTES);
+        proof_bytes.extend_from_slice(root_b.compress().as_bytes());
+        proof_bytes.extend_from_slice(&dlog.to_bytes());
+
+        encode_proof_body_with_commit(&proof_bytes, root_a.compress().as_bytes())
+    }
+
+    /// Prove the public list `shuffled` is a reordering of the hidden list `original`, whose
+    /// root is `multiset_root(original, blinding)` as for `prove_set_equality`. Since `shuffled`
+    /// is public, the verifier computes its multiset hash itself, and a Schnorr proof shows
+    /// `root - hash(shuffled)` is a multiple of `B_blinding`. Body:
+    /// `[u32 n][shuffled 8n][dlog proof 64]`, commitment: `root`.
+    pub fn prove_shuffle(
+        original: &[u64],
+        shuffled: &[u64],
+        blinding: &Scalar,
+    ) -> Result<Vec<u8>, String> {
+        let b_blinding = PedersenGens::default().B_blinding;
+        let root = multiset_root(original, blinding);
+        let excess = root - sigma::multiset_hash(shuffled);
+        if excess != blinding * b_blinding {
+            return Err("lists are not permutations of each other".to_string());
+        }
+
+        let mut transcript = Transcript::new(b"libzkp_shuffle");
+        let dlog = sigma::prove_dlog(&mut transcript, &b_blinding, &excess, blinding);
+
+        let mut proof_bytes = Vec::with_capacity(4 + 8 * shuffled.len() + sigma::DLOG_PROOF_BYTES);
+        proof_bytes.extend_from_slice(&(shuffled.len() as u32).to_le_bytes());
+        for v in shuffled {
+            proof_bytes.extend_from_slice(&v.to_le_bytes());
+        }
+        proof_bytes.extend_from_slice(&dlog.to_bytes());
+
+        encode_proof_body_with_commit(&proof_bytes, root.compress().as_bytes())
+    }
+
+    /// Verify a proof from `prove_shuffle`; the caller compares the root and the embedded list
+    /// against the expected ones.
+    pub fn verify_shuffle(proof_data: &[u8]) -> bool {
+        let (proof_bytes, root_slice) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        let n = match proof_bytes.get(0..4).and_then(|b| b.try_into().ok()) {
+            Some(b) => u32::from_le_bytes(b) as usize,
+            None => return false,
+        };
+        if n == 0 || n > MAX_U64_VEC_LEN || proof_bytes.len() != 4 + 8 * n + sigma::DLOG_PROOF_BYTES
+        {
+            return false;
+        }
+        let shuffled: Vec<u64> = proof_bytes[4..4 + 8 * n]
+            .chunks_exact(8)
+            .filter_map(|c| read_u64_le(c, 0))
+            .collect();
+        let root = match parse_compressed_32(root_slice).and_then(|c| c.decompress()) {
+            Some(p) => p,
+            None => return false,
+        };
+        let dlog = match DlogProof::from_bytes(&proof_bytes[4 + 8 * n..]) {
+            Some(d) => d,
+            None => return false,
+        };
+
+        let pc_gens = PedersenGens::default();
+        let mut transcript = Transcript::new(b"libzkp_shuffle");
+        sigma::verify_dlog(
+            &mut transcript,
+            &pc_gens.B_blinding,
+            &(root - sigma::multiset_hash(&shuffled)),
+            &dlog,
+        )
+    }
+
+    /// Prove `set_a` and `set_b` share no element: every element has a Pedersen commitment
+    /// under its blinding in `blindings` `[set_a's, set_b's]`, and for each pair `(a_i, b_j)` a
+    /// non-zero proof on `A_i - B_j` shows `a_i != b_j`. Each root is the SHA-256 digest of its
+    /// set's commitments (see `element_commitments`). Body:
+    /// `[root_b 32][u32 n_a][u32 n_b][A_i 32*n_a][B_j 32*n_b][non-zero proof 96]*(n_a*n_b)`,
+    /// commitment: `root_a`.
+    pub fn prove_disjoint(
+        set_a: &[u64],
+        set_b: &[u64],
+        blindings: [&[Scalar]; 2],
+    ) -> Result<Vec<u8>, String> {
+        let [blindings_a, blindings_b] = blindings;
+        if set_a.is_empty() || set_b.is_empty() {
+            return Err("sets cannot be empty".to_string());
+        }
+        if set_a.len() != blindings_a.len() || set_b.len() != blindings_b.len() {
+            return Err("need one blinding per element".to_string());
+        }
+        if set_a.len().saturating_mul(set_b.len()) > MAX_DISJOINT_PAIRS {
+            return Err(format!(
+                "at most {} element pairs supported",
+                MAX_DISJOINT_PAIRS
+            ));
+        }
+        let (commits_a, root_a) = element_commitments(set_a, blindings_a);
+        let (commits_b, root_b) = element_commitments(set_b, blindings_b);
+
+        let mut proof_bytes = Vec::new();
+        proof_bytes.extend_from_slice(&root_b);
+        proof_bytes.extend_from_slice(&(set_a.len() as u32).to_le_bytes());
+        proof_bytes.extend_from_slice(&(set_b.len() as u32).to_le_bytes());
+        proof_bytes.extend_from_slice(&commits_a);
+        proof_bytes.extend_from_slice(&commits_b);
+
+        let pc_gens = PedersenGens::default();
+        let mut transcript = disjoint_transcript(&root_a, &root_b);
+        for (&a, r_a) in set_a.iter().zip(blindings_a.iter()) {
+            for (&b, r_b) in set_b.iter().zip(blindings_b.iter()) {
+                let value = Scalar::from(a) - Scalar::from(b);
+                let blinding = r_a - r_b;
+                let diff = pc_gens.commit(value, blinding);
+                let proof = sigma::prove_nonzero(&mut transcript, &diff, &value, &blinding)
+                    .ok_or_else(|| "sets share an element".to_string())?;
+                proof_bytes.extend_from_slice(&proof.to_bytes());
+            }
+        }
+
+        encode_proof_body_with_commit(&proof_bytes, &root_a)
+    }
+
+    /// Verify a proof from `prove_disjoint`; the roots are read from the proof and must be
+    /// compared against the expected ones by the caller.
+    pub fn verify_disjoint(proof_data: &[u8]) -> bool {
+        let (proof_bytes, root_a) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        let read_u32 = |at: usize| {
+            proof_bytes
+                .get(at..at + 4)
+                .and_then(|b| b.try_into().ok())
+                .map(|b| u32::from_le_bytes(b) as usize)
+        };
+        let (n_a, n_b) = match (read_u32(32), read_u32(36)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return false,
+        };
+        if n_a == 0 || n_b == 0 || n_a.saturating_mul(n_b) > MAX_DISJOINT_PAIRS {
+            return false;
+        }
+        let commits_end = 40 + 32 * (n_a + n_b);
+        if proof_bytes.len() != commits_end + sigma::NONZERO_PROOF_BYTES * n_a * n_b {
+            return false;
+        }
+        let commits_a = &proof_bytes[40..40 + 32 * n_a];
+        let commits_b = &proof_bytes[40 + 32 * n_a..commits_end];
+        let expected_a: [u8; 32] = Sha256::digest(commits_a).into();
+        let expected_b: [u8; 32] = Sha256::digest(commits_b).into();
+        if root_a != expected_a.as_slice() || proof_bytes[0..32] != expected_b {
+            return false;
+        }
+        let decompress = |commits: &[u8]| -> Option<Vec<RistrettoPoint>> {
+            commits
+                .chunks(32)
+                .map(|c| parse_compressed_32(c)?.decompress())
+                .collect()
+        };
+        let (points_a, points_b) = match (decompress(commits_a), decompress(commits_b)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return false,
+        };
+
+        let mut transcript = disjoint_transcript(&expected_a, &expected_b);
+        let mut proofs = proof_bytes[commits_end..].chunks(sigma::NONZERO_PROOF_BYTES);
+        for a in &points_a {
+            for b in &points_b {
+                let proof = match proofs.next().and_then(NonZeroProof::from_bytes) {
+                    Some(p) => p,
+                    None => return false,
+                };
+                if !sigma::verify_nonzero(&mut transcript, &(a - b), &proof) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Verify a proof produced by `prove_set_equality` against its embedded roots.
+    pub fn verify_set_equality(proof_data: &[u8]) -> bool {
+        let (proof_bytes, root_a_slice) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        if proof_bytes.len() != 32 + sigma::DLOG_PROOF_BYTES {
+            return false;
+        }
+        let root_a = match parse_compressed_32(root_a_slice).and_then(|c| c.decompress()) {
+            Some(p) => p,
+            None => return false,
+        };
+        let root_b = match parse_compressed_32(proof_bytes).and_then(|c| c.decompress()) {
+            Some(p) => p,
+            None => return false,
+        };
+        let dlog = match DlogProof::from_bytes(&proof_bytes[32..]) {
+            Some(d) => d,
+            None => return false,
+        };
+
+        let pc_gens = PedersenGens::default();
+        let mut transcript = Transcript::new(b"libzkp_set_equality");
+        sigma::verify_dlog(
+            &mut transcript,
+            &pc_gens.B_blinding,
+            &(root_a - root_b),
+            &dlog,
+        )
+    }
+
+    /// Prove the committed value equals `option_a` or `option_b` without saying which.
+    /// With `C = value * B + r * B_blinding`, exactly one of `C - option_a * B` and
+    /// `C - option_b * B` is a multiple of `B_blinding`; a two-branch OR proof shows that.
+    /// Body: `[option_a 8][option_b 8][OR proof 128]`, commitment: `C`.
+    pub fn prove_binary_choice(
+        value: u64,
+        option_a: u64,
+        option_b: u64,
+    ) -> Result<Vec<u8>, String> {
+        if option_a == option_b {
+            return Err("options must differ".to_string());
+        }
+        let known = if value == option_a {
+            0
+        } else if value == option_b {
+            1
+        } else {
+            return Err("value is neither option".to_string());
+        };
+        let pc_gens = PedersenGens::default();
+        let blinding = Zeroizing::new(random_blinding());
+        let commit = pc_gens.commit(Scalar::from(value), *blinding);
+        let branches = [
+            commit - Scalar::from(option_a) * pc_gens.B,
+            commit - Scalar::from(option_b) * pc_gens.B,
+        ];
+        let mut transcript = Transcript::new(b"libzkp_binary_choice");
+        let or_proof = sigma::prove_or_dlog(
+            &mut transcript,
+            &pc_gens.B_blinding,
+            &branches,
+            known,
+            &blinding,
+        );
+
+        let mut proof_bytes = Vec::new();
+        proof_bytes.extend_from_slice(&option_a.to_le_bytes());
+        proof_bytes.extend_from_slice(&option_b.to_le_bytes());
+        proof_bytes.extend_from_slice(&or_proof.to_bytes());
+
+        encode_proof_body_with_commit(&proof_bytes, commit.compress().as_bytes())
+    }
+
+    /// Verify a proof from `prove_binary_choice` for the given options.
+    pub fn verify_binary_choice(proof_data: &[u8], option_a: u64, option_b: u64) -> bool {
+        if option_a == option_b {
+            return false;
+        }
+        let (proof_bytes, commit_slice) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        if read_u64_le(proof_bytes, 0) != Some(option_a)
+            || read_u64_le(proof_bytes, 8) != Some(option_b)
+        {
+            return false;
+        }
+        let or_proof = match OrDlogProof::from_bytes(&proof_bytes[16..], 2) {
+            Some(p) => p,
+            None => return false,
+        };
+        let commit = match parse_compressed_32(commit_slice).and_then(|c| c.decompress()) {
+            Some(p) => p,
+            None => return false,
+        };
+        let pc_gens = PedersenGens::default();
+        let branches = [
+            commit - Scalar::from(option_a) * pc_gens.B,
+            commit - Scalar::from(option_b) * pc_gens.B,
+        ];
+        let mut transcript = Transcript::new(b"libzkp_binary_choice");
+        sigma::verify_or_dlog(&mut transcript, &pc_gens.B_blinding, &branches, &or_proof)
+    }
+
+    /// Prove the value committed as `C = value * B + blinding * B_blinding` is in `set`
+    /// (sorted, distinct) without revealing which element: an OR proof over `C - s * B` for
+    /// every element `s`, one of which is `blinding * B_blinding`. Returns the OR proof,
+    /// `64 * set.len()` bytes.
+    pub fn prove_committed_membership(
+        value: u64,
+        blinding: &Scalar,
+        set: &[u64],
+    ) -> Result<Vec<u8>, String> {
+        let known = set
+            .iter()
+            .position(|&s| s == value)
+            .ok_or_else(|| "value is not in the set".to_string())?;
+        let commit = PedersenGens::default().commit(Scalar::from(value), *blinding);
+        let mut transcript = Transcript::new(b"libzkp_committed_membership");
+        let or_proof = sigma::prove_or_dlog(
+            &mut transcript,
+            &PedersenGens::default().B_blinding,
+            &membership_branches(commit, set),
+            known,
+            blinding,
+        );
+        Ok(or_proof.to_bytes())
+    }
+
+    /// Verify a proof from `prove_committed_membership` against the 32-byte commitment.
+    pub fn verify_committed_membership(commitment: &[u8], set: &[u64], proof: &[u8]) -> bool {
+        let commit = match parse_compressed_32(commitment).and_then(|c| c.decompress()) {
+            Some(p) if commitment.len() == 32 => p,
+            _ => return false,
+        };
+        let or_proof = match OrDlogProof::from_bytes(proof, set.len()) {
+            Some(p) => p,
+            None => return false,
+        };
+        let mut transcript = Transcript::new(b"libzkp_committed_membership");
+        sigma::verify_or_dlog(
+            &mut transcript,
+            &PedersenGens::default().B_blinding,
+            &membership_branches(commit, set),
+            &or_proof,
+        )
+    }
+
+    /// Prove `value` is a multiple of `modulus` without revealing it.
+    /// Commits to the quotient `q` with blinding `r` and to `value` with blinding `modulus * r`,
+    /// so `V == modulus * Q` holds as points; an aggregated range proof keeps both `value` and
+    /// `q` in `[0, 2^64)`, which rules out wrap-around modulo the group order.
+    /// Body: `[modulus 8][Q 32][u32 len][aggregated range proof]`, commitment: `V`.
+    pub fn prove_divisible(value: u64, modulus: u64) -> Result<Vec<u8>, String> {
+        if modulus == 0 {
+            return Err("modulus must be non-zero".to_string());
+        }
+        if !value.is_multiple_of(modulus) {
+            return Err("value is not divisible by modulus".to_string());
+        }
+        let quotient = value / modulus;
+        let quotient_blinding = Zeroizing::new(random_blinding());
+        let value_blinding = Scalar::from(modulus) * *quotient_blinding;
+
+        let (range_proof, commitments) = prove_aggregated_u64(
+            &[value, quotient],
+            &[value_blinding, *quotient_blinding],
+            b"libzkp_divisible",
+        )?;
+
+        let mut proof_bytes = Vec::new();
+        proof_bytes.extend_from_slice(&modulus.to_le_bytes());
+        proof_bytes.extend_from_slice(commitments[1].as_bytes());
+        write_range_proof(&mut proof_bytes, &range_proof);
+
+        encode_proof_body_with_commit(&proof_bytes, commitments[0].as_bytes())
+    }
+
+    /// Verify a proof produced by `prove_divisible` for the given `modulus`.
+    pub fn verify_divisible(proof_data: &[u8], modulus: u64) -> bool {
+        if modulus == 0 {
+            return false;
+        }
+        let (proof_bytes, commit_slice) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        if read_u64_le(proof_bytes, 0) != Some(modulus) {
+            return false;
+        }
+        let value_commit = match parse_compressed_32(commit_slice) {
+            Some(c) => c,
+            None => return false,
+        };
+        let quotient_commit = match proof_bytes.get(8..40).and_then(parse_compressed_32) {
+            Some(c) => c,
+            None => return false,
+        };
+        let mut reader = &proof_bytes[40..];
+        let range_proof = match read_range_proof(&mut reader) {
+            Some(rp) => rp,
+            None => return false,
+        };
+        if !reader.is_empty() {
+            return false;
+        }
 
-            if reader.len() < rp_len {
-                return false;
-            }
-            let rp_bytes = &reader[0..rp_len];
-            let range_proof = match RangeProof::from_bytes(rp_bytes) {
-                Ok(rp) => rp,
-                Err(_) => return false,
+        let (value_point, quotient_point) =
+            match (value_commit.decompress(), quotient_commit.decompress()) {
+                (Some(v), Some(q)) => (v, q),
+                _ => return false,
             };
-            range_proofs.push(range_proof);
-            reader = &reader[rp_len..];
+        if value_point != Scalar::from(modulus) * quotient_point {
+            return false;
         }
 
-        for i in 1..num_values {
-            if reader.len() < 32 {
-                return false;
-            }
-            let diff_commit = match parse_compressed_32(reader) {
-                Some(c) => c,
-                None => return false,
-            };
-            reader = &reader[32..];
+        verify_aggregated_u64(
+            &range_proof,
+            &[value_commit, quotient_commit],
+            b"libzkp_divisible",
+        )
+    }
 
-            let commit_i = commitments[i].decompress();
-            let commit_prev = commitments[i - 1].decompress();
+    /// Prove `min <= value <= max` and `value > other_value` for a value committed as
+    /// `V = value * B + value_blinding * B_blinding` and another party's commitment
+    /// `O = other_value * B + other_blinding * B_blinding`. The verifier derives
+    /// `V - min*B`, `max*B - V` and `V - O - B` itself, and one aggregated 64-bit range
+    /// proof shows all three are non-negative.
+    /// Body: `[min 8][max 8][O 32][u32 len][aggregated range proof]`, commitment: `V`.
+    pub fn prove_range_and_greater(
+        value: u64,
+        min: u64,
+        max: u64,
+        other_value: u64,
+        value_blinding: &Scalar,
+        other_blinding: &Scalar,
+    ) -> Result<Vec<u8>, String> {
+        if value < min || value > max {
+            return Err("value out of range".to_string());
+        }
+        if value <= other_value {
+            return Err("value must be greater than the other value".to_string());
+        }
+        let value_commit = pedersen_commit(value, value_blinding);
+        let other_commit = pedersen_commit(other_value, other_blinding);
+
+        let (range_proof, _) = prove_aggregated_u64(
+            &[value - min, max - value, value - other_value - 1],
+            &[
+                *value_blinding,
+                -value_blinding,
+                value_blinding - other_blinding,
+            ],
+            b"libzkp_range_and_greater",
+        )?;
 
-            if commit_i.is_none() || commit_prev.is_none() {
-                return false;
-            }
+        let mut proof_bytes = Vec::new();
+        proof_bytes.extend_from_slice(&min.to_le_bytes());
+        proof_bytes.extend_from_slice(&max.to_le_bytes());
+        proof_bytes.extend_from_slice(other_commit.as_bytes());
+        write_range_proof(&mut proof_bytes, &range_proof);
 
-            let expected_diff = match (commit_i, commit_prev) {
-                (Some(ci), Some(cp)) => ci - cp,
-                _ => return false,
-            };
-            if expected_diff.compress() != diff_commit {
-                return false;
-            }
-            // Verify non-negativity of the difference via the corresponding range proof
-            let mut transcript = Transcript::new(b"libzkp_consistency");
-            if range_proofs[i - 1]
-                .verify_single(&bp_gens, &pc_gens, &mut transcript, &diff_commit, 64)
-                .is_err()
-            {
-                return false;
-            }
+        encode_proof_body_with_commit(&proof_bytes, value_commit.as_bytes())
+    }
+
+    /// Verify a proof from `prove_range_and_greater` against the public bounds and the
+    /// other party's 32-byte commitment.
+    pub fn verify_range_and_greater(
+        proof_data: &[u8],
+        min: u64,
+        max: u64,
+        other_commitment: &[u8],
+    ) -> bool {
+        if min > max || other_commitment.len() != 32 {
+            return false;
+        }
+        let (proof_bytes, commit_slice) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        if read_u64_le(proof_bytes, 0) != Some(min) || read_u64_le(proof_bytes, 8) != Some(max) {
+            return false;
+        }
+        if proof_bytes.get(16..48) != Some(other_commitment) {
+            return false;
+        }
+        let mut reader = &proof_bytes[48..];
+        let range_proof = match read_range_proof(&mut reader) {
+            Some(rp) => rp,
+            None => return false,
+        };
+        if !reader.is_empty() {
+            return false;
         }
 
-        true
+        let value_point = match parse_compressed_32(commit_slice).and_then(|c| c.decompress()) {
+            Some(p) => p,
+            None => return false,
+        };
+        let other_point = match parse_compressed_32(other_commitment).and_then(|c| c.decompress()) {
+            Some(p) => p,
+            None => return false,
+        };
+        let base = PedersenGens::default().B;
+        let commitments = [
+            (value_point - Scalar::from(min) * base).compress(),
+            (Scalar::from(max) * base - value_point).compress(),
+            (value_point - other_point - base).compress(),
+        ];
+
+        verify_aggregated_u64(&range_proof, &commitments, b"libzkp_range_and_greater")
     }
 
-    /// Universal threshold verifier: reads n_bits from the wire format.
-    pub fn verify_threshold(proof_data: &[u8], threshold: u64) -> bool {
-        let (proof_bytes, sum_commit_slice) = match decode_proof_body_and_commit(proof_data) {
+    /// Prove `min <= value <= max` where the bounds are hidden behind Pedersen commitments
+    /// `M` and `X`. `blindings` are `[value, min, max]`. The verifier derives `V - M` and
+    /// `X - V`; one aggregated 64-bit range proof covers `value`, `value - min` and `max - value`.
+    /// Body: `[M 32][X 32][u32 len][aggregated range proof]`, commitment: `V`.
+    pub fn prove_in_committed_interval(
+        value: u64,
+        min: u64,
+        max: u64,
+        blindings: &[Scalar; 3],
+    ) -> Result<Vec<u8>, String> {
+        Self::prove_committed_interval_gap(
+            value,
+            min,
+            max,
+            blindings,
+            0,
+            b"libzkp_committed_interval",
+        )
+    }
+
+    /// Verify a proof from `prove_in_committed_interval`; the bound commitments are read from
+    /// the payload and must be compared against the expected ones by the caller.
+    pub fn verify_in_committed_interval(proof_data: &[u8]) -> bool {
+        Self::verify_committed_interval_gap(proof_data, 0, b"libzkp_committed_interval")
+    }
+
+    /// Like `prove_in_committed_interval`, but proves `low < value < high`: the differences
+    /// are `value - low - 1` and `high - value - 1`, derived as `V - L - B` and `H - V - B`.
+    /// Same body layout, its own transcript label.
+    pub fn prove_strictly_between(
+        value: u64,
+        low: u64,
+        high: u64,
+        blindings: &[Scalar; 3],
+    ) -> Result<Vec<u8>, String> {
+        Self::prove_committed_interval_gap(
+            value,
+            low,
+            high,
+            blindings,
+            1,
+            b"libzkp_strictly_between",
+        )
+    }
+
+    /// Verify a proof from `prove_strictly_between`; as for `verify_in_committed_interval`,
+    /// the caller compares the embedded bound commitments.
+    pub fn verify_strictly_between(proof_data: &[u8]) -> bool {
+        Self::verify_committed_interval_gap(proof_data, 1, b"libzkp_strictly_between")
+    }
+
+    /// Interval proof over committed bounds with `value - min >= gap` and `max - value >= gap`.
+    fn prove_committed_interval_gap(
+        value: u64,
+        min: u64,
+        max: u64,
+        blindings: &[Scalar; 3],
+        gap: u64,
+        label: &'static [u8],
+    ) -> Result<Vec<u8>, String> {
+        let lower_diff = value.checked_sub(min).and_then(|d| d.checked_sub(gap));
+        let upper_diff = max.checked_sub(value).and_then(|d| d.checked_sub(gap));
+        let (Some(lower_diff), Some(upper_diff)) = (lower_diff, upper_diff) else {
+            return Err("value out of range".to_string());
+        };
+        let [value_blinding, min_blinding, max_blinding] = blindings;
+        let value_commit = pedersen_commit(value, value_blinding);
+        let min_commit = pedersen_commit(min, min_blinding);
+        let max_commit = pedersen_commit(max, max_blinding);
+
+        let (range_proof, _) = prove_aggregated_u64(
+            &[value, lower_diff, upper_diff],
+            &[
+                *value_blinding,
+                value_blinding - min_blinding,
+                max_blinding - value_blinding,
+            ],
+            label,
+        )?;
+
+        let mut proof_bytes = Vec::new();
+        proof_bytes.extend_from_slice(min_commit.as_bytes());
+        proof_bytes.extend_from_slice(max_commit.as_bytes());
+        write_range_proof(&mut proof_bytes, &range_proof);
+
+        encode_proof_body_with_commit(&proof_bytes, value_commit.as_bytes())
+    }
+
+    fn verify_committed_interval_gap(proof_data: &[u8], gap: u64, label: &'static [u8]) -> bool {
+        let (proof_bytes, commit_slice) = match decode_proof_body_and_commit(proof_data) {
             Some(p) => p,
             None => return false,
         };
+        let points: Option<Vec<RistrettoPoint>> = [
+            commit_slice.get(0..32),
+            proof_bytes.get(0..32),
+            proof_bytes.get(32..64),
+        ]
+        .into_iter()
+        .map(|s| s.and_then(parse_compressed_32)?.decompress())
+        .collect();
+        let (value_point, min_point, max_point) = match points.as_deref() {
+            Some([v, m, x]) => (*v, *m, *x),
+            _ => return false,
+        };
+        let mut reader = &proof_bytes[64..];
+        let range_proof = match read_range_proof(&mut reader) {
+            Some(rp) => rp,
+            None => return false,
+        };
+        if !reader.is_empty() {
+            return false;
+        }
 
-        let mut reader = proof_bytes;
-        if reader.len() < 12 {
+        let gap_point = Scalar::from(gap) * PedersenGens::default().B;
+        let commitments = [
+            value_point.compress(),
+            (value_point - min_point - gap_point).compress(),
+            (max_point - value_point - gap_point).compress(),
+        ];
+        verify_aggregated_u64(&range_proof, &commitments, label)
+    }
+
+    /// Prove `min <= (sum w_i * v_i) / (sum w_i) <= max` for hidden `values` and public
+    /// `weights`, checked without division as `min*W <= S <= max*W`. Each value gets its own
+    /// commitment `V_i`; the verifier forms `S = sum w_i * V_i` and one aggregated range proof
+    /// covers every `v_i`, `S - min*W` and `max*W - S`.
+    /// Body: `[min 8][max 8][u32 n][w_i 8*n][V_i 32*n][u32 len][aggregated range proof]`,
+    /// commitment: `S`.
+    pub fn prove_weighted_average_range(
+        values: &[u64],
+        weights: &[u64],
+        min: u64,
+        max: u64,
+    ) -> Result<Vec<u8>, String> {
+        if values.is_empty() || values.len() != weights.len() {
+            return Err("values and weights must be non-empty and of equal length".to_string());
+        }
+        let overflow = || "integer overflow in weighted sum".to_string();
+        let total_weight = weights
+            .iter()
+            .try_fold(0u64, |acc, &w| acc.checked_add(w))
+            .ok_or_else(overflow)?;
+        if total_weight == 0 {
+            return Err("total weight must be non-zero".to_string());
+        }
+        let weighted_sum = values
+            .iter()
+            .zip(weights)
+            .try_fold(0u64, |acc, (&v, &w)| acc.checked_add(v.checked_mul(w)?))
+            .ok_or_else(overflow)?;
+        let lower = min.checked_mul(total_weight).ok_or_else(overflow)?;
+        let upper = max.checked_mul(total_weight).ok_or_else(overflow)?;
+        if weighted_sum < lower || weighted_sum > upper {
+            return Err("weighted average out of range".to_string());
+        }
+
+        let blindings = random_blindings(values.len());
+        let sum_blinding = Zeroizing::new(
+            blindings
+                .iter()
+                .zip(weights)
+                .map(|(r, &w)| Scalar::from(w) * r)
+                .sum::<Scalar>(),
+        );
+
+        let mut range_values = Zeroizing::new(values.to_vec());
+        range_values.push(weighted_sum - lower);
+        range_values.push(upper - weighted_sum);
+        let mut range_blindings = blindings;
+        range_blindings.push(*sum_blinding);
+        range_blindings.push(-*sum_blinding);
+
+        let (range_proof, commitments) =
+            prove_aggregated_u64(&range_values, &range_blindings, b"libzkp_weighted_average")?;
+
+        let mut proof_bytes = Vec::new();
+        proof_bytes.extend_from_slice(&min.to_le_bytes());
+        proof_bytes.extend_from_slice(&max.to_le_bytes());
+        proof_bytes.extend_from_slice(&(weights.len() as u32).to_le_bytes());
+        for w in weights {
+            proof_bytes.extend_from_slice(&w.to_le_bytes());
+        }
+        for c in &commitments[..values.len()] {
+            proof_bytes.extend_from_slice(c.as_bytes());
+        }
+        write_range_proof(&mut proof_bytes, &range_proof);
+
+        let sum_commit = pedersen_commit(weighted_sum, &sum_blinding);
+        encode_proof_body_with_commit(&proof_bytes, sum_commit.as_bytes())
+    }
+
+    /// Verify a proof from `prove_weighted_average_range` for the given weights and bounds.
+    pub fn verify_weighted_average_range(
+        proof_data: &[u8],
+        weights: &[u64],
+        min: u64,
+        max: u64,
+    ) -> bool {
+        let n = weights.len();
+        if n == 0 || n + 2 > MAX_AGGREGATED_RANGE_VALUES || min > max {
             return false;
         }
-        let proof_threshold = match read_u64_le(reader, 0) {
-            Some(v) => v,
+        if weights.iter().all(|&w| w == 0) {
+            return false;
+        }
+        let (proof_bytes, commit_slice) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
             None => return false,
         };
-        if proof_threshold != threshold {
+        if read_u64_le(proof_bytes, 0) != Some(min) || read_u64_le(proof_bytes, 8) != Some(max) {
+            return false;
+        }
+        if proof_bytes.get(16..20) != Some(&(n as u32).to_le_bytes()[..]) {
+            return false;
+        }
+        let weights_end = 20 + 8 * n;
+        let embedded_ok = (0..n).all(|i| read_u64_le(proof_bytes, 20 + 8 * i) == Some(weights[i]));
+        if !embedded_ok {
+            return false;
+        }
+        let commits_end = weights_end + 32 * n;
+        let value_commits: Vec<CompressedRistretto> = match proof_bytes
+            .get(weights_end..commits_end)
+            .map(|b| b.chunks(32).filter_map(parse_compressed_32).collect())
+        {
+            Some(c) => c,
+            None => return false,
+        };
+        let mut reader = &proof_bytes[commits_end..];
+        let range_proof = match read_range_proof(&mut reader) {
+            Some(rp) => rp,
+            None => return false,
+        };
+        if !reader.is_empty() {
             return false;
         }
-        let n_bits = u32::from_le_bytes(match reader[8..12].try_into() {
-            Ok(arr) => arr,
-            Err(_) => return false,
-        }) as usize;
-        reader = &reader[12..];
 
-        if reader.len() < 4 {
+        let mut sum_point = RistrettoPoint::identity();
+        for (c, &w) in value_commits.iter().zip(weights) {
+            match c.decompress() {
+                Some(p) => sum_point += Scalar::from(w) * p,
+                None => return false,
+            }
+        }
+        if parse_compressed_32(commit_slice) != Some(sum_point.compress()) {
             return false;
         }
-        let rp_len = u32::from_le_bytes(match reader[0..4].try_into() {
-            Ok(arr) => arr,
-            Err(_) => return false,
-        }) as usize;
-        reader = &reader[4..];
 
-        if reader.len() < rp_len {
+        let total_weight: Scalar = weights.iter().map(|&w| Scalar::from(w)).sum();
+        let base = PedersenGens::default().B;
+        let mut commitments = value_commits;
+        commitments.push((sum_point - Scalar::from(min) * total_weight * base).compress());
+        commitments.push((Scalar::from(max) * total_weight * base - sum_point).compress());
+
+        verify_aggregated_u64(&range_proof, &commitments, b"libzkp_weighted_average")
+    }
+
+    /// Prove `|x - mean(values)| <= max_deviation` for hidden `values` and hidden `x`, checked
+    /// without division as `|n*x - S| <= n*D` so a non-integer mean is compared exactly. Each
+    /// value gets a commitment `V_i` and `x` the commitment `X`; the verifier forms
+    /// `S = sum V_i` and one aggregated range proof covers every `v_i`, `x`,
+    /// `n*X + n*D - S` and `S + n*D - n*X`.
+    /// Body: `[max_deviation 8][u32 n][V_i 32*n][u32 len][aggregated range proof]`,
+    /// commitment: `X`.
+    pub fn prove_within_of_mean(
+        values: &[u64],
+        my_value: u64,
+        max_deviation: u64,
+    ) -> Result<Vec<u8>, String> {
+        if values.is_empty() {
+            return Err("values cannot be empty".to_string());
+        }
+        let overflow = || "integer overflow in mean calculation".to_string();
+        let n = values.len() as u64;
+        let sum = values
+            .iter()
+            .try_fold(0u64, |acc, &v| acc.checked_add(v))
+            .ok_or_else(overflow)?;
+        let scaled_value = my_value.checked_mul(n).ok_or_else(overflow)?;
+        let scaled_deviation = max_deviation.checked_mul(n).ok_or_else(overflow)?;
+        let lower_diff = scaled_value
+            .checked_add(scaled_deviation)
+            .ok_or_else(overflow)?
+            .checked_sub(sum)
+            .ok_or_else(|| "value deviates too far from the mean".to_string())?;
+        let upper_diff = sum
+            .checked_add(scaled_deviation)
+            .ok_or_else(overflow)?
+            .checked_sub(scaled_value)
+            .ok_or_else(|| "value deviates too far from the mean".to_string())?;
+
+        let blindings = random_blindings(values.len());
+        let value_blinding = Zeroizing::new(random_blinding());
+        let sum_blinding = Zeroizing::new(blindings.iter().sum::<Scalar>());
+        let diff_blinding = Zeroizing::new(Scalar::from(n) * *value_blinding - *sum_blinding);
+
+        let mut range_values = Zeroizing::new(values.to_vec());
+        range_values.extend_from_slice(&[my_value, lower_diff, upper_diff]);
+        let mut range_blindings = blindings;
+        range_blindings.extend_from_slice(&[*value_blinding, *diff_blinding, -*diff_blinding]);
+
+        let (range_proof, commitments) =
+            prove_aggregated_u64(&range_values, &range_blindings, b"libzkp_within_of_mean")?;
+
+        let mut proof_bytes = Vec::new();
+        proof_bytes.extend_from_slice(&max_deviation.to_le_bytes());
+        proof_bytes.extend_from_slice(&(values.len() as u32).to_le_bytes());
+        for c in &commitments[..values.len()] {
+            proof_bytes.extend_from_slice(c.as_bytes());
+        }
+        write_range_proof(&mut proof_bytes, &range_proof);
+
+        encode_proof_body_with_commit(&proof_bytes, commitments[values.len()].as_bytes())
+    }
+
+    /// Verify a proof from `prove_within_of_mean` for the given maximum deviation.
+    pub fn verify_within_of_mean(proof_data: &[u8], max_deviation: u64) -> bool {
+        let (proof_bytes, commit_slice) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
+            None => return false,
+        };
+        if read_u64_le(proof_bytes, 0) != Some(max_deviation) {
             return false;
         }
-        let range_proof = match RangeProof::from_bytes(&reader[0..rp_len]) {
-            Ok(rp) => rp,
-            Err(_) => return false,
+        let n = match proof_bytes
+            .get(8..12)
+            .and_then(|b| b.try_into().ok())
+            .map(|b| u32::from_le_bytes(b) as usize)
+        {
+            Some(n) => n,
+            None => return false,
         };
-        reader = &reader[rp_len..];
-
-        if reader.len() < 32 {
+        if n == 0 || n + 3 > MAX_AGGREGATED_RANGE_VALUES {
             return false;
         }
-        let diff_commit = match parse_compressed_32(reader) {
-            Some(c) => c,
+        let commits_end = 12 + 32 * n;
+        let points: Vec<RistrettoPoint> = match proof_bytes.get(12..commits_end).and_then(|b| {
+            b.chunks(32)
+                .map(|c| parse_compressed_32(c)?.decompress())
+                .collect()
+        }) {
+            Some(points) => points,
+            None => return false,
+        };
+        let value_point = match parse_compressed_32(commit_slice).and_then(|c| c.decompress()) {
+            Some(p) => p,
             None => return false,
         };
+        let mut reader = &proof_bytes[commits_end..];
+        let range_proof = match read_range_proof(&mut reader) {
+            Some(rp) => rp,
+            None => return false,
+        };
+        if !reader.is_empty() {
+            return false;
+        }
 
-        let (pc_gens, bp_gens) = bp_gens_pair_bits(n_bits, 2);
+        let sum_point: RistrettoPoint = points.iter().sum();
+        let scaled_value = Scalar::from(n as u64) * value_point;
+        let scaled_deviation =
+            Scalar::from(n as u64) * Scalar::from(max_deviation) * PedersenGens::default().B;
+        let mut commitments: Vec<CompressedRistretto> =
+            points.iter().map(|p| p.compress()).collect();
+        commitments.push(value_point.compress());
+        commitments.push((scaled_value + scaled_deviation - sum_point).compress());
+        commitments.push((sum_point + scaled_deviation - scaled_value).compress());
+
+        verify_aggregated_u64(&range_proof, &commitments, b"libzkp_within_of_mean")
+    }
 
-        let sum_commit = match parse_compressed_32(sum_commit_slice) {
-            Some(c) => c,
+    /// Prove at least `min_count` of `values` exceed `threshold`. Each value `v_i` gets a
+    /// committed selector bit `s_i`; one aggregated range proof covers `v_i`, `s_i`, `1 - s_i`
+    /// (so `s_i` is 0 or 1), `v_i - (threshold + 1) * s_i` (so a selected value exceeds the
+    /// threshold) and `sum(s_i) - min_count`, all but `v_i` and `s_i` derived homomorphically.
+    /// Body: `[threshold 8][u32 min_count][u32 n][V_i 32n][S_i 32n][u32 len][range proof]`;
+    /// the commitment opens to the number of selected values.
+    pub fn prove_count_above(
+        values: &[u64],
+        threshold: u64,
+        min_count: usize,
+    ) -> Result<Vec<u8>, String> {
+        let n = values.len();
+        if n == 0 || 4 * n + 1 > MAX_AGGREGATED_RANGE_VALUES {
+            return Err(format!(
+                "count above supports 1 to {} values",
+                (MAX_AGGREGATED_RANGE_VALUES - 1) / 4
+            ));
+        }
+        let selected: Zeroizing<Vec<u64>> =
+            Zeroizing::new(values.iter().map(|&v| u64::from(v > threshold)).collect());
+        let count = selected.iter().sum::<u64>();
+        let surplus = count
+            .checked_sub(min_count as u64)
+            .ok_or_else(|| "fewer than min_count values exceed the threshold".to_string())?;
+
+        let value_blindings = random_blindings(n);
+        let selector_blindings = random_blindings(n);
+        let step = Scalar::from(threshold) + Scalar::ONE;
+
+        let mut range_values = Zeroizing::new(values.to_vec());
+        range_values.extend_from_slice(&selected);
+        range_values.extend(selected.iter().map(|s| 1 - s));
+        range_values.extend(values.iter().zip(selected.iter()).map(|(&v, &s)| {
+            if s == 1 {
+                v - threshold - 1
+            } else {
+                v
+            }
+        }));
+        range_values.push(surplus);
+
+        let mut range_blindings = Zeroizing::new(value_blindings.to_vec());
+        range_blindings.extend_from_slice(&selector_blindings);
+        range_blindings.extend(selector_blindings.iter().map(|q| -q));
+        range_blindings.extend(
+            value_blindings
+                .iter()
+                .zip(selector_blindings.iter())
+                .map(|(r, q)| r - step * q),
+        );
+        let count_blinding: Scalar = selector_blindings.iter().sum();
+        range_blindings.push(count_blinding);
+
+        let (range_proof, commitments) =
+            prove_aggregated_u64(&range_values, &range_blindings, b"libzkp_count_above")?;
+
+        let mut proof_bytes = Vec::new();
+        proof_bytes.extend_from_slice(&threshold.to_le_bytes());
+        proof_bytes.extend_from_slice(&(min_count as u32).to_le_bytes());
+        proof_bytes.extend_from_slice(&(n as u32).to_le_bytes());
+        for c in &commitments[..2 * n] {
+            proof_bytes.extend_from_slice(c.as_bytes());
+        }
+        write_range_proof(&mut proof_bytes, &range_proof);
+
+        let count_commitment = PedersenGens::default().commit(Scalar::from(count), count_blinding);
+        encode_proof_body_with_commit(&proof_bytes, count_commitment.compress().as_bytes())
+    }
+
+    /// Verify a proof from `prove_count_above` for the given threshold and minimum count.
+    pub fn verify_count_above(proof_data: &[u8], threshold: u64, min_count: usize) -> bool {
+        let (proof_bytes, commit_slice) = match decode_proof_body_and_commit(proof_data) {
+            Some(p) => p,
             None => return false,
         };
-        let sum_commit_point = match sum_commit.decompress() {
+        let read_u32 = |at: usize| {
+            proof_bytes
+                .get(at..at + 4)
+                .and_then(|b| b.try_into().ok())
+                .map(|b| u32::from_le_bytes(b) as usize)
+        };
+        if read_u64_le(proof_bytes, 0) != Some(threshold) || read_u32(8) != Some(min_count) {
+            return false;
+        }
+        let n = match read_u32(12) {
+            Some(n) if n > 0 && 4 * n < MAX_AGGREGATED_RANGE_VALUES => n,
+            _ => return false,
+        };
+        let commits_end = 16 + 64 * n;
+        let points: Vec<RistrettoPoint> = match proof_bytes.get(16..commits_end).and_then(|b| {
+            b.chunks(32)
+                .map(|c| parse_compressed_32(c)?.decompress())
+                .collect()
+        }) {
+            Some(points) => points,
+            None => return false,
+        };
+        let count_point = match parse_compressed_32(commit_slice).and_then(|c| c.decompress()) {
             Some(p) => p,
             None => return false,
         };
-        let expected_diff_commit =
-            (sum_commit_point - (Scalar::from(threshold) * pc_gens.B)).compress();
-
-        if expected_diff_commit != diff_commit {
+        let mut reader = &proof_bytes[commits_end..];
+        let range_proof = match read_range_proof(&mut reader) {
+            Some(rp) => rp,
+            None => return false,
+        };
+        if !reader.is_empty() {
             return false;
         }
 
-        let mut transcript = Transcript::new(b"libzkp_threshold");
-        range_proof
-            .verify_single(
-                &bp_gens,
-                &pc_gens,
-                &mut transcript,
-                &expected_diff_commit,
-                n_bits,
-            )
-            .is_ok()
+        let (value_points, selector_points) = points.split_at(n);
+        if selector_points.iter().sum::<RistrettoPoint>() != count_point {
+            return false;
+        }
+        let base = PedersenGens::default().B;
+        let step = Scalar::from(threshold) + Scalar::ONE;
+        let mut commitments: Vec<CompressedRistretto> =
+            points.iter().map(|p| p.compress()).collect();
+        commitments.extend(selector_points.iter().map(|s| (base - s).compress()));
+        commitments.extend(
+            value_points
+                .iter()
+                .zip(selector_points)
+                .map(|(v, s)| (v - step * s).compress()),
+        );
+        commitments.push((count_point - Scalar::from(min_count as u64) * base).compress());
+
+        verify_aggregated_u64(&range_proof, &commitments, b"libzkp_count_above")
     }
 }
 
@@ -637,7 +3108,7 @@ impl ZkpBackend for BulletproofsBackend {
         };
 
         let (pc_gens, bp_gens) = bp_gens_pair(1);
-        let blinding = random_blinding();
+        let blinding = Zeroizing::new(random_blinding());
 
         let mut transcript = Transcript::new(b"libzkp_bulletproof");
         let (proof, commit) = match RangeProof::prove_single(
@@ -697,6 +3168,43 @@ mod tests {
         assert_eq!(cc, commit.as_slice());
     }
 
+    #[test]
+    fn solvency_rejects_negative_liability_entry() {
+        // Assets {100}, liabilities {300, -250}: the totals 100 >= 50 prove fine, so only the
+        // entry range proofs catch the liability committed as `-250*G + r*H`.
+        let r = random_blindings(3);
+        let pc_gens = PedersenGens::default();
+        let entries = [
+            pedersen_commit(100, &r[0]),
+            pedersen_commit(300, &r[1]),
+            (pc_gens.commit(-Scalar::from(250u64), r[2])).compress(),
+        ];
+        let totals = [r[0], r[1] + r[2]];
+        let totals_proof =
+            prove_ordered_pair_range(100, 50, &totals, 0, b"libzkp_solvency", "").unwrap();
+        let points: Vec<RistrettoPoint> = entries.iter().map(|c| c.decompress().unwrap()).collect();
+        assert!(verify_ordered_pair_range(
+            points[0],
+            points[1] + points[2],
+            &totals_proof,
+            0,
+            b"libzkp_solvency"
+        ));
+        let (entry_proof, _) =
+            prove_aggregated_u64(&[100, 300, 250], &r, b"libzkp_solvency_entries").unwrap();
+
+        let mut body = Vec::new();
+        body.extend_from_slice(&1u32.to_le_bytes());
+        body.extend_from_slice(&2u32.to_le_bytes());
+        for c in &entries {
+            body.extend_from_slice(c.as_bytes());
+        }
+        write_range_proof(&mut body, &totals_proof);
+        write_range_proof(&mut body, &entry_proof);
+        let forged = encode_proof_body_with_commit(&body, entries[0].as_bytes()).unwrap();
+        assert!(!BulletproofsBackend::verify_solvency(&forged));
+    }
+
     #[test]
     fn prove_range_roundtrip() {
         let p = BulletproofsBackend::prove_range_with_bounds(5, 0, 10).unwrap();
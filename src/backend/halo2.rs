@@ -0,0 +1,564 @@
+//! Halo2 (PLONKish, IPA over the Pasta curves) backend: range and equality proofs with no
+//! trusted setup. Parameters are derived deterministically from `K`, so keys are rebuilt per
+//! process instead of being persisted like the Groth16 ones.
+//!
+//! Both circuits bind the hidden value to a public commitment over the Pallas base field,
+//! MiMC-5 keyed by a secret blinding in Miyaguchi–Preneel mode ([`Halo2Backend::commit_value`]),
+//! so the commitment cannot be opened by trying candidate values. It is a different field from
+//! the BN254 commitment of `commit_value_snark`, so the two are not interchangeable.
+
+use super::ZkpBackend;
+use crate::utils::encoding::read_u64_le;
+use ff::{Field, PrimeField};
+use halo2_proofs::arithmetic::FieldExt;
+use halo2_proofs::circuit::{AssignedCell, Layouter, SimpleFloorPlanner};
+use halo2_proofs::pasta::{EqAffine, Fp};
+use halo2_proofs::plonk::{
+    create_proof, keygen_pk, keygen_vk, verify_proof, Advice, Circuit, Column, ConstraintSystem,
+    Error, Expression, Fixed, Instance, ProvingKey, Selector, SingleVerifier,
+};
+use halo2_proofs::poly::commitment::Params;
+use halo2_proofs::poly::Rotation;
+use halo2_proofs::transcript::{Blake2bRead, Blake2bWrite, Challenge255};
+use rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+use zeroize::Zeroizing;
+
+/// log2 of the circuit size; 2^9 rows fit the range circuit (the keyed MiMC plus two 64-bit
+/// decompositions) with room for the prover's zero-knowledge blinding rows.
+const K: u32 = 9;
+
+/// MiMC-5 rounds, as for the BN254 commitment (x^5 is a permutation of the Pallas field too).
+pub const HALO2_MIMC_ROUNDS: usize = 110;
+
+static MIMC_CONSTANTS: OnceLock<Vec<Fp>> = OnceLock::new();
+static PARAMS: OnceLock<Params<EqAffine>> = OnceLock::new();
+static RANGE_PK: OnceLock<Option<ProvingKey<EqAffine>>> = OnceLock::new();
+static EQUALITY_PK: OnceLock<Option<ProvingKey<EqAffine>>> = OnceLock::new();
+
+fn mimc_constants() -> &'static [Fp] {
+    MIMC_CONSTANTS.get_or_init(|| {
+        (0..HALO2_MIMC_ROUNDS)
+            .map(|i| {
+                let mut hasher = Sha256::new();
+                hasher.update(b"libzkp_halo2_mimc_v1:");
+                hasher.update((i as u64).to_le_bytes());
+                let mut wide = [0u8; 64];
+                wide[..32].copy_from_slice(&hasher.finalize());
+                Fp::from_bytes_wide(&wide)
+            })
+            .collect()
+    })
+}
+
+/// `E_r(v) + v + r`, where `E_r` is MiMC-5 keyed by the blinding `r`.
+fn mimc_native(value: u64, blinding: Fp) -> Fp {
+    let value = Fp::from(value);
+    let encrypted = mimc_constants().iter().fold(value, |x, c| {
+        let t = x + blinding + c;
+        let t2 = t.square();
+        t2.square() * t
+    });
+    encrypted + value + blinding
+}
+
+fn blinding_from_bytes(bytes: &[u8; 32]) -> Option<Fp> {
+    Option::from(Fp::from_repr(*bytes))
+}
+
+fn params() -> &'static Params<EqAffine> {
+    PARAMS.get_or_init(|| Params::new(K))
+}
+
+fn setup<C: Circuit<Fp>>(circuit: &C) -> Option<ProvingKey<EqAffine>> {
+    let vk = keygen_vk(params(), circuit).ok()?;
+    keygen_pk(params(), vk, circuit).ok()
+}
+
+fn range_pk() -> Option<&'static ProvingKey<EqAffine>> {
+    RANGE_PK
+        .get_or_init(|| setup(&RangeCircuit::default()))
+        .as_ref()
+}
+
+fn equality_pk() -> Option<&'static ProvingKey<EqAffine>> {
+    EQUALITY_PK
+        .get_or_init(|| setup(&EqualityCircuit::default()))
+        .as_ref()
+}
+
+fn prove_with<C: Circuit<Fp>>(pk: &ProvingKey<EqAffine>, circuit: C, instance: &[Fp]) -> Vec<u8> {
+    let mut transcript = Blake2bWrite::<_, EqAffine, Challenge255<_>>::init(vec![]);
+    match create_proof(
+        params(),
+        pk,
+        &[circuit],
+        &[&[instance]],
+        OsRng,
+        &mut transcript,
+    ) {
+        Ok(()) => transcript.finalize(),
+        Err(_) => vec![],
+    }
+}
+
+fn verify_with(pk: &ProvingKey<EqAffine>, proof: &[u8], instance: &[Fp]) -> bool {
+    let mut transcript = Blake2bRead::<_, EqAffine, Challenge255<_>>::init(proof);
+    verify_proof(
+        params(),
+        pk.get_vk(),
+        SingleVerifier::new(params()),
+        &[&[instance]],
+        &mut transcript,
+    )
+    .is_ok()
+}
+
+fn fp_from_commitment(bytes: &[u8]) -> Option<Fp> {
+    let repr: [u8; 32] = bytes.try_into().ok()?;
+    Option::from(Fp::from_repr(repr))
+}
+
+// ===== Circuit configuration =====
+// Columns: two advice (`a`, `b`), the MiMC round constants, a constants column for
+// `assign_advice_from_constant`, and one instance column.
+// Gates:
+//   mimc: a[next] = (a[cur] + b[cur] + rc[cur])^5  (b holds the key on every round)
+//   bit:  b[cur] is boolean and a[cur] = 2 * a[prev] + b[cur]  (MSB-first recomposition)
+//   sub:  a[next] = a[cur] - b[cur]
+//   add:  a[next] = a[cur] + b[cur]
+
+#[derive(Clone, Debug)]
+struct Halo2Config {
+    a: Column<Advice>,
+    b: Column<Advice>,
+    round_constant: Column<Fixed>,
+    instance: Column<Instance>,
+    s_mimc: Selector,
+    s_bit: Selector,
+    s_sub: Selector,
+    s_add: Selector,
+}
+
+impl Halo2Config {
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self {
+        let a = meta.advice_column();
+        let b = meta.advice_column();
+        let round_constant = meta.fixed_column();
+        let constants = meta.fixed_column();
+        let instance = meta.instance_column();
+        meta.enable_equality(a);
+        meta.enable_equality(b);
+        meta.enable_equality(instance);
+        meta.enable_constant(constants);
+
+        let s_mimc = meta.selector();
+        let s_bit = meta.selector();
+        let s_sub = meta.selector();
+        let s_add = meta.selector();
+
+        meta.create_gate("mimc round", |meta| {
+            let s = meta.query_selector(s_mimc);
+            let x = meta.query_advice(a, Rotation::cur());
+            let key = meta.query_advice(b, Rotation::cur());
+            let c = meta.query_fixed(round_constant, Rotation::cur());
+            let out = meta.query_advice(a, Rotation::next());
+            let t = x + key + c;
+            let t2 = t.clone() * t.clone();
+            vec![s * (out - t2.clone() * t2 * t)]
+        });
+
+        meta.create_gate("bit recomposition", |meta| {
+            let s = meta.query_selector(s_bit);
+            let bit = meta.query_advice(b, Rotation::cur());
+            let acc = meta.query_advice(a, Rotation::cur());
+            let prev = meta.query_advice(a, Rotation::prev());
+            let one = Expression::Constant(Fp::one());
+            let two = Expression::Constant(Fp::from(2));
+            vec![
+                s.clone() * bit.clone() * (one - bit.clone()),
+                s * (acc - two * prev - bit),
+            ]
+        });
+
+        meta.create_gate("sub", |meta| {
+            let s = meta.query_selector(s_sub);
+            let lhs = meta.query_advice(a, Rotation::cur());
+            let rhs = meta.query_advice(b, Rotation::cur());
+            let out = meta.query_advice(a, Rotation::next());
+            vec![s * (lhs - rhs - out)]
+        });
+
+        meta.create_gate("add", |meta| {
+            let s = meta.query_selector(s_add);
+            let lhs = meta.query_advice(a, Rotation::cur());
+            let rhs = meta.query_advice(b, Rotation::cur());
+            let out = meta.query_advice(a, Rotation::next());
+            vec![s * (lhs + rhs - out)]
+        });
+
+        Self {
+            a,
+            b,
+            round_constant,
+            instance,
+            s_mimc,
+            s_bit,
+            s_sub,
+            s_add,
+        }
+    }
+
+    fn load_private(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        value: Option<Fp>,
+    ) -> Result<AssignedCell<Fp, Fp>, Error> {
+        layouter.assign_region(
+            || "private value",
+            |mut region| {
+                region.assign_advice(|| "value", self.a, 0, || value.ok_or(Error::Synthesis))
+            },
+        )
+    }
+
+    /// Commitment `E_r(x) + x + r` to `x` under the blinding `r` (see [`mimc_native`]): one row
+    /// per MiMC round with `r` copied beside the state, then two additions.
+    fn commit(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        x: &AssignedCell<Fp, Fp>,
+        r: &AssignedCell<Fp, Fp>,
+    ) -> Result<AssignedCell<Fp, Fp>, Error> {
+        layouter.assign_region(
+            || "mimc commitment",
+            |mut region| {
+                let mut cur = x.copy_advice(|| "mimc input", &mut region, self.a, 0)?;
+                let rounds = mimc_constants().len();
+                for (i, &c) in mimc_constants().iter().enumerate() {
+                    self.s_mimc.enable(&mut region, i)?;
+                    region.assign_fixed(|| "round constant", self.round_constant, i, || Ok(c))?;
+                    let key = r.copy_advice(|| "mimc key", &mut region, self.b, i)?;
+                    let next = cur.value().zip(key.value()).map(|(v, k)| {
+                        let t = *v + k + c;
+                        let t2 = t.square();
+                        t2.square() * t
+                    });
+                    cur = region.assign_advice(
+                        || "mimc state",
+                        self.a,
+                        i + 1,
+                        || next.ok_or(Error::Synthesis),
+                    )?;
+                }
+                // Feed-forward: add the key, then the input.
+                for (offset, addend) in [r, x].into_iter().enumerate() {
+                    let row = rounds + offset;
+                    self.s_add.enable(&mut region, row)?;
+                    let addend = addend.copy_advice(|| "addend", &mut region, self.b, row)?;
+                    let sum = cur.value().zip(addend.value()).map(|(l, r)| *l + r);
+                    cur = region.assign_advice(
+                        || "feed-forward",
+                        self.a,
+                        row + 1,
+                        || sum.ok_or(Error::Synthesis),
+                    )?;
+                }
+                Ok(cur)
+            },
+        )
+    }
+
+    /// `lhs - rhs`, where `rhs` is either a cell or a row of the instance column.
+    fn sub(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        lhs: Operand<'_>,
+        rhs: Operand<'_>,
+    ) -> Result<AssignedCell<Fp, Fp>, Error> {
+        layouter.assign_region(
+            || "sub",
+            |mut region| {
+                self.s_sub.enable(&mut region, 0)?;
+                let l = lhs.assign(self, &mut region, self.a)?;
+                let r = rhs.assign(self, &mut region, self.b)?;
+                let diff = l.value().zip(r.value()).map(|(l, r)| *l - r);
+                region.assign_advice(|| "difference", self.a, 1, || diff.ok_or(Error::Synthesis))
+            },
+        )
+    }
+
+    /// Constrain `x < 2^64` by recomposing it from 64 boolean cells.
+    fn range_u64(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        x: &AssignedCell<Fp, Fp>,
+    ) -> Result<(), Error> {
+        let bits: Option<Vec<bool>> = x.value().and_then(|v| {
+            let repr = v.to_repr();
+            // Values that do not fit in 64 bits have no witness; proving then fails.
+            repr[8..].iter().all(|&b| b == 0).then(|| {
+                let word = u64::from_le_bytes(repr[..8].try_into().expect("8-byte slice"));
+                (0..64).rev().map(|i| (word >> i) & 1 == 1).collect()
+            })
+        });
+        layouter.assign_region(
+            || "range u64",
+            |mut region| {
+                let mut acc =
+                    region.assign_advice_from_constant(|| "acc", self.a, 0, Fp::zero())?;
+                for i in 0..64 {
+                    let row = i + 1;
+                    self.s_bit.enable(&mut region, row)?;
+                    let bit = bits.as_ref().map(|b| Fp::from(b[i] as u64));
+                    region.assign_advice(|| "bit", self.b, row, || bit.ok_or(Error::Synthesis))?;
+                    let next = acc.value().zip(bit).map(|(acc, bit)| acc.double() + bit);
+                    acc = region.assign_advice(
+                        || "acc",
+                        self.a,
+                        row,
+                        || next.ok_or(Error::Synthesis),
+                    )?;
+                }
+                region.constrain_equal(acc.cell(), x.cell())
+            },
+        )
+    }
+}
+
+/// Operand of [`Halo2Config::sub`].
+#[derive(Clone, Copy)]
+enum Operand<'a> {
+    Cell(&'a AssignedCell<Fp, Fp>),
+    Instance(usize),
+}
+
+impl Operand<'_> {
+    fn assign(
+        self,
+        config: &Halo2Config,
+        region: &mut halo2_proofs::circuit::Region<'_, Fp>,
+        column: Column<Advice>,
+    ) -> Result<AssignedCell<Fp, Fp>, Error> {
+        match self {
+            Operand::Cell(cell) => cell.copy_advice(|| "operand", region, column, 0),
+            Operand::Instance(row) => {
+                region.assign_advice_from_instance(|| "operand", config.instance, row, column, 0)
+            }
+        }
+    }
+}
+
+// ===== Range Circuit =====
+// Proves: commit(value, r) == commitment AND value - min, max - value both fit in 64 bits.
+// Public: [commitment, min, max]
+
+#[derive(Clone, Default)]
+struct RangeCircuit {
+    value: Zeroizing<Option<u64>>,
+    blinding: Zeroizing<Option<[u8; 32]>>,
+}
+
+impl Circuit<Fp> for RangeCircuit {
+    type Config = Halo2Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        Halo2Config::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let value =
+            config.load_private(layouter.namespace(|| "value"), self.value.map(Fp::from))?;
+        let blinding = config.load_private(
+            layouter.namespace(|| "blinding"),
+            self.blinding.as_ref().and_then(blinding_from_bytes),
+        )?;
+        let commitment = config.commit(layouter.namespace(|| "commit"), &value, &blinding)?;
+        layouter.constrain_instance(commitment.cell(), config.instance, 0)?;
+
+        let above_min = config.sub(
+            layouter.namespace(|| "value - min"),
+            Operand::Cell(&value),
+            Operand::Instance(1),
+        )?;
+        config.range_u64(layouter.namespace(|| "value >= min"), &above_min)?;
+        let below_max = config.sub(
+            layouter.namespace(|| "max - value"),
+            Operand::Instance(2),
+            Operand::Cell(&value),
+        )?;
+        config.range_u64(layouter.namespace(|| "value <= max"), &below_max)
+    }
+}
+
+// ===== Equality Circuit =====
+// Proves: commit(a, r) == commitment AND a == b
+// Public: [commitment]
+
+#[derive(Clone, Default)]
+struct EqualityCircuit {
+    a: Zeroizing<Option<u64>>,
+    b: Zeroizing<Option<u64>>,
+    blinding: Zeroizing<Option<[u8; 32]>>,
+}
+
+impl Circuit<Fp> for EqualityCircuit {
+    type Config = Halo2Config;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+        Halo2Config::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fp>,
+    ) -> Result<(), Error> {
+        let a = config.load_private(layouter.namespace(|| "a"), self.a.map(Fp::from))?;
+        let b = config.load_private(layouter.namespace(|| "b"), self.b.map(Fp::from))?;
+        layouter.assign_region(
+            || "a == b",
+            |mut region| region.constrain_equal(a.cell(), b.cell()),
+        )?;
+        let blinding = config.load_private(
+            layouter.namespace(|| "blinding"),
+            self.blinding.as_ref().and_then(blinding_from_bytes),
+        )?;
+        let commitment = config.commit(layouter.namespace(|| "commit"), &a, &blinding)?;
+        layouter.constrain_instance(commitment.cell(), config.instance, 0)
+    }
+}
+
+pub struct Halo2Backend;
+
+impl Halo2Backend {
+    /// Fresh random blinding for [`Self::commit_value`] (canonical Pallas base field element,
+    /// little-endian). Keep it secret: together with the value it opens the commitment.
+    pub fn random_blinding() -> [u8; 32] {
+        Fp::random(OsRng).to_repr()
+    }
+
+    /// 32-byte commitment to `value` under `blinding` (Pallas base field, little-endian), used
+    /// as the public input of both circuits. `None` if `blinding` is not canonical.
+    pub fn commit_value(value: u64, blinding: &[u8; 32]) -> Option<[u8; 32]> {
+        blinding_from_bytes(blinding).map(|r| mimc_native(value, r).to_repr())
+    }
+
+    /// Prove `min <= value <= max` for the value under `commit_value(value, blinding)`.
+    /// Returns an empty vector if the value is out of range or proving fails.
+    pub fn prove_range(value: u64, blinding: &[u8; 32], min: u64, max: u64) -> Vec<u8> {
+        if min > max || value < min || value > max {
+            return vec![];
+        }
+        let (pk, commitment) = match range_pk().zip(blinding_from_bytes(blinding)) {
+            Some((pk, r)) => (pk, mimc_native(value, r)),
+            None => return vec![],
+        };
+        let instance = [commitment, Fp::from(min), Fp::from(max)];
+        let circuit = RangeCircuit {
+            value: Zeroizing::new(Some(value)),
+            blinding: Zeroizing::new(Some(*blinding)),
+        };
+        prove_with(pk, circuit, &instance)
+    }
+
+    pub fn verify_range(proof: &[u8], commitment: &[u8], min: u64, max: u64) -> bool {
+        let (pk, commitment) = match range_pk().zip(fp_from_commitment(commitment)) {
+            Some(p) => p,
+            None => return false,
+        };
+        verify_with(pk, proof, &[commitment, Fp::from(min), Fp::from(max)])
+    }
+
+    /// Prove `a == b` for the value under `commit_value(a, blinding)`. Returns an empty
+    /// vector if the values differ or proving fails.
+    pub fn prove_equality(a: u64, b: u64, blinding: &[u8; 32]) -> Vec<u8> {
+        if a != b {
+            return vec![];
+        }
+        let (pk, commitment) = match equality_pk().zip(blinding_from_bytes(blinding)) {
+            Some((pk, r)) => (pk, mimc_native(a, r)),
+            None => return vec![],
+        };
+        let circuit = EqualityCircuit {
+            a: Zeroizing::new(Some(a)),
+            b: Zeroizing::new(Some(b)),
+            blinding: Zeroizing::new(Some(*blinding)),
+        };
+        prove_with(pk, circuit, &[commitment])
+    }
+
+    pub fn verify_equality(proof: &[u8], commitment: &[u8]) -> bool {
+        let (pk, commitment) = match equality_pk().zip(fp_from_commitment(commitment)) {
+            Some(p) => p,
+            None => return false,
+        };
+        verify_with(pk, proof, &[commitment])
+    }
+}
+
+/// `prove` takes `[value 8][min 8][max 8]` (range) or `[a 8][b 8]` (equality), commits under a
+/// fresh blinding and returns `[commitment 32][proof]`; `verify` takes that output with
+/// `[min 8][max 8]` or empty data.
+impl ZkpBackend for Halo2Backend {
+    fn prove(data: &[u8]) -> Vec<u8> {
+        let blinding = Zeroizing::new(Self::random_blinding());
+        let (value, proof) = match data.len() {
+            24 => {
+                let (value, min, max) = match (
+                    read_u64_le(data, 0),
+                    read_u64_le(data, 8),
+                    read_u64_le(data, 16),
+                ) {
+                    (Some(v), Some(lo), Some(hi)) => (v, lo, hi),
+                    _ => return vec![],
+                };
+                (value, Self::prove_range(value, &blinding, min, max))
+            }
+            16 => {
+                let (a, b) = match (read_u64_le(data, 0), read_u64_le(data, 8)) {
+                    (Some(a), Some(b)) => (a, b),
+                    _ => return vec![],
+                };
+                (a, Self::prove_equality(a, b, &blinding))
+            }
+            _ => return vec![],
+        };
+        let commitment = match Self::commit_value(value, &blinding) {
+            Some(c) if !proof.is_empty() => c,
+            _ => return vec![],
+        };
+        let mut out = commitment.to_vec();
+        out.extend_from_slice(&proof);
+        out
+    }
+    fn verify(proof: &[u8], data: &[u8]) -> bool {
+        if proof.len() <= 32 {
+            return false;
+        }
+        let (commitment, proof) = proof.split_at(32);
+        match data.len() {
+            16 => match (read_u64_le(data, 0), read_u64_le(data, 8)) {
+                (Some(min), Some(max)) => Self::verify_range(proof, commitment, min, max),
+                _ => false,
+            },
+            0 => Self::verify_equality(proof, commitment),
+            _ => false,
+        }
+    }
+}
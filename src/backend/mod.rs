@@ -1,8 +1,70 @@
 pub mod bulletproofs;
+#[cfg(feature = "halo2")]
+pub mod halo2;
+pub(crate) mod range_batch;
+pub(crate) mod sigma;
 pub mod snark;
 pub mod stark;
 
+use crate::utils::error_handling::ZkpError;
+
 pub trait ZkpBackend {
     fn prove(data: &[u8]) -> Vec<u8>;
     fn verify(_proof: &[u8], _data: &[u8]) -> bool;
 }
+
+/// Backend picked per call for statements that more than one backend can prove.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendKind {
+    /// No setup, randomized Pedersen commitments.
+    Bulletproofs,
+    /// Groth16: smallest proofs, circuit-specific setup.
+    Snark,
+    /// No trusted setup; requires the `halo2` feature.
+    Halo2,
+}
+
+impl BackendKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Bulletproofs => "bulletproofs",
+            Self::Snark => "snark",
+            Self::Halo2 => "halo2",
+        }
+    }
+
+    /// Error unless this build includes the backend.
+    pub fn ensure_available(self) -> Result<(), ZkpError> {
+        if self == Self::Halo2 && !cfg!(feature = "halo2") {
+            return Err(ZkpError::ConfigError(
+                "backend halo2 requires the `halo2` feature".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Error for a statement this backend has no circuit or protocol for.
+    pub fn unsupported(self, statement: &str) -> ZkpError {
+        ZkpError::InvalidInput(format!(
+            "{} proofs are not available on the {} backend",
+            statement,
+            self.name()
+        ))
+    }
+}
+
+impl std::str::FromStr for BackendKind {
+    type Err = ZkpError;
+
+    fn from_str(s: &str) -> Result<Self, ZkpError> {
+        match s.to_ascii_lowercase().as_str() {
+            "bulletproofs" => Ok(Self::Bulletproofs),
+            "snark" | "groth16" => Ok(Self::Snark),
+            "halo2" => Ok(Self::Halo2),
+            _ => Err(ZkpError::InvalidInput(format!(
+                "unknown backend {}; expected bulletproofs, snark or halo2",
+                s
+            ))),
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! Batch verification of single-value Bulletproofs range proofs. Each proof's verification
+//! equation (as in `RangeProof::verify_single`) is scaled by a fresh random weight and the
+//! weighted sum is checked with one multiscalar multiplication, so the generators shared by
+//! every proof are paid for once. A forged proof passes only with negligible probability.
+
+use super::bulletproofs::random_blinding;
+use super::sigma::challenge_scalar;
+use bulletproofs::PedersenGens;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::{IsIdentity, VartimeMultiscalarMul};
+use merlin::Transcript;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use std::sync::OnceLock;
+
+/// Widest supported range proof; also the number of cached `G`/`H` generators.
+const MAX_BITS: usize = 64;
+
+/// One single-value range proof to check: `proof` is `RangeProof::to_bytes` output for the
+/// commitment `commitment`, proven over `n_bits` under a transcript labelled `label`.
+pub(crate) struct RangeStatement<'a> {
+    pub proof: &'a [u8],
+    pub commitment: RistrettoPoint,
+    pub label: &'static [u8],
+    pub n_bits: usize,
+}
+
+/// Party-0 `G` and `H` vectors of `BulletproofGens::new(64, _)`. The crate does not expose
+/// `H`, so both are re-derived from the same SHAKE256 generator chain.
+fn party_zero_gens() -> &'static (Vec<RistrettoPoint>, Vec<RistrettoPoint>) {
+    static GENS: OnceLock<(Vec<RistrettoPoint>, Vec<RistrettoPoint>)> = OnceLock::new();
+    GENS.get_or_init(|| (generator_chain(b'G'), generator_chain(b'H')))
+}
+
+fn generator_chain(prefix: u8) -> Vec<RistrettoPoint> {
+    let mut shake = Shake256::default();
+    shake.update(b"GeneratorsChain");
+    shake.update(&[prefix, 0, 0, 0, 0]);
+    let mut reader = shake.finalize_xof();
+    (0..MAX_BITS)
+        .map(|_| {
+            let mut uniform = [0u8; 64];
+            reader.read(&mut uniform);
+            RistrettoPoint::from_uniform_bytes(&uniform)
+        })
+        .collect()
+}
+
+fn read_scalar(bytes: &[u8]) -> Option<Scalar> {
+    Option::from(Scalar::from_canonical_bytes(bytes.try_into().ok()?))
+}
+
+/// Append a proof point, rejecting the identity like the upstream verifier does.
+fn append_point(
+    transcript: &mut Transcript,
+    label: &'static [u8],
+    bytes: &[u8],
+) -> Option<CompressedRistretto> {
+    let point = CompressedRistretto::from_slice(bytes).ok()?;
+    if point.is_identity() {
+        return None;
+    }
+    transcript.append_message(label, point.as_bytes());
+    Some(point)
+}
+
+/// `1 + x + ... + x^(n-1)`.
+fn sum_of_powers(x: &Scalar, n: usize) -> Scalar {
+    let mut sum = Scalar::ZERO;
+    let mut power = Scalar::ONE;
+    for _ in 0..n {
+        sum += power;
+        power *= x;
+    }
+    sum
+}
+
+/// Scalars multiplying the generators every proof shares.
+struct SharedScalars {
+    b: Scalar,
+    b_blinding: Scalar,
+    g: Vec<Scalar>,
+    h: Vec<Scalar>,
+}
+
+/// Replay one proof's transcript and add its verification equation, scaled by a random
+/// weight, to the accumulators. `None` if the proof is malformed.
+fn accumulate(
+    statement: &RangeStatement,
+    shared: &mut SharedScalars,
+    scalars: &mut Vec<Scalar>,
+    points: &mut Vec<Option<RistrettoPoint>>,
+) -> Option<()> {
+    let n = statement.n_bits;
+    if !matches!(n, 8 | 16 | 32 | 64) {
+        return None;
+    }
+    let lg_n = n.trailing_zeros() as usize;
+    let bytes = statement.proof;
+    if bytes.len() != 32 * (7 + 2 * lg_n + 2) {
+        return None;
+    }
+    let chunk = |i: usize| &bytes[32 * i..32 * (i + 1)];
+
+    let mut transcript = Transcript::new(statement.label);
+    transcript.append_message(b"dom-sep", b"rangeproof v1");
+    transcript.append_u64(b"n", n as u64);
+    transcript.append_u64(b"m", 1);
+    transcript.append_message(b"V", statement.commitment.compress().as_bytes());
+
+    let a_point = append_point(&mut transcript, b"A", chunk(0))?;
+    let s_point = append_point(&mut transcript, b"S", chunk(1))?;
+    let y = challenge_scalar(&mut transcript, b"y");
+    let z = challenge_scalar(&mut transcript, b"z");
+    let t1_point = append_point(&mut transcript, b"T_1", chunk(2))?;
+    let t2_point = append_point(&mut transcript, b"T_2", chunk(3))?;
+    let x = challenge_scalar(&mut transcript, b"x");
+
+    let t_x = read_scalar(chunk(4))?;
+    let t_x_blinding = read_scalar(chunk(5))?;
+    let e_blinding = read_scalar(chunk(6))?;
+    transcript.append_message(b"t_x", t_x.as_bytes());
+    transcript.append_message(b"t_x_blinding", t_x_blinding.as_bytes());
+    transcript.append_message(b"e_blinding", e_blinding.as_bytes());
+    let w = challenge_scalar(&mut transcript, b"w");
+
+    transcript.append_message(b"dom-sep", b"ipp v1");
+    transcript.append_u64(b"n", n as u64);
+    let mut ls = Vec::with_capacity(lg_n);
+    let mut rs = Vec::with_capacity(lg_n);
+    let mut u_sq = Vec::with_capacity(lg_n);
+    for k in 0..lg_n {
+        ls.push(append_point(&mut transcript, b"L", chunk(7 + 2 * k))?);
+        rs.push(append_point(&mut transcript, b"R", chunk(8 + 2 * k))?);
+        u_sq.push(challenge_scalar(&mut transcript, b"u"));
+    }
+    let ipp_a = read_scalar(chunk(7 + 2 * lg_n))?;
+    let ipp_b = read_scalar(chunk(8 + 2 * lg_n))?;
+
+    let mut u_inv_sq = u_sq.clone();
+    let all_inv = Scalar::batch_invert(&mut u_inv_sq);
+    for u in u_sq.iter_mut().chain(u_inv_sq.iter_mut()) {
+        *u = *u * *u;
+    }
+    let mut s = Vec::with_capacity(n);
+    s.push(all_inv);
+    for i in 1..n {
+        let lg_i = (usize::BITS - 1 - i.leading_zeros()) as usize;
+        s.push(s[i - (1 << lg_i)] * u_sq[(lg_n - 1) - lg_i]);
+    }
+
+    // `weight` separates proofs; `c` separates the two equations inside one proof.
+    let weight = random_blinding();
+    let c = random_blinding();
+    let zz = z * z;
+    let delta = (z - zz) * sum_of_powers(&y, n) - zz * z * sum_of_powers(&Scalar::from(2u64), n);
+
+    let y_inv = y.invert();
+    let mut y_inv_power = Scalar::ONE;
+    let mut two_power = Scalar::ONE;
+    for i in 0..n {
+        shared.g[i] += weight * (-z - ipp_a * s[i]);
+        shared.h[i] += weight * (z + y_inv_power * (zz * two_power - ipp_b * s[n - 1 - i]));
+        y_inv_power *= y_inv;
+        two_power += two_power;
+    }
+    shared.b += weight * (w * (t_x - ipp_a * ipp_b) + c * (delta - t_x));
+    shared.b_blinding += weight * (-e_blinding - c * t_x_blinding);
+
+    scalars.extend([weight, weight * x, weight * c * x, weight * c * x * x]);
+    points.extend([a_point, s_point, t1_point, t2_point].map(|p| p.decompress()));
+    scalars.extend(u_sq.iter().chain(&u_inv_sq).map(|u| weight * u));
+    points.extend(ls.iter().chain(&rs).map(|p| p.decompress()));
+    scalars.push(weight * c * zz);
+    points.push(Some(statement.commitment));
+    Some(())
+}
+
+/// Whether every statement holds. An empty batch is vacuously valid.
+pub(crate) fn verify_range_statements(statements: &[RangeStatement]) -> bool {
+    let mut shared = SharedScalars {
+        b: Scalar::ZERO,
+        b_blinding: Scalar::ZERO,
+        g: vec![Scalar::ZERO; MAX_BITS],
+        h: vec![Scalar::ZERO; MAX_BITS],
+    };
+    let mut scalars = Vec::new();
+    let mut points = Vec::new();
+    for statement in statements {
+        if accumulate(statement, &mut shared, &mut scalars, &mut points).is_none() {
+            return false;
+        }
+    }
+
+    let pc_gens = PedersenGens::default();
+    let (g_gens, h_gens) = party_zero_gens();
+    scalars.extend([shared.b, shared.b_blinding]);
+    points.extend([Some(pc_gens.B), Some(pc_gens.B_blinding)]);
+    scalars.extend(shared.g.iter().chain(&shared.h).copied());
+    points.extend(g_gens.iter().chain(h_gens).map(|p| Some(*p)));
+
+    RistrettoPoint::optional_multiscalar_mul(scalars, points).is_some_and(|p| p.is_identity())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bulletproofs::{BulletproofGens, RangeProof};
+
+    #[test]
+    fn derived_generators_match_bulletproof_gens() {
+        let bp_gens = BulletproofGens::new(MAX_BITS, 1);
+        let expected: Vec<RistrettoPoint> = bp_gens.share(0).G(MAX_BITS).copied().collect();
+        assert_eq!(party_zero_gens().0, expected);
+    }
+
+    #[test]
+    fn batch_matches_single_verification() {
+        let pc_gens = PedersenGens::default();
+        let bp_gens = BulletproofGens::new(MAX_BITS, 1);
+        let proofs: Vec<(Vec<u8>, RistrettoPoint, usize)> =
+            [(5u64, 8), (1 << 20, 32), (u64::MAX, 64)]
+                .iter()
+                .map(|&(value, n_bits)| {
+                    let mut transcript = Transcript::new(b"range_batch_test");
+                    let (proof, commit) = RangeProof::prove_single(
+                        &bp_gens,
+                        &pc_gens,
+                        &mut transcript,
+                        value,
+                        &random_blinding(),
+                        n_bits,
+                    )
+                    .unwrap();
+                    (proof.to_bytes(), commit.decompress().unwrap(), n_bits)
+                })
+                .collect();
+        fn statements(proofs: &[(Vec<u8>, RistrettoPoint, usize)]) -> Vec<RangeStatement<'_>> {
+            proofs
+                .iter()
+                .map(|(proof, commitment, n_bits)| RangeStatement {
+                    proof,
+                    commitment: *commitment,
+                    label: b"range_batch_test",
+                    n_bits: *n_bits,
+                })
+                .collect()
+        }
+        assert!(verify_range_statements(&statements(&proofs)));
+
+        let mut wrong_commitment = proofs.clone();
+        wrong_commitment[1].1 += pc_gens.B;
+        assert!(!verify_range_statements(&statements(&wrong_commitment)));
+
+        let mut wrong_bits = proofs;
+        wrong_bits[0].2 = 16;
+        assert!(!verify_range_statements(&statements(&wrong_bits)));
+    }
+}
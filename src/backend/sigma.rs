@@ -0,0 +1,346 @@
+//! Sigma-protocol building blocks over the Ristretto group shared with the Bulletproofs backend.
+//! Challenges are derived with merlin transcripts (Fiat–Shamir); callers pick the transcript label.
+
+use bulletproofs::PedersenGens;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use merlin::Transcript;
+use sha2::{Digest, Sha512};
+
+use super::bulletproofs::random_blinding;
+
+/// Hash arbitrary bytes to a Ristretto point (uniform, unknown discrete log).
+pub(crate) fn hash_to_point(domain: &[u8], data: &[u8]) -> RistrettoPoint {
+    let mut h = Sha512::new();
+    h.update(domain);
+    h.update(data);
+    let wide: [u8; 64] = h.finalize().into();
+    RistrettoPoint::from_uniform_bytes(&wide)
+}
+
+/// Multiset hash: sum of per-element hash points. Order-independent, duplicates counted.
+pub(crate) fn multiset_hash(elements: &[u64]) -> RistrettoPoint {
+    elements
+        .iter()
+        .map(|e| hash_to_point(b"libzkp_multiset_v1", &e.to_le_bytes()))
+        .sum()
+}
+
+pub(crate) fn challenge_scalar(transcript: &mut Transcript, label: &'static [u8]) -> Scalar {
+    let mut buf = [0u8; 64];
+    transcript.challenge_bytes(label, &mut buf);
+    Scalar::from_bytes_mod_order_wide(&buf)
+}
+
+/// Schnorr proof of knowledge of `x` with `point = x * base`. Wire format: `[R 32][s 32]`.
+pub(crate) struct DlogProof {
+    r: CompressedRistretto,
+    s: Scalar,
+}
+
+pub(crate) const DLOG_PROOF_BYTES: usize = 64;
+
+impl DlogProof {
+    pub(crate) fn to_bytes(&self) -> [u8; DLOG_PROOF_BYTES] {
+        let mut out = [0u8; DLOG_PROOF_BYTES];
+        out[0..32].copy_from_slice(self.r.as_bytes());
+        out[32..64].copy_from_slice(self.s.as_bytes());
+        out
+    }
+
+    pub(crate) fn from_bytes(data: &[u8]) -> Option<Self> {
+        if data.len() != DLOG_PROOF_BYTES {
+            return None;
+        }
+        let r = CompressedRistretto::from_slice(&data[0..32]).ok()?;
+        let s_bytes: [u8; 32] = data[32..64].try_into().ok()?;
+        let s = Option::from(Scalar::from_canonical_bytes(s_bytes))?;
+        Some(Self { r, s })
+    }
+}
+
+pub(crate) fn prove_dlog(
+    transcript: &mut Transcript,
+    base: &RistrettoPoint,
+    point: &RistrettoPoint,
+    witness: &Scalar,
+) -> DlogProof {
+    let k = random_blinding();
+    let r = (k * base).compress();
+    transcript.append_message(b"dlog_base", base.compress().as_bytes());
+    transcript.append_message(b"dlog_point", point.compress().as_bytes());
+    transcript.append_message(b"dlog_R", r.as_bytes());
+    let c = challenge_scalar(transcript, b"dlog_c");
+    DlogProof {
+        r,
+        s: k + c * witness,
+    }
+}
+
+pub(crate) fn verify_dlog(
+    transcript: &mut Transcript,
+    base: &RistrettoPoint,
+    point: &RistrettoPoint,
+    proof: &DlogProof,
+) -> bool {
+    let r_point = match proof.r.decompress() {
+        Some(p) => p,
+        None => return false,
+    };
+    transcript.append_message(b"dlog_base", base.compress().as_bytes());
+    transcript.append_message(b"dlog_point", point.compress().as_bytes());
+    transcript.append_message(b"dlog_R", proof.r.as_bytes());
+    let c = challenge_scalar(transcript, b"dlog_c");
+    proof.s * base == r_point + c * point
+}
+
+/// Cramer–Damgård–Schoenmakers OR proof: knowledge of `x` with `points[j] = x * base` for
+/// one undisclosed `j`. Wire format: `[c_i 32]*n [s_i 32]*n`.
+pub(crate) struct OrDlogProof {
+    challenges: Vec<Scalar>,
+    responses: Vec<Scalar>,
+}
+
+impl OrDlogProof {
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(64 * self.challenges.len());
+        for c in &self.challenges {
+            out.extend_from_slice(c.as_bytes());
+        }
+        for s in &self.responses {
+            out.extend_from_slice(s.as_bytes());
+        }
+        out
+    }
+
+    pub(crate) fn from_bytes(data: &[u8], branches: usize) -> Option<Self> {
+        if branches == 0 || data.len() != branches.checked_mul(64)? {
+            return None;
+        }
+        let scalars: Vec<Scalar> = data
+            .chunks(32)
+            .map(|chunk| {
+                let bytes: [u8; 32] = chunk.try_into().ok()?;
+                Option::from(Scalar::from_canonical_bytes(bytes))
+            })
+            .collect::<Option<_>>()?;
+        let (challenges, responses) = scalars.split_at(branches);
+        Some(Self {
+            challenges: challenges.to_vec(),
+            responses: responses.to_vec(),
+        })
+    }
+}
+
+fn or_challenge(
+    transcript: &mut Transcript,
+    base: &RistrettoPoint,
+    points: &[RistrettoPoint],
+    commitments: &[RistrettoPoint],
+) -> Scalar {
+    transcript.append_message(b"or_base", base.compress().as_bytes());
+    for p in points {
+        transcript.append_message(b"or_point", p.compress().as_bytes());
+    }
+    for r in commitments {
+        transcript.append_message(b"or_R", r.compress().as_bytes());
+    }
+    challenge_scalar(transcript, b"or_c")
+}
+
+/// Prove knowledge of `witness` with `points[known] = witness * base`; the other branches are
+/// simulated. Panics if `known` is out of bounds.
+pub(crate) fn prove_or_dlog(
+    transcript: &mut Transcript,
+    base: &RistrettoPoint,
+    points: &[RistrettoPoint],
+    known: usize,
+    witness: &Scalar,
+) -> OrDlogProof {
+    let n = points.len();
+    let mut challenges: Vec<Scalar> = (0..n).map(|_| random_blinding()).collect();
+    let mut responses: Vec<Scalar> = (0..n).map(|_| random_blinding()).collect();
+    let k = random_blinding();
+    let commitments: Vec<RistrettoPoint> = (0..n)
+        .map(|i| {
+            if i == known {
+                k * base
+            } else {
+                responses[i] * base - challenges[i] * points[i]
+            }
+        })
+        .collect();
+
+    let c = or_challenge(transcript, base, points, &commitments);
+    let simulated: Scalar = (0..n).filter(|&i| i != known).map(|i| challenges[i]).sum();
+    challenges[known] = c - simulated;
+    responses[known] = k + challenges[known] * witness;
+    OrDlogProof {
+        challenges,
+        responses,
+    }
+}
+
+pub(crate) fn verify_or_dlog(
+    transcript: &mut Transcript,
+    base: &RistrettoPoint,
+    points: &[RistrettoPoint],
+    proof: &OrDlogProof,
+) -> bool {
+    if points.is_empty()
+        || proof.challenges.len() != points.len()
+        || proof.responses.len() != points.len()
+    {
+        return false;
+    }
+    let commitments: Vec<RistrettoPoint> = points
+        .iter()
+        .zip(proof.challenges.iter().zip(&proof.responses))
+        .map(|(p, (c, s))| s * base - c * p)
+        .collect();
+    let c = or_challenge(transcript, base, points, &commitments);
+    proof.challenges.iter().sum::<Scalar>() == c
+}
+
+/// Proof that a Pedersen commitment `C = v * B + r * B_blinding` hides a non-zero `v`:
+/// knowledge of `(x, y) = (1/v, -r/v)` with `B = x * C + y * B_blinding`, which has no
+/// solution when `v = 0`. Wire format: `[R 32][s_x 32][s_y 32]`.
+pub(crate) struct NonZeroProof {
+    r: CompressedRistretto,
+    s_x: Scalar,
+    s_y: Scalar,
+}
+
+pub(crate) const NONZERO_PROOF_BYTES: usize = 96;
+
+impl NonZeroProof {
+    pub(crate) fn to_bytes(&self) -> [u8; NONZERO_PROOF_BYTES] {
+        let mut out = [0u8; NONZERO_PROOF_BYTES];
+        out[0..32].copy_from_slice(self.r.as_bytes());
+        out[32..64].copy_from_slice(self.s_x.as_bytes());
+        out[64..96].copy_from_slice(self.s_y.as_bytes());
+        out
+    }
+
+    pub(crate) fn from_bytes(data: &[u8]) -> Option<Self> {
+        if data.len() != NONZERO_PROOF_BYTES {
+            return None;
+        }
+        let r = CompressedRistretto::from_slice(&data[0..32]).ok()?;
+        let scalar = |at: usize| -> Option<Scalar> {
+            let bytes: [u8; 32] = data[at..at + 32].try_into().ok()?;
+            Option::from(Scalar::from_canonical_bytes(bytes))
+        };
+        Some(Self {
+            r,
+            s_x: scalar(32)?,
+            s_y: scalar(64)?,
+        })
+    }
+}
+
+fn nonzero_challenge(
+    transcript: &mut Transcript,
+    commitment: &RistrettoPoint,
+    r: &CompressedRistretto,
+) -> Scalar {
+    transcript.append_message(b"nonzero_C", commitment.compress().as_bytes());
+    transcript.append_message(b"nonzero_R", r.as_bytes());
+    challenge_scalar(transcript, b"nonzero_c")
+}
+
+/// Prove `commitment` opens to a non-zero `value` under `blinding`; `None` if `value` is zero.
+pub(crate) fn prove_nonzero(
+    transcript: &mut Transcript,
+    commitment: &RistrettoPoint,
+    value: &Scalar,
+    blinding: &Scalar,
+) -> Option<NonZeroProof> {
+    if *value == Scalar::ZERO {
+        return None;
+    }
+    let h = PedersenGens::default().B_blinding;
+    let x = value.invert();
+    let y = -(blinding * x);
+    let (k_x, k_y) = (random_blinding(), random_blinding());
+    let r = (k_x * commitment + k_y * h).compress();
+    let c = nonzero_challenge(transcript, commitment, &r);
+    Some(NonZeroProof {
+        r,
+        s_x: k_x + c * x,
+        s_y: k_y + c * y,
+    })
+}
+
+pub(crate) fn verify_nonzero(
+    transcript: &mut Transcript,
+    commitment: &RistrettoPoint,
+    proof: &NonZeroProof,
+) -> bool {
+    let r_point = match proof.r.decompress() {
+        Some(p) => p,
+        None => return false,
+    };
+    let gens = PedersenGens::default();
+    let c = nonzero_challenge(transcript, commitment, &proof.r);
+    proof.s_x * commitment + proof.s_y * gens.B_blinding == r_point + c * gens.B
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dlog_roundtrip_and_wrong_point() {
+        let h = PedersenGens::default().B_blinding;
+        let x = random_blinding();
+        let p = x * h;
+        let proof = prove_dlog(&mut Transcript::new(b"t"), &h, &p, &x);
+        let proof = DlogProof::from_bytes(&proof.to_bytes()).unwrap();
+        assert!(verify_dlog(&mut Transcript::new(b"t"), &h, &p, &proof));
+        assert!(!verify_dlog(
+            &mut Transcript::new(b"t"),
+            &h,
+            &(p + h),
+            &proof
+        ));
+    }
+
+    #[test]
+    fn or_dlog_hides_branch_and_rejects_unknown() {
+        let h = PedersenGens::default().B_blinding;
+        let x = random_blinding();
+        let points = [random_blinding() * h, x * h, random_blinding() * h];
+        let proof = prove_or_dlog(&mut Transcript::new(b"t"), &h, &points, 1, &x);
+        let proof = OrDlogProof::from_bytes(&proof.to_bytes(), 3).unwrap();
+        assert!(verify_or_dlog(
+            &mut Transcript::new(b"t"),
+            &h,
+            &points,
+            &proof
+        ));
+
+        // Claiming a branch without its witness does not verify.
+        let forged = prove_or_dlog(&mut Transcript::new(b"t"), &h, &points, 0, &x);
+        assert!(!verify_or_dlog(
+            &mut Transcript::new(b"t"),
+            &h,
+            &points,
+            &forged
+        ));
+    }
+
+    #[test]
+    fn nonzero_rejects_commitment_to_zero() {
+        let gens = PedersenGens::default();
+        let (v, r) = (Scalar::from(7u64), random_blinding());
+        let c = gens.commit(v, r);
+        let proof = prove_nonzero(&mut Transcript::new(b"t"), &c, &v, &r).unwrap();
+        let proof = NonZeroProof::from_bytes(&proof.to_bytes()).unwrap();
+        assert!(verify_nonzero(&mut Transcript::new(b"t"), &c, &proof));
+
+        let zero = gens.commit(Scalar::ZERO, r);
+        assert!(prove_nonzero(&mut Transcript::new(b"t"), &zero, &Scalar::ZERO, &r).is_none());
+        assert!(!verify_nonzero(&mut Transcript::new(b"t"), &zero, &proof));
+    }
+}
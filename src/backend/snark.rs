@@ -1,77 +1,99 @@
 use super::ZkpBackend;
 use crate::utils::encoding::read_u64_le;
 use crate::utils::error_handling::ZkpError;
+use crate::utils::validation::evaluate_quadratic;
+use ark_bls12_381::Bls12_381;
 use ark_bn254::{Bn254, Fr};
-use ark_ff::{AdditiveGroup, PrimeField};
+use ark_ec::pairing::Pairing;
+use ark_ff::PrimeField;
 use ark_groth16::Groth16;
 use ark_r1cs_std::fields::fp::FpVar;
 use ark_r1cs_std::prelude::*;
-use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, ConstraintSystemRef, OptimizationGoal, SynthesisError,
+    SynthesisMode,
+};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 use ark_snark::SNARK;
 use ark_std::rand::rngs::OsRng;
+use merkle::{
+    dummy_merkle_circuit, dummy_non_membership_circuit, merkle_root, non_membership_root,
+    normalize_merkle_set, normalize_non_membership_set, prove_merkle_membership,
+    prove_non_membership, verify_merkle_membership, verify_non_membership,
+};
 use sha2::{Digest, Sha256};
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Mutex, OnceLock};
+use variance::{dummy_variance_circuit, prove_variance_below, verify_variance_below};
+use voting::{
+    ballot_opens, dummy_vote_circuit, eligibility_root, normalize_voter_keys, prove_vote,
+    verify_vote, voter_key, SnarkVote,
+};
+use zeroize::Zeroizing;
+
+pub mod ceremony;
+pub mod merkle;
+pub mod powers_of_tau;
+pub mod variance;
+pub mod voting;
 
 // ===== Key directory configuration =====
-static SNARK_KEY_DIR_OVERRIDE: Mutex<Option<PathBuf>> = Mutex::new(None);
+// The override and the "a setup has started" flag live behind one lock so that
+// `set_snark_key_dir` cannot slip in between a setup reading the directory and
+// the setup being published.
+struct KeyDirState {
+    override_dir: Option<PathBuf>,
+    frozen: bool,
+}
 
-fn get_key_dir() -> Option<PathBuf> {
-    if let Ok(guard) = SNARK_KEY_DIR_OVERRIDE.lock() {
-        if let Some(ref p) = *guard {
-            return Some(p.clone());
-        }
+static SNARK_KEY_DIR_STATE: Mutex<KeyDirState> = Mutex::new(KeyDirState {
+    override_dir: None,
+    frozen: false,
+});
+
+/// Resolve the key directory for a setup and freeze it for the rest of the process.
+fn resolve_key_dir_for_setup() -> Option<PathBuf> {
+    let mut state = SNARK_KEY_DIR_STATE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    state.frozen = true;
+    if let Some(ref p) = state.override_dir {
+        return Some(p.clone());
     }
     env::var("LIBZKP_SNARK_KEY_DIR").ok().map(PathBuf::from)
 }
 
-fn key_paths(prefix: &str) -> Option<(PathBuf, PathBuf)> {
-    get_key_dir().map(|dir| {
-        (
-            dir.join(format!("{}_pk.bin", prefix)),
-            dir.join(format!("{}_vk.bin", prefix)),
-        )
-    })
+/// The key directory without freezing it, for read-only queries.
+fn configured_key_dir() -> Option<PathBuf> {
+    let state = SNARK_KEY_DIR_STATE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(ref p) = state.override_dir {
+        return Some(p.clone());
+    }
+    env::var("LIBZKP_SNARK_KEY_DIR").ok().map(PathBuf::from)
 }
 
-fn load_pk_vk(
-    pk_path: &Path,
-    vk_path: &Path,
-) -> Result<
-    Option<(
-        ark_groth16::ProvingKey<Bn254>,
-        ark_groth16::VerifyingKey<Bn254>,
-    )>,
-    String,
-> {
-    if !pk_path.exists() || !vk_path.exists() {
-        return Ok(None);
-    }
-
-    let pk_bytes = fs::read(pk_path)
-        .map_err(|e| format!("failed to read proving key {}: {:?}", pk_path.display(), e))?;
-    let vk_bytes = fs::read(vk_path).map_err(|e| {
-        format!(
-            "failed to read verifying key {}: {:?}",
-            vk_path.display(),
-            e
-        )
-    })?;
-
-    let pk = ark_groth16::ProvingKey::<Bn254>::deserialize_uncompressed(&pk_bytes[..])
-        .map_err(|e| format!("failed to deserialize proving key: {:?}", e))?;
-    let vk = ark_groth16::VerifyingKey::<Bn254>::deserialize_uncompressed(&vk_bytes[..])
-        .map_err(|e| format!("failed to deserialize verifying key: {:?}", e))?;
+fn key_paths(dir: &Path, stem: &str) -> (PathBuf, PathBuf) {
+    (
+        dir.join(format!("{}_pk.bin", stem)),
+        dir.join(format!("{}_vk.bin", stem)),
+    )
+}
 
-    Ok(Some((pk, vk)))
+fn read_key<T: CanonicalDeserialize>(path: &Path, what: &str) -> Result<T, String> {
+    let bytes = fs::read(path)
+        .map_err(|e| format!("failed to read {} {}: {:?}", what, path.display(), e))?;
+    T::deserialize_uncompressed(&bytes[..])
+        .map_err(|e| format!("failed to deserialize {}: {:?}", what, e))
 }
 
-fn persist_pk_vk(
-    pk: &ark_groth16::ProvingKey<Bn254>,
-    vk: &ark_groth16::VerifyingKey<Bn254>,
+fn persist_pk_vk<E: Pairing>(
+    pk: &ark_groth16::ProvingKey<E>,
+    vk: &ark_groth16::VerifyingKey<E>,
     pk_path: &Path,
     vk_path: &Path,
 ) -> Result<(), String> {
@@ -114,94 +136,683 @@ fn persist_pk_vk(
     Ok(())
 }
 
-type SnarkKeyPair = (
-    ark_groth16::ProvingKey<Bn254>,
-    ark_groth16::VerifyingKey<Bn254>,
-);
-
-fn load_or_generate_setup<G>(prefix: &str, generate: G) -> Result<SnarkKeyPair, String>
-where
-    G: FnOnce() -> Result<SnarkKeyPair, String>,
-{
-    if let Some((pk_path, vk_path)) = key_paths(prefix) {
-        match load_pk_vk(&pk_path, &vk_path)? {
-            Some(pair) => return Ok(pair),
-            None => {
-                let pair = generate()?;
-                if let Err(e) = persist_pk_vk(&pair.0, &pair.1, &pk_path, &vk_path) {
-                    let _ = e;
-                }
-                return Ok(pair);
-            }
+type SnarkKeyPair<E> = (ark_groth16::ProvingKey<E>, ark_groth16::VerifyingKey<E>);
+
+// ===== Key versions =====
+// Version 1 of a circuit's keys keeps the original file names (`equality_mimc_pk.bin`);
+// later versions insert `_v{n}` after the prefix (`equality_mimc_v2_pk.bin`). The newest
+// version proves and every version still in the key directory verifies, so rotating keys
+// does not invalidate proofs issued under the previous ones until they are retired.
+
+/// File stem of `version` of the `prefix` keys on curve `E`.
+fn versioned_stem<E: SnarkEngine>(prefix: &str, version: u32) -> String {
+    match version {
+        1 => format!("{}{}", prefix, E::KEY_SUFFIX),
+        _ => format!("{}_v{}{}", prefix, version, E::KEY_SUFFIX),
+    }
+}
+
+fn parse_version<E: SnarkEngine>(prefix: &str, stem: &str) -> Option<u32> {
+    let rest = stem.strip_prefix(prefix)?.strip_suffix(E::KEY_SUFFIX)?;
+    let version = match rest {
+        "" => 1,
+        _ => rest.strip_prefix("_v")?.parse().ok()?,
+    };
+    (versioned_stem::<E>(prefix, version) == stem).then_some(version)
+}
+
+/// Trusted versions of the `prefix` keys in `dir` (both files present, not retired), newest
+/// first, and the highest version ever installed, retired ones included.
+fn scan_key_versions<E: SnarkEngine>(dir: &Path, prefix: &str) -> (Vec<u32>, u32) {
+    let mut trusted = Vec::new();
+    let mut highest = 0;
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let name = entry.file_name();
+        let Some(name) = name.to_str() else {
+            continue;
+        };
+        let (name, retired) = match name.strip_suffix(".retired") {
+            Some(name) => (name, true),
+            None => (name, false),
+        };
+        let Some(stem) = name.strip_suffix("_vk.bin") else {
+            continue;
+        };
+        let Some(version) = parse_version::<E>(prefix, stem) else {
+            continue;
+        };
+        highest = highest.max(version);
+        if !retired && key_paths(dir, stem).0.exists() {
+            trusted.push(version);
         }
     }
-    generate()
+    trusted.sort_unstable_by(|a, b| b.cmp(a));
+    (trusted, highest)
+}
+
+/// A circuit's loaded keys: the newest version proves, every trusted version verifies.
+struct KeySet<E: Pairing> {
+    pk: ark_groth16::ProvingKey<E>,
+    /// Verifying keys of the trusted versions, newest first.
+    vks: Vec<ark_groth16::VerifyingKey<E>>,
+}
+
+/// Load `circuit`'s keys for `E` from the key directory, or generate (and persist) them
+/// as version 1.
+fn load_or_generate_setup<E: SnarkEngine>(circuit: SnarkCircuit) -> Result<KeySet<E>, String> {
+    let generate = || {
+        circuit
+            .generate_keys::<E>()
+            .map(|(pk, vk)| KeySet { pk, vks: vec![vk] })
+    };
+    let Some(dir) = resolve_key_dir_for_setup() else {
+        return generate();
+    };
+    let prefix = circuit.key_prefix();
+    let (trusted, _) = scan_key_versions::<E>(&dir, &prefix);
+    let Some(&newest) = trusted.first() else {
+        let keys = generate()?;
+        let (pk_path, vk_path) = key_paths(&dir, &versioned_stem::<E>(&prefix, 1));
+        if let Err(e) = persist_pk_vk(&keys.pk, &keys.vks[0], &pk_path, &vk_path) {
+            let _ = e;
+        }
+        return Ok(keys);
+    };
+    let paths = |version| key_paths(&dir, &versioned_stem::<E>(&prefix, version));
+    let vks = trusted
+        .iter()
+        .map(|&version| read_key(&paths(version).1, "verifying key"))
+        .collect::<Result<_, _>>()?;
+    Ok(KeySet {
+        pk: read_key(&paths(newest).0, "proving key")?,
+        vks,
+    })
+}
+
+/// Key directory for changing `circuit`'s key versions on `E`. Refused once this process
+/// has loaded those keys, since it would keep using the old set.
+fn key_admin_dir<E: SnarkEngine>(circuit: SnarkCircuit) -> Result<PathBuf, ZkpError> {
+    circuit.validate()?;
+    if circuit.setup_slot::<E>().get().is_some() {
+        return Err(ZkpError::ConfigError(format!(
+            "{} keys are already loaded in this process; change key versions before its first proof",
+            circuit.key_prefix()
+        )));
+    }
+    resolve_key_dir_for_setup().ok_or_else(|| {
+        ZkpError::ConfigError(
+            "no SNARK key directory; set LIBZKP_SNARK_KEY_DIR or call set_snark_key_dir"
+                .to_string(),
+        )
+    })
+}
+
+/// Write `pk`/`vk` as the next version of `circuit`'s keys and return that version.
+fn install_keys<E: SnarkEngine>(
+    circuit: SnarkCircuit,
+    pk: &ark_groth16::ProvingKey<E>,
+    vk: &ark_groth16::VerifyingKey<E>,
+) -> Result<u32, ZkpError> {
+    let dir = key_admin_dir::<E>(circuit)?;
+    let prefix = circuit.key_prefix();
+    let version = scan_key_versions::<E>(&dir, &prefix).1 + 1;
+    let (pk_path, vk_path) = key_paths(&dir, &versioned_stem::<E>(&prefix, version));
+    persist_pk_vk(pk, vk, &pk_path, &vk_path).map_err(ZkpError::StorageError)?;
+    Ok(version)
+}
+
+fn rotate_keys_on<E: SnarkEngine>(circuit: SnarkCircuit) -> Result<u32, ZkpError> {
+    // Fail before the setup, which takes seconds for the membership circuit.
+    key_admin_dir::<E>(circuit)?;
+    let (pk, vk) = circuit
+        .generate_keys::<E>()
+        .map_err(ZkpError::BackendError)?;
+    install_keys(circuit, &pk, &vk)
+}
+
+fn retire_key_version_on<E: SnarkEngine>(
+    circuit: SnarkCircuit,
+    version: u32,
+) -> Result<(), ZkpError> {
+    let dir = key_admin_dir::<E>(circuit)?;
+    let prefix = circuit.key_prefix();
+    let (trusted, _) = scan_key_versions::<E>(&dir, &prefix);
+    if !trusted.contains(&version) {
+        return Err(ZkpError::InvalidInput(format!(
+            "{} has no trusted key version {}",
+            prefix, version
+        )));
+    }
+    if trusted[0] == version {
+        return Err(ZkpError::InvalidInput(format!(
+            "version {} is the newest {} key; rotate before retiring it",
+            version, prefix
+        )));
+    }
+    // The verifying key goes first: without it the version is no longer trusted.
+    let (pk_path, vk_path) = key_paths(&dir, &versioned_stem::<E>(&prefix, version));
+    for path in [vk_path, pk_path] {
+        let mut retired = path.clone().into_os_string();
+        retired.push(".retired");
+        fs::rename(&path, &retired).map_err(|e| {
+            ZkpError::StorageError(format!("failed to retire {}: {:?}", path.display(), e))
+        })?;
+    }
+    Ok(())
+}
+
+fn key_versions_on<E: SnarkEngine>(circuit: SnarkCircuit) -> Vec<u32> {
+    configured_key_dir()
+        .map(|dir| scan_key_versions::<E>(&dir, &circuit.key_prefix()).0)
+        .unwrap_or_default()
 }
 
 pub fn set_snark_key_dir(path: &str) -> Result<(), ZkpError> {
+    let requested = PathBuf::from(path);
+    let mut state = SNARK_KEY_DIR_STATE
+        .lock()
+        .map_err(|_| ZkpError::ConfigError("SNARK key directory lock poisoned".to_string()))?;
+    check_key_dir(&state, path)?;
+    if state.override_dir.is_none() {
+        state.override_dir = Some(requested);
+    }
+    Ok(())
+}
+
+/// Whether [`set_snark_key_dir`] would accept `path`, without setting it.
+pub(crate) fn check_snark_key_dir(path: &str) -> Result<(), ZkpError> {
+    let state = SNARK_KEY_DIR_STATE
+        .lock()
+        .map_err(|_| ZkpError::ConfigError("SNARK key directory lock poisoned".to_string()))?;
+    check_key_dir(&state, path)
+}
+
+fn check_key_dir(state: &KeyDirState, path: &str) -> Result<(), ZkpError> {
     if path.is_empty() {
         return Err(ZkpError::ConfigError(
             "SNARK key directory cannot be empty".to_string(),
         ));
     }
-    if UNIVERSAL_SETUP.get().is_some() || MEMBERSHIP_SETUP.get().is_some() {
+    if state.frozen || any_setup_initialized() {
         return Err(ZkpError::ConfigError(
             "SNARK setup is already initialized; set LIBZKP_SNARK_KEY_DIR before first proof"
                 .to_string(),
         ));
     }
-
     let requested = PathBuf::from(path);
-    let mut guard = SNARK_KEY_DIR_OVERRIDE
-        .lock()
-        .map_err(|_| ZkpError::ConfigError("SNARK key directory lock poisoned".to_string()))?;
-    if let Some(existing) = guard.as_ref() {
-        if existing != &requested {
-            return Err(ZkpError::ConfigError(format!(
-                "SNARK key directory already set to {}; new value {} rejected",
-                existing.display(),
-                requested.display()
-            )));
+    match state.override_dir.as_ref() {
+        Some(existing) if existing != &requested => Err(ZkpError::ConfigError(format!(
+            "SNARK key directory already set to {}; new value {} rejected",
+            existing.display(),
+            requested.display()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+pub fn is_snark_initialized() -> bool {
+    any_setup_initialized()
+}
+
+// ===== Curve selection =====
+// Every circuit can be instantiated over BN254 (the default) or BLS12-381. The curve is
+// process-wide and fixed by the first SNARK commitment, proof or verification: commitments,
+// keys and proofs made on one curve mean nothing on the other.
+
+/// Pairing curve for SNARK commitments, keys and proofs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SnarkCurve {
+    #[default]
+    Bn254,
+    Bls12_381,
+}
+
+impl SnarkCurve {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Bn254 => "bn254",
+            Self::Bls12_381 => "bls12_381",
         }
-    } else {
-        *guard = Some(requested.clone());
+    }
+}
+
+impl std::str::FromStr for SnarkCurve {
+    type Err = ZkpError;
+
+    fn from_str(s: &str) -> Result<Self, ZkpError> {
+        match s.to_ascii_lowercase().as_str() {
+            "bn254" => Ok(Self::Bn254),
+            "bls12_381" | "bls12-381" => Ok(Self::Bls12_381),
+            _ => Err(ZkpError::ConfigError(format!(
+                "unknown SNARK curve {}; expected bn254 or bls12_381",
+                s
+            ))),
+        }
+    }
+}
+
+static SNARK_CURVE: OnceLock<SnarkCurve> = OnceLock::new();
+
+/// Select the SNARK curve. Call before the first SNARK use; afterwards only the curve already
+/// in use is accepted.
+pub fn set_snark_curve(curve: SnarkCurve) -> Result<(), ZkpError> {
+    SNARK_CURVE.get_or_init(|| curve);
+    check_snark_curve(curve)
+}
+
+/// Whether [`set_snark_curve`] would accept `curve`, without fixing it.
+pub(crate) fn check_snark_curve(curve: SnarkCurve) -> Result<(), ZkpError> {
+    let active = SNARK_CURVE.get().copied().unwrap_or(curve);
+    if active != curve {
+        return Err(ZkpError::ConfigError(format!(
+            "SNARK curve is already fixed to {}; set it before the first SNARK use",
+            active.name()
+        )));
     }
     Ok(())
 }
 
-pub fn is_snark_initialized() -> bool {
-    UNIVERSAL_SETUP.get().is_some() || MEMBERSHIP_SETUP.get().is_some()
+/// The selected curve (BN254 unless [`set_snark_curve`] chose another), without fixing it.
+pub fn snark_curve() -> SnarkCurve {
+    SNARK_CURVE.get().copied().unwrap_or_default()
+}
+
+/// The curve for a SNARK operation; fixes the default on first use.
+fn active_curve() -> SnarkCurve {
+    *SNARK_CURVE.get_or_init(SnarkCurve::default)
+}
+
+/// Call a function generic over [`SnarkEngine`] with the active curve's engine.
+macro_rules! on_active_curve {
+    ($f:ident($($arg:expr),* $(,)?)) => {
+        match active_curve() {
+            SnarkCurve::Bn254 => $f::<Bn254>($($arg),*),
+            SnarkCurve::Bls12_381 => $f::<Bls12_381>($($arg),*),
+        }
+    };
+}
+
+/// A pairing curve the circuits are instantiated over, with its own setup cache.
+trait SnarkEngine: Pairing<ScalarField: MimcField> {
+    /// Appended to key file names; empty for BN254 so existing key directories keep loading.
+    const KEY_SUFFIX: &'static str;
+
+    fn setups() -> &'static SetupCache<Self>;
+}
+
+type SetupSlot<E> = OnceLock<Result<KeySet<E>, String>>;
+
+struct SetupCache<E: Pairing> {
+    equality: SetupSlot<E>,
+    membership: SetupSlot<E>,
+    polynomial: SetupSlot<E>,
+    non_membership: SetupSlot<E>,
+    merkle_membership: SetupSlot<E>,
+    vote: SetupSlot<E>,
+    variance: SetupSlot<E>,
+    /// Width-bounded equality setups, indexed by `bits - 1`.
+    equality_widths: [SetupSlot<E>; MAX_EQUALITY_BITS],
+}
+
+impl<E: Pairing> SetupCache<E> {
+    const fn new() -> Self {
+        Self {
+            equality: OnceLock::new(),
+            membership: OnceLock::new(),
+            polynomial: OnceLock::new(),
+            non_membership: OnceLock::new(),
+            merkle_membership: OnceLock::new(),
+            vote: OnceLock::new(),
+            variance: OnceLock::new(),
+            equality_widths: [const { OnceLock::new() }; MAX_EQUALITY_BITS],
+        }
+    }
+
+    fn any_initialized(&self) -> bool {
+        self.equality.get().is_some()
+            || self.membership.get().is_some()
+            || self.polynomial.get().is_some()
+            || self.non_membership.get().is_some()
+            || self.merkle_membership.get().is_some()
+            || self.vote.get().is_some()
+            || self.variance.get().is_some()
+            || self.equality_widths.iter().any(|s| s.get().is_some())
+    }
+}
+
+/// A circuit with its own Groth16 keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnarkCircuit {
+    Equality,
+    /// Width-bounded equality (`prove_equality_zk_bits`), `1..=MAX_EQUALITY_BITS`.
+    EqualityBits(usize),
+    Membership,
+    PolynomialNonneg,
+    /// Non-membership against the gap tree of a set (`merkle`).
+    NonMembership,
+    /// Membership against a Merkle root (`merkle`), for sets beyond `MAX_SET_SIZE`.
+    MerkleMembership,
+    /// Eligibility and one-of-N selection for private voting (`voting`).
+    Vote,
+    /// Bound on the variance of hidden values (`variance`).
+    Variance,
+}
+
+impl SnarkCircuit {
+    /// Key file prefix, e.g. `equality_mimc_w32`. The `_mimc` suffix keeps keys from the
+    /// earlier SHA-256 circuits from being loaded.
+    pub fn key_prefix(self) -> String {
+        match self {
+            Self::Equality => "equality_mimc".to_string(),
+            Self::EqualityBits(bits) => format!("equality_mimc_w{}", bits),
+            Self::Membership => "membership_mimc".to_string(),
+            Self::PolynomialNonneg => "polynomial_nonneg_mimc".to_string(),
+            Self::NonMembership => "non_membership_merkle_mimc".to_string(),
+            Self::MerkleMembership => "merkle_membership_mimc".to_string(),
+            Self::Vote => "vote_mimc".to_string(),
+            Self::Variance => "variance_mimc".to_string(),
+        }
+    }
+
+    fn validate(self) -> Result<(), ZkpError> {
+        match self {
+            Self::EqualityBits(bits) if !(1..=MAX_EQUALITY_BITS).contains(&bits) => {
+                Err(ZkpError::InvalidInput(format!(
+                    "equality width must be in 1..={}, got {}",
+                    MAX_EQUALITY_BITS, bits
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The cache slot for this circuit's keys; the width must be valid.
+    fn setup_slot<E: SnarkEngine>(self) -> &'static SetupSlot<E> {
+        let setups = E::setups();
+        match self {
+            Self::Equality => &setups.equality,
+            Self::EqualityBits(bits) => &setups.equality_widths[bits - 1],
+            Self::Membership => &setups.membership,
+            Self::PolynomialNonneg => &setups.polynomial,
+            Self::NonMembership => &setups.non_membership,
+            Self::MerkleMembership => &setups.merkle_membership,
+            Self::Vote => &setups.vote,
+            Self::Variance => &setups.variance,
+        }
+    }
+
+    /// The circuit's constraint system, synthesized and finalized as the Groth16 setup does.
+    fn synthesize<F: MimcField>(self) -> Option<ConstraintSystemRef<F>> {
+        fn run<F: PrimeField>(
+            circuit: impl ConstraintSynthesizer<F>,
+        ) -> Option<ConstraintSystemRef<F>> {
+            let cs = ConstraintSystem::<F>::new_ref();
+            cs.set_optimization_goal(OptimizationGoal::Constraints);
+            cs.set_mode(SynthesisMode::Setup);
+            circuit.generate_constraints(cs.clone()).ok()?;
+            cs.finalize();
+            Some(cs)
+        }
+        match self {
+            Self::Equality => run(SnarkBackend::dummy_equality_circuit::<F>(None)),
+            Self::EqualityBits(bits) => run(SnarkBackend::dummy_equality_circuit::<F>(Some(bits))),
+            Self::Membership => run(SnarkBackend::dummy_membership_circuit::<F>()),
+            Self::PolynomialNonneg => run(SnarkBackend::dummy_polynomial_circuit::<F>()),
+            Self::NonMembership => run(dummy_non_membership_circuit::<F>()),
+            Self::MerkleMembership => run(dummy_merkle_circuit::<F>()),
+            Self::Vote => run(dummy_vote_circuit::<F>()),
+            Self::Variance => run(dummy_variance_circuit::<F>()),
+        }
+    }
+
+    /// Instance and total variable counts of the synthesized circuit.
+    fn shape<F: MimcField>(self) -> Option<(usize, usize)> {
+        let cs = self.synthesize::<F>()?;
+        let instance = cs.num_instance_variables();
+        Some((instance, instance + cs.num_witness_variables()))
+    }
+
+    fn generate_keys<E: SnarkEngine>(self) -> Result<SnarkKeyPair<E>, String> {
+        match self {
+            Self::Equality => circuit_setup(SnarkBackend::dummy_equality_circuit(None)),
+            Self::EqualityBits(bits) => {
+                circuit_setup(SnarkBackend::dummy_equality_circuit(Some(bits)))
+            }
+            Self::Membership => circuit_setup(SnarkBackend::dummy_membership_circuit()),
+            Self::PolynomialNonneg => circuit_setup(SnarkBackend::dummy_polynomial_circuit()),
+            Self::NonMembership => circuit_setup(dummy_non_membership_circuit()),
+            Self::MerkleMembership => circuit_setup(dummy_merkle_circuit()),
+            Self::Vote => circuit_setup(dummy_vote_circuit()),
+            Self::Variance => circuit_setup(dummy_variance_circuit()),
+        }
+    }
+}
+
+impl std::str::FromStr for SnarkCircuit {
+    type Err = ZkpError;
+
+    /// `equality`, `equality_w{bits}`, `membership`, `polynomial_nonneg`, `non_membership`,
+    /// `merkle_membership`, `vote` or `variance`.
+    fn from_str(s: &str) -> Result<Self, ZkpError> {
+        let circuit = match s {
+            "equality" => Self::Equality,
+            "membership" => Self::Membership,
+            "polynomial_nonneg" => Self::PolynomialNonneg,
+            "non_membership" => Self::NonMembership,
+            "merkle_membership" => Self::MerkleMembership,
+            "vote" => Self::Vote,
+            "variance" => Self::Variance,
+            _ => match s.strip_prefix("equality_w").map(str::parse) {
+                Some(Ok(bits)) => Self::EqualityBits(bits),
+                _ => {
+                    return Err(ZkpError::InvalidInput(format!(
+                        "unknown SNARK circuit {}; expected equality, equality_w{{bits}}, membership, polynomial_nonneg, non_membership, merkle_membership, vote or variance",
+                        s
+                    )))
+                }
+            },
+        };
+        circuit.validate()?;
+        Ok(circuit)
+    }
+}
+
+impl SnarkEngine for Bn254 {
+    const KEY_SUFFIX: &'static str = "";
+
+    fn setups() -> &'static SetupCache<Self> {
+        static CACHE: SetupCache<Bn254> = SetupCache::new();
+        &CACHE
+    }
+}
+
+impl SnarkEngine for Bls12_381 {
+    const KEY_SUFFIX: &'static str = "_bls12_381";
+
+    fn setups() -> &'static SetupCache<Self> {
+        static CACHE: SetupCache<Bls12_381> = SetupCache::new();
+        &CACHE
+    }
+}
+
+/// Generate new keys for `circuit` on the active curve and install them as its next version.
+/// New proofs use them; proofs under older versions keep verifying until those are retired.
+/// Needs a key directory and must run before this process loads the circuit's keys; other
+/// processes pick the new version up when they next load them.
+pub fn rotate_keys(circuit: SnarkCircuit) -> Result<u32, ZkpError> {
+    on_active_curve!(rotate_keys_on(circuit))
+}
+
+/// Stop trusting `version` of `circuit`'s keys on the active curve. The files are renamed
+/// with a `.retired` extension and the number is never reused. The newest version cannot be
+/// retired.
+pub fn retire_key_version(circuit: SnarkCircuit, version: u32) -> Result<(), ZkpError> {
+    on_active_curve!(retire_key_version_on(circuit, version))
+}
+
+/// Trusted key versions of `circuit` on the active curve, newest first; empty when there
+/// is no key directory or no keys yet.
+pub fn key_versions(circuit: SnarkCircuit) -> Result<Vec<u32>, ZkpError> {
+    circuit.validate()?;
+    Ok(on_active_curve!(key_versions_on(circuit)))
+}
+
+// ===== In-memory key export / import =====
+// For keys kept outside the filesystem (a KMS, or shipped over the network): export the
+// keys a circuit proves with, and install them in another process before its first use.
+
+fn export_keys_on<E: SnarkEngine>(circuit: SnarkCircuit) -> Result<(Vec<u8>, Vec<u8>), ZkpError> {
+    let keys = match setup::<E>(circuit) {
+        Some(Ok(keys)) => keys,
+        Some(Err(e)) => return Err(ZkpError::BackendError(e.clone())),
+        None => return Err(ZkpError::InvalidInput("unsupported circuit".to_string())),
+    };
+    let ser = |e| ZkpError::SerializationError(format!("failed to serialize key: {:?}", e));
+    let (mut pk, mut vk) = (Vec::new(), Vec::new());
+    keys.pk.serialize_uncompressed(&mut pk).map_err(ser)?;
+    keys.vks[0].serialize_uncompressed(&mut vk).map_err(ser)?;
+    Ok((pk, vk))
+}
+
+fn import_keys_on<E: SnarkEngine>(
+    circuit: SnarkCircuit,
+    pk_bytes: &[u8],
+    vk_bytes: &[u8],
+) -> Result<(), ZkpError> {
+    let pk = ark_groth16::ProvingKey::<E>::deserialize_uncompressed(pk_bytes)
+        .map_err(|e| ZkpError::InvalidInput(format!("invalid proving key: {:?}", e)))?;
+    let vk = ark_groth16::VerifyingKey::<E>::deserialize_uncompressed(vk_bytes)
+        .map_err(|e| ZkpError::InvalidInput(format!("invalid verifying key: {:?}", e)))?;
+    if pk.vk != vk {
+        return Err(ZkpError::InvalidInput(
+            "proving and verifying keys are not a pair".to_string(),
+        ));
+    }
+    match circuit.shape::<E::ScalarField>() {
+        Some((instance, total))
+            if vk.gamma_abc_g1.len() == instance && pk.a_query.len() == total => {}
+        _ => {
+            return Err(ZkpError::InvalidInput(format!(
+                "keys do not match the {} circuit",
+                circuit.key_prefix()
+            )))
+        }
+    }
+    circuit
+        .setup_slot::<E>()
+        .set(Ok(KeySet { pk, vks: vec![vk] }))
+        .map_err(|_| {
+            ZkpError::ConfigError(format!(
+                "{} keys are already loaded in this process; import before its first proof",
+                circuit.key_prefix()
+            ))
+        })
+}
+
+/// Serialized (uncompressed) proving and verifying key that `circuit` proves with on the
+/// active curve, loading or generating them first if needed.
+pub fn export_keys(circuit: SnarkCircuit) -> Result<(Vec<u8>, Vec<u8>), ZkpError> {
+    circuit.validate()?;
+    on_active_curve!(export_keys_on(circuit))
+}
+
+/// Use the given keys (from [`export_keys`]) for `circuit` in this process instead of the key
+/// directory. They are the only trusted verifying key. Must run before the circuit's keys
+/// are first used.
+pub fn import_keys(
+    circuit: SnarkCircuit,
+    pk_bytes: &[u8],
+    vk_bytes: &[u8],
+) -> Result<(), ZkpError> {
+    circuit.validate()?;
+    on_active_curve!(import_keys_on(circuit, pk_bytes, vk_bytes))
+}
+
+// ===== Proof serialization =====
+// A Groth16 proof is 256 bytes uncompressed and 128 compressed on BN254 (384 and 192 on
+// BLS12-381); compressed points keep only x-coordinates plus a sign flag. The two lengths
+// never collide on a curve, so verifiers accept either form.
+
+static COMPRESS_PROOFS: AtomicBool = AtomicBool::new(false);
+
+/// Serialize new proofs compressed (128 bytes on BN254) instead of uncompressed (256 bytes).
+/// Only proving is affected. Off by default so proofs stay readable by releases that
+/// predate it.
+pub fn set_proof_compression(enabled: bool) {
+    COMPRESS_PROOFS.store(enabled, Ordering::Relaxed);
+}
+
+pub fn proof_compression_enabled() -> bool {
+    COMPRESS_PROOFS.load(Ordering::Relaxed)
+}
+
+/// Serialize in the configured form; empty on failure, like the provers' other errors.
+fn serialize_proof<E: Pairing>(proof: &ark_groth16::Proof<E>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let result = if proof_compression_enabled() {
+        proof.serialize_compressed(&mut bytes)
+    } else {
+        proof.serialize_uncompressed(&mut bytes)
+    };
+    match result {
+        Ok(()) => bytes,
+        Err(_) => vec![],
+    }
+}
+
+/// Deserialize either form, detected by length. Trailing bytes are rejected.
+fn deserialize_proof<E: Pairing>(bytes: &[u8]) -> Option<ark_groth16::Proof<E>> {
+    let empty = ark_groth16::Proof::<E>::default();
+    if bytes.len() == empty.compressed_size() {
+        ark_groth16::Proof::deserialize_compressed(bytes).ok()
+    } else if bytes.len() == empty.uncompressed_size() {
+        ark_groth16::Proof::deserialize_uncompressed(bytes).ok()
+    } else {
+        None
+    }
 }
 
 // ===== MiMC-5 hash function =====
-// MiMC-5 over BN254 Fr field.
-// S-box: f(x) = x^5 (valid since gcd(5, p-1) = 1 for BN254).
-// Rounds: 110 (>= ceil(log_5(p)) = ceil(254/2.322) ≈ 110).
+// MiMC-5 over the scalar field of the SNARK curve (BN254 or BLS12-381 Fr).
+// S-box: f(x) = x^5 (valid since gcd(5, p-1) = 1 for both fields).
+// Rounds: 110 (>= ceil(log_5(p)) = ceil(255/2.322) ≈ 110).
 // Round constants derived deterministically from SHA-256 of "libzkp_mimc_v1:{i}".
 
 pub const MIMC_ROUNDS: usize = 110;
 
-static MIMC_CONSTANTS: OnceLock<Box<[Fr; MIMC_ROUNDS]>> = OnceLock::new();
-
-fn get_mimc_constants() -> &'static [Fr; MIMC_ROUNDS] {
-    MIMC_CONSTANTS.get_or_init(|| {
-        let mut constants = Box::new([Fr::ZERO; MIMC_ROUNDS]);
-        for i in 0..MIMC_ROUNDS {
-            let mut hasher = Sha256::new();
-            hasher.update(b"libzkp_mimc_v1:");
-            hasher.update(&(i as u64).to_le_bytes());
-            let hash = hasher.finalize();
-            constants[i] = Fr::from_le_bytes_mod_order(&hash);
-        }
-        constants
-    })
+/// A scalar field with cached MiMC round constants.
+trait MimcField: PrimeField {
+    fn mimc_constants() -> &'static [Self; MIMC_ROUNDS];
 }
 
-/// Compute MiMC-5 hash of a u64 value natively (for commitment generation).
-pub fn mimc_hash_native(value: u64) -> Fr {
-    let constants = get_mimc_constants();
-    let mut x = Fr::from(value);
-    for &c in constants.iter() {
+fn derive_mimc_constants<F: PrimeField>() -> Box<[F; MIMC_ROUNDS]> {
+    let mut constants = Box::new([F::ZERO; MIMC_ROUNDS]);
+    for (i, constant) in constants.iter_mut().enumerate() {
+        let mut hasher = Sha256::new();
+        hasher.update(b"libzkp_mimc_v1:");
+        hasher.update((i as u64).to_le_bytes());
+        *constant = F::from_le_bytes_mod_order(&hasher.finalize());
+    }
+    constants
+}
+
+impl MimcField for Fr {
+    fn mimc_constants() -> &'static [Self; MIMC_ROUNDS] {
+        static CONSTANTS: OnceLock<Box<[Fr; MIMC_ROUNDS]>> = OnceLock::new();
+        CONSTANTS.get_or_init(derive_mimc_constants)
+    }
+}
+
+impl MimcField for ark_bls12_381::Fr {
+    fn mimc_constants() -> &'static [Self; MIMC_ROUNDS] {
+        static CONSTANTS: OnceLock<Box<[ark_bls12_381::Fr; MIMC_ROUNDS]>> = OnceLock::new();
+        CONSTANTS.get_or_init(derive_mimc_constants)
+    }
+}
+
+fn mimc_hash<F: MimcField>(value: u64) -> F {
+    let mut x = F::from(value);
+    for &c in F::mimc_constants().iter() {
         let t = x + c;
         let t2 = t * t;
         let t4 = t2 * t2;
@@ -210,8 +821,22 @@ pub fn mimc_hash_native(value: u64) -> Fr {
     x
 }
 
-/// Serialize an Fr element to 32 bytes (canonical little-endian).
-pub fn fr_to_commitment(f: Fr) -> [u8; 32] {
+/// Compute the BN254 MiMC-5 hash of a u64 value natively.
+pub fn mimc_hash_native(value: u64) -> Fr {
+    mimc_hash(value)
+}
+
+/// 32-byte MiMC commitment to `value` on the active curve, as used by equality, membership
+/// and polynomial proofs.
+pub fn mimc_commitment(value: u64) -> [u8; 32] {
+    fn commit<E: SnarkEngine>(value: u64) -> [u8; 32] {
+        fr_to_commitment(mimc_hash::<E::ScalarField>(value))
+    }
+    on_active_curve!(commit(value))
+}
+
+/// Serialize a scalar field element to 32 bytes (canonical little-endian).
+pub fn fr_to_commitment<F: PrimeField>(f: F) -> [u8; 32] {
     let mut bytes = Vec::with_capacity(32);
     f.serialize_uncompressed(&mut bytes)
         .expect("Fr serialization is infallible");
@@ -220,26 +845,25 @@ pub fn fr_to_commitment(f: Fr) -> [u8; 32] {
     arr
 }
 
-/// Deserialize 32 bytes to an Fr element.
-fn fr_from_commitment(bytes: &[u8]) -> Option<Fr> {
+/// Deserialize 32 bytes to a scalar field element.
+fn fr_from_commitment<F: PrimeField>(bytes: &[u8]) -> Option<F> {
     if bytes.len() != 32 {
         return None;
     }
-    Fr::deserialize_uncompressed(bytes).ok()
+    F::deserialize_uncompressed(bytes).ok()
 }
 
 /// Compute MiMC-5 in-circuit using FpVar arithmetic (3 constraints per round).
-fn mimc_hash_circuit(x_init: FpVar<Fr>) -> Result<FpVar<Fr>, SynthesisError> {
-    let constants = get_mimc_constants();
+fn mimc_hash_circuit<F: MimcField>(x_init: FpVar<F>) -> Result<FpVar<F>, SynthesisError> {
     let mut x = x_init;
-    for &c in constants.iter() {
+    for &c in F::mimc_constants().iter() {
         let c_var = FpVar::constant(c);
         // t = x + c (linear combination, zero constraints)
-        let t: FpVar<Fr> = x + c_var;
+        let t: FpVar<F> = x + c_var;
         // t^2 (1 constraint)
-        let t2: FpVar<Fr> = t.clone() * &t;
+        let t2: FpVar<F> = t.clone() * &t;
         // t^4 (1 constraint)
-        let t4: FpVar<Fr> = t2.clone() * &t2;
+        let t4: FpVar<F> = t2.clone() * &t2;
         // t^5 = t^4 * t (1 constraint)
         x = t4 * t;
     }
@@ -251,35 +875,65 @@ fn mimc_hash_circuit(x_init: FpVar<Fr>) -> Result<FpVar<Fr>, SynthesisError> {
 // Witness: a, b
 // Public: commitment (Fr)
 // Constraints: ~332 (1 equality + 330 MiMC + 1 commit check)
+//
+// Width-bounded variant (`value_bits = Some(w)`): additionally recomposes `a` from `w`
+// boolean witnesses, so every bit at position >= w must be zero (+w+1 constraints).
+// The circuit shape depends on `w`, so each width needs its own Groth16 setup
+// (keys prefixed `equality_mimc_w{w}`); pick one or two widths and reuse them rather
+// than deriving a width per value, since every new width pays a full setup.
+
+/// Largest value width accepted by the width-bounded equality circuit.
+pub const MAX_EQUALITY_BITS: usize = 64;
 
 #[derive(Clone)]
-struct EqualityCircuit {
-    a: Option<u64>,
-    b: Option<u64>,
-    pub commitment: Option<Fr>,
+struct EqualityCircuit<F: PrimeField> {
+    // Secret witnesses are wiped when the circuit is dropped after proving.
+    a: Zeroizing<Option<u64>>,
+    b: Zeroizing<Option<u64>>,
+    pub commitment: Option<F>,
+    value_bits: Option<usize>,
 }
 
-impl ConstraintSynthesizer<Fr> for EqualityCircuit {
-    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
-        let a_var = FpVar::<Fr>::new_witness(cs.clone(), || {
-            self.a
-                .map(Fr::from)
+/// Enforce `value < 2^bits` by recomposing `var` from `bits` boolean witnesses.
+pub(super) fn enforce_bit_width<F: PrimeField>(
+    cs: ConstraintSystemRef<F>,
+    var: &FpVar<F>,
+    value: Option<u64>,
+    bits: usize,
+) -> Result<(), SynthesisError> {
+    let mut acc = FpVar::<F>::zero();
+    for i in 0..bits {
+        let bit = Boolean::new_witness(cs.clone(), || {
+            value
+                .map(|v| (v >> i) & 1 == 1)
                 .ok_or(SynthesisError::AssignmentMissing)
         })?;
-        let b_var = FpVar::<Fr>::new_witness(cs.clone(), || {
-            self.b
-                .map(Fr::from)
-                .ok_or(SynthesisError::AssignmentMissing)
+        acc += FpVar::from(bit) * F::from(1u64 << i);
+    }
+    acc.enforce_equal(var)
+}
+
+impl<F: MimcField> ConstraintSynthesizer<F> for EqualityCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+        let a_var = FpVar::<F>::new_witness(cs.clone(), || {
+            self.a.map(F::from).ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let b_var = FpVar::<F>::new_witness(cs.clone(), || {
+            self.b.map(F::from).ok_or(SynthesisError::AssignmentMissing)
         })?;
 
         // Enforce a == b
         a_var.enforce_equal(&b_var)?;
 
+        if let Some(bits) = self.value_bits {
+            enforce_bit_width(cs.clone(), &a_var, *self.a, bits)?;
+        }
+
         // Compute MiMC5(a) in-circuit
         let hash_var = mimc_hash_circuit(a_var)?;
 
         // Public input: expected commitment (single Fr element)
-        let commitment_var = FpVar::<Fr>::new_input(cs.clone(), || {
+        let commitment_var = FpVar::<F>::new_input(cs.clone(), || {
             self.commitment.ok_or(SynthesisError::AssignmentMissing)
         })?;
 
@@ -292,207 +946,562 @@ impl ConstraintSynthesizer<Fr> for EqualityCircuit {
 
 pub struct SnarkBackend;
 
-static UNIVERSAL_SETUP: OnceLock<Result<SnarkKeyPair, String>> = OnceLock::new();
+fn any_setup_initialized() -> bool {
+    Bn254::setups().any_initialized() || Bls12_381::setups().any_initialized()
+}
 
-static MEMBERSHIP_SETUP: OnceLock<Result<SnarkKeyPair, String>> = OnceLock::new();
+fn circuit_setup<E: SnarkEngine>(
+    circuit: impl ConstraintSynthesizer<E::ScalarField>,
+) -> Result<SnarkKeyPair<E>, String> {
+    Groth16::<E>::circuit_specific_setup(circuit, &mut OsRng)
+        .map_err(|e| format!("setup failed: {:?}", e))
+}
 
-fn get_membership_setup() -> &'static Result<SnarkKeyPair, String> {
-    MEMBERSHIP_SETUP.get_or_init(SnarkBackend::load_or_generate_membership_setup)
+/// Keys for `circuit`, loaded or generated on first use.
+fn setup<E: SnarkEngine>(circuit: SnarkCircuit) -> Option<&'static Result<KeySet<E>, String>> {
+    circuit.validate().ok()?;
+    Some(
+        circuit
+            .setup_slot::<E>()
+            .get_or_init(|| load_or_generate_setup(circuit)),
+    )
 }
 
-impl SnarkBackend {
-    fn load_or_generate_membership_setup() -> Result<SnarkKeyPair, String> {
-        // Use "_mimc" suffix to avoid loading stale SHA-256 based keys
-        load_or_generate_setup("membership_mimc", || Self::generate_membership_setup())
+/// Equality setup for `value_bits` (`None`: unbounded); `None` for an unsupported width.
+fn equality_setup<E: SnarkEngine>(
+    value_bits: Option<usize>,
+) -> Option<&'static Result<KeySet<E>, String>> {
+    setup(value_bits.map_or(SnarkCircuit::Equality, SnarkCircuit::EqualityBits))
+}
+
+/// Prove `circuit` with the newest keys of a setup from [`setup`]; empty if unavailable.
+fn prove_with_setup<E: SnarkEngine>(
+    setup: Option<&Result<KeySet<E>, String>>,
+    circuit: impl ConstraintSynthesizer<E::ScalarField>,
+) -> Vec<u8> {
+    let Some(Ok(keys)) = setup else {
+        return vec![];
+    };
+    match Groth16::<E>::prove(&keys.pk, circuit, &mut OsRng) {
+        Ok(proof) => serialize_proof(&proof),
+        Err(_) => vec![],
+    }
+}
+
+fn verify_with_setup<E: SnarkEngine>(
+    setup: Option<&Result<KeySet<E>, String>>,
+    proof_data: &[u8],
+    public_inputs: &[E::ScalarField],
+) -> bool {
+    let Some(Ok(keys)) = setup else {
+        return false;
+    };
+    let Some(proof) = deserialize_proof::<E>(proof_data) else {
+        return false;
+    };
+    // The proof may come from any trusted key version.
+    keys.vks.iter().any(|vk| {
+        Groth16::<E>::process_vk(vk).is_ok_and(|pvk| {
+            Groth16::<E>::verify_with_processed_vk(&pvk, public_inputs, &proof).unwrap_or(false)
+        })
+    })
+}
+
+fn prove_equality<E: SnarkEngine>(
+    a: u64,
+    b: u64,
+    hash_input: [u8; 32],
+    value_bits: Option<usize>,
+) -> Vec<u8> {
+    if a != b {
+        return vec![];
     }
+    let commitment_fr = match fr_from_commitment(&hash_input) {
+        Some(f) => f,
+        None => return vec![],
+    };
+    let circuit = EqualityCircuit {
+        a: Zeroizing::new(Some(a)),
+        b: Zeroizing::new(Some(b)),
+        commitment: Some(commitment_fr),
+        value_bits,
+    };
+    prove_with_setup(equality_setup::<E>(value_bits), circuit)
+}
+
+fn verify_equality<E: SnarkEngine>(
+    proof_data: &[u8],
+    hash_input: &[u8],
+    value_bits: Option<usize>,
+) -> bool {
+    let commitment_fr = match fr_from_commitment(hash_input) {
+        Some(f) => f,
+        None => return false,
+    };
+    // Public input ordering matches generate_constraints: [commitment]
+    verify_with_setup(
+        equality_setup::<E>(value_bits),
+        proof_data,
+        &[commitment_fr],
+    )
+}
+
+fn prove_membership_below<E: SnarkEngine>(
+    value: u64,
+    set: &[u64],
+    limit: usize,
+    commitment: [u8; 32],
+) -> Vec<u8> {
+    if set.is_empty() || set.len() > MAX_SET_SIZE || limit == 0 || limit > set.len() {
+        return vec![];
+    }
+
+    let commitment_fr = match fr_from_commitment(&commitment) {
+        Some(f) => f,
+        None => return vec![],
+    };
+
+    let pos = match set[..limit].iter().position(|&x| x == value) {
+        Some(i) => i,
+        None => return vec![],
+    };
+
+    let mut set_values = vec![0u64; MAX_SET_SIZE];
+    let mut is_real = vec![false; MAX_SET_SIZE];
+    for (i, &v) in set.iter().enumerate() {
+        set_values[i] = v;
+        is_real[i] = i < limit;
+    }
+    let mut sel = Zeroizing::new(vec![Some(false); MAX_SET_SIZE]);
+    sel[pos] = Some(true);
+
+    let circuit = MembershipCircuit {
+        value: Zeroizing::new(Some(value)),
+        sel,
+        set_values,
+        is_real,
+        commitment: Some(commitment_fr),
+    };
+    prove_with_setup(setup::<E>(SnarkCircuit::Membership), circuit)
+}
+
+fn verify_membership_below<E: SnarkEngine>(
+    proof_data: &[u8],
+    set: &[u64],
+    limit: usize,
+    commitment: &[u8],
+) -> bool {
+    if set.is_empty() || set.len() > MAX_SET_SIZE || limit == 0 || limit > set.len() {
+        return false;
+    }
+    let commitment_fr = match fr_from_commitment(commitment) {
+        Some(f) => f,
+        None => return false,
+    };
+
+    verify_with_setup(
+        setup::<E>(SnarkCircuit::Membership),
+        proof_data,
+        &set_public_inputs(commitment_fr, set, limit),
+    )
+}
+
+/// Public inputs of the membership and non-membership circuits, in `generate_constraints`
+/// order: `[commitment, set[0..MAX_SET_SIZE], is_real[0..MAX_SET_SIZE]]`, with the set padded
+/// with zeros and `is_real[i] = i < limit`.
+fn set_public_inputs<F: PrimeField>(commitment: F, set: &[u64], limit: usize) -> Vec<F> {
+    let mut public_inputs = vec![commitment];
+    for i in 0..MAX_SET_SIZE {
+        public_inputs.push(F::from(set.get(i).copied().unwrap_or(0)));
+    }
+    for i in 0..MAX_SET_SIZE {
+        public_inputs.push(F::from(i < limit));
+    }
+    public_inputs
+}
 
-    fn generate_membership_setup() -> Result<SnarkKeyPair, String> {
-        let rng = &mut OsRng;
-        let dummy = MembershipCircuit {
-            value: Some(0),
-            sel: vec![Some(false); MAX_SET_SIZE],
+fn prove_polynomial_nonneg<E: SnarkEngine>(
+    x: u64,
+    y: u64,
+    coefficients: [i64; 3],
+    commitment: [u8; 32],
+) -> Vec<u8> {
+    let commitment_fr = match fr_from_commitment(&commitment) {
+        Some(f) => f,
+        None => return vec![],
+    };
+    let circuit = PolynomialCircuit {
+        x: Zeroizing::new(Some(x)),
+        y: Zeroizing::new(Some(y)),
+        coefficients,
+        commitment: Some(commitment_fr),
+    };
+    prove_with_setup(setup::<E>(SnarkCircuit::PolynomialNonneg), circuit)
+}
+
+fn verify_polynomial_nonneg<E: SnarkEngine>(
+    proof_data: &[u8],
+    coefficients: [i64; 3],
+    commitment: &[u8],
+) -> bool {
+    let commitment_fr = match fr_from_commitment(commitment) {
+        Some(f) => f,
+        None => return false,
+    };
+
+    // Public input ordering matches generate_constraints: [commitment, a, b, c]
+    let mut public_inputs = vec![commitment_fr];
+    public_inputs.extend(coefficients.map(fr_from_i64::<E::ScalarField>));
+
+    verify_with_setup(
+        setup::<E>(SnarkCircuit::PolynomialNonneg),
+        proof_data,
+        &public_inputs,
+    )
+}
+
+impl SnarkBackend {
+    fn dummy_membership_circuit<F: PrimeField>() -> MembershipCircuit<F> {
+        MembershipCircuit {
+            value: Zeroizing::new(Some(0)),
+            sel: Zeroizing::new(vec![Some(false); MAX_SET_SIZE]),
             set_values: vec![0u64; MAX_SET_SIZE],
             is_real: vec![false; MAX_SET_SIZE],
-            commitment: Some(Fr::ZERO),
-        };
-        Groth16::<Bn254>::circuit_specific_setup(dummy, rng)
-            .map_err(|e| format!("setup failed: {:?}", e))
+            commitment: Some(F::ZERO),
+        }
     }
 
-    fn get_universal_setup() -> &'static Result<SnarkKeyPair, String> {
-        UNIVERSAL_SETUP.get_or_init(Self::load_or_generate_equality_setup)
+    fn dummy_equality_circuit<F: PrimeField>(value_bits: Option<usize>) -> EqualityCircuit<F> {
+        EqualityCircuit {
+            a: Zeroizing::new(Some(0)),
+            b: Zeroizing::new(Some(0)),
+            commitment: Some(F::ZERO),
+            value_bits,
+        }
     }
 
-    fn load_or_generate_equality_setup() -> Result<SnarkKeyPair, String> {
-        load_or_generate_setup("equality_mimc", || Self::generate_equality_setup())
+    fn dummy_polynomial_circuit<F: PrimeField>() -> PolynomialCircuit<F> {
+        PolynomialCircuit {
+            x: Zeroizing::new(Some(0)),
+            y: Zeroizing::new(Some(0)),
+            coefficients: [0; 3],
+            commitment: Some(F::ZERO),
+        }
     }
 
-    fn generate_equality_setup() -> Result<SnarkKeyPair, String> {
-        let rng = &mut OsRng;
-        let dummy_circuit = EqualityCircuit {
-            a: Some(0),
-            b: Some(0),
-            commitment: Some(Fr::ZERO),
-        };
-        Groth16::<Bn254>::circuit_specific_setup(dummy_circuit, rng)
-            .map_err(|e| format!("setup failed: {:?}", e))
+    /// Synthesize `circuit` into a fresh constraint system and count its R1CS constraints
+    /// (0 if synthesis fails). Needs no setup or keys. Counts are the same on both curves.
+    fn constraint_count(circuit: impl ConstraintSynthesizer<Fr>) -> usize {
+        let cs = ark_relations::r1cs::ConstraintSystem::<Fr>::new_ref();
+        match circuit.generate_constraints(cs.clone()) {
+            Ok(()) => cs.num_constraints(),
+            Err(_) => 0,
+        }
+    }
+
+    /// Number of R1CS constraints in the membership circuit (fixed `MAX_SET_SIZE` slots).
+    pub fn membership_constraint_count() -> usize {
+        Self::constraint_count(Self::dummy_membership_circuit())
+    }
+
+    /// Number of R1CS constraints in the unbounded equality circuit.
+    pub fn equality_constraint_count() -> usize {
+        Self::constraint_count(Self::dummy_equality_circuit(None))
     }
 
     /// Prove equality: MiMC5(a) == commitment AND a == b.
-    /// `hash_input` must be `fr_to_commitment(mimc_hash_native(a))`.
+    /// `hash_input` must be `mimc_commitment(a)`.
     pub fn prove_equality_zk(a: u64, b: u64, hash_input: [u8; 32]) -> Vec<u8> {
-        if a != b {
+        on_active_curve!(prove_equality(a, b, hash_input, None))
+    }
+
+    /// Like `prove_equality_zk`, but the circuit also enforces `a < 2^bits`.
+    /// `bits` must be in `1..=MAX_EQUALITY_BITS`; each width uses its own setup.
+    /// Returns an empty vector if `a` does not fit in `bits`.
+    pub fn prove_equality_zk_bits(a: u64, b: u64, hash_input: [u8; 32], bits: usize) -> Vec<u8> {
+        if bits < 64 && a >> bits != 0 {
             return vec![];
         }
+        on_active_curve!(prove_equality(a, b, hash_input, Some(bits)))
+    }
 
-        let commitment_fr = match fr_from_commitment(&hash_input) {
-            Some(f) => f,
-            None => return vec![],
-        };
+    /// Verify a proof from `prove_equality_zk_bits` for the same `bits`.
+    pub fn verify_equality_zk_bits(proof_data: &[u8], hash_input: &[u8], bits: usize) -> bool {
+        on_active_curve!(verify_equality(proof_data, hash_input, Some(bits)))
+    }
 
-        let circuit = EqualityCircuit {
-            a: Some(a),
-            b: Some(b),
-            commitment: Some(commitment_fr),
-        };
+    /// Verify an equality proof. `hash_input` must be the 32-byte MiMC commitment.
+    pub fn verify_equality_zk(proof_data: &[u8], hash_input: &[u8]) -> bool {
+        on_active_curve!(verify_equality(proof_data, hash_input, None))
+    }
 
-        let setup = match Self::get_universal_setup() {
-            Ok(pair) => pair,
-            Err(_) => return vec![],
-        };
-        let rng = &mut OsRng;
-        let proof = match Groth16::<Bn254>::prove(&setup.0, circuit, rng) {
-            Ok(p) => p,
+    /// Prove set membership: MiMC5(value) == commitment AND value ∈ set.
+    /// `commitment` must be `mimc_commitment(value)`.
+    /// The set is normalized with [`Self::normalize_set`] first, as it is on verification.
+    pub fn prove_membership_zk(value: u64, set: Vec<u64>, commitment: [u8; 32]) -> Vec<u8> {
+        let set = match Self::normalize_set(set) {
+            Ok(set) => set,
             Err(_) => return vec![],
         };
+        let limit = set.len();
+        Self::prove_membership_zk_below(value, set, limit, commitment)
+    }
 
-        let mut bytes = Vec::new();
-        if proof.serialize_uncompressed(&mut bytes).is_err() {
-            return vec![];
-        }
-        bytes
+    /// Root of the gap tree over `set` (normalized with
+    /// [`merkle::normalize_non_membership_set`]) on the active curve, against which
+    /// non-membership proofs verify.
+    pub fn non_membership_set_root(set: Vec<u64>) -> Result<[u8; 32], ZkpError> {
+        let set = normalize_non_membership_set(set)?;
+        Ok(on_active_curve!(non_membership_root(&set)))
     }
 
-    /// Verify an equality proof. `hash_input` must be the 32-byte MiMC commitment.
-    pub fn verify_equality_zk(proof_data: &[u8], hash_input: &[u8]) -> bool {
-        let proof = match ark_groth16::Proof::<Bn254>::deserialize_uncompressed(proof_data) {
-            Ok(p) => p,
-            Err(_) => return false,
-        };
+    /// Prove `value` is not in `set` (up to [`merkle::MAX_NON_MEMBERSHIP_SET_SIZE`] elements)
+    /// by a path to the gap that holds it; `commitment` must be `mimc_commitment(value)`.
+    /// Returns the proof and the root, or `None` if the value is in the set or proving fails.
+    pub fn prove_non_membership_zk(
+        value: u64,
+        set: Vec<u64>,
+        commitment: [u8; 32],
+    ) -> Option<(Vec<u8>, [u8; 32])> {
+        let set = normalize_non_membership_set(set).ok()?;
+        on_active_curve!(prove_non_membership(value, &set, commitment))
+    }
 
-        let setup = match Self::get_universal_setup() {
-            Ok(pair) => pair,
-            Err(_) => return false,
-        };
-        let pvk = match Groth16::<Bn254>::process_vk(&setup.1) {
-            Ok(pvk) => pvk,
-            Err(_) => return false,
-        };
+    /// Verify a proof from [`Self::prove_non_membership_zk`] against the set's gap-tree `root`.
+    pub fn verify_non_membership_zk(proof_data: &[u8], root: &[u8], commitment: &[u8]) -> bool {
+        on_active_curve!(verify_non_membership(proof_data, root, commitment))
+    }
 
-        let commitment_fr = match fr_from_commitment(hash_input) {
-            Some(f) => f,
-            None => return false,
-        };
+    /// Root of the Merkle tree over `set` (normalized with [`merkle::normalize_merkle_set`]) on
+    /// the active curve; a verifier can compute it once and reuse it for every proof.
+    pub fn merkle_set_root(set: Vec<u64>) -> Result<[u8; 32], ZkpError> {
+        let set = normalize_merkle_set(set)?;
+        Ok(on_active_curve!(merkle_root(&set)))
+    }
 
-        // Public input ordering matches generate_constraints: [commitment]
-        let public_inputs = vec![commitment_fr];
+    /// Prove `value` is in `set` (up to [`merkle::MAX_MERKLE_SET_SIZE`] elements) by a Merkle
+    /// path to the set's root; `commitment` must be `mimc_commitment(value)`. Returns the proof
+    /// and the root, or `None` if the value is not in the set or proving fails.
+    pub fn prove_merkle_membership_zk(
+        value: u64,
+        set: Vec<u64>,
+        commitment: [u8; 32],
+    ) -> Option<(Vec<u8>, [u8; 32])> {
+        let set = normalize_merkle_set(set).ok()?;
+        on_active_curve!(prove_merkle_membership(value, &set, commitment))
+    }
 
-        Groth16::<Bn254>::verify_with_processed_vk(&pvk, &public_inputs, &proof).unwrap_or(false)
+    /// Verify a proof from [`Self::prove_merkle_membership_zk`] against the set's `root`.
+    pub fn verify_merkle_membership_zk(proof_data: &[u8], root: &[u8], commitment: &[u8]) -> bool {
+        on_active_curve!(verify_merkle_membership(proof_data, root, commitment))
     }
 
-    /// Prove set membership: MiMC5(value) == commitment AND value ∈ set.
-    /// `commitment` must be `fr_to_commitment(mimc_hash_native(value))`.
-    pub fn prove_membership_zk(value: u64, set: Vec<u64>, commitment: [u8; 32]) -> Vec<u8> {
-        if set.is_empty() || set.len() > MAX_SET_SIZE {
-            return vec![];
-        }
+    /// Voter key for a 32-byte secret on the active curve; it is published in the
+    /// eligibility list, the secret is kept by the voter.
+    pub fn voter_key(secret: &[u8; 32]) -> [u8; 32] {
+        on_active_curve!(voter_key(secret))
+    }
 
-        let commitment_fr = match fr_from_commitment(&commitment) {
-            Some(f) => f,
-            None => return vec![],
-        };
+    /// Root of the eligibility tree over `keys` (normalized with
+    /// [`voting::normalize_voter_keys`]).
+    pub fn eligibility_root(keys: Vec<[u8; 32]>) -> Result<[u8; 32], ZkpError> {
+        let keys = normalize_voter_keys(keys)?;
+        on_active_curve!(eligibility_root(&keys))
+            .ok_or_else(|| ZkpError::InvalidInput("invalid voter key".to_string()))
+    }
 
-        let pos = match set.iter().position(|&x| x == value) {
-            Some(i) => i,
-            None => return vec![],
-        };
+    /// Vote for option `choice` of `num_options` (at most [`voting::MAX_VOTE_OPTIONS`]) in
+    /// `election_id` with the voter key of `secret`, which must be in `keys`. `None` if it is
+    /// not, the choice is out of range or proving fails.
+    pub fn prove_vote_zk(
+        secret: &[u8; 32],
+        keys: Vec<[u8; 32]>,
+        election_id: u64,
+        num_options: usize,
+        choice: usize,
+    ) -> Option<SnarkVote> {
+        let keys = normalize_voter_keys(keys).ok()?;
+        on_active_curve!(prove_vote(secret, &keys, election_id, num_options, choice))
+    }
 
-        let mut set_values = vec![0u64; MAX_SET_SIZE];
-        let mut is_real = vec![false; MAX_SET_SIZE];
-        for (i, &v) in set.iter().enumerate() {
-            set_values[i] = v;
-            is_real[i] = true;
-        }
-        let mut sel = vec![Some(false); MAX_SET_SIZE];
-        sel[pos] = Some(true);
-
-        let circuit = MembershipCircuit {
-            value: Some(value),
-            sel,
-            set_values,
-            is_real,
-            commitment: Some(commitment_fr),
-        };
+    /// Verify a proof from [`Self::prove_vote_zk`] against its public values.
+    pub fn verify_vote_zk(
+        proof_data: &[u8],
+        root: &[u8],
+        election_id: u64,
+        nullifier: &[u8],
+        ballot: &[u8],
+        num_options: usize,
+    ) -> bool {
+        on_active_curve!(verify_vote(
+            proof_data,
+            root,
+            election_id,
+            nullifier,
+            ballot,
+            num_options
+        ))
+    }
 
-        let setup = match get_membership_setup() {
-            Ok(pair) => pair,
-            Err(_) => return vec![],
-        };
-        let rng = &mut OsRng;
-        let proof = match Groth16::<Bn254>::prove(&setup.0, circuit, rng) {
-            Ok(p) => p,
-            Err(_) => return vec![],
-        };
+    /// Whether `ballot` from [`Self::prove_vote_zk`] seals option `choice` under `blinding`.
+    pub fn ballot_opens(ballot: &[u8], choice: usize, blinding: &[u8]) -> bool {
+        on_active_curve!(ballot_opens(ballot, choice, blinding))
+    }
 
-        let mut bytes = Vec::new();
-        if proof.serialize_uncompressed(&mut bytes).is_err() {
-            return vec![];
+    /// Prove the population variance of `values` (1 to [`variance::MAX_VARIANCE_VALUES`]) is
+    /// below `bound`. Returns the proof and a blinded commitment to the values, or `None` if
+    /// the bound does not hold or proving fails.
+    pub fn prove_variance_below_zk(values: &[u64], bound: u64) -> Option<(Vec<u8>, [u8; 32])> {
+        on_active_curve!(prove_variance_below(values, bound))
+    }
+
+    /// Verify a proof from [`Self::prove_variance_below_zk`] over `count` committed values.
+    pub fn verify_variance_below_zk(
+        proof_data: &[u8],
+        commitment: &[u8],
+        bound: u64,
+        count: usize,
+    ) -> bool {
+        on_active_curve!(verify_variance_below(proof_data, commitment, bound, count))
+    }
+
+    /// Canonical form of a membership set: sorted ascending with duplicates removed. Errors if
+    /// the set is empty or has more than `MAX_SET_SIZE` distinct elements. The membership
+    /// circuit binds the set slot by slot, so prover and verifier must normalize identically.
+    pub fn normalize_set(mut set: Vec<u64>) -> Result<Vec<u64>, ZkpError> {
+        set.sort_unstable();
+        set.dedup();
+        if set.is_empty() {
+            return Err(ZkpError::InvalidInput("set cannot be empty".to_string()));
         }
-        bytes
+        if set.len() > MAX_SET_SIZE {
+            return Err(ZkpError::InvalidInput(format!(
+                "set size {} exceeds maximum allowed size {}",
+                set.len(),
+                MAX_SET_SIZE
+            )));
+        }
+        Ok(set)
+    }
+
+    /// Like [`Self::prove_membership_zk`], but only the first `limit` slots are selectable
+    /// (`is_real[i] = i < limit`), so the proof also shows the value's index is below `limit`.
+    /// The whole set is still bound as public input.
+    pub fn prove_membership_zk_below(
+        value: u64,
+        set: Vec<u64>,
+        limit: usize,
+        commitment: [u8; 32],
+    ) -> Vec<u8> {
+        on_active_curve!(prove_membership_below(value, &set, limit, commitment))
     }
 
     /// Verify a membership proof. `commitment` must be the 32-byte MiMC commitment.
+    /// The set is normalized with [`Self::normalize_set`] first, as it is when proving.
     pub fn verify_membership_zk(proof_data: &[u8], set: &[u64], commitment: &[u8]) -> bool {
-        if set.is_empty() || set.len() > MAX_SET_SIZE {
-            return false;
-        }
-        if commitment.len() != 32 {
-            return false;
+        match Self::normalize_set(set.to_vec()) {
+            Ok(normalized) => {
+                Self::verify_membership_zk_either(proof_data, set, &normalized, commitment)
+            }
+            Err(_) => false,
         }
+    }
 
-        let proof = match ark_groth16::Proof::<Bn254>::deserialize_uncompressed(proof_data) {
-            Ok(p) => p,
-            Err(_) => return false,
-        };
+    /// Proofs made before sets were normalized bound `set` in the order (and with the
+    /// duplicates) it was given, so fall back to that order when it is not normalized.
+    fn verify_membership_zk_either(
+        proof_data: &[u8],
+        set: &[u64],
+        normalized: &[u64],
+        commitment: &[u8],
+    ) -> bool {
+        Self::verify_membership_zk_below(proof_data, normalized, normalized.len(), commitment)
+            || (set != normalized
+                && Self::verify_membership_zk_below(proof_data, set, set.len(), commitment))
+    }
 
-        let setup = match get_membership_setup() {
-            Ok(pair) => pair,
-            Err(_) => return false,
-        };
-        let pvk = match Groth16::<Bn254>::process_vk(&setup.1) {
-            Ok(pvk) => pvk,
-            Err(_) => return false,
-        };
+    /// Like [`Self::verify_membership_zk`], but first compares the length of `set` (the set the
+    /// prover embedded with the proof) with `expected_len`, the length of the verifier's set,
+    /// both after [`Self::normalize_set`]. The `is_real` public inputs are derived from the set
+    /// length, so a mismatch would otherwise only surface as a failed pairing check.
+    pub fn verify_membership_zk_with_len(
+        proof_data: &[u8],
+        set: &[u64],
+        expected_len: usize,
+        commitment: &[u8],
+    ) -> Result<bool, ZkpError> {
+        let normalized = Self::normalize_set(set.to_vec())?;
+        if normalized.len() != expected_len {
+            return Err(ZkpError::InvalidInput(
+                "set length mismatch between prove and verify".to_string(),
+            ));
+        }
+        Ok(Self::verify_membership_zk_either(
+            proof_data,
+            set,
+            &normalized,
+            commitment,
+        ))
+    }
 
-        let commitment_fr = match fr_from_commitment(commitment) {
-            Some(f) => f,
-            None => return false,
+    /// Prove `a*x^2 + b*x + c >= 0` for the hidden `x` behind `commitment`
+    /// (`mimc_commitment(x)`), with `coefficients = [a, b, c]`.
+    /// Returns an empty vector if the result is negative or does not fit in a `u64`.
+    pub fn prove_polynomial_nonneg_zk(
+        x: u64,
+        coefficients: [i64; 3],
+        commitment: [u8; 32],
+    ) -> Vec<u8> {
+        let y = evaluate_quadratic(x, coefficients).and_then(|v| u64::try_from(v).ok());
+        let Some(y) = y else {
+            return vec![];
         };
+        on_active_curve!(prove_polynomial_nonneg(x, y, coefficients, commitment))
+    }
 
-        // Public input ordering matches generate_constraints:
-        // [commitment, set[0], ..., set[MAX_SET_SIZE-1], is_real[0], ..., is_real[MAX_SET_SIZE-1]]
-        let mut public_inputs: Vec<Fr> = vec![commitment_fr];
-        for i in 0..MAX_SET_SIZE {
-            let v = if i < set.len() { set[i] } else { 0u64 };
-            public_inputs.push(Fr::from(v));
-        }
-        for i in 0..MAX_SET_SIZE {
-            let flag = if i < set.len() { 1u64 } else { 0u64 };
-            public_inputs.push(Fr::from(flag));
-        }
+    /// Verify a proof from [`Self::prove_polynomial_nonneg_zk`] for the same coefficients.
+    pub fn verify_polynomial_nonneg_zk(
+        proof_data: &[u8],
+        coefficients: [i64; 3],
+        commitment: &[u8],
+    ) -> bool {
+        on_active_curve!(verify_polynomial_nonneg(
+            proof_data,
+            coefficients,
+            commitment
+        ))
+    }
+
+    /// Verify a proof from [`Self::prove_membership_zk_below`] with the same `limit`.
+    pub fn verify_membership_zk_below(
+        proof_data: &[u8],
+        set: &[u64],
+        limit: usize,
+        commitment: &[u8],
+    ) -> bool {
+        on_active_curve!(verify_membership_below(proof_data, set, limit, commitment))
+    }
+}
 
-        Groth16::<Bn254>::verify_with_processed_vk(&pvk, &public_inputs, &proof).unwrap_or(false)
+/// Enforce that exactly one slot is selected and that it is a real one: `sel[i] <= is_real[i]`
+/// and `sum(sel) == 1`. Shared by membership and voting.
+fn enforce_one_hot<F: PrimeField>(
+    sel: &[Boolean<F>],
+    is_real: &[Boolean<F>],
+) -> Result<(), SynthesisError> {
+    if sel.len() != is_real.len() {
+        return Err(SynthesisError::Unsatisfiable);
     }
+    let mut sum_sel = FpVar::<F>::zero();
+    for (sel_i, is_real_i) in sel.iter().zip(is_real) {
+        let sel_fp: FpVar<F> = sel_i.clone().into();
+        sum_sel += sel_fp.clone();
+
+        let is_real_fp: FpVar<F> = is_real_i.clone().into();
+        let one_minus_is_real = FpVar::<F>::one() - is_real_fp;
+        (sel_fp * one_minus_is_real).enforce_equal(&FpVar::<F>::zero())?;
+    }
+    sum_sel.enforce_equal(&FpVar::<F>::one())
 }
 
 // ===== ZK Set Membership circuit =====
@@ -503,20 +1512,21 @@ impl SnarkBackend {
 pub const MAX_SET_SIZE: usize = 64;
 
 #[derive(Clone)]
-struct MembershipCircuit {
-    value: Option<u64>,
-    sel: Vec<Option<bool>>,
+struct MembershipCircuit<F: PrimeField> {
+    // Secret witnesses, wiped on drop like `EqualityCircuit`'s.
+    value: Zeroizing<Option<u64>>,
+    sel: Zeroizing<Vec<Option<bool>>>,
     set_values: Vec<u64>,
     is_real: Vec<bool>,
-    commitment: Option<Fr>,
+    commitment: Option<F>,
 }
 
-impl ConstraintSynthesizer<Fr> for MembershipCircuit {
-    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> Result<(), SynthesisError> {
+impl<F: MimcField> ConstraintSynthesizer<F> for MembershipCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
         // Witness: value as field element
-        let value_var = FpVar::<Fr>::new_witness(cs.clone(), || {
+        let value_var = FpVar::<F>::new_witness(cs.clone(), || {
             self.value
-                .map(Fr::from)
+                .map(F::from)
                 .ok_or(SynthesisError::AssignmentMissing)
         })?;
 
@@ -524,7 +1534,7 @@ impl ConstraintSynthesizer<Fr> for MembershipCircuit {
         let hash_var = mimc_hash_circuit(value_var.clone())?;
 
         // Public input: commitment (single Fr)
-        let commitment_var = FpVar::<Fr>::new_input(cs.clone(), || {
+        let commitment_var = FpVar::<F>::new_input(cs.clone(), || {
             self.commitment.ok_or(SynthesisError::AssignmentMissing)
         })?;
 
@@ -536,13 +1546,13 @@ impl ConstraintSynthesizer<Fr> for MembershipCircuit {
         }
 
         // Public inputs: set values
-        let mut set_vars: Vec<FpVar<Fr>> = Vec::with_capacity(MAX_SET_SIZE);
+        let mut set_vars: Vec<FpVar<F>> = Vec::with_capacity(MAX_SET_SIZE);
         for v in self.set_values.into_iter() {
-            set_vars.push(FpVar::<Fr>::new_input(cs.clone(), || Ok(Fr::from(v)))?);
+            set_vars.push(FpVar::<F>::new_input(cs.clone(), || Ok(F::from(v)))?);
         }
 
         // Public inputs: is_real flags
-        let mut is_real_bools: Vec<Boolean<Fr>> = Vec::with_capacity(MAX_SET_SIZE);
+        let mut is_real_bools: Vec<Boolean<F>> = Vec::with_capacity(MAX_SET_SIZE);
         for b in self.is_real.into_iter() {
             is_real_bools.push(Boolean::new_input(cs.clone(), || Ok(b))?);
         }
@@ -552,38 +1562,80 @@ impl ConstraintSynthesizer<Fr> for MembershipCircuit {
         }
 
         // Witness: selection bits (one-hot)
-        let mut sel_bools: Vec<Boolean<Fr>> = Vec::with_capacity(MAX_SET_SIZE);
-        for bit in self.sel.into_iter() {
+        let mut sel_bools: Vec<Boolean<F>> = Vec::with_capacity(MAX_SET_SIZE);
+        for &bit in self.sel.iter() {
             sel_bools.push(Boolean::new_witness(cs.clone(), || {
                 bit.ok_or(SynthesisError::AssignmentMissing)
             })?);
         }
 
-        // Enforce booleanity and one-hot (sum == 1)
-        let mut sum_sel = FpVar::<Fr>::zero();
-        for (i, sel_i) in sel_bools.iter().enumerate() {
-            let sel_fp: FpVar<Fr> = sel_i.clone().into();
-            sum_sel += sel_fp.clone();
-
-            // sel[i] <= is_real[i]
-            let is_real_fp: FpVar<Fr> = is_real_bools[i].clone().into();
-            let one_minus_is_real = FpVar::<Fr>::one() - is_real_fp;
-            (sel_fp * one_minus_is_real).enforce_equal(&FpVar::<Fr>::zero())?;
-        }
-        sum_sel.enforce_equal(&FpVar::<Fr>::one())?;
+        enforce_one_hot(&sel_bools, &is_real_bools)?;
 
         // Enforce value ∈ set: sum_i sel[i] * (value - set[i]) == 0
-        let mut acc = FpVar::<Fr>::zero();
+        let mut acc = FpVar::<F>::zero();
         for i in 0..MAX_SET_SIZE {
-            let sel_fp: FpVar<Fr> = sel_bools[i].clone().into();
+            let sel_fp: FpVar<F> = sel_bools[i].clone().into();
             acc += sel_fp * (value_var.clone() - set_vars[i].clone());
         }
-        acc.enforce_equal(&FpVar::<Fr>::zero())?;
+        acc.enforce_equal(&FpVar::<F>::zero())?;
 
         Ok(())
     }
 }
 
+// ===== Quadratic non-negativity circuit =====
+// Proves: MiMC5(x) == commitment AND a*x^2 + b*x + c == y with x, y < 2^64
+// Public inputs: commitment (Fr), a, b, c (signed, negatives as p - |v|)
+// Witness: x, bits of x, bits of y
+// With x < 2^64 and |a|, |b|, |c| < 2^63 the integer value of a*x^2 + b*x + c is below
+// 2^192 in magnitude, far from p, so the in-field evaluation never wraps and the 64-bit
+// decomposition of y shows the integer result is non-negative.
+
+#[derive(Clone)]
+struct PolynomialCircuit<F: PrimeField> {
+    // Secret witnesses, wiped on drop like `EqualityCircuit`'s.
+    x: Zeroizing<Option<u64>>,
+    y: Zeroizing<Option<u64>>,
+    coefficients: [i64; 3],
+    commitment: Option<F>,
+}
+
+fn fr_from_i64<F: PrimeField>(v: i64) -> F {
+    let magnitude = F::from(v.unsigned_abs());
+    if v < 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+impl<F: MimcField> ConstraintSynthesizer<F> for PolynomialCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+        let x_var = FpVar::<F>::new_witness(cs.clone(), || {
+            self.x.map(F::from).ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        enforce_bit_width(cs.clone(), &x_var, *self.x, 64)?;
+
+        let hash_var = mimc_hash_circuit(x_var.clone())?;
+        let commitment_var = FpVar::<F>::new_input(cs.clone(), || {
+            self.commitment.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        hash_var.enforce_equal(&commitment_var)?;
+
+        let mut coefficient_vars = Vec::with_capacity(3);
+        for c in self.coefficients {
+            coefficient_vars.push(FpVar::<F>::new_input(cs.clone(), || {
+                Ok(fr_from_i64::<F>(c))
+            })?);
+        }
+
+        // y = a*x^2 + b*x + c, then y < 2^64 (range gadget)
+        let y_var = &coefficient_vars[0] * x_var.square()?
+            + &coefficient_vars[1] * &x_var
+            + &coefficient_vars[2];
+        enforce_bit_width(cs, &y_var, *self.y, 64)
+    }
+}
 impl ZkpBackend for SnarkBackend {
     fn prove(data: &[u8]) -> Vec<u8> {
         if data.len() != 48 {
@@ -626,7 +1678,7 @@ mod tests {
     fn fr_commitment_roundtrip() {
         let f = mimc_hash_native(123);
         let bytes = fr_to_commitment(f);
-        let f2 = fr_from_commitment(&bytes).unwrap();
+        let f2 = fr_from_commitment::<Fr>(&bytes).unwrap();
         assert_eq!(f, f2);
     }
 
@@ -639,4 +1691,86 @@ mod tests {
         let wrong = fr_to_commitment(mimc_hash_native(99));
         assert!(!SnarkBackend::verify_equality_zk(&proof, &wrong));
     }
+
+    #[test]
+    fn compressed_proofs_verify_alongside_uncompressed() {
+        let commitment = fr_to_commitment(mimc_hash_native(7));
+        set_proof_compression(true);
+        let compressed = SnarkBackend::prove_equality_zk(7, 7, commitment);
+        set_proof_compression(false);
+        let uncompressed = SnarkBackend::prove_equality_zk(7, 7, commitment);
+        assert_eq!(compressed.len(), 128);
+        assert_eq!(uncompressed.len(), 256);
+        assert!(SnarkBackend::verify_equality_zk(&compressed, &commitment));
+        assert!(SnarkBackend::verify_equality_zk(&uncompressed, &commitment));
+
+        let mut recompressed = Vec::new();
+        deserialize_proof::<Bn254>(&uncompressed)
+            .unwrap()
+            .serialize_compressed(&mut recompressed)
+            .unwrap();
+        assert!(SnarkBackend::verify_equality_zk(&recompressed, &commitment));
+        let mut padded = uncompressed;
+        padded.push(0);
+        assert!(!SnarkBackend::verify_equality_zk(&padded, &commitment));
+    }
+
+    #[test]
+    fn groth16_equality_at_32_bit_width() {
+        let commitment = fr_to_commitment(mimc_hash_native(4_000_000_000));
+        let proof =
+            SnarkBackend::prove_equality_zk_bits(4_000_000_000, 4_000_000_000, commitment, 32);
+        assert!(!proof.is_empty(), "proof generation failed");
+        assert!(SnarkBackend::verify_equality_zk_bits(
+            &proof,
+            &commitment,
+            32
+        ));
+        assert!(!SnarkBackend::verify_equality_zk_bits(
+            &proof,
+            &commitment,
+            16
+        ));
+        assert!(!SnarkBackend::verify_equality_zk(&proof, &commitment));
+    }
+
+    #[test]
+    fn equality_width_rejects_wide_value() {
+        use ark_relations::r1cs::ConstraintSystem;
+
+        let wide = 1u64 << 32;
+        let commitment = fr_to_commitment(mimc_hash_native(wide));
+        assert!(SnarkBackend::prove_equality_zk_bits(wide, wide, commitment, 32).is_empty());
+
+        // Bypass the prover's pre-check: the recomposition from 32 bits cannot match.
+        let circuit = EqualityCircuit {
+            a: Zeroizing::new(Some(wide)),
+            b: Zeroizing::new(Some(wide)),
+            commitment: Some(mimc_hash_native(wide)),
+            value_bits: Some(32),
+        };
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(!cs.is_satisfied().unwrap());
+
+        let circuit = EqualityCircuit {
+            a: Zeroizing::new(Some(wide - 1)),
+            b: Zeroizing::new(Some(wide - 1)),
+            commitment: Some(mimc_hash_native(wide - 1)),
+            value_bits: Some(32),
+        };
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        circuit.generate_constraints(cs.clone()).unwrap();
+        assert!(cs.is_satisfied().unwrap());
+    }
+
+    #[test]
+    fn constraint_counts_are_stable() {
+        let membership = SnarkBackend::membership_constraint_count();
+        let equality = SnarkBackend::equality_constraint_count();
+        assert!(equality > 0);
+        assert!(membership > equality);
+        assert_eq!(SnarkBackend::membership_constraint_count(), membership);
+        assert_eq!(SnarkBackend::equality_constraint_count(), equality);
+    }
 }
@@ -0,0 +1,514 @@
+//! Multi-party Groth16 setup ("phase 2"). [`initialize`] derives the initial keys for one
+//! circuit from a verified [`super::powers_of_tau`] accumulator, each participant runs
+//! [`contribute`] on the latest file in turn, anyone can check the whole chain with [`verify`],
+//! and [`finalize`] installs the keys in the key directory as the circuit's next key version,
+//! which the provers then use.
+//!
+//! The initial keys contain no secret of their own: they are the accumulator's powers moved to
+//! the circuit's Lagrange basis, with `gamma = delta = 1`, so anyone can recompute them from
+//! the phase-1 file. A contribution multiplies `delta` by a fresh secret `d` (and divides the
+//! `h`/`l` queries by it), then proves knowledge of `d` with a Schnorr proof bound to the
+//! transcript so far. `tau`, `alpha`, `beta` and `delta` are unknown unless every contributor
+//! to a phase colludes.
+//!
+//! File layout: `[magic 8][curve 1][circuit 1][bits 4][phase 1 hash 32][initial key]
+//! [current key][u32 n]` then `n` contributions `[delta_after G1][schnorr commitment G1]
+//! [response Fr]`, group elements in arkworks uncompressed form. The phase 1 hash is the
+//! accumulator's final transcript hash.
+
+use super::powers_of_tau::{self, Accumulator};
+use super::{install_keys, SnarkCircuit, SnarkCurve, SnarkEngine};
+use crate::utils::error_handling::{ZkpError, ZkpResult};
+use ark_bls12_381::Bls12_381;
+use ark_bn254::Bn254;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, One, PrimeField, UniformRand, Zero};
+use ark_groth16::{ProvingKey, VerifyingKey};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+const MAGIC: &[u8; 8] = b"LZKPCRM2";
+const HEADER_LEN: usize = 46;
+
+fn circuit_tag(circuit: SnarkCircuit) -> (u8, u32) {
+    match circuit {
+        SnarkCircuit::Equality => (0, 0),
+        SnarkCircuit::EqualityBits(bits) => (1, bits as u32),
+        SnarkCircuit::Membership => (2, 0),
+        SnarkCircuit::PolynomialNonneg => (3, 0),
+        SnarkCircuit::NonMembership => (4, 0),
+        SnarkCircuit::MerkleMembership => (5, 0),
+        SnarkCircuit::Vote => (6, 0),
+        SnarkCircuit::Variance => (7, 0),
+    }
+}
+
+fn circuit_from_tag(tag: u8, bits: u32) -> ZkpResult<SnarkCircuit> {
+    let circuit = match tag {
+        0 => SnarkCircuit::Equality,
+        1 => SnarkCircuit::EqualityBits(bits as usize),
+        2 => SnarkCircuit::Membership,
+        3 => SnarkCircuit::PolynomialNonneg,
+        4 => SnarkCircuit::NonMembership,
+        5 => SnarkCircuit::MerkleMembership,
+        6 => SnarkCircuit::Vote,
+        7 => SnarkCircuit::Variance,
+        _ => return Err(format_error("unknown circuit")),
+    };
+    circuit.validate()?;
+    Ok(circuit)
+}
+
+fn format_error(msg: &str) -> ZkpError {
+    ZkpError::InvalidProofFormat(format!("ceremony file: {}", msg))
+}
+
+struct Contribution<E: Pairing> {
+    delta_after: E::G1Affine,
+    commitment: E::G1Affine,
+    response: E::ScalarField,
+}
+
+struct Ceremony<E: Pairing> {
+    circuit: SnarkCircuit,
+    phase1: [u8; 32],
+    initial: ProvingKey<E>,
+    current: ProvingKey<E>,
+    contributions: Vec<Contribution<E>>,
+}
+
+fn curve_tag(curve: SnarkCurve) -> u8 {
+    match curve {
+        SnarkCurve::Bn254 => 0,
+        SnarkCurve::Bls12_381 => 1,
+    }
+}
+
+/// Curve and circuit from the fixed-size header.
+fn read_header(bytes: &[u8]) -> ZkpResult<(SnarkCurve, SnarkCircuit)> {
+    if bytes.len() < HEADER_LEN || &bytes[..8] != MAGIC {
+        return Err(format_error("bad magic"));
+    }
+    let curve = match bytes[8] {
+        0 => SnarkCurve::Bn254,
+        1 => SnarkCurve::Bls12_381,
+        _ => return Err(format_error("unknown curve")),
+    };
+    let bits = u32::from_le_bytes([bytes[10], bytes[11], bytes[12], bytes[13]]);
+    Ok((curve, circuit_from_tag(bytes[9], bits)?))
+}
+
+impl<E: SnarkEngine> Ceremony<E> {
+    fn to_bytes(&self, curve: SnarkCurve) -> ZkpResult<Vec<u8>> {
+        let ser = |e| ZkpError::SerializationError(format!("ceremony file: {:?}", e));
+        let (tag, bits) = circuit_tag(self.circuit);
+        let mut out = MAGIC.to_vec();
+        out.push(curve_tag(curve));
+        out.push(tag);
+        out.extend_from_slice(&bits.to_le_bytes());
+        out.extend_from_slice(&self.phase1);
+        self.initial.serialize_uncompressed(&mut out).map_err(ser)?;
+        self.current.serialize_uncompressed(&mut out).map_err(ser)?;
+        out.extend_from_slice(&(self.contributions.len() as u32).to_le_bytes());
+        for c in &self.contributions {
+            (c.delta_after, c.commitment, c.response)
+                .serialize_uncompressed(&mut out)
+                .map_err(ser)?;
+        }
+        Ok(out)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> ZkpResult<Self> {
+        let (_, circuit) = read_header(bytes)?;
+        let mut phase1 = [0u8; 32];
+        phase1.copy_from_slice(&bytes[14..HEADER_LEN]);
+        let mut reader = &bytes[HEADER_LEN..];
+        let initial = ProvingKey::<E>::deserialize_uncompressed(&mut reader)
+            .map_err(|_| format_error("bad initial key"))?;
+        let current = ProvingKey::<E>::deserialize_uncompressed(&mut reader)
+            .map_err(|_| format_error("bad current key"))?;
+        let n = u32::deserialize_uncompressed(&mut reader)
+            .map_err(|_| format_error("missing contribution count"))?;
+        let mut contributions = Vec::new();
+        for _ in 0..n {
+            let (delta_after, commitment, response) =
+                <(E::G1Affine, E::G1Affine, E::ScalarField)>::deserialize_uncompressed(&mut reader)
+                    .map_err(|_| format_error("bad contribution"))?;
+            contributions.push(Contribution {
+                delta_after,
+                commitment,
+                response,
+            });
+        }
+        if !reader.is_empty() {
+            return Err(format_error("trailing bytes"));
+        }
+        Ok(Self {
+            circuit,
+            phase1,
+            initial,
+            current,
+            contributions,
+        })
+    }
+
+    /// Hash committing to the circuit, phase 1 and initial key; the root of the transcript.
+    fn initial_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"libzkp_ceremony_v2");
+        let (tag, bits) = circuit_tag(self.circuit);
+        hasher.update([tag]);
+        hasher.update(bits.to_le_bytes());
+        hasher.update(self.phase1);
+        let mut key = Vec::new();
+        let _ = self.initial.serialize_uncompressed(&mut key);
+        hasher.update(key);
+        hasher.finalize().into()
+    }
+}
+
+fn point_bytes<G: CanonicalSerialize>(point: &G) -> Vec<u8> {
+    let mut out = Vec::new();
+    let _ = point.serialize_uncompressed(&mut out);
+    out
+}
+
+/// Schnorr challenge for knowledge of `d` with `delta_after = d * delta_before`.
+fn challenge<E: Pairing>(
+    transcript: &[u8; 32],
+    delta_before: &E::G1Affine,
+    delta_after: &E::G1Affine,
+    commitment: &E::G1Affine,
+) -> E::ScalarField {
+    let mut hasher = Sha256::new();
+    hasher.update(b"libzkp_ceremony_challenge");
+    hasher.update(transcript);
+    for point in [delta_before, delta_after, commitment] {
+        hasher.update(point_bytes(point));
+    }
+    E::ScalarField::from_le_bytes_mod_order(&hasher.finalize())
+}
+
+/// Transcript hash after `contribution`; participants keep theirs to find it in [`verify`].
+fn next_hash<E: Pairing>(transcript: &[u8; 32], contribution: &Contribution<E>) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(transcript);
+    hasher.update(point_bytes(&contribution.delta_after));
+    hasher.update(point_bytes(&contribution.commitment));
+    hasher.update(point_bytes(&contribution.response));
+    hasher.finalize().into()
+}
+
+/// `points[i] * scalar` for every point, normalized in one batch.
+fn scale_all<G: CurveGroup>(points: &[G::Affine], scalar: G::ScalarField) -> Vec<G::Affine> {
+    let scaled: Vec<G> = points.iter().map(|p| *p * scalar).collect();
+    G::normalize_batch(&scaled)
+}
+
+/// `[f(τ)]` for each Lagrange basis polynomial `f` of `domain`, from the powers `[τ^i]`.
+fn lagrange<G: CurveGroup>(
+    domain: &GeneralEvaluationDomain<G::ScalarField>,
+    powers: &[G::Affine],
+) -> Vec<G> {
+    domain.ifft(&powers.iter().map(|p| p.into_group()).collect::<Vec<G>>())
+}
+
+/// `point * coeff`, skipping the multiplication for the common unit coefficients.
+fn times<G: CurveGroup>(point: G, coeff: &G::ScalarField) -> G {
+    if coeff.is_one() {
+        point
+    } else {
+        point * coeff
+    }
+}
+
+/// The circuit's keys for the accumulator's secrets and `gamma = delta = 1`, computed as the
+/// Groth16 generator does (libsnark QAP reduction) but in the exponent.
+fn initial_key<E: SnarkEngine>(
+    circuit: SnarkCircuit,
+    acc: &Accumulator<E>,
+) -> ZkpResult<ProvingKey<E>> {
+    let matrices = circuit
+        .synthesize::<E::ScalarField>()
+        .and_then(|cs| cs.to_matrices())
+        .ok_or_else(|| ZkpError::BackendError("circuit synthesis failed".to_string()))?;
+    let (constraints, inputs) = (matrices.num_constraints, matrices.num_instance_variables);
+    let domain = GeneralEvaluationDomain::<E::ScalarField>::new(constraints + inputs)
+        .ok_or_else(|| ZkpError::BackendError("circuit too large".to_string()))?;
+    let size = domain.size();
+    if size > acc.size() {
+        return Err(ZkpError::InvalidInput(format!(
+            "circuit needs powers of tau of size {}, the accumulator has {}",
+            size,
+            acc.size()
+        )));
+    }
+
+    let u_g1 = lagrange::<E::G1>(&domain, &acc.tau_g1[..size]);
+    let u_g2 = lagrange::<E::G2>(&domain, &acc.tau_g2[..size]);
+    let alpha_u = lagrange::<E::G1>(&domain, &acc.alpha_tau_g1[..size]);
+    let beta_u = lagrange::<E::G1>(&domain, &acc.beta_tau_g1[..size]);
+
+    // Per variable: a(τ), b(τ) and beta*a(τ) + alpha*b(τ) + c(τ).
+    let variables = inputs + matrices.num_witness_variables;
+    let mut a = vec![E::G1::zero(); variables];
+    let mut b_g1 = vec![E::G1::zero(); variables];
+    let mut b_g2 = vec![E::G2::zero(); variables];
+    let mut abc = vec![E::G1::zero(); variables];
+    for j in 0..inputs {
+        a[j] += u_g1[constraints + j];
+        abc[j] += beta_u[constraints + j];
+    }
+    for i in 0..constraints {
+        for (coeff, j) in &matrices.a[i] {
+            a[*j] += times(u_g1[i], coeff);
+            abc[*j] += times(beta_u[i], coeff);
+        }
+        for (coeff, j) in &matrices.b[i] {
+            b_g1[*j] += times(u_g1[i], coeff);
+            b_g2[*j] += times(u_g2[i], coeff);
+            abc[*j] += times(alpha_u[i], coeff);
+        }
+        for (coeff, j) in &matrices.c[i] {
+            abc[*j] += times(u_g1[i], coeff);
+        }
+    }
+    // t(τ) τ^i = τ^(size + i) - τ^i
+    let h: Vec<E::G1> = (0..size - 1)
+        .map(|i| acc.tau_g1[size + i].into_group() - acc.tau_g1[i])
+        .collect();
+
+    let (g1, g2) = (acc.tau_g1[0], acc.tau_g2[0]);
+    Ok(ProvingKey {
+        vk: VerifyingKey {
+            alpha_g1: acc.alpha_tau_g1[0],
+            beta_g2: acc.beta_g2,
+            gamma_g2: g2,
+            delta_g2: g2,
+            gamma_abc_g1: E::G1::normalize_batch(&abc[..inputs]),
+        },
+        beta_g1: acc.beta_tau_g1[0],
+        delta_g1: g1,
+        a_query: E::G1::normalize_batch(&a),
+        b_g1_query: E::G1::normalize_batch(&b_g1),
+        b_g2_query: E::G2::normalize_batch(&b_g2),
+        h_query: E::G1::normalize_batch(&h),
+        l_query: E::G1::normalize_batch(&abc[inputs..]),
+    })
+}
+
+fn initialize_on<E: SnarkEngine>(
+    circuit: SnarkCircuit,
+    phase1: &[u8],
+    curve: SnarkCurve,
+) -> ZkpResult<Vec<u8>> {
+    let (acc, phase1) = powers_of_tau::load::<E>(phase1)?;
+    let pk = initial_key(circuit, &acc)?;
+    Ceremony {
+        circuit,
+        phase1,
+        initial: pk.clone(),
+        current: pk,
+        contributions: vec![],
+    }
+    .to_bytes(curve)
+}
+
+fn contribute_on<E: SnarkEngine>(
+    params: &[u8],
+    curve: SnarkCurve,
+) -> ZkpResult<(Vec<u8>, [u8; 32])> {
+    let mut ceremony = Ceremony::<E>::from_bytes(params)?;
+    let hashes = verify_chain(&ceremony)?;
+    let transcript = hashes
+        .last()
+        .copied()
+        .unwrap_or_else(|| ceremony.initial_hash());
+
+    let d = Zeroizing::new(E::ScalarField::rand(&mut OsRng));
+    let d_inv = d
+        .inverse()
+        .ok_or_else(|| ZkpError::CryptoError("zero contribution".to_string()))?;
+    let delta_before = ceremony.current.delta_g1;
+    let delta_after = (delta_before * *d).into_affine();
+
+    let k = Zeroizing::new(E::ScalarField::rand(&mut OsRng));
+    let commitment = (delta_before * *k).into_affine();
+    let c = challenge::<E>(&transcript, &delta_before, &delta_after, &commitment);
+    let contribution = Contribution {
+        delta_after,
+        commitment,
+        response: *k + c * *d,
+    };
+
+    let key = &mut ceremony.current;
+    key.delta_g1 = delta_after;
+    key.vk.delta_g2 = (key.vk.delta_g2 * *d).into_affine();
+    key.h_query = scale_all::<E::G1>(&key.h_query, d_inv);
+    key.l_query = scale_all::<E::G1>(&key.l_query, d_inv);
+
+    let hash = next_hash(&transcript, &contribution);
+    ceremony.contributions.push(contribution);
+    Ok((ceremony.to_bytes(curve)?, hash))
+}
+
+/// Random linear combination of `points`, for comparing whole queries with two pairings.
+fn combine<E: Pairing>(points: &[E::G1Affine], weights: &[E::ScalarField]) -> E::G1 {
+    E::G1::msm(points, &weights[..points.len()]).unwrap_or_default()
+}
+
+/// Check the contributions against the file's own initial key.
+fn verify_chain<E: SnarkEngine>(ceremony: &Ceremony<E>) -> ZkpResult<Vec<[u8; 32]>> {
+    let invalid = |msg: &str| ZkpError::VerificationFailed(format!("ceremony: {}", msg));
+    let (initial, current) = (&ceremony.initial, &ceremony.current);
+
+    match ceremony.circuit.shape::<E::ScalarField>() {
+        Some((instance, total))
+            if initial.vk.gamma_abc_g1.len() == instance && initial.a_query.len() == total => {}
+        _ => return Err(invalid("initial key does not match the circuit")),
+    }
+
+    // Contributions may only touch delta and the h/l queries.
+    let unchanged = initial.vk.alpha_g1 == current.vk.alpha_g1
+        && initial.vk.beta_g2 == current.vk.beta_g2
+        && initial.vk.gamma_g2 == current.vk.gamma_g2
+        && initial.vk.gamma_abc_g1 == current.vk.gamma_abc_g1
+        && initial.beta_g1 == current.beta_g1
+        && initial.a_query == current.a_query
+        && initial.b_g1_query == current.b_g1_query
+        && initial.b_g2_query == current.b_g2_query
+        && initial.h_query.len() == current.h_query.len()
+        && initial.l_query.len() == current.l_query.len();
+    if !unchanged {
+        return Err(invalid("contribution changed more than delta"));
+    }
+
+    let mut transcript = ceremony.initial_hash();
+    let mut hashes = Vec::with_capacity(ceremony.contributions.len());
+    let mut delta_before = initial.delta_g1;
+    for contribution in &ceremony.contributions {
+        if contribution.delta_after.is_zero() {
+            return Err(invalid("zero delta"));
+        }
+        let c = challenge::<E>(
+            &transcript,
+            &delta_before,
+            &contribution.delta_after,
+            &contribution.commitment,
+        );
+        if delta_before * contribution.response
+            != contribution.commitment.into_group() + contribution.delta_after * c
+        {
+            return Err(invalid("bad proof of knowledge"));
+        }
+        transcript = next_hash(&transcript, contribution);
+        hashes.push(transcript);
+        delta_before = contribution.delta_after;
+    }
+    if current.delta_g1 != delta_before {
+        return Err(invalid("delta does not match the last contribution"));
+    }
+
+    // The setup draws its own generators, so compare against beta, which uses the same ones.
+    let (beta_g1, beta_g2) = (initial.beta_g1, initial.vk.beta_g2);
+    if E::pairing(current.delta_g1, beta_g2) != E::pairing(beta_g1, current.vk.delta_g2)
+        || E::pairing(initial.delta_g1, beta_g2) != E::pairing(beta_g1, initial.vk.delta_g2)
+    {
+        return Err(invalid("delta in G1 and G2 differ"));
+    }
+
+    // h and l scale by 1/delta: e(h_initial, delta_initial) == e(h_current, delta_current).
+    let n = initial.h_query.len().max(initial.l_query.len());
+    let weights: Vec<E::ScalarField> = (0..n).map(|_| E::ScalarField::rand(&mut OsRng)).collect();
+    for (before, after) in [
+        (&initial.h_query, &current.h_query),
+        (&initial.l_query, &current.l_query),
+    ] {
+        if E::pairing(combine::<E>(before, &weights), initial.vk.delta_g2)
+            != E::pairing(combine::<E>(after, &weights), current.vk.delta_g2)
+        {
+            return Err(invalid("h or l query not rescaled by the contributions"));
+        }
+    }
+    Ok(hashes)
+}
+
+/// Check the contributions, then the initial key against phase 1.
+fn verify_on<E: SnarkEngine>(params: &[u8], phase1: &[u8]) -> ZkpResult<Vec<[u8; 32]>> {
+    let invalid = |msg: &str| ZkpError::VerificationFailed(format!("ceremony: {}", msg));
+    let ceremony = Ceremony::<E>::from_bytes(params)?;
+    let hashes = verify_chain(&ceremony)?;
+    if powers_of_tau::curve_of(phase1)? != read_header(params)?.0 {
+        return Err(invalid("phase 1 is on another curve"));
+    }
+    let (acc, phase1_hash) = powers_of_tau::load::<E>(phase1)?;
+    if phase1_hash != ceremony.phase1 {
+        return Err(invalid("built on another phase 1"));
+    }
+    if initial_key(ceremony.circuit, &acc)? != ceremony.initial {
+        return Err(invalid("initial key does not match phase 1"));
+    }
+    Ok(hashes)
+}
+
+fn finalize_on<E: SnarkEngine>(params: &[u8], phase1: &[u8]) -> ZkpResult<u32> {
+    if verify_on::<E>(params, phase1)?.is_empty() {
+        return Err(ZkpError::InvalidInput(
+            "ceremony has no contributions".to_string(),
+        ));
+    }
+    let ceremony = Ceremony::<E>::from_bytes(params)?;
+    install_keys(ceremony.circuit, &ceremony.current, &ceremony.current.vk)
+}
+
+macro_rules! on_file_curve {
+    ($params:expr, $f:ident($($arg:expr),*)) => {
+        match read_header($params)?.0 {
+            SnarkCurve::Bn254 => $f::<Bn254>($($arg),*),
+            SnarkCurve::Bls12_381 => $f::<Bls12_381>($($arg),*),
+        }
+    };
+}
+
+/// Start a ceremony for `circuit` from a powers of tau file with at least one contribution,
+/// on that file's curve. The returned file has no contributions yet; pass it to the first
+/// participant.
+pub fn initialize(circuit: SnarkCircuit, phase1: &[u8]) -> ZkpResult<Vec<u8>> {
+    circuit.validate()?;
+    let curve = powers_of_tau::curve_of(phase1)?;
+    match curve {
+        SnarkCurve::Bn254 => initialize_on::<Bn254>(circuit, phase1, curve),
+        SnarkCurve::Bls12_381 => initialize_on::<Bls12_381>(circuit, phase1, curve),
+    }
+}
+
+/// Check the contributions so far, add one with fresh randomness, and return the new file plus
+/// this contribution's transcript hash (publish it so others can find it in [`verify`]). The
+/// initial key is checked against phase 1 by [`verify`] and [`finalize`].
+pub fn contribute(params: &[u8]) -> ZkpResult<(Vec<u8>, [u8; 32])> {
+    let curve = read_header(params)?.0;
+    on_file_curve!(params, contribute_on(params, curve))
+}
+
+/// Check the initial key against the `phase1` powers of tau file it was built on, then every
+/// contribution and the final key. Returns the transcript hash after each contribution, in
+/// order.
+pub fn verify(params: &[u8], phase1: &[u8]) -> ZkpResult<Vec<[u8; 32]>> {
+    on_file_curve!(params, verify_on(params, phase1))
+}
+
+/// Verify `params` against `phase1` and install its keys as the circuit's next key version
+/// (see [`super::rotate_keys`]), returning that version. Needs at least one contribution, and
+/// must run before this process loads the circuit's keys.
+pub fn finalize(params: &[u8], phase1: &[u8]) -> ZkpResult<u32> {
+    on_file_curve!(params, finalize_on(params, phase1))
+}
+
+/// The circuit a ceremony file is for.
+pub fn circuit_of(params: &[u8]) -> ZkpResult<SnarkCircuit> {
+    Ok(read_header(params)?.1)
+}
@@ -0,0 +1,474 @@
+//! Set membership against a Merkle root, for sets far larger than `MAX_SET_SIZE`. The set is
+//! normalized (sorted, deduplicated) and its elements' MiMC commitments become the leaves of a
+//! fixed-depth tree padded with empty (zero) leaves. The circuit opens the value's commitment
+//! and walks a hidden path to the public root, so only the root and the commitment are public
+//! inputs, whatever the size of the set.
+//!
+//! Nodes are compressed with keyed MiMC-5 in Miyaguchi–Preneel mode,
+//! `H(l, r) = E_r(l) + l + r`, one permutation (330 constraints) per level.
+//!
+//! Non-membership uses the same tree over the set's gaps: the normalized set is bracketed by
+//! the sentinels `-1` and `2^64`, and each leaf `H(lo, hi)` hashes two neighbouring bounds. The
+//! circuit opens one gap leaf and shows `lo < value < hi`, which no element can satisfy.
+
+use super::{
+    enforce_bit_width, fr_from_commitment, fr_to_commitment, mimc_hash, mimc_hash_circuit,
+    prove_with_setup, setup, verify_with_setup, MimcField, SnarkCircuit, SnarkEngine,
+};
+use crate::utils::error_handling::ZkpError;
+use ark_ff::PrimeField;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::prelude::*;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use zeroize::Zeroizing;
+
+/// Levels between a leaf and the root.
+pub const MERKLE_DEPTH: usize = 24;
+
+/// Largest number of distinct elements a Merkle membership set may hold.
+pub const MAX_MERKLE_SET_SIZE: usize = 1 << MERKLE_DEPTH;
+
+/// Largest number of distinct elements a non-membership set may hold: its gaps, one more
+/// than its elements, must fit in the tree.
+pub const MAX_NON_MEMBERSHIP_SET_SIZE: usize = MAX_MERKLE_SET_SIZE - 1;
+
+/// Canonical form of a Merkle membership set: sorted ascending, duplicates removed, non-empty
+/// and at most [`MAX_MERKLE_SET_SIZE`] elements. Leaf positions follow this order, so prover
+/// and verifier derive the same root from the same elements in any order.
+pub fn normalize_merkle_set(mut set: Vec<u64>) -> Result<Vec<u64>, ZkpError> {
+    set.sort_unstable();
+    set.dedup();
+    if set.is_empty() {
+        return Err(ZkpError::InvalidInput("set cannot be empty".to_string()));
+    }
+    if set.len() > MAX_MERKLE_SET_SIZE {
+        return Err(ZkpError::InvalidInput(format!(
+            "set size {} exceeds maximum allowed size {}",
+            set.len(),
+            MAX_MERKLE_SET_SIZE
+        )));
+    }
+    Ok(set)
+}
+
+/// [`normalize_merkle_set`] for a non-membership set of at most
+/// [`MAX_NON_MEMBERSHIP_SET_SIZE`] distinct elements.
+pub fn normalize_non_membership_set(set: Vec<u64>) -> Result<Vec<u64>, ZkpError> {
+    let set = normalize_merkle_set(set)?;
+    if set.len() > MAX_NON_MEMBERSHIP_SET_SIZE {
+        return Err(ZkpError::InvalidInput(format!(
+            "set size {} exceeds maximum allowed size {}",
+            set.len(),
+            MAX_NON_MEMBERSHIP_SET_SIZE
+        )));
+    }
+    Ok(set)
+}
+
+/// Bound below every `u64`, bracketing the first gap of a non-membership set.
+fn gap_floor<F: PrimeField>() -> F {
+    -F::ONE
+}
+
+/// Bound above every `u64`, bracketing the last gap of a non-membership set.
+fn gap_ceiling<F: PrimeField>() -> F {
+    F::from(u64::MAX) + F::ONE
+}
+
+pub(super) fn compress<F: MimcField>(left: F, right: F) -> F {
+    let mut x = left;
+    for &c in F::mimc_constants().iter() {
+        let t = x + right + c;
+        let t2 = t * t;
+        let t4 = t2 * t2;
+        x = t4 * t;
+    }
+    x + left + right
+}
+
+pub(super) fn compress_circuit<F: MimcField>(
+    left: &FpVar<F>,
+    right: &FpVar<F>,
+) -> Result<FpVar<F>, SynthesisError> {
+    let mut x = left.clone();
+    for &c in F::mimc_constants().iter() {
+        let t: FpVar<F> = x + right + FpVar::constant(c);
+        let t2: FpVar<F> = t.clone() * &t;
+        let t4: FpVar<F> = t2.clone() * &t2;
+        x = t4 * t;
+    }
+    Ok(x + left + right)
+}
+
+/// Roots of all-empty subtrees, indexed by height (`[0]` is the empty leaf).
+fn empty_roots<F: MimcField>() -> Vec<F> {
+    let mut roots = vec![F::ZERO];
+    for i in 0..MERKLE_DEPTH {
+        roots.push(compress(roots[i], roots[i]));
+    }
+    roots
+}
+
+/// Populated nodes of each level; nodes right of them are empty subtrees.
+pub(super) struct MerkleTree<F> {
+    levels: Vec<Vec<F>>,
+    empty: Vec<F>,
+}
+
+impl<F: MimcField> MerkleTree<F> {
+    /// `set` must be normalized.
+    fn new(set: &[u64]) -> Self {
+        Self::from_leaves(set.iter().map(|&v| mimc_hash::<F>(v)).collect())
+    }
+
+    /// Tree over leaves in the given order; at most `MAX_MERKLE_SET_SIZE` of them.
+    pub(super) fn from_leaves(leaves: Vec<F>) -> Self {
+        let empty = empty_roots::<F>();
+        let mut levels = vec![leaves];
+        for height in 0..MERKLE_DEPTH {
+            let below = &levels[height];
+            let level = below
+                .chunks(2)
+                .map(|pair| compress(pair[0], pair.get(1).copied().unwrap_or(empty[height])))
+                .collect();
+            levels.push(level);
+        }
+        Self { levels, empty }
+    }
+
+    /// Tree over the gaps of a normalized set, leaf `i` being `H(bound[i], bound[i + 1])` for
+    /// the bounds `[-1, set.., 2^64]`.
+    fn gaps(set: &[u64]) -> Self {
+        let bounds: Vec<F> = std::iter::once(gap_floor())
+            .chain(set.iter().map(|&v| F::from(v)))
+            .chain(std::iter::once(gap_ceiling()))
+            .collect();
+        Self::from_leaves(bounds.windows(2).map(|b| compress(b[0], b[1])).collect())
+    }
+
+    pub(super) fn root(&self) -> F {
+        self.levels[MERKLE_DEPTH][0]
+    }
+
+    /// Sibling of each node on the path from leaf `index` up, and whether that node is a
+    /// right child.
+    pub(super) fn path(&self, index: usize) -> (Vec<F>, Vec<bool>) {
+        (0..MERKLE_DEPTH)
+            .map(|height| {
+                let node = index >> height;
+                let sibling = self.levels[height]
+                    .get(node ^ 1)
+                    .copied()
+                    .unwrap_or(self.empty[height]);
+                (sibling, node & 1 == 1)
+            })
+            .unzip()
+    }
+}
+
+// Proves: MiMC5(value) == commitment AND the leaf MiMC5(value) lies under root
+// Public inputs: commitment, root
+// Witness: value, siblings[MERKLE_DEPTH], is_right[MERKLE_DEPTH]
+pub(super) struct MerkleMembershipCircuit<F: PrimeField> {
+    // Secret witnesses: the path reveals the value's position in the set.
+    value: Zeroizing<Option<u64>>,
+    siblings: Zeroizing<Vec<Option<F>>>,
+    is_right: Zeroizing<Vec<Option<bool>>>,
+    commitment: Option<F>,
+    root: Option<F>,
+}
+
+impl<F: MimcField> ConstraintSynthesizer<F> for MerkleMembershipCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+        let value_var = FpVar::<F>::new_witness(cs.clone(), || {
+            self.value
+                .map(F::from)
+                .ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let leaf = mimc_hash_circuit(value_var)?;
+        let commitment_var = FpVar::<F>::new_input(cs.clone(), || {
+            self.commitment.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        leaf.enforce_equal(&commitment_var)?;
+        let root_var = FpVar::<F>::new_input(cs.clone(), || {
+            self.root.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        if self.siblings.len() != MERKLE_DEPTH || self.is_right.len() != MERKLE_DEPTH {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let mut node = leaf;
+        for (sibling, is_right) in self.siblings.iter().zip(self.is_right.iter()) {
+            let sibling = FpVar::<F>::new_witness(cs.clone(), || {
+                sibling.ok_or(SynthesisError::AssignmentMissing)
+            })?;
+            let is_right = Boolean::new_witness(cs.clone(), || {
+                is_right.ok_or(SynthesisError::AssignmentMissing)
+            })?;
+            let left = is_right.select(&sibling, &node)?;
+            let right = is_right.select(&node, &sibling)?;
+            node = compress_circuit(&left, &right)?;
+        }
+        node.enforce_equal(&root_var)?;
+
+        Ok(())
+    }
+}
+
+pub(super) fn dummy_merkle_circuit<F: PrimeField>() -> MerkleMembershipCircuit<F> {
+    MerkleMembershipCircuit {
+        value: Zeroizing::new(Some(0)),
+        siblings: Zeroizing::new(vec![Some(F::ZERO); MERKLE_DEPTH]),
+        is_right: Zeroizing::new(vec![Some(false); MERKLE_DEPTH]),
+        commitment: Some(F::ZERO),
+        root: Some(F::ZERO),
+    }
+}
+
+/// Root of a normalized set on curve `E`.
+pub(super) fn merkle_root<E: SnarkEngine>(set: &[u64]) -> [u8; 32] {
+    fr_to_commitment(MerkleTree::<E::ScalarField>::new(set).root())
+}
+
+/// Proof and root for `value` in the normalized `set`; `None` if the value is not in it or
+/// proving fails.
+pub(super) fn prove_merkle_membership<E: SnarkEngine>(
+    value: u64,
+    set: &[u64],
+    commitment: [u8; 32],
+) -> Option<(Vec<u8>, [u8; 32])> {
+    let index = set.binary_search(&value).ok()?;
+    let commitment_fr = fr_from_commitment(&commitment)?;
+    let tree = MerkleTree::<E::ScalarField>::new(set);
+    let (siblings, is_right) = tree.path(index);
+    let circuit = MerkleMembershipCircuit {
+        value: Zeroizing::new(Some(value)),
+        siblings: Zeroizing::new(siblings.into_iter().map(Some).collect()),
+        is_right: Zeroizing::new(is_right.into_iter().map(Some).collect()),
+        commitment: Some(commitment_fr),
+        root: Some(tree.root()),
+    };
+    let proof = prove_with_setup(setup::<E>(SnarkCircuit::MerkleMembership), circuit);
+    (!proof.is_empty()).then(|| (proof, fr_to_commitment(tree.root())))
+}
+
+pub(super) fn verify_merkle_membership<E: SnarkEngine>(
+    proof_data: &[u8],
+    root: &[u8],
+    commitment: &[u8],
+) -> bool {
+    let (Some(commitment_fr), Some(root_fr)) =
+        (fr_from_commitment(commitment), fr_from_commitment(root))
+    else {
+        return false;
+    };
+    // Public input ordering matches generate_constraints: [commitment, root]
+    verify_with_setup(
+        setup::<E>(SnarkCircuit::MerkleMembership),
+        proof_data,
+        &[commitment_fr, root_fr],
+    )
+}
+
+// Proves: MiMC5(value) == commitment AND lo < value < hi for a gap leaf H(lo, hi) under root
+// Public inputs: commitment, root
+// Witness: value, lo, hi, value - lo - 1 and hi - value - 1 (both < 2^64), the leaf's path
+pub(super) struct MerkleNonMembershipCircuit<F: PrimeField> {
+    // Secret witnesses: the gap and its path reveal where the value falls in the set.
+    value: Zeroizing<Option<u64>>,
+    lo: Zeroizing<Option<F>>,
+    hi: Zeroizing<Option<F>>,
+    above_lo: Zeroizing<Option<u64>>,
+    below_hi: Zeroizing<Option<u64>>,
+    siblings: Zeroizing<Vec<Option<F>>>,
+    is_right: Zeroizing<Vec<Option<bool>>>,
+    commitment: Option<F>,
+    root: Option<F>,
+}
+
+impl<F: MimcField> ConstraintSynthesizer<F> for MerkleNonMembershipCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+        let value_var = FpVar::<F>::new_witness(cs.clone(), || {
+            self.value
+                .map(F::from)
+                .ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let hash = mimc_hash_circuit(value_var.clone())?;
+        let commitment_var = FpVar::<F>::new_input(cs.clone(), || {
+            self.commitment.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        hash.enforce_equal(&commitment_var)?;
+        let root_var = FpVar::<F>::new_input(cs.clone(), || {
+            self.root.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        // Bounds are at least -1 and at most 2^64, so both differences below are integers
+        // under 2^65 in magnitude: fitting in 64 bits makes them non-negative.
+        let lo = FpVar::<F>::new_witness(cs.clone(), || {
+            self.lo.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let hi = FpVar::<F>::new_witness(cs.clone(), || {
+            self.hi.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let one = FpVar::<F>::one();
+        enforce_bit_width(
+            cs.clone(),
+            &(value_var.clone() - &lo - &one),
+            *self.above_lo,
+            64,
+        )?;
+        enforce_bit_width(
+            cs.clone(),
+            &(hi.clone() - &value_var - &one),
+            *self.below_hi,
+            64,
+        )?;
+
+        if self.siblings.len() != MERKLE_DEPTH || self.is_right.len() != MERKLE_DEPTH {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let mut node = compress_circuit(&lo, &hi)?;
+        for (sibling, is_right) in self.siblings.iter().zip(self.is_right.iter()) {
+            let sibling = FpVar::<F>::new_witness(cs.clone(), || {
+                sibling.ok_or(SynthesisError::AssignmentMissing)
+            })?;
+            let is_right = Boolean::new_witness(cs.clone(), || {
+                is_right.ok_or(SynthesisError::AssignmentMissing)
+            })?;
+            let left = is_right.select(&sibling, &node)?;
+            let right = is_right.select(&node, &sibling)?;
+            node = compress_circuit(&left, &right)?;
+        }
+        node.enforce_equal(&root_var)?;
+
+        Ok(())
+    }
+}
+
+pub(super) fn dummy_non_membership_circuit<F: PrimeField>() -> MerkleNonMembershipCircuit<F> {
+    MerkleNonMembershipCircuit {
+        value: Zeroizing::new(Some(0)),
+        lo: Zeroizing::new(Some(gap_floor())),
+        hi: Zeroizing::new(Some(gap_ceiling())),
+        above_lo: Zeroizing::new(Some(0)),
+        below_hi: Zeroizing::new(Some(u64::MAX)),
+        siblings: Zeroizing::new(vec![Some(F::ZERO); MERKLE_DEPTH]),
+        is_right: Zeroizing::new(vec![Some(false); MERKLE_DEPTH]),
+        commitment: Some(F::ZERO),
+        root: Some(F::ZERO),
+    }
+}
+
+/// Gap-tree root of a normalized non-membership set on curve `E`.
+pub(super) fn non_membership_root<E: SnarkEngine>(set: &[u64]) -> [u8; 32] {
+    fr_to_commitment(MerkleTree::<E::ScalarField>::gaps(set).root())
+}
+
+/// Proof and gap-tree root for `value` outside the normalized `set`; `None` if the value is
+/// in it or proving fails.
+pub(super) fn prove_non_membership<E: SnarkEngine>(
+    value: u64,
+    set: &[u64],
+    commitment: [u8; 32],
+) -> Option<(Vec<u8>, [u8; 32])> {
+    // The gap holding `value` lies after every element below it.
+    let gap = match set.binary_search(&value) {
+        Ok(_) => return None,
+        Err(gap) => gap,
+    };
+    let commitment_fr = fr_from_commitment(&commitment)?;
+    let (lo, above_lo) = match gap.checked_sub(1).map(|i| set[i]) {
+        Some(lo) => (E::ScalarField::from(lo), value - lo - 1),
+        None => (gap_floor(), value),
+    };
+    let (hi, below_hi) = match set.get(gap) {
+        Some(&hi) => (E::ScalarField::from(hi), hi - value - 1),
+        None => (gap_ceiling(), u64::MAX - value),
+    };
+    let tree = MerkleTree::<E::ScalarField>::gaps(set);
+    let (siblings, is_right) = tree.path(gap);
+    let circuit = MerkleNonMembershipCircuit {
+        value: Zeroizing::new(Some(value)),
+        lo: Zeroizing::new(Some(lo)),
+        hi: Zeroizing::new(Some(hi)),
+        above_lo: Zeroizing::new(Some(above_lo)),
+        below_hi: Zeroizing::new(Some(below_hi)),
+        siblings: Zeroizing::new(siblings.into_iter().map(Some).collect()),
+        is_right: Zeroizing::new(is_right.into_iter().map(Some).collect()),
+        commitment: Some(commitment_fr),
+        root: Some(tree.root()),
+    };
+    let proof = prove_with_setup(setup::<E>(SnarkCircuit::NonMembership), circuit);
+    (!proof.is_empty()).then(|| (proof, fr_to_commitment(tree.root())))
+}
+
+pub(super) fn verify_non_membership<E: SnarkEngine>(
+    proof_data: &[u8],
+    root: &[u8],
+    commitment: &[u8],
+) -> bool {
+    let (Some(commitment_fr), Some(root_fr)) =
+        (fr_from_commitment(commitment), fr_from_commitment(root))
+    else {
+        return false;
+    };
+    // Public input ordering matches generate_constraints: [commitment, root]
+    verify_with_setup(
+        setup::<E>(SnarkCircuit::NonMembership),
+        proof_data,
+        &[commitment_fr, root_fr],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bn254::Fr;
+    use ark_relations::r1cs::ConstraintSystem;
+
+    /// Gap circuit for `value` against the gap at index `gap` of the normalized `set`.
+    fn gap_circuit(
+        set: &[u64],
+        value: u64,
+        gap: usize,
+        above_lo: u64,
+        below_hi: u64,
+    ) -> MerkleNonMembershipCircuit<Fr> {
+        let tree = MerkleTree::<Fr>::gaps(set);
+        let bound = |i: usize| match i {
+            0 => gap_floor(),
+            i if i > set.len() => gap_ceiling(),
+            i => Fr::from(set[i - 1]),
+        };
+        let (siblings, is_right) = tree.path(gap);
+        MerkleNonMembershipCircuit {
+            value: Zeroizing::new(Some(value)),
+            lo: Zeroizing::new(Some(bound(gap))),
+            hi: Zeroizing::new(Some(bound(gap + 1))),
+            above_lo: Zeroizing::new(Some(above_lo)),
+            below_hi: Zeroizing::new(Some(below_hi)),
+            siblings: Zeroizing::new(siblings.into_iter().map(Some).collect()),
+            is_right: Zeroizing::new(is_right.into_iter().map(Some).collect()),
+            commitment: Some(mimc_hash::<Fr>(value)),
+            root: Some(tree.root()),
+        }
+    }
+
+    fn satisfied(circuit: MerkleNonMembershipCircuit<Fr>) -> bool {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        circuit.generate_constraints(cs.clone()).unwrap();
+        cs.is_satisfied().unwrap()
+    }
+
+    #[test]
+    fn gap_circuit_rejects_a_member() {
+        let set = [10, 20, 30];
+        assert!(satisfied(gap_circuit(&set, 15, 1, 4, 4)));
+        assert!(satisfied(gap_circuit(&set, 0, 0, 0, 9)));
+        assert!(satisfied(gap_circuit(&set, u64::MAX, 3, u64::MAX - 31, 0)));
+
+        // 20 sits on the bound of both neighbouring gaps, so one of its distances is -1.
+        assert!(!satisfied(gap_circuit(&set, 20, 1, 9, u64::MAX)));
+        assert!(!satisfied(gap_circuit(&set, 20, 2, u64::MAX, 9)));
+    }
+}
@@ -0,0 +1,443 @@
+//! Universal Groth16 setup ("phase 1", powers of tau). The accumulator holds `τ^i·G1`,
+//! `τ^i·G2`, `ατ^i·G1`, `βτ^i·G1` and `β·G2` for a domain of `2^log_size` constraints, and
+//! serves every circuit up to that size. [`initialize`] starts from `τ = α = β = 1`, which
+//! everyone knows; each participant runs [`contribute`] in turn, which multiplies `τ`, `α` and
+//! `β` by fresh secrets, and [`verify`] checks the chain. The result is unknown unless every
+//! contributor colludes. [`super::ceremony`] derives a circuit's initial keys from a verified
+//! accumulator and then runs phase 2 on `delta`.
+//!
+//! A contribution stores the new `τ·G1`, `α·G1` and `β·G1` with a Schnorr proof of knowledge of
+//! each factor, bound to the transcript so far. The verifier checks that the accumulator is a
+//! well-formed sequence of powers with random linear combinations and a few pairings.
+//!
+//! File layout: `[magic 8][curve 1][log_size 1][accumulator][u32 n]` then `n` contributions
+//! `[τ G1][α G1][β G1][3 × (schnorr commitment G1, response Fr)]`, group elements in arkworks
+//! uncompressed form. The accumulator is `τ^i·G1` for `i < 2N - 1`, then `ατ^i·G1`, `βτ^i·G1`
+//! and `τ^i·G2` for `i < N`, then `β·G2`, with `N = 2^log_size`.
+
+use super::{active_curve, SnarkCurve, SnarkEngine};
+use crate::utils::error_handling::{ZkpError, ZkpResult};
+use ark_bls12_381::Bls12_381;
+use ark_bn254::Bn254;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{AdditiveGroup, One, PrimeField, UniformRand, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+const MAGIC: &[u8; 8] = b"LZKPPOT1";
+
+/// Largest `log_size`: domains of up to `2^22` constraints and public inputs.
+pub const MAX_LOG_SIZE: u32 = 22;
+
+fn format_error(msg: &str) -> ZkpError {
+    ZkpError::InvalidProofFormat(format!("powers of tau file: {}", msg))
+}
+
+/// Powers of the phase-1 secrets, see the module docs.
+pub(super) struct Accumulator<E: Pairing> {
+    pub(super) tau_g1: Vec<E::G1Affine>,
+    pub(super) tau_g2: Vec<E::G2Affine>,
+    pub(super) alpha_tau_g1: Vec<E::G1Affine>,
+    pub(super) beta_tau_g1: Vec<E::G1Affine>,
+    pub(super) beta_g2: E::G2Affine,
+}
+
+impl<E: Pairing> Accumulator<E> {
+    /// The accumulator for `τ = α = β = 1`.
+    fn identity(log_size: u32) -> Self {
+        let n = 1usize << log_size;
+        let g1 = E::G1Affine::generator();
+        let g2 = E::G2Affine::generator();
+        Self {
+            tau_g1: vec![g1; 2 * n - 1],
+            tau_g2: vec![g2; n],
+            alpha_tau_g1: vec![g1; n],
+            beta_tau_g1: vec![g1; n],
+            beta_g2: g2,
+        }
+    }
+
+    /// Domain size `N` the accumulator serves.
+    pub(super) fn size(&self) -> usize {
+        self.tau_g2.len()
+    }
+}
+
+/// Factors `τ`, `α` and `β` are proven in this order.
+const FACTORS: usize = 3;
+
+struct Contribution<E: Pairing> {
+    /// `τ·G1`, `α·G1` and `β·G1` after this contribution.
+    after: [E::G1Affine; FACTORS],
+    commitments: [E::G1Affine; FACTORS],
+    responses: [E::ScalarField; FACTORS],
+}
+
+struct PowersOfTau<E: Pairing> {
+    log_size: u32,
+    accumulator: Accumulator<E>,
+    contributions: Vec<Contribution<E>>,
+}
+
+fn curve_tag(curve: SnarkCurve) -> u8 {
+    match curve {
+        SnarkCurve::Bn254 => 0,
+        SnarkCurve::Bls12_381 => 1,
+    }
+}
+
+/// Curve and `log_size` from the fixed-size header.
+fn read_header(bytes: &[u8]) -> ZkpResult<(SnarkCurve, u32)> {
+    if bytes.len() < 10 || &bytes[..8] != MAGIC {
+        return Err(format_error("bad magic"));
+    }
+    let curve = match bytes[8] {
+        0 => SnarkCurve::Bn254,
+        1 => SnarkCurve::Bls12_381,
+        _ => return Err(format_error("unknown curve")),
+    };
+    let log_size = bytes[9] as u32;
+    if !(1..=MAX_LOG_SIZE).contains(&log_size) {
+        return Err(format_error("unsupported size"));
+    }
+    Ok((curve, log_size))
+}
+
+/// The curve a powers of tau file is on.
+pub(super) fn curve_of(bytes: &[u8]) -> ZkpResult<SnarkCurve> {
+    Ok(read_header(bytes)?.0)
+}
+
+fn read_points<G: CanonicalDeserialize>(reader: &mut &[u8], count: usize) -> ZkpResult<Vec<G>> {
+    (0..count)
+        .map(|_| {
+            G::deserialize_uncompressed(&mut *reader).map_err(|_| format_error("bad accumulator"))
+        })
+        .collect()
+}
+
+impl<E: SnarkEngine> PowersOfTau<E> {
+    fn to_bytes(&self, curve: SnarkCurve) -> ZkpResult<Vec<u8>> {
+        let ser = |e| ZkpError::SerializationError(format!("powers of tau file: {:?}", e));
+        let acc = &self.accumulator;
+        let mut out = MAGIC.to_vec();
+        out.push(curve_tag(curve));
+        out.push(self.log_size as u8);
+        for p in acc
+            .tau_g1
+            .iter()
+            .chain(&acc.alpha_tau_g1)
+            .chain(&acc.beta_tau_g1)
+        {
+            p.serialize_uncompressed(&mut out).map_err(ser)?;
+        }
+        for p in acc.tau_g2.iter().chain([&acc.beta_g2]) {
+            p.serialize_uncompressed(&mut out).map_err(ser)?;
+        }
+        out.extend_from_slice(&(self.contributions.len() as u32).to_le_bytes());
+        for c in &self.contributions {
+            (c.after, c.commitments, c.responses)
+                .serialize_uncompressed(&mut out)
+                .map_err(ser)?;
+        }
+        Ok(out)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> ZkpResult<Self> {
+        let (_, log_size) = read_header(bytes)?;
+        let n = 1usize << log_size;
+        let mut reader = &bytes[10..];
+        let tau_g1 = read_points(&mut reader, 2 * n - 1)?;
+        let alpha_tau_g1 = read_points(&mut reader, n)?;
+        let beta_tau_g1 = read_points(&mut reader, n)?;
+        let mut tau_g2 = read_points(&mut reader, n + 1)?;
+        let beta_g2 = tau_g2
+            .pop()
+            .ok_or_else(|| format_error("bad accumulator"))?;
+        let count = u32::deserialize_uncompressed(&mut reader)
+            .map_err(|_| format_error("missing contribution count"))?;
+        let mut contributions = Vec::new();
+        for _ in 0..count {
+            let (after, commitments, responses) =
+                <(
+                    [E::G1Affine; FACTORS],
+                    [E::G1Affine; FACTORS],
+                    [E::ScalarField; FACTORS],
+                )>::deserialize_uncompressed(&mut reader)
+                .map_err(|_| format_error("bad contribution"))?;
+            contributions.push(Contribution {
+                after,
+                commitments,
+                responses,
+            });
+        }
+        if !reader.is_empty() {
+            return Err(format_error("trailing bytes"));
+        }
+        Ok(Self {
+            log_size,
+            accumulator: Accumulator {
+                tau_g1,
+                tau_g2,
+                alpha_tau_g1,
+                beta_tau_g1,
+                beta_g2,
+            },
+            contributions,
+        })
+    }
+}
+
+/// Root of the transcript: the starting accumulator is fixed by the curve and size.
+fn initial_hash(curve: SnarkCurve, log_size: u32) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"libzkp_powers_of_tau_v1");
+    hasher.update([curve_tag(curve), log_size as u8]);
+    hasher.finalize().into()
+}
+
+fn point_bytes<G: CanonicalSerialize>(point: &G) -> Vec<u8> {
+    let mut out = Vec::new();
+    let _ = point.serialize_uncompressed(&mut out);
+    out
+}
+
+/// Schnorr challenge for knowledge of factor `index` with `after = x * before`.
+fn challenge<E: Pairing>(
+    transcript: &[u8; 32],
+    index: usize,
+    before: &E::G1Affine,
+    after: &E::G1Affine,
+    commitment: &E::G1Affine,
+) -> E::ScalarField {
+    let mut hasher = Sha256::new();
+    hasher.update(b"libzkp_powers_of_tau_challenge");
+    hasher.update(transcript);
+    hasher.update([index as u8]);
+    for point in [before, after, commitment] {
+        hasher.update(point_bytes(point));
+    }
+    E::ScalarField::from_le_bytes_mod_order(&hasher.finalize())
+}
+
+/// Transcript hash after `contribution`; participants keep theirs to find it in [`verify`].
+fn next_hash<E: Pairing>(transcript: &[u8; 32], contribution: &Contribution<E>) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(transcript);
+    for i in 0..FACTORS {
+        hasher.update(point_bytes(&contribution.after[i]));
+        hasher.update(point_bytes(&contribution.commitments[i]));
+        hasher.update(point_bytes(&contribution.responses[i]));
+    }
+    hasher.finalize().into()
+}
+
+/// `points[i] * (first * step^i)` for every point, normalized in one batch.
+fn scale_powers<G: CurveGroup>(
+    points: &[G::Affine],
+    first: G::ScalarField,
+    step: G::ScalarField,
+) -> Vec<G::Affine> {
+    let mut scalar = first;
+    let scaled: Vec<G> = points
+        .iter()
+        .map(|p| {
+            let q = *p * scalar;
+            scalar *= step;
+            q
+        })
+        .collect();
+    G::normalize_batch(&scaled)
+}
+
+fn random_weights<F: UniformRand>(n: usize) -> Vec<F> {
+    (0..n).map(|_| F::rand(&mut OsRng)).collect()
+}
+
+/// Whether each of `points` is `x` times the one before, given `[1]_2` and `[x]_2`, by one
+/// random linear combination: `e(Σ ρ_i P_{i+1}, [1]_2) == e(Σ ρ_i P_i, [x]_2)`.
+fn powers_in_g1<E: Pairing>(points: &[E::G1Affine], one: E::G2Affine, x: E::G2Affine) -> bool {
+    let n = points.len() - 1;
+    let weights = random_weights::<E::ScalarField>(n);
+    let (Ok(high), Ok(low)) = (
+        E::G1::msm(&points[1..], &weights),
+        E::G1::msm(&points[..n], &weights),
+    ) else {
+        return false;
+    };
+    E::pairing(high, one) == E::pairing(low, x)
+}
+
+/// [`powers_in_g1`] for points in G2, given `[1]_1` and `[x]_1`.
+fn powers_in_g2<E: Pairing>(points: &[E::G2Affine], one: E::G1Affine, x: E::G1Affine) -> bool {
+    let n = points.len() - 1;
+    let weights = random_weights::<E::ScalarField>(n);
+    let (Ok(high), Ok(low)) = (
+        E::G2::msm(&points[1..], &weights),
+        E::G2::msm(&points[..n], &weights),
+    ) else {
+        return false;
+    };
+    E::pairing(one, high) == E::pairing(x, low)
+}
+
+fn initialize_on<E: SnarkEngine>(log_size: u32, curve: SnarkCurve) -> ZkpResult<Vec<u8>> {
+    PowersOfTau::<E> {
+        log_size,
+        accumulator: Accumulator::identity(log_size),
+        contributions: vec![],
+    }
+    .to_bytes(curve)
+}
+
+fn contribute_on<E: SnarkEngine>(params: &[u8]) -> ZkpResult<(Vec<u8>, [u8; 32])> {
+    let (curve, log_size) = read_header(params)?;
+    let hashes = verify_on::<E>(params)?;
+    let mut ceremony = PowersOfTau::<E>::from_bytes(params)?;
+    let transcript = hashes
+        .last()
+        .copied()
+        .unwrap_or_else(|| initial_hash(curve, log_size));
+
+    let secrets: [Zeroizing<E::ScalarField>; FACTORS] =
+        std::array::from_fn(|_| Zeroizing::new(E::ScalarField::rand(&mut OsRng)));
+    if secrets.iter().any(|s| s.is_zero()) {
+        return Err(ZkpError::CryptoError("zero contribution".to_string()));
+    }
+    let (tau, alpha, beta) = (*secrets[0], *secrets[1], *secrets[2]);
+
+    let acc = &ceremony.accumulator;
+    let before = [acc.tau_g1[1], acc.alpha_tau_g1[0], acc.beta_tau_g1[0]];
+    let after: [E::G1Affine; FACTORS] =
+        std::array::from_fn(|i| (before[i] * *secrets[i]).into_affine());
+    let mut commitments = [E::G1Affine::zero(); FACTORS];
+    let mut responses = [E::ScalarField::ZERO; FACTORS];
+    for i in 0..FACTORS {
+        let k = Zeroizing::new(E::ScalarField::rand(&mut OsRng));
+        commitments[i] = (before[i] * *k).into_affine();
+        let c = challenge::<E>(&transcript, i, &before[i], &after[i], &commitments[i]);
+        responses[i] = *k + c * *secrets[i];
+    }
+    let contribution = Contribution {
+        after,
+        commitments,
+        responses,
+    };
+
+    let acc = &mut ceremony.accumulator;
+    acc.tau_g1 = scale_powers::<E::G1>(&acc.tau_g1, E::ScalarField::one(), tau);
+    acc.tau_g2 = scale_powers::<E::G2>(&acc.tau_g2, E::ScalarField::one(), tau);
+    acc.alpha_tau_g1 = scale_powers::<E::G1>(&acc.alpha_tau_g1, alpha, tau);
+    acc.beta_tau_g1 = scale_powers::<E::G1>(&acc.beta_tau_g1, beta, tau);
+    acc.beta_g2 = (acc.beta_g2 * beta).into_affine();
+
+    let hash = next_hash(&transcript, &contribution);
+    ceremony.contributions.push(contribution);
+    Ok((ceremony.to_bytes(curve)?, hash))
+}
+
+fn verify_ceremony<E: SnarkEngine>(
+    ceremony: &PowersOfTau<E>,
+    curve: SnarkCurve,
+) -> ZkpResult<Vec<[u8; 32]>> {
+    let invalid = |msg: &str| ZkpError::VerificationFailed(format!("powers of tau: {}", msg));
+    let acc = &ceremony.accumulator;
+    let (g1, g2) = (E::G1Affine::generator(), E::G2Affine::generator());
+
+    // Each factor's chain of Schnorr proofs, from 1 to the accumulator's value.
+    let mut transcript = initial_hash(curve, ceremony.log_size);
+    let mut hashes = Vec::with_capacity(ceremony.contributions.len());
+    let mut before = [g1; FACTORS];
+    for contribution in &ceremony.contributions {
+        for (i, before) in before.iter().enumerate() {
+            let after = contribution.after[i];
+            if after.is_zero() {
+                return Err(invalid("zero contribution"));
+            }
+            let c = challenge::<E>(&transcript, i, before, &after, &contribution.commitments[i]);
+            if *before * contribution.responses[i]
+                != contribution.commitments[i].into_group() + after * c
+            {
+                return Err(invalid("bad proof of knowledge"));
+            }
+        }
+        transcript = next_hash(&transcript, contribution);
+        hashes.push(transcript);
+        before = contribution.after;
+    }
+    if [acc.tau_g1[1], acc.alpha_tau_g1[0], acc.beta_tau_g1[0]] != before {
+        return Err(invalid("accumulator does not match the last contribution"));
+    }
+
+    // The accumulator holds powers of one tau, scaled by alpha and beta.
+    let (tau_g1, tau_g2) = (acc.tau_g1[1], acc.tau_g2[1]);
+    if acc.tau_g1[0] != g1
+        || acc.tau_g2[0] != g2
+        || !powers_in_g1::<E>(&acc.tau_g1, g2, tau_g2)
+        || !powers_in_g2::<E>(&acc.tau_g2, g1, tau_g1)
+        || !powers_in_g1::<E>(&acc.alpha_tau_g1, g2, tau_g2)
+        || !powers_in_g1::<E>(&acc.beta_tau_g1, g2, tau_g2)
+        || E::pairing(acc.beta_tau_g1[0], g2) != E::pairing(g1, acc.beta_g2)
+    {
+        return Err(invalid("accumulator is not a sequence of powers"));
+    }
+    Ok(hashes)
+}
+
+fn verify_on<E: SnarkEngine>(params: &[u8]) -> ZkpResult<Vec<[u8; 32]>> {
+    let curve = read_header(params)?.0;
+    verify_ceremony(&PowersOfTau::<E>::from_bytes(params)?, curve)
+}
+
+/// The accumulator of a verified file with at least one contribution, and its final
+/// transcript hash.
+pub(super) fn load<E: SnarkEngine>(params: &[u8]) -> ZkpResult<(Accumulator<E>, [u8; 32])> {
+    let curve = read_header(params)?.0;
+    let ceremony = PowersOfTau::<E>::from_bytes(params)?;
+    let hash = verify_ceremony(&ceremony, curve)?
+        .last()
+        .copied()
+        .ok_or_else(|| ZkpError::InvalidInput("powers of tau has no contributions".to_string()))?;
+    Ok((ceremony.accumulator, hash))
+}
+
+macro_rules! on_file_curve {
+    ($params:expr, $f:ident($($arg:expr),*)) => {
+        match read_header($params)?.0 {
+            SnarkCurve::Bn254 => $f::<Bn254>($($arg),*),
+            SnarkCurve::Bls12_381 => $f::<Bls12_381>($($arg),*),
+        }
+    };
+}
+
+/// Start a powers of tau accumulator for circuits of up to `2^log_size` constraints plus
+/// public inputs (`1..=MAX_LOG_SIZE`) on the active SNARK curve. The returned file has no
+/// contributions yet; pass it to the first participant.
+pub fn initialize(log_size: u32) -> ZkpResult<Vec<u8>> {
+    if !(1..=MAX_LOG_SIZE).contains(&log_size) {
+        return Err(ZkpError::InvalidInput(format!(
+            "log_size must be in 1..={}, got {}",
+            MAX_LOG_SIZE, log_size
+        )));
+    }
+    let curve = active_curve();
+    match curve {
+        SnarkCurve::Bn254 => initialize_on::<Bn254>(log_size, curve),
+        SnarkCurve::Bls12_381 => initialize_on::<Bls12_381>(log_size, curve),
+    }
+}
+
+/// Verify `params`, add a contribution with fresh randomness, and return the new file plus
+/// this contribution's transcript hash (publish it so others can find it in [`verify`]).
+pub fn contribute(params: &[u8]) -> ZkpResult<(Vec<u8>, [u8; 32])> {
+    on_file_curve!(params, contribute_on(params))
+}
+
+/// Check every contribution and the accumulator. Returns the transcript hash after each
+/// contribution, in order.
+pub fn verify(params: &[u8]) -> ZkpResult<Vec<[u8; 32]>> {
+    on_file_curve!(params, verify_on(params))
+}
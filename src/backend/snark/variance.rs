@@ -0,0 +1,194 @@
+//! Variance bound: a prover shows the population variance of up to [`MAX_VARIANCE_VALUES`]
+//! hidden values is below a public bound, without revealing the values.
+//!
+//! With `n` values, `S = Σx` and `Q = Σx²`, the variance is `(n·Q - S²) / n²`, so the circuit
+//! checks `n·Q - S² < bound·n²` without division: the margin `bound·n² - (n·Q - S²) - 1` must
+//! fit in 128 bits. A violated bound leaves a negative margin, which is a field element near
+//! the modulus and has no such decomposition. The values, zero-padded to
+//! [`MAX_VARIANCE_VALUES`], are bound to the proof by a blinded chain `H(…H(r, w_0)…, w_k)` of
+//! Merkle node compressions over words `w_j` packing [`VALUES_PER_WORD`] values each.
+
+use super::merkle::{compress, compress_circuit};
+use super::{
+    enforce_bit_width, fr_from_commitment, fr_to_commitment, prove_with_setup, setup,
+    verify_with_setup, MimcField, SnarkCircuit, SnarkEngine,
+};
+use ark_ff::{AdditiveGroup, PrimeField, UniformRand};
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::prelude::*;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_std::rand::rngs::OsRng;
+use zeroize::Zeroizing;
+
+/// Largest number of values in one variance proof.
+pub const MAX_VARIANCE_VALUES: usize = 64;
+
+/// 64-bit values packed into one field element of the commitment chain.
+const VALUES_PER_WORD: usize = 3;
+
+/// `n·Σx² - (Σx)²`, i.e. `n²` times the population variance, as the sum of squared pairwise
+/// differences; `None` if it overflows `u128`.
+pub fn variance_spread(values: &[u64]) -> Option<u128> {
+    let mut spread = 0u128;
+    for (i, &a) in values.iter().enumerate() {
+        for &b in &values[i + 1..] {
+            let d = a.abs_diff(b) as u128;
+            spread = spread.checked_add(d * d)?;
+        }
+    }
+    Some(spread)
+}
+
+// Proves: commitment == chain(blinding, packed values) AND each value < 2^64
+//         AND padding values are zero
+//         AND bound*n^2 - (n*Q - S^2) - 1 == lo + hi*2^64 with lo, hi < 2^64
+// Public inputs: commitment, bound, is_real[MAX_VARIANCE_VALUES]
+// Witness: values[MAX_VARIANCE_VALUES], blinding, lo, hi
+pub(super) struct VarianceCircuit<F: PrimeField> {
+    // Secret witnesses: the values and the margin, which would narrow down the variance.
+    values: Zeroizing<Vec<Option<u64>>>,
+    blinding: Zeroizing<Option<F>>,
+    margin: Zeroizing<Option<u128>>,
+    commitment: Option<F>,
+    bound: u64,
+    is_real: Vec<bool>,
+}
+
+impl<F: MimcField> ConstraintSynthesizer<F> for VarianceCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+        let commitment_var = FpVar::<F>::new_input(cs.clone(), || {
+            self.commitment.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let bound_var = FpVar::<F>::new_input(cs.clone(), || Ok(F::from(self.bound)))?;
+        if self.is_real.len() != MAX_VARIANCE_VALUES || self.values.len() != MAX_VARIANCE_VALUES {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let mut is_real_bools: Vec<Boolean<F>> = Vec::with_capacity(MAX_VARIANCE_VALUES);
+        for &b in self.is_real.iter() {
+            is_real_bools.push(Boolean::new_input(cs.clone(), || Ok(b))?);
+        }
+
+        let mut n = FpVar::<F>::zero();
+        let mut sum = FpVar::<F>::zero();
+        let mut sum_sq = FpVar::<F>::zero();
+        let mut xs = Vec::with_capacity(MAX_VARIANCE_VALUES);
+        for (value, is_real) in self.values.iter().zip(is_real_bools.iter()) {
+            let x = FpVar::<F>::new_witness(cs.clone(), || {
+                value.map(F::from).ok_or(SynthesisError::AssignmentMissing)
+            })?;
+            enforce_bit_width(cs.clone(), &x, *value, 64)?;
+            // x * (1 - is_real) == 0: padding slots hold zero
+            x.mul_equals(&FpVar::from(!is_real), &FpVar::zero())?;
+            n += FpVar::from(is_real.clone());
+            sum_sq += x.square()?;
+            sum += &x;
+            xs.push(x);
+        }
+
+        let mut node = FpVar::<F>::new_witness(cs.clone(), || {
+            self.blinding.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        for word in xs.chunks(VALUES_PER_WORD) {
+            let mut packed = FpVar::<F>::zero();
+            for (i, x) in word.iter().enumerate() {
+                packed += x * word_shift::<F>(i);
+            }
+            node = compress_circuit(&node, &packed)?;
+        }
+        node.enforce_equal(&commitment_var)?;
+
+        let margin = bound_var * n.square()? - (n * sum_sq - sum.square()?) - FpVar::one();
+        let lo = FpVar::<F>::new_witness(cs.clone(), || {
+            self.margin
+                .map(|m| F::from(m as u64))
+                .ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        enforce_bit_width(cs.clone(), &lo, self.margin.map(|m| m as u64), 64)?;
+        let hi = FpVar::<F>::new_witness(cs.clone(), || {
+            self.margin
+                .map(|m| F::from((m >> 64) as u64))
+                .ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        enforce_bit_width(cs, &hi, self.margin.map(|m| (m >> 64) as u64), 64)?;
+        (lo + hi * F::from(1u128 << 64)).enforce_equal(&margin)
+    }
+}
+
+pub(super) fn dummy_variance_circuit<F: PrimeField>() -> VarianceCircuit<F> {
+    VarianceCircuit {
+        values: Zeroizing::new(vec![Some(0); MAX_VARIANCE_VALUES]),
+        blinding: Zeroizing::new(Some(F::ZERO)),
+        margin: Zeroizing::new(Some(0)),
+        commitment: Some(F::ZERO),
+        bound: 0,
+        is_real: vec![false; MAX_VARIANCE_VALUES],
+    }
+}
+
+/// `2^(64 * i)`, the weight of the `i`-th value in a word.
+fn word_shift<F: PrimeField>(i: usize) -> F {
+    F::from(2u64).pow([64 * i as u64])
+}
+
+fn is_real_flags(count: usize) -> Vec<bool> {
+    (0..MAX_VARIANCE_VALUES).map(|i| i < count).collect()
+}
+
+/// Proof and commitment that the variance of `values` is below `bound`; `None` if there are no
+/// values or more than [`MAX_VARIANCE_VALUES`], the bound does not hold or proving fails.
+pub(super) fn prove_variance_below<E: SnarkEngine>(
+    values: &[u64],
+    bound: u64,
+) -> Option<(Vec<u8>, [u8; 32])> {
+    let n = values.len();
+    if !(1..=MAX_VARIANCE_VALUES).contains(&n) {
+        return None;
+    }
+    let scaled_bound = bound as u128 * (n * n) as u128;
+    let margin = scaled_bound
+        .checked_sub(variance_spread(values)?)?
+        .checked_sub(1)?;
+
+    let blinding = Zeroizing::new(E::ScalarField::rand(&mut OsRng));
+    let mut padded = values.to_vec();
+    padded.resize(MAX_VARIANCE_VALUES, 0);
+    let commitment = padded
+        .chunks(VALUES_PER_WORD)
+        .fold(*blinding, |node, word| {
+            let packed = word
+                .iter()
+                .enumerate()
+                .fold(E::ScalarField::ZERO, |acc, (i, &x)| {
+                    acc + E::ScalarField::from(x) * word_shift::<E::ScalarField>(i)
+                });
+            compress(node, packed)
+        });
+    let circuit = VarianceCircuit {
+        values: Zeroizing::new(padded.into_iter().map(Some).collect()),
+        blinding: Zeroizing::new(Some(*blinding)),
+        margin: Zeroizing::new(Some(margin)),
+        commitment: Some(commitment),
+        bound,
+        is_real: is_real_flags(n),
+    };
+    let proof = prove_with_setup(setup::<E>(SnarkCircuit::Variance), circuit);
+    (!proof.is_empty()).then(|| (proof, fr_to_commitment(commitment)))
+}
+
+pub(super) fn verify_variance_below<E: SnarkEngine>(
+    proof_data: &[u8],
+    commitment: &[u8],
+    bound: u64,
+    count: usize,
+) -> bool {
+    if !(1..=MAX_VARIANCE_VALUES).contains(&count) {
+        return false;
+    }
+    let Some(commitment_fr) = fr_from_commitment::<E::ScalarField>(commitment) else {
+        return false;
+    };
+    // Public input ordering matches generate_constraints
+    let mut inputs = vec![commitment_fr, E::ScalarField::from(bound)];
+    inputs.extend(is_real_flags(count).into_iter().map(E::ScalarField::from));
+    verify_with_setup(setup::<E>(SnarkCircuit::Variance), proof_data, &inputs)
+}
@@ -0,0 +1,265 @@
+//! Private voting: a voter proves their key is in the eligibility tree and that their sealed
+//! ballot selects exactly one of the election's options, without revealing which key or which
+//! option. A per-election nullifier lets tallies reject double votes without linking a vote to
+//! its voter.
+//!
+//! With `s` the voter's secret and `H` the Merkle node compression:
+//! - voter key `H(0, s)`, the leaf published in the eligibility tree;
+//! - nullifier `H(s, election_id)`, the same for every vote of one voter in one election;
+//! - ballot `H(choice, r)` for a random blinding `r`, opened only to the tallier.
+
+use super::merkle::{compress, compress_circuit, MerkleTree, MAX_MERKLE_SET_SIZE, MERKLE_DEPTH};
+use super::{
+    enforce_one_hot, fr_from_commitment, fr_to_commitment, prove_with_setup, setup,
+    verify_with_setup, MimcField, SnarkCircuit, SnarkEngine,
+};
+use crate::utils::error_handling::ZkpError;
+use ark_ff::{AdditiveGroup, PrimeField, UniformRand};
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::prelude::*;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystemRef, SynthesisError};
+use ark_std::rand::rngs::OsRng;
+use zeroize::Zeroizing;
+
+/// Largest number of options in one election.
+pub const MAX_VOTE_OPTIONS: usize = 16;
+
+/// Canonical form of an eligibility list: sorted, duplicates removed, non-empty and at most
+/// [`MAX_MERKLE_SET_SIZE`] keys. Leaf positions follow this order.
+pub fn normalize_voter_keys(mut keys: Vec<[u8; 32]>) -> Result<Vec<[u8; 32]>, ZkpError> {
+    keys.sort_unstable();
+    keys.dedup();
+    if keys.is_empty() {
+        return Err(ZkpError::InvalidInput(
+            "eligibility list cannot be empty".to_string(),
+        ));
+    }
+    if keys.len() > MAX_MERKLE_SET_SIZE {
+        return Err(ZkpError::InvalidInput(format!(
+            "{} voter keys exceed maximum {}",
+            keys.len(),
+            MAX_MERKLE_SET_SIZE
+        )));
+    }
+    Ok(keys)
+}
+
+/// A cast vote: the proof, its public values and the ballot's blinding.
+pub struct SnarkVote {
+    pub proof: Vec<u8>,
+    pub root: [u8; 32],
+    pub nullifier: [u8; 32],
+    pub ballot: [u8; 32],
+    pub blinding: [u8; 32],
+}
+
+// Proves: H(0, secret) lies under root AND nullifier == H(secret, election_id)
+//         AND sel is one-hot over the real options AND ballot == H(Σ i*sel[i], blinding)
+// Public inputs: root, election_id, nullifier, ballot, is_real[MAX_VOTE_OPTIONS]
+// Witness: secret, siblings[MERKLE_DEPTH], is_right[MERKLE_DEPTH], sel[MAX_VOTE_OPTIONS],
+//          blinding
+pub(super) struct VoteCircuit<F: PrimeField> {
+    // Secret witnesses: the path reveals the voter, the selection their choice.
+    secret: Zeroizing<Option<F>>,
+    siblings: Zeroizing<Vec<Option<F>>>,
+    is_right: Zeroizing<Vec<Option<bool>>>,
+    sel: Zeroizing<Vec<Option<bool>>>,
+    blinding: Zeroizing<Option<F>>,
+    root: Option<F>,
+    election_id: u64,
+    nullifier: Option<F>,
+    ballot: Option<F>,
+    is_real: Vec<bool>,
+}
+
+impl<F: MimcField> ConstraintSynthesizer<F> for VoteCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> Result<(), SynthesisError> {
+        let root_var = FpVar::<F>::new_input(cs.clone(), || {
+            self.root.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let election_var = FpVar::<F>::new_input(cs.clone(), || Ok(F::from(self.election_id)))?;
+        let nullifier_var = FpVar::<F>::new_input(cs.clone(), || {
+            self.nullifier.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        let ballot_var = FpVar::<F>::new_input(cs.clone(), || {
+            self.ballot.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        if self.is_real.len() != MAX_VOTE_OPTIONS {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let mut is_real_bools: Vec<Boolean<F>> = Vec::with_capacity(MAX_VOTE_OPTIONS);
+        for &b in self.is_real.iter() {
+            is_real_bools.push(Boolean::new_input(cs.clone(), || Ok(b))?);
+        }
+
+        let secret_var = FpVar::<F>::new_witness(cs.clone(), || {
+            self.secret.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+
+        // Eligibility: the voter key's path to the root
+        if self.siblings.len() != MERKLE_DEPTH || self.is_right.len() != MERKLE_DEPTH {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let mut node = compress_circuit(&FpVar::zero(), &secret_var)?;
+        for (sibling, is_right) in self.siblings.iter().zip(self.is_right.iter()) {
+            let sibling = FpVar::<F>::new_witness(cs.clone(), || {
+                sibling.ok_or(SynthesisError::AssignmentMissing)
+            })?;
+            let is_right = Boolean::new_witness(cs.clone(), || {
+                is_right.ok_or(SynthesisError::AssignmentMissing)
+            })?;
+            let left = is_right.select(&sibling, &node)?;
+            let right = is_right.select(&node, &sibling)?;
+            node = compress_circuit(&left, &right)?;
+        }
+        node.enforce_equal(&root_var)?;
+
+        compress_circuit(&secret_var, &election_var)?.enforce_equal(&nullifier_var)?;
+
+        // Exactly one real option, sealed in the ballot
+        if self.sel.len() != MAX_VOTE_OPTIONS {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+        let mut sel_bools: Vec<Boolean<F>> = Vec::with_capacity(MAX_VOTE_OPTIONS);
+        for &bit in self.sel.iter() {
+            sel_bools.push(Boolean::new_witness(cs.clone(), || {
+                bit.ok_or(SynthesisError::AssignmentMissing)
+            })?);
+        }
+        enforce_one_hot(&sel_bools, &is_real_bools)?;
+        let mut choice = FpVar::<F>::zero();
+        for (i, sel_i) in sel_bools.iter().enumerate() {
+            let sel_fp: FpVar<F> = sel_i.clone().into();
+            choice += sel_fp * F::from(i as u64);
+        }
+        let blinding_var = FpVar::<F>::new_witness(cs.clone(), || {
+            self.blinding.ok_or(SynthesisError::AssignmentMissing)
+        })?;
+        compress_circuit(&choice, &blinding_var)?.enforce_equal(&ballot_var)?;
+
+        Ok(())
+    }
+}
+
+pub(super) fn dummy_vote_circuit<F: PrimeField>() -> VoteCircuit<F> {
+    VoteCircuit {
+        secret: Zeroizing::new(Some(F::ZERO)),
+        siblings: Zeroizing::new(vec![Some(F::ZERO); MERKLE_DEPTH]),
+        is_right: Zeroizing::new(vec![Some(false); MERKLE_DEPTH]),
+        sel: Zeroizing::new(vec![Some(false); MAX_VOTE_OPTIONS]),
+        blinding: Zeroizing::new(Some(F::ZERO)),
+        root: Some(F::ZERO),
+        election_id: 0,
+        nullifier: Some(F::ZERO),
+        ballot: Some(F::ZERO),
+        is_real: vec![false; MAX_VOTE_OPTIONS],
+    }
+}
+
+fn is_real_flags(num_options: usize) -> Vec<bool> {
+    (0..MAX_VOTE_OPTIONS).map(|i| i < num_options).collect()
+}
+
+/// Voter key for a 32-byte secret (reduced into the scalar field) on curve `E`.
+pub(super) fn voter_key<E: SnarkEngine>(secret: &[u8; 32]) -> [u8; 32] {
+    let secret = Zeroizing::new(E::ScalarField::from_le_bytes_mod_order(secret));
+    fr_to_commitment(compress(E::ScalarField::ZERO, *secret))
+}
+
+fn eligibility_tree<E: SnarkEngine>(keys: &[[u8; 32]]) -> Option<MerkleTree<E::ScalarField>> {
+    let leaves = keys
+        .iter()
+        .map(|k| fr_from_commitment(k))
+        .collect::<Option<Vec<_>>>()?;
+    Some(MerkleTree::from_leaves(leaves))
+}
+
+/// Root over normalized `keys`; `None` if a key is not a field element of `E`.
+pub(super) fn eligibility_root<E: SnarkEngine>(keys: &[[u8; 32]]) -> Option<[u8; 32]> {
+    Some(fr_to_commitment(eligibility_tree::<E>(keys)?.root()))
+}
+
+/// Vote for option `choice` of `num_options`; `None` if the voter's key is not in the
+/// normalized `keys`, the choice is out of range or proving fails.
+pub(super) fn prove_vote<E: SnarkEngine>(
+    secret: &[u8; 32],
+    keys: &[[u8; 32]],
+    election_id: u64,
+    num_options: usize,
+    choice: usize,
+) -> Option<SnarkVote> {
+    if num_options > MAX_VOTE_OPTIONS || choice >= num_options {
+        return None;
+    }
+    let index = keys.binary_search(&voter_key::<E>(secret)).ok()?;
+    let tree = eligibility_tree::<E>(keys)?;
+    let (siblings, is_right) = tree.path(index);
+    let secret_fr = Zeroizing::new(E::ScalarField::from_le_bytes_mod_order(secret));
+    let blinding = Zeroizing::new(E::ScalarField::rand(&mut OsRng));
+    let nullifier = compress(*secret_fr, E::ScalarField::from(election_id));
+    let ballot = compress(E::ScalarField::from(choice as u64), *blinding);
+    let circuit = VoteCircuit {
+        secret: Zeroizing::new(Some(*secret_fr)),
+        siblings: Zeroizing::new(siblings.into_iter().map(Some).collect()),
+        is_right: Zeroizing::new(is_right.into_iter().map(Some).collect()),
+        sel: Zeroizing::new((0..MAX_VOTE_OPTIONS).map(|i| Some(i == choice)).collect()),
+        blinding: Zeroizing::new(Some(*blinding)),
+        root: Some(tree.root()),
+        election_id,
+        nullifier: Some(nullifier),
+        ballot: Some(ballot),
+        is_real: is_real_flags(num_options),
+    };
+    let proof = prove_with_setup(setup::<E>(SnarkCircuit::Vote), circuit);
+    (!proof.is_empty()).then(|| SnarkVote {
+        proof,
+        root: fr_to_commitment(tree.root()),
+        nullifier: fr_to_commitment(nullifier),
+        ballot: fr_to_commitment(ballot),
+        blinding: fr_to_commitment(*blinding),
+    })
+}
+
+pub(super) fn verify_vote<E: SnarkEngine>(
+    proof_data: &[u8],
+    root: &[u8],
+    election_id: u64,
+    nullifier: &[u8],
+    ballot: &[u8],
+    num_options: usize,
+) -> bool {
+    if !(1..=MAX_VOTE_OPTIONS).contains(&num_options) {
+        return false;
+    }
+    let (Some(root_fr), Some(nullifier_fr), Some(ballot_fr)) = (
+        fr_from_commitment::<E::ScalarField>(root),
+        fr_from_commitment(nullifier),
+        fr_from_commitment(ballot),
+    ) else {
+        return false;
+    };
+    // Public input ordering matches generate_constraints
+    let mut inputs = vec![
+        root_fr,
+        E::ScalarField::from(election_id),
+        nullifier_fr,
+        ballot_fr,
+    ];
+    inputs.extend(
+        is_real_flags(num_options)
+            .into_iter()
+            .map(E::ScalarField::from),
+    );
+    verify_with_setup(setup::<E>(SnarkCircuit::Vote), proof_data, &inputs)
+}
+
+/// Whether `ballot` seals option `choice` under `blinding`.
+pub(super) fn ballot_opens<E: SnarkEngine>(ballot: &[u8], choice: usize, blinding: &[u8]) -> bool {
+    let (Some(ballot_fr), Some(blinding_fr)) = (
+        fr_from_commitment::<E::ScalarField>(ballot),
+        fr_from_commitment(blinding),
+    ) else {
+        return false;
+    };
+    compress(E::ScalarField::from(choice as u64), blinding_fr) == ballot_fr
+}
@@ -186,6 +186,15 @@ impl StarkBackend {
     }
 
     fn verify_improvement(proof_data: &[u8], old: u64, new: u64) -> Result<bool, String> {
+        // The AIR only pins the boundary values, so reject a non-increasing claim up front
+        // instead of relying on the prover-side check.
+        if new <= old {
+            return Err(format!(
+                "improvement requires old < new (got old={}, new={})",
+                old, new
+            ));
+        }
+
         // Deserialize the proof
         let proof = Proof::from_bytes(proof_data)
             .map_err(|e| format!("failed to deserialize proof: {:?}", e))?;
@@ -264,4 +273,20 @@ mod tests {
         assert!(!proof.is_empty());
         assert!(StarkBackend::verify(&proof, &data));
     }
+
+    #[test]
+    fn stark_verify_rejects_non_increasing_claim() {
+        let mut data = Vec::with_capacity(16);
+        data.extend_from_slice(&5u64.to_le_bytes());
+        data.extend_from_slice(&10u64.to_le_bytes());
+        let proof = StarkBackend::prove(&data);
+        assert!(!proof.is_empty());
+
+        let mut reversed = Vec::with_capacity(16);
+        reversed.extend_from_slice(&10u64.to_le_bytes());
+        reversed.extend_from_slice(&5u64.to_le_bytes());
+        assert!(!StarkBackend::verify(&proof, &reversed));
+        assert!(StarkBackend::verify_improvement(&proof, 10, 5).is_err());
+        assert!(StarkBackend::verify_improvement(&proof, 7, 7).is_err());
+    }
 }
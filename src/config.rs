@@ -0,0 +1,129 @@
+//! One-time process configuration. [`configure`] installs every global setting (SNARK key
+//! directory, curve and proof compression, proof cache limits, batch store directory, rayon thread
+//! pool) in one call, and must run before the first proof: once any subsystem has initialized
+//! with its defaults, or after a previous successful `configure`, it returns
+//! [`ZkpError::ConfigError`].
+
+use crate::backend::snark;
+use crate::utils::error_handling::{ZkpError, ZkpResult};
+use crate::utils::performance;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Default [`LibzkpConfig::cache_max_size`], matching the lazily created cache.
+pub const DEFAULT_CACHE_MAX_SIZE: usize = 1000;
+/// Default [`LibzkpConfig::cache_ttl_seconds`], matching the lazily created cache.
+pub const DEFAULT_CACHE_TTL_SECONDS: u64 = 3600;
+
+/// Settings applied by [`configure`]. `None` keeps the existing behaviour for that subsystem
+/// (environment variables, rayon's default pool size).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LibzkpConfig {
+    /// Where SNARK proving/verifying keys are stored (see `set_snark_key_dir`).
+    pub snark_key_dir: Option<PathBuf>,
+    pub cache_max_size: usize,
+    pub cache_ttl_seconds: u64,
+    /// Batch store directory; requires the `batch-store` feature.
+    pub batch_store_dir: Option<PathBuf>,
+    /// Size of the global rayon pool; requires the `parallel` feature.
+    pub num_threads: Option<usize>,
+    /// Serialize new Groth16 proofs compressed (see `set_snark_proof_compression`).
+    pub compress_snark_proofs: bool,
+    /// Pairing curve for SNARK proofs (see `set_snark_curve`).
+    pub snark_curve: snark::SnarkCurve,
+}
+
+impl Default for LibzkpConfig {
+    fn default() -> Self {
+        Self {
+            snark_key_dir: None,
+            cache_max_size: DEFAULT_CACHE_MAX_SIZE,
+            cache_ttl_seconds: DEFAULT_CACHE_TTL_SECONDS,
+            batch_store_dir: None,
+            num_threads: None,
+            compress_snark_proofs: false,
+            snark_curve: snark::SnarkCurve::Bn254,
+        }
+    }
+}
+
+/// Whether [`configure`] has completed; the lock also serializes concurrent calls.
+static CONFIGURED: Mutex<bool> = Mutex::new(false);
+
+/// Apply `config` to every subsystem. Call once, before any proving or cache use. Every field
+/// is checked before anything is applied, so a rejected config leaves the process unconfigured.
+pub fn configure(config: LibzkpConfig) -> ZkpResult<()> {
+    let mut configured = CONFIGURED
+        .lock()
+        .map_err(|_| ZkpError::ConfigError("configuration lock poisoned".to_string()))?;
+    if *configured {
+        return Err(ZkpError::ConfigError(
+            "libzkp is already configured; configure may only be called once".to_string(),
+        ));
+    }
+    if snark::is_snark_initialized() || performance::global_state_initialized() {
+        return Err(ZkpError::ConfigError(
+            "libzkp is already in use; call configure before any proving".to_string(),
+        ));
+    }
+    validate(&config)?;
+
+    // rayon cannot say whether its global pool exists without building it, so this goes first.
+    #[cfg(feature = "parallel")]
+    if let Some(n) = config.num_threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(n)
+            .build_global()
+            .map_err(|e| ZkpError::ConfigError(format!("thread pool: {}", e)))?;
+    }
+    snark::set_snark_curve(config.snark_curve)?;
+    if let Some(dir) = &config.snark_key_dir {
+        snark::set_snark_key_dir(&dir.to_string_lossy())?;
+    }
+    #[cfg(feature = "batch-store")]
+    if let Some(dir) = &config.batch_store_dir {
+        crate::advanced::batch_store::set_batch_store_dir(dir)?;
+    }
+    performance::init_global_state(config.cache_max_size, config.cache_ttl_seconds)?;
+    snark::set_proof_compression(config.compress_snark_proofs);
+
+    *configured = true;
+    Ok(())
+}
+
+/// Whether [`configure`] has completed successfully in this process.
+pub fn is_configured() -> bool {
+    CONFIGURED.lock().map(|c| *c).unwrap_or(false)
+}
+
+fn validate(config: &LibzkpConfig) -> ZkpResult<()> {
+    if config.cache_max_size == 0 {
+        return Err(ZkpError::ConfigError(
+            "cache_max_size must be positive".to_string(),
+        ));
+    }
+    if config.num_threads == Some(0) {
+        return Err(ZkpError::ConfigError(
+            "num_threads must be positive".to_string(),
+        ));
+    }
+    if cfg!(not(feature = "parallel")) && config.num_threads.is_some() {
+        return Err(ZkpError::ConfigError(
+            "num_threads requires the `parallel` feature".to_string(),
+        ));
+    }
+    if cfg!(not(feature = "batch-store")) && config.batch_store_dir.is_some() {
+        return Err(ZkpError::ConfigError(
+            "batch_store_dir requires the `batch-store` feature".to_string(),
+        ));
+    }
+    snark::check_snark_curve(config.snark_curve)?;
+    if let Some(dir) = &config.snark_key_dir {
+        snark::check_snark_key_dir(&dir.to_string_lossy())?;
+    }
+    #[cfg(feature = "batch-store")]
+    if let Some(dir) = &config.batch_store_dir {
+        crate::advanced::batch_store::create_batch_store_dir(dir)?;
+    }
+    Ok(())
+}
@@ -1,8 +1,11 @@
 pub mod advanced;
 pub mod backend;
+pub mod config;
 pub mod proof;
 pub mod utils;
 
+pub use config::{configure, LibzkpConfig};
+
 #[cfg(feature = "python")]
 mod python_api;
 
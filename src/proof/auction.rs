@@ -0,0 +1,33 @@
+//! Sealed-bid auctions: a bidder proves their hidden bid is acceptable before it is revealed.
+//! A bid proof is a keyed range proof from [`crate::proof::range_proof`]; its Pedersen
+//! commitment seals the bid, and the returned blinding opens it when bids are revealed.
+
+use crate::proof::range_proof::{prove_range_keyed, verify_and_extract};
+use crate::utils::commitment::{blinding_from_slice, commit_value_pedersen};
+use crate::utils::error_handling::ZkpResult;
+use crate::utils::proof_helpers::parse_and_validate_proof;
+
+const SCHEME_ID: u8 = 1;
+
+/// Prove a hidden `bid` is at least `min_increment`, the lowest acceptable bid (the current
+/// price plus the auction's increment), and at most the bidder's public `budget`. Returns the
+/// proof and the blinding that opens its commitment (see [`open_bid`]).
+pub fn prove_bid_validity(
+    bid: u64,
+    budget: u64,
+    min_increment: u64,
+) -> ZkpResult<(Vec<u8>, [u8; 32])> {
+    prove_range_keyed(bid, min_increment, budget)
+}
+
+pub fn verify_bid_validity(proof: Vec<u8>, budget: u64, min_increment: u64) -> bool {
+    verify_and_extract(proof, min_increment, budget).is_some()
+}
+
+/// Whether the proof's sealed bid is `bid` under `blinding`. Checks the opening only; verify
+/// the bid itself with [`verify_bid_validity`].
+pub fn open_bid(proof: &[u8], bid: u64, blinding: &[u8]) -> ZkpResult<bool> {
+    let proof = parse_and_validate_proof(proof, SCHEME_ID)?;
+    let blinding = blinding_from_slice(blinding)?;
+    Ok(commit_value_pedersen(bid, &blinding)? == proof.commitment)
+}
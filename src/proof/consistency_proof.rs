@@ -1,12 +1,13 @@
 use crate::backend::bulletproofs::BulletproofsBackend;
-use crate::utils::error_handling::ZkpResult;
+use crate::utils::error_handling::{ZkpError, ZkpResult};
 use crate::utils::proof_helpers::{
     create_proof, extract_bulletproofs_components, parse_and_validate_proof,
     reconstruct_bulletproofs_proof,
 };
-use crate::utils::validation::validate_consistency_params;
+use crate::utils::validation::{validate_bounded_consistency_params, validate_consistency_params};
 
 const SCHEME_ID: u8 = 6;
+const BOUNDED_CONSISTENCY_SCHEME_ID: u8 = 14;
 
 pub fn prove_consistency(data: Vec<u64>) -> ZkpResult<Vec<u8>> {
     validate_consistency_params(&data)?;
@@ -28,3 +29,65 @@ pub fn verify_consistency(proof: Vec<u8>) -> bool {
     let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
     BulletproofsBackend::verify_consistency(&backend_proof)
 }
+
+/// The `prefix_digest` for [`verify_consistency_suffix`]: SHA-256 of the first
+/// `through_index + 1` value commitments of a consistency proof (its commitment when
+/// `through_index` is the last index). Does not verify the proof; do that first.
+pub fn consistency_prefix_digest(proof: Vec<u8>, through_index: usize) -> ZkpResult<Vec<u8>> {
+    let proof = parse_and_validate_proof(&proof, SCHEME_ID)?;
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::consistency_prefix_digest(&backend_proof, through_index)
+        .map(|d| d.to_vec())
+        .ok_or_else(|| {
+            ZkpError::InvalidInput(format!(
+                "through_index {} is not an index of the proof's values",
+                through_index
+            ))
+        })
+}
+
+/// Verify only the steps from `data[from_index]` onward, for append-only logs whose prefix
+/// `data[..=from_index]` was already checked with [`verify_consistency`]. `prefix_digest` comes
+/// from [`consistency_prefix_digest`] on that verified proof; the proof is rejected unless its
+/// first `from_index + 1` value commitments are exactly the verified ones. `from_index` must be below the number
+/// of values; `n - 1` checks no steps.
+pub fn verify_consistency_suffix(
+    proof: Vec<u8>,
+    from_index: usize,
+    prefix_digest: Vec<u8>,
+) -> bool {
+    let proof = match parse_and_validate_proof(&proof, SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_consistency_from(&backend_proof, from_index, &prefix_digest)
+}
+
+/// Prove `data` is non-decreasing with every step `data[i] - data[i-1]` in `[0, max_step]`,
+/// e.g. "readings never jump by more than 10". `max_step` is public and bound into the proof.
+pub fn prove_bounded_consistency(data: Vec<u64>, max_step: u64) -> ZkpResult<Vec<u8>> {
+    validate_bounded_consistency_params(&data, max_step)?;
+
+    let backend_proof = BulletproofsBackend::prove_bounded_consistency(&data, max_step)
+        .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(
+        BOUNDED_CONSISTENCY_SCHEME_ID,
+        proof_bytes,
+        commitment,
+    ))
+}
+
+pub fn verify_bounded_consistency(proof: Vec<u8>, max_step: u64) -> bool {
+    let proof = match parse_and_validate_proof(&proof, BOUNDED_CONSISTENCY_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_bounded_consistency(&backend_proof, max_step)
+}
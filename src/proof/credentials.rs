@@ -0,0 +1,278 @@
+//! Issuer-committed attribute credentials. An issuer commits to named `u64` attributes and
+//! hands the openings to the holder, who later proves statements about single attributes (a
+//! range over `age`, membership of `country` in a set) against the issuer's published
+//! commitment without revealing the other attributes.
+//!
+//! Each attribute is a leaf `SHA-256(name || pedersen)` over the value's Pedersen commitment;
+//! the credential commitment hashes the leaves in name order. A presentation discloses the
+//! proven attribute's Pedersen commitment and only the digests of the other leaves, and both
+//! range and membership proofs are made against that commitment, so the value and its blinding
+//! never leave the holder. The commitment is not signed: verifiers must obtain it from the
+//! issuer over an authenticated channel.
+
+use crate::backend::bulletproofs::{scalar_from_canonical, BulletproofsBackend};
+use crate::proof::range_proof::{prove_range_with_blinding, verify_and_extract};
+use crate::utils::commitment::{commit_value_pedersen, random_pedersen_blinding};
+use crate::utils::encoding::{read_length_prefixed_u32, read_u64_le};
+use crate::utils::error_handling::{ZkpError, ZkpResult};
+use crate::utils::limits::{MAX_CREDENTIAL_ATTRIBUTES, MAX_U64_VEC_LEN};
+use crate::utils::validation::validate_credential_attributes;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use zeroize::{Zeroize, Zeroizing};
+
+const LEAF_TAG: &[u8] = b"libzkp_credential_leaf";
+const COMMITMENT_TAG: &[u8] = b"libzkp_credential";
+
+/// Opening of one attribute: the value and its Pedersen blinding.
+#[derive(Clone)]
+struct Attribute {
+    value: u64,
+    blinding: [u8; 32],
+}
+
+impl Attribute {
+    fn pedersen(&self) -> ZkpResult<Vec<u8>> {
+        commit_value_pedersen(self.value, &self.blinding)
+    }
+}
+
+fn attribute_leaf(name: &str, pedersen: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(LEAF_TAG);
+    hasher.update((name.len() as u32).to_le_bytes());
+    hasher.update(name.as_bytes());
+    hasher.update(pedersen);
+    hasher.finalize().into()
+}
+
+/// `set` sorted and deduplicated for a membership presentation: 1 to `MAX_U64_VEC_LEN`
+/// distinct elements, each a branch of the OR proof.
+fn membership_set(mut set: Vec<u64>) -> ZkpResult<Vec<u64>> {
+    set.sort_unstable();
+    set.dedup();
+    if set.is_empty() || set.len() > MAX_U64_VEC_LEN {
+        return Err(ZkpError::InvalidInput(format!(
+            "membership set must have 1 to {} distinct elements",
+            MAX_U64_VEC_LEN
+        )));
+    }
+    Ok(set)
+}
+
+/// Commitment over concatenated 32-byte leaves.
+fn commitment_over(leaves: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(COMMITMENT_TAG);
+    hasher.update(((leaves.len() / 32) as u32).to_le_bytes());
+    hasher.update(leaves);
+    hasher.finalize().into()
+}
+
+/// A credential as held by its holder: every attribute with its openings. Store it like the
+/// attribute values themselves ([`Credential::to_bytes`]); anyone holding it can produce
+/// presentations. Secrets are zeroized on drop.
+#[derive(Clone)]
+pub struct Credential {
+    attributes: BTreeMap<String, Attribute>,
+}
+
+impl Drop for Credential {
+    fn drop(&mut self) {
+        for attribute in self.attributes.values_mut() {
+            attribute.value.zeroize();
+            attribute.blinding.zeroize();
+        }
+    }
+}
+
+impl Credential {
+    /// Issuer side: commit to `attributes` under fresh blindings. Publish [`Self::commitment`]
+    /// and hand the credential to the holder.
+    pub fn issue(attributes: BTreeMap<String, u64>) -> ZkpResult<Self> {
+        validate_credential_attributes(attributes.keys().map(String::as_str))?;
+        let attributes = attributes
+            .into_iter()
+            .map(|(name, value)| {
+                let attribute = Attribute {
+                    value,
+                    blinding: random_pedersen_blinding(),
+                };
+                (name, attribute)
+            })
+            .collect();
+        Ok(Self { attributes })
+    }
+
+    /// Attribute names in commitment order.
+    pub fn attribute_names(&self) -> impl Iterator<Item = &str> {
+        self.attributes.keys().map(String::as_str)
+    }
+
+    fn attribute(&self, name: &str) -> ZkpResult<&Attribute> {
+        self.attributes.get(name).ok_or_else(|| {
+            ZkpError::InvalidInput(format!("credential has no attribute {:?}", name))
+        })
+    }
+
+    /// Leaves in name order, concatenated.
+    fn leaves(&self) -> ZkpResult<Vec<u8>> {
+        let mut leaves = Vec::with_capacity(self.attributes.len() * 32);
+        for (name, attribute) in &self.attributes {
+            leaves.extend_from_slice(&attribute_leaf(name, &attribute.pedersen()?));
+        }
+        Ok(leaves)
+    }
+
+    /// The 32-byte commitment the issuer publishes and verifiers check presentations against.
+    pub fn commitment(&self) -> ZkpResult<[u8; 32]> {
+        Ok(commitment_over(&self.leaves()?))
+    }
+
+    /// `[u32 n][leaves 32n][pedersen 32]`.
+    fn disclosure(&self, name: &str) -> ZkpResult<(Vec<u8>, &Attribute)> {
+        let attribute = self.attribute(name)?;
+        let leaves = self.leaves()?;
+        let mut out = Vec::with_capacity(4 + leaves.len() + 32);
+        out.extend_from_slice(&(self.attributes.len() as u32).to_le_bytes());
+        out.extend_from_slice(&leaves);
+        out.extend_from_slice(&attribute.pedersen()?);
+        Ok((out, attribute))
+    }
+
+    /// Prove attribute `name` lies in `[min, max]`. The presentation verifies with
+    /// [`verify_attribute_range`].
+    pub fn prove_range(&self, name: &str, min: u64, max: u64) -> ZkpResult<Vec<u8>> {
+        let (mut presentation, attribute) = self.disclosure(name)?;
+        let proof = prove_range_with_blinding(attribute.value, min, max, &attribute.blinding)?;
+        presentation.extend_from_slice(&proof);
+        Ok(presentation)
+    }
+
+    /// Prove attribute `name` is in `set` (up to `MAX_U64_VEC_LEN` distinct elements) with an
+    /// OR proof over the disclosed Pedersen commitment, 64 bytes per distinct element. The
+    /// presentation verifies with [`verify_attribute_membership`].
+    pub fn prove_membership(&self, name: &str, set: Vec<u64>) -> ZkpResult<Vec<u8>> {
+        let set = membership_set(set)?;
+        let (mut presentation, attribute) = self.disclosure(name)?;
+        let blinding = scalar_from_canonical(&attribute.blinding)
+            .map(Zeroizing::new)
+            .ok_or_else(|| ZkpError::InvalidInput("blinding is not a canonical scalar".into()))?;
+        let proof =
+            BulletproofsBackend::prove_committed_membership(attribute.value, &blinding, &set)
+                .map_err(ZkpError::InvalidInput)?;
+        presentation.extend_from_slice(&proof);
+        Ok(presentation)
+    }
+
+    /// `[u32 n]` then per attribute `[u32 name_len][name][value 8][blinding 32]`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&(self.attributes.len() as u32).to_le_bytes());
+        for (name, attribute) in &self.attributes {
+            out.extend_from_slice(&(name.len() as u32).to_le_bytes());
+            out.extend_from_slice(name.as_bytes());
+            out.extend_from_slice(&attribute.value.to_le_bytes());
+            out.extend_from_slice(&attribute.blinding);
+        }
+        out
+    }
+
+    pub fn from_bytes(data: &[u8]) -> ZkpResult<Self> {
+        let invalid = |what: &str| ZkpError::SerializationError(format!("credential: {}", what));
+        let count = data
+            .get(..4)
+            .and_then(|b| b.try_into().ok())
+            .map(u32::from_le_bytes)
+            .ok_or_else(|| invalid("missing attribute count"))? as usize;
+        if count > MAX_CREDENTIAL_ATTRIBUTES {
+            return Err(invalid("too many attributes"));
+        }
+
+        let mut reader = &data[4..];
+        let mut attributes = BTreeMap::new();
+        for _ in 0..count {
+            let name = read_length_prefixed_u32(&mut reader)
+                .and_then(|b| std::str::from_utf8(b).ok())
+                .ok_or_else(|| invalid("invalid attribute name"))?;
+            let opening = reader
+                .get(..40)
+                .ok_or_else(|| invalid("truncated attribute"))?;
+            let attribute = Attribute {
+                value: read_u64_le(opening, 0).ok_or_else(|| invalid("truncated attribute"))?,
+                blinding: opening[8..40].try_into().expect("32-byte slice"),
+            };
+            reader = &reader[40..];
+            if scalar_from_canonical(&attribute.blinding).is_none() {
+                return Err(invalid("blinding is not a canonical scalar"));
+            }
+            if attributes.insert(name.to_string(), attribute).is_some() {
+                return Err(invalid("duplicate attribute name"));
+            }
+        }
+        if !reader.is_empty() {
+            return Err(invalid("trailing bytes"));
+        }
+        validate_credential_attributes(attributes.keys().map(String::as_str))?;
+        Ok(Self { attributes })
+    }
+}
+
+/// A parsed presentation: the leaves, the disclosed Pedersen commitment and the inner proof.
+struct Presentation<'a> {
+    leaves: &'a [u8],
+    pedersen: &'a [u8],
+    proof: &'a [u8],
+}
+
+impl<'a> Presentation<'a> {
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        let count = u32::from_le_bytes(data.get(..4)?.try_into().ok()?) as usize;
+        if count == 0 || count > MAX_CREDENTIAL_ATTRIBUTES {
+            return None;
+        }
+        let leaves_end = 4 + 32 * count;
+        Some(Self {
+            leaves: data.get(4..leaves_end)?,
+            pedersen: data.get(leaves_end..leaves_end + 32)?,
+            proof: &data[leaves_end + 32..],
+        })
+    }
+
+    /// The disclosed attribute is one of the leaves and the leaves hash to `commitment`.
+    fn matches(&self, commitment: &[u8], name: &str) -> bool {
+        let leaf = attribute_leaf(name, self.pedersen);
+        self.leaves.chunks_exact(32).any(|l| l == leaf)
+            && commitment_over(self.leaves) == commitment
+    }
+}
+
+/// Verify a presentation from [`Credential::prove_range`] against the issuer's commitment.
+pub fn verify_attribute_range(
+    presentation: Vec<u8>,
+    commitment: &[u8],
+    name: &str,
+    min: u64,
+    max: u64,
+) -> bool {
+    let Some(p) = Presentation::parse(&presentation) else {
+        return false;
+    };
+    p.matches(commitment, name)
+        && verify_and_extract(p.proof.to_vec(), min, max).is_some_and(|c| c == p.pedersen)
+}
+
+/// Verify a presentation from [`Credential::prove_membership`] against the issuer's
+/// commitment. Order and duplicates in `set` do not matter.
+pub fn verify_attribute_membership(
+    presentation: Vec<u8>,
+    commitment: &[u8],
+    name: &str,
+    set: Vec<u64>,
+) -> bool {
+    let (Some(p), Ok(set)) = (Presentation::parse(&presentation), membership_set(set)) else {
+        return false;
+    };
+    p.matches(commitment, name)
+        && BulletproofsBackend::verify_committed_membership(p.pedersen, &set, p.proof)
+}
@@ -1,11 +1,19 @@
+use crate::backend::bulletproofs::{scalars_from_canonical, BulletproofsBackend};
 use crate::backend::snark::SnarkBackend;
+use crate::backend::BackendKind;
 use crate::proof::Proof;
 use crate::utils::commitment::commit_value_snark;
-use crate::utils::error_handling::ZkpResult;
-use crate::utils::proof_helpers::{parse_and_validate_proof, validate_standard_commitment};
+use crate::utils::error_handling::{ZkpError, ZkpResult};
+use crate::utils::proof_helpers::{
+    create_proof, extract_bulletproofs_components, parse_and_validate_proof,
+    reconstruct_bulletproofs_proof, validate_standard_commitment,
+};
 use crate::utils::validation::validate_equality_params;
 
 const SCHEME_ID: u8 = 2;
+const DIFFERENCE_SCHEME_ID: u8 = 26;
+#[cfg(feature = "halo2")]
+const HALO2_SCHEME_ID: u8 = 29;
 
 pub fn prove_equality(val1: u64, val2: u64) -> ZkpResult<Vec<u8>> {
     validate_equality_params(val1, val2)?;
@@ -59,3 +67,104 @@ pub fn verify_equality(proof: Vec<u8>, val1: u64, val2: u64) -> bool {
 pub fn verify_equality_with_commitment(proof: Vec<u8>, expected_commitment: Vec<u8>) -> bool {
     verify_equality_inner(proof, expected_commitment)
 }
+
+/// Equality proof on a chosen backend. `Snark` is [`prove_equality`] (smallest proof, needs a
+/// Groth16 setup); `Halo2` needs no trusted setup and publishes `Halo2Backend::commit_value`
+/// under a fresh blinding that is then discarded. Bulletproofs has no equality protocol, so it
+/// is rejected.
+pub fn prove_equality_with_backend(
+    val1: u64,
+    val2: u64,
+    backend: BackendKind,
+) -> ZkpResult<Vec<u8>> {
+    backend.ensure_available()?;
+    match backend {
+        BackendKind::Snark => prove_equality(val1, val2),
+        #[cfg(feature = "halo2")]
+        BackendKind::Halo2 => {
+            use crate::backend::halo2::Halo2Backend;
+            use zeroize::Zeroizing;
+
+            validate_equality_params(val1, val2)?;
+            let blinding = Zeroizing::new(Halo2Backend::random_blinding());
+            let halo2_proof = Halo2Backend::prove_equality(val1, val2, &blinding);
+            let commitment = Halo2Backend::commit_value(val1, &blinding);
+            match commitment {
+                Some(commitment) if !halo2_proof.is_empty() => Ok(create_proof(
+                    HALO2_SCHEME_ID,
+                    halo2_proof,
+                    commitment.to_vec(),
+                )),
+                _ => Err(ZkpError::ProofGenerationFailed(
+                    "Halo2 equality proof generation failed".to_string(),
+                )),
+            }
+        }
+        _ => Err(backend.unsupported("equality")),
+    }
+}
+
+/// Verify a proof from [`prove_equality_with_backend`] made on `backend`. The Halo2
+/// commitment is blinded, so it cannot be recomputed from `val1`: that proof is checked
+/// against the commitment it carries.
+pub fn verify_equality_with_backend(
+    proof: Vec<u8>,
+    val1: u64,
+    val2: u64,
+    backend: BackendKind,
+) -> bool {
+    match backend {
+        BackendKind::Snark => verify_equality(proof, val1, val2),
+        #[cfg(feature = "halo2")]
+        BackendKind::Halo2 => {
+            use crate::utils::proof_helpers::verify_proof_cryptographic;
+
+            if val1 != val2 {
+                return false;
+            }
+            match parse_and_validate_proof(&proof, HALO2_SCHEME_ID) {
+                Ok(p) => verify_proof_cryptographic(&p),
+                Err(_) => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Prove that the hidden values under the Pedersen commitments `commit_a` and `commit_b`
+/// differ by exactly `diff` (`a - b == diff`), given both blindings `[r_a, r_b]`. Errors if
+/// the commitments do not open to such values, including when `b > a`.
+pub fn prove_difference(
+    commit_a: &[u8],
+    commit_b: &[u8],
+    diff: u64,
+    blindings: &[[u8; 32]; 2],
+) -> ZkpResult<Vec<u8>> {
+    let scalars = scalars_from_canonical(blindings)
+        .ok_or_else(|| ZkpError::InvalidInput("blinding is not a canonical scalar".into()))?;
+
+    let backend_proof = BulletproofsBackend::prove_difference([commit_a, commit_b], diff, &scalars)
+        .map_err(ZkpError::InvalidInput)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(DIFFERENCE_SCHEME_ID, proof_bytes, commitment))
+}
+
+/// Verify a difference proof against both published commitments and the public `diff`.
+pub fn verify_difference(proof: Vec<u8>, commit_a: &[u8], commit_b: &[u8], diff: u64) -> bool {
+    let proof = match parse_and_validate_proof(&proof, DIFFERENCE_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+    if proof.commitment != commit_a || proof.proof.get(0..32) != Some(commit_b) {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_difference(&backend_proof, diff)
+}
@@ -1,12 +1,23 @@
+use crate::backend::bulletproofs::{
+    scalar_from_canonical, scalars_from_canonical, BulletproofsBackend,
+};
 use crate::backend::{stark::StarkBackend, ZkpBackend};
 use crate::proof::Proof;
 use crate::utils::commitment::{commit_improvement, validate_improvement_commitment};
+use crate::utils::encoding::read_u64_le;
 use crate::utils::error_handling::{ZkpError, ZkpResult};
-use crate::utils::proof_helpers::parse_and_validate_proof;
-use crate::utils::validation::validate_improvement_params;
+use crate::utils::proof_helpers::{
+    create_proof, extract_bulletproofs_components, parse_and_validate_proof,
+    reconstruct_bulletproofs_proof, validate_standard_commitment,
+};
+use crate::utils::validation::{validate_balance_update_params, validate_improvement_params};
 
 const SCHEME_ID: u8 = 5;
+const EXACT_DELTA_SCHEME_ID: u8 = 19;
+const BALANCE_UPDATE_SCHEME_ID: u8 = 38;
 
+/// Prove `new > old`. The payload carries `old` and `new`, bound by the SHA-256 commitment and
+/// the STARK public inputs, so [`verify_improvement_enveloped`] needs no external parameters.
 pub fn prove_improvement(old: u64, new: u64) -> ZkpResult<Vec<u8>> {
     validate_improvement_params(old, new)?;
 
@@ -35,33 +46,141 @@ pub fn prove_improvement(old: u64, new: u64) -> ZkpResult<Vec<u8>> {
 }
 
 pub fn verify_improvement(proof: Vec<u8>, old: u64) -> bool {
-    let proof = match parse_and_validate_proof(&proof, SCHEME_ID) {
+    matches!(verify_improvement_enveloped(proof), Some((stored_old, _)) if stored_old == old)
+}
+
+/// Verify an improvement proof using only the values embedded in it and return the proven
+/// `(old, new)` pair, or `None` if the envelope, the commitment or the STARK proof is invalid.
+pub fn verify_improvement_enveloped(proof: Vec<u8>) -> Option<(u64, u64)> {
+    let proof = parse_and_validate_proof(&proof, SCHEME_ID).ok()?;
+
+    let old = read_u64_le(&proof.proof, 0)?;
+    let new = read_u64_le(&proof.proof, 8)?;
+
+    validate_improvement_commitment(&proof.commitment, old, new).ok()?;
+
+    let mut data = Vec::new();
+    data.extend_from_slice(&old.to_le_bytes());
+    data.extend_from_slice(&new.to_le_bytes());
+
+    StarkBackend::verify(&proof.proof[16..], &data).then_some((old, new))
+}
+
+/// Verify an audit trail of one metric: `proofs[i]` must prove `values[i] -> values[i + 1]`,
+/// so there is exactly one proof per consecutive pair and `values` is strictly increasing.
+/// Missing, extra or reordered proofs fail. Needs at least two values.
+pub fn verify_history(proofs: Vec<Vec<u8>>, values: Vec<u64>) -> bool {
+    if values.len() < 2 || proofs.len() != values.len() - 1 {
+        return false;
+    }
+    if values.windows(2).any(|w| w[0] >= w[1]) {
+        return false;
+    }
+    proofs
+        .into_iter()
+        .zip(values.windows(2))
+        .all(|(proof, w)| verify_improvement_enveloped(proof) == Some((w[0], w[1])))
+}
+
+/// Prove the Pedersen commitments satisfy `new == old + delta` (e.g. a balance update by a
+/// hidden amount) without revealing any of the three values. Commit with
+/// `commit_value_pedersen` / `commit_delta_pedersen`; `blindings` are `[old, new, delta]`.
+/// The delta may be negative, and the relation holds modulo the group order, so pair it with
+/// a range proof on `new` where wrap-around matters. Errors if the commitments do not satisfy
+/// the relation under the given blindings.
+pub fn prove_exact_delta(
+    old_commitment: &[u8],
+    new_commitment: &[u8],
+    delta_commitment: &[u8],
+    blindings: &[[u8; 32]; 3],
+) -> ZkpResult<Vec<u8>> {
+    let scalars = scalars_from_canonical(blindings)
+        .ok_or_else(|| ZkpError::InvalidInput("blinding is not a canonical scalar".into()))?;
+
+    let backend_proof = BulletproofsBackend::prove_exact_delta(
+        [old_commitment, new_commitment, delta_commitment],
+        &scalars,
+    )
+    .map_err(ZkpError::InvalidInput)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(EXACT_DELTA_SCHEME_ID, proof_bytes, commitment))
+}
+
+/// Verify an exact-delta proof against the three published commitments.
+pub fn verify_exact_delta(
+    proof: Vec<u8>,
+    old_commitment: &[u8],
+    new_commitment: &[u8],
+    delta_commitment: &[u8],
+) -> bool {
+    let proof = match parse_and_validate_proof(&proof, EXACT_DELTA_SCHEME_ID) {
         Ok(p) => p,
         Err(_) => return false,
     };
 
-    if proof.proof.len() < 16 {
+    if validate_standard_commitment(&proof.commitment).is_err() {
         return false;
     }
-    let stored_old = match proof.proof[0..8].try_into() {
-        Ok(arr) => u64::from_le_bytes(arr),
-        Err(_) => return false,
-    };
-    if stored_old != old {
+    if proof.commitment != new_commitment
+        || proof.proof.get(0..32) != Some(old_commitment)
+        || proof.proof.get(32..64) != Some(delta_commitment)
+    {
         return false;
     }
-    let new = match proof.proof[8..16].try_into() {
-        Ok(arr) => u64::from_le_bytes(arr),
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_exact_delta(&backend_proof)
+}
+
+/// Prove that the hidden balance `old`, committed as `commit_value_pedersen(old, old_blinding)`,
+/// minus the public `amount` leaves a non-negative new balance, committed under a fresh
+/// blinding. The proof's commitment is the new balance's; the returned blinding opens it and
+/// is the `old_blinding` of the next update.
+pub fn prove_balance_update(
+    old: u64,
+    old_blinding: &[u8; 32],
+    amount: u64,
+) -> ZkpResult<(Vec<u8>, [u8; 32])> {
+    validate_balance_update_params(old, amount)?;
+    let old_blinding = scalar_from_canonical(old_blinding)
+        .ok_or_else(|| ZkpError::InvalidInput("blinding is not a canonical scalar".into()))?;
+
+    let (backend_proof, new_blinding) =
+        BulletproofsBackend::prove_balance_update(old, &old_blinding, amount)
+            .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok((
+        create_proof(BALANCE_UPDATE_SCHEME_ID, proof_bytes, commitment),
+        new_blinding.to_bytes(),
+    ))
+}
+
+/// Verify a balance update from `old_commitment` to `new_commitment` by the public `amount`.
+pub fn verify_balance_update(
+    proof: Vec<u8>,
+    old_commitment: &[u8],
+    new_commitment: &[u8],
+    amount: u64,
+) -> bool {
+    let proof = match parse_and_validate_proof(&proof, BALANCE_UPDATE_SCHEME_ID) {
+        Ok(p) => p,
         Err(_) => return false,
     };
 
-    if validate_improvement_commitment(&proof.commitment, old, new).is_err() {
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+    if proof.commitment != new_commitment
+        || proof.proof.get(0..32) != Some(old_commitment)
+        || read_u64_le(&proof.proof, 32) != Some(amount)
+    {
         return false;
     }
 
-    let mut data = Vec::new();
-    data.extend_from_slice(&old.to_le_bytes());
-    data.extend_from_slice(&new.to_le_bytes());
-
-    StarkBackend::verify(&proof.proof[16..], &data)
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_balance_update(&backend_proof)
 }
@@ -0,0 +1,84 @@
+use crate::backend::bulletproofs::{scalars_from_canonical, BulletproofsBackend};
+use crate::utils::error_handling::{ZkpError, ZkpResult};
+use crate::utils::proof_helpers::{
+    create_proof, extract_bulletproofs_components, parse_and_validate_proof,
+    reconstruct_bulletproofs_proof, validate_standard_commitment,
+};
+use crate::utils::validation::{validate_greater_than, validate_inequality_params};
+
+const SCHEME_ID: u8 = 31;
+const GREATER_THAN_SCHEME_ID: u8 = 32;
+
+/// Prove that two values committed as `commit_value_pedersen(val1, r_1)` and
+/// `commit_value_pedersen(val2, r_2)` differ without revealing either, given `blindings`
+/// `[r_1, r_2]`. The proof carries the commitment to `val1` as its commitment and the one to
+/// `val2` in its payload.
+pub fn prove_inequality(val1: u64, val2: u64, blindings: &[[u8; 32]; 2]) -> ZkpResult<Vec<u8>> {
+    validate_inequality_params(val1, val2)?;
+    let scalars = scalars_from_canonical(blindings)
+        .ok_or_else(|| ZkpError::InvalidInput("blinding is not a canonical scalar".into()))?;
+
+    let backend_proof = BulletproofsBackend::prove_inequality(val1, val2, &scalars)
+        .map_err(ZkpError::InvalidInput)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(SCHEME_ID, proof_bytes, commitment))
+}
+
+/// Verify an inequality proof against both published commitments.
+pub fn verify_inequality(proof: Vec<u8>, commit_a: &[u8], commit_b: &[u8]) -> bool {
+    let proof = match parse_and_validate_proof(&proof, SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+    if proof.commitment != commit_a || proof.proof.get(0..32) != Some(commit_b) {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_inequality(&backend_proof)
+}
+
+/// Prove `a > b` for values committed as `commit_value_pedersen(a, r_a)` and
+/// `commit_value_pedersen(b, r_b)`, given `blindings` `[r_a, r_b]`, without revealing either
+/// value (`a`'s commitment is the proof commitment, `b`'s is in the payload). Unlike
+/// `prove_improvement`, no value appears in the proof.
+pub fn prove_greater_than(a: u64, b: u64, blindings: &[[u8; 32]; 2]) -> ZkpResult<Vec<u8>> {
+    validate_greater_than(a, b)?;
+    let scalars = scalars_from_canonical(blindings)
+        .ok_or_else(|| ZkpError::InvalidInput("blinding is not a canonical scalar".into()))?;
+
+    let backend_proof =
+        BulletproofsBackend::prove_greater_than(a, b, &scalars).map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(
+        GREATER_THAN_SCHEME_ID,
+        proof_bytes,
+        commitment,
+    ))
+}
+
+/// Verify a greater-than proof against both published commitments.
+pub fn verify_greater_than(proof: Vec<u8>, commit_a: &[u8], commit_b: &[u8]) -> bool {
+    let proof = match parse_and_validate_proof(&proof, GREATER_THAN_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+    if proof.commitment != commit_a || proof.proof.get(0..32) != Some(commit_b) {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_greater_than(&backend_proof)
+}
@@ -1,13 +1,1281 @@
+#[cfg(feature = "halo2")]
+use crate::backend::BackendKind;
+use crate::utils::bloom::BloomFilterParams;
 use crate::utils::error_handling::{ZkpError, ZkpResult};
+use crate::utils::proof_helpers;
+use sha2::{Digest, Sha256};
 
 pub const PROOF_VERSION: u8 = 2;
 
+/// High bit of the version byte: set when the envelope's length fields are big-endian.
+pub const BIG_ENDIAN_FLAG: u8 = 0x80;
+
+/// Byte order of the envelope's `proof_len`/`commitment_len` fields. Scheme payloads are
+/// always little-endian; only the header is affected. Existing proofs (flag clear) are
+/// little-endian, which is also what [`Proof::new`] produces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ByteOrder {
+    #[default]
+    LittleEndian,
+    BigEndian,
+}
+
+impl ByteOrder {
+    fn read_u32(self, bytes: [u8; 4]) -> u32 {
+        match self {
+            ByteOrder::LittleEndian => u32::from_le_bytes(bytes),
+            ByteOrder::BigEndian => u32::from_be_bytes(bytes),
+        }
+    }
+
+    fn write_u32(self, value: u32) -> [u8; 4] {
+        match self {
+            ByteOrder::LittleEndian => value.to_le_bytes(),
+            ByteOrder::BigEndian => value.to_be_bytes(),
+        }
+    }
+}
+
+/// Per-scheme `(minimum proof payload length, exact commitment length)` for known schemes.
+/// Used by [`Proof::from_bytes_strict`] to reject structurally valid but mis-split encodings.
+pub fn scheme_length_requirements(scheme: u8) -> Option<(usize, usize)> {
+    scheme_info(scheme).map(|s| (s.min_payload_len, s.commitment_len))
+}
+
+/// Static description of a proof scheme for tooling and SDK generators.
+#[derive(Debug, Clone, Copy)]
+pub struct SchemeInfo {
+    pub id: u8,
+    /// Name accepted by `verify_proofs_parallel`.
+    pub name: &'static str,
+    /// Public parameters the verifier passes besides the proof, in argument order.
+    pub verify_params: &'static [&'static str],
+    /// Payload fields in order as `(name, size)`. A size is a byte count or an expression over
+    /// earlier fields (`8*n`); `4+len` is a u32-length-prefixed blob and `rest` the remainder.
+    pub payload: &'static [(&'static str, &'static str)],
+    /// Minimum proof payload length accepted by [`Proof::from_bytes_strict`].
+    pub min_payload_len: usize,
+    /// Exact commitment length accepted by [`Proof::from_bytes_strict`].
+    pub commitment_len: usize,
+    /// Length of the payload prefix holding the public parameters, hashed by
+    /// [`Proof::statement_fingerprint`].
+    pub(crate) statement_len: fn(&[u8]) -> usize,
+    /// Whether the envelope commitment is a Pedersen commitment to a single value (the proven
+    /// value, or the sum for threshold schemes).
+    pub(crate) pedersen_value: bool,
+    /// Cryptographic check of a parsed proof, used by
+    /// [`crate::utils::proof_helpers::verify_proof_cryptographic`].
+    pub(crate) verify_payload: fn(&Proof) -> bool,
+    prove: fn(ProofInputs) -> ZkpResult<Vec<u8>>,
+    verify: fn(Vec<u8>, ProofInputs) -> ZkpResult<bool>,
+}
+
+const RANGE_PROOF: (&str, &str) = ("range_proof", "4+len");
+const VALUE_COMMITMENTS: (&str, &str) = ("value_commitments", "32*n");
+
+/// Every scheme this build can verify, ordered by id. [`prove`], [`verify`], the strict parser,
+/// statement fingerprints and [`proof_helpers::verify_proof_cryptographic`] all dispatch through
+/// this table, so a new scheme only needs its entry here and a [`ProofKind`] variant.
+pub const SCHEMES: &[SchemeInfo] = &[
+    SchemeInfo {
+        id: 1,
+        name: "range",
+        verify_params: &["min", "max"],
+        payload: &[
+            ("min", "8"),
+            ("max", "8"),
+            ("n_bits", "4"),
+            ("range_proof_min", "4+len"),
+            ("range_proof_max", "4+len"),
+            ("diff_min_commitment", "32"),
+            ("diff_max_commitment", "32"),
+        ],
+        // [min 8][max 8][n_bits 4][...]
+        min_payload_len: 20,
+        commitment_len: 32,
+        // [min 8][max 8]
+        statement_len: |_| 16,
+        pedersen_value: true,
+        verify_payload: proof_helpers::verify_range_payload,
+        prove: |i| {
+            range_proof::prove_range(
+                required(i.value, "value")?,
+                required(i.min, "min")?,
+                required(i.max, "max")?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(range_proof::verify_range(
+                proof,
+                required(i.min, "min")?,
+                required(i.max, "max")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 2,
+        name: "equality",
+        verify_params: &["val1", "val2"],
+        payload: &[("snark_proof", "rest")],
+        // Groth16 proof bytes
+        min_payload_len: 1,
+        commitment_len: 32,
+        statement_len: |_| 0,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_equality_payload,
+        prove: |i| {
+            equality_proof::prove_equality(
+                required(i.value, "value")?,
+                required(i.other_value, "other_value")?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(equality_proof::verify_equality(
+                proof,
+                required(i.value, "value")?,
+                required(i.other_value, "other_value")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 3,
+        name: "threshold",
+        verify_params: &["threshold"],
+        payload: &[
+            ("threshold", "8"),
+            ("n_bits", "4"),
+            RANGE_PROOF,
+            ("diff_commitment", "32"),
+        ],
+        // [threshold 8][n_bits 4][...]
+        min_payload_len: 12,
+        commitment_len: 32,
+        // [threshold 8]
+        statement_len: |_| 8,
+        pedersen_value: true,
+        verify_payload: proof_helpers::verify_threshold_payload,
+        prove: |i| threshold_proof::prove_threshold(i.values, required(i.threshold, "threshold")?),
+        verify: |proof, i| {
+            Ok(threshold_proof::verify_threshold(
+                proof,
+                required(i.threshold, "threshold")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 4,
+        name: "membership",
+        verify_params: &["set"],
+        payload: &[
+            ("set_len", "4"),
+            ("set", "8*set_len"),
+            ("snark_proof", "rest"),
+        ],
+        // [u32 set_len][>= 1 u64][snark proof]
+        min_payload_len: 4 + 8 + 1,
+        commitment_len: 32,
+        // [u32 set_len][set]
+        statement_len: |p| counted(p, 0, 4),
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_membership_payload,
+        prove: |i| set_membership::prove_membership(required(i.value, "value")?, i.set),
+        verify: |proof, i| Ok(set_membership::verify_membership(proof, i.set)),
+    },
+    SchemeInfo {
+        id: 5,
+        name: "improvement",
+        verify_params: &["old"],
+        payload: &[("old", "8"), ("new", "8"), ("stark_proof", "rest")],
+        // [old 8][new 8][stark proof]
+        min_payload_len: 16 + 1,
+        commitment_len: 32,
+        // [old 8][new 8]
+        statement_len: |_| 16,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_improvement_payload,
+        prove: |i| {
+            improvement_proof::prove_improvement(
+                required(i.value, "value")?,
+                required(i.other_value, "other_value")?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(improvement_proof::verify_improvement(
+                proof,
+                required(i.value, "value")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 6,
+        name: "consistency",
+        verify_params: &[],
+        payload: &[
+            ("n", "4"),
+            VALUE_COMMITMENTS,
+            ("range_proofs", "(4+len)*(n-1)"),
+            ("diff_commitments", "32*(n-1)"),
+        ],
+        // [u32 n][...]
+        min_payload_len: 4,
+        commitment_len: 32,
+        // [u32 n]
+        statement_len: |_| 4,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_consistency_payload,
+        prove: |i| consistency_proof::prove_consistency(i.values),
+        verify: |proof, _| Ok(consistency_proof::verify_consistency(proof)),
+    },
+    SchemeInfo {
+        id: 7,
+        name: "set_equality",
+        verify_params: &["root_a", "root_b"],
+        payload: &[("root_b", "32"), ("dlog_proof", "64")],
+        // [root_b 32][dlog proof 64]
+        min_payload_len: 96,
+        commitment_len: 32,
+        // [root_b 32]
+        statement_len: |_| 32,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_set_equality_payload,
+        prove: |i| {
+            set_membership::prove_set_equality(
+                i.set,
+                i.other_set,
+                &required_blindings::<2>(&i.blindings)?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(set_membership::verify_set_equality(
+                proof,
+                required_nth(&i.roots, 0, "roots")?.clone(),
+                required_nth(&i.roots, 1, "roots")?.clone(),
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 8,
+        name: "divisible",
+        verify_params: &["modulus"],
+        payload: &[("modulus", "8"), ("quotient_commitment", "32"), RANGE_PROOF],
+        // [modulus 8][quotient commitment 32][u32 len][range proof]
+        min_payload_len: 8 + 32 + 4,
+        commitment_len: 32,
+        // [modulus 8]
+        statement_len: |_| 8,
+        pedersen_value: true,
+        verify_payload: proof_helpers::verify_divisible_payload,
+        prove: |i| {
+            range_proof::prove_divisible(
+                required(i.value, "value")?,
+                required(i.modulus, "modulus")?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(range_proof::verify_divisible(
+                proof,
+                required(i.modulus, "modulus")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 9,
+        name: "range_and_greater",
+        verify_params: &["min", "max", "other_commitment"],
+        payload: &[
+            ("min", "8"),
+            ("max", "8"),
+            ("other_commitment", "32"),
+            RANGE_PROOF,
+        ],
+        // [min 8][max 8][other commitment 32][u32 len][range proof]
+        min_payload_len: 8 + 8 + 32 + 4,
+        commitment_len: 32,
+        // [min 8][max 8][other commitment 32]
+        statement_len: |_| 48,
+        pedersen_value: true,
+        verify_payload: proof_helpers::verify_range_and_greater_payload,
+        prove: |i| {
+            let [value_blinding, other_blinding] = required_blindings::<2>(&i.blindings)?;
+            range_proof::prove_range_and_greater(
+                required(i.value, "value")?,
+                required(i.min, "min")?,
+                required(i.max, "max")?,
+                required(i.other_value, "other_value")?,
+                required_nth(&i.commitments, 0, "commitments")?,
+                &value_blinding,
+                &other_blinding,
+            )
+        },
+        verify: |proof, i| {
+            Ok(range_proof::verify_range_and_greater(
+                proof,
+                required(i.min, "min")?,
+                required(i.max, "max")?,
+                required_nth(&i.commitments, 0, "commitments")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 10,
+        name: "weighted_average",
+        verify_params: &["weights_hash", "min", "max"],
+        payload: &[
+            ("min", "8"),
+            ("max", "8"),
+            ("n", "4"),
+            ("weights", "8*n"),
+            VALUE_COMMITMENTS,
+            RANGE_PROOF,
+        ],
+        // [min 8][max 8][u32 n][weights 8n][value commitments 32n][u32 len][range proof]
+        min_payload_len: 8 + 8 + 4 + 8 + 32 + 4,
+        commitment_len: 32,
+        // [min 8][max 8][u32 n][weights 8n]
+        statement_len: |p| counted(p, 16, 20),
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_weighted_average_payload,
+        prove: |i| {
+            range_proof::prove_weighted_average_range(
+                i.values,
+                i.weights,
+                required(i.min, "min")?,
+                required(i.max, "max")?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(range_proof::verify_weighted_average_range(
+                proof,
+                &crate::utils::commitment::weights_hash(&i.weights),
+                required(i.min, "min")?,
+                required(i.max, "max")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 11,
+        name: "committed_interval",
+        verify_params: &["value_commitment", "min_commitment", "max_commitment"],
+        payload: &[
+            ("min_commitment", "32"),
+            ("max_commitment", "32"),
+            RANGE_PROOF,
+        ],
+        // [min commitment 32][max commitment 32][u32 len][range proof]
+        min_payload_len: 32 + 32 + 4,
+        commitment_len: 32,
+        // [min commitment 32][max commitment 32]
+        statement_len: |_| 64,
+        pedersen_value: true,
+        verify_payload: proof_helpers::verify_committed_interval_payload,
+        prove: |i| {
+            range_proof::prove_in_committed_interval(
+                required(i.value, "value")?,
+                required(i.min, "min")?,
+                required(i.max, "max")?,
+                required_nth(&i.commitments, 1, "commitments")?,
+                required_nth(&i.commitments, 2, "commitments")?,
+                &required_blindings::<3>(&i.blindings)?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(range_proof::verify_in_committed_interval(
+                proof,
+                required_nth(&i.commitments, 0, "commitments")?,
+                required_nth(&i.commitments, 1, "commitments")?,
+                required_nth(&i.commitments, 2, "commitments")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 12,
+        name: "binary_choice",
+        verify_params: &["option_a", "option_b"],
+        payload: &[("option_a", "8"), ("option_b", "8"), ("or_proof", "128")],
+        // [option_a 8][option_b 8][OR proof 128]
+        min_payload_len: 8 + 8 + 128,
+        commitment_len: 32,
+        // [option_a 8][option_b 8]
+        statement_len: |_| 16,
+        pedersen_value: true,
+        verify_payload: proof_helpers::verify_binary_choice_payload,
+        prove: |i| {
+            set_membership::prove_binary_choice(
+                required(i.value, "value")?,
+                required(i.option_a, "option_a")?,
+                required(i.option_b, "option_b")?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(set_membership::verify_binary_choice(
+                proof,
+                required(i.option_a, "option_a")?,
+                required(i.option_b, "option_b")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 13,
+        name: "positive_threshold",
+        verify_params: &["threshold"],
+        payload: &[
+            ("threshold", "8"),
+            ("n", "4"),
+            VALUE_COMMITMENTS,
+            RANGE_PROOF,
+        ],
+        // [threshold 8][u32 n][value commitments 32n][u32 len][range proof]
+        min_payload_len: 8 + 4 + 32 + 4,
+        commitment_len: 32,
+        // [threshold 8]
+        statement_len: |_| 8,
+        pedersen_value: true,
+        verify_payload: proof_helpers::verify_positive_threshold_payload,
+        prove: |i| {
+            threshold_proof::prove_positive_threshold(i.values, required(i.threshold, "threshold")?)
+        },
+        verify: |proof, i| {
+            Ok(threshold_proof::verify_positive_threshold(
+                proof,
+                required(i.threshold, "threshold")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 14,
+        name: "bounded_consistency",
+        verify_params: &["max_step"],
+        payload: &[
+            ("max_step", "8"),
+            ("n", "4"),
+            VALUE_COMMITMENTS,
+            ("range_proofs", "(4+len)*(n-1)"),
+        ],
+        // [max_step 8][u32 n][value commitments 32n][...]
+        min_payload_len: 8 + 4 + 32,
+        commitment_len: 32,
+        // [max_step 8][u32 n]
+        statement_len: |_| 12,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_bounded_consistency_payload,
+        prove: |i| {
+            consistency_proof::prove_bounded_consistency(
+                i.values,
+                required(i.max_step, "max_step")?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(consistency_proof::verify_bounded_consistency(
+                proof,
+                required(i.max_step, "max_step")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 15,
+        name: "exact_sum",
+        verify_params: &["total"],
+        payload: &[
+            ("total", "8"),
+            ("n", "4"),
+            VALUE_COMMITMENTS,
+            ("dlog_proof", "64"),
+        ],
+        // [total 8][u32 n][value commitments 32n][dlog proof 64]
+        min_payload_len: 8 + 4 + 32 + 64,
+        commitment_len: 32,
+        // [total 8]
+        statement_len: |_| 8,
+        pedersen_value: true,
+        verify_payload: proof_helpers::verify_exact_sum_payload,
+        prove: |i| threshold_proof::prove_exact_sum(i.values, required(i.total, "total")?),
+        verify: |proof, i| {
+            Ok(threshold_proof::verify_exact_sum(
+                proof,
+                required(i.total, "total")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 16,
+        name: "rank",
+        verify_params: &["set_root", "max_rank"],
+        payload: &[
+            ("prefix_digest", "32"),
+            ("k", "4"),
+            ("top", "8*k"),
+            ("snark_proof", "rest"),
+        ],
+        // [prefix digest 32][u32 k][top k elements][snark proof]
+        min_payload_len: 32 + 4 + 8 + 1,
+        commitment_len: 32,
+        // [prefix digest 32][u32 k][top k elements]
+        statement_len: |p| counted(p, 32, 36),
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_rank_payload,
+        prove: |i| {
+            set_membership::prove_rank(
+                required(i.value, "value")?,
+                i.set,
+                required(i.max_rank, "max_rank")?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(set_membership::verify_rank(
+                proof,
+                required_nth(&i.roots, 0, "roots")?.clone(),
+                required(i.max_rank, "max_rank")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 17,
+        name: "membership_index_below",
+        verify_params: &["set", "k"],
+        payload: &[
+            ("k", "4"),
+            ("set_len", "4"),
+            ("set", "8*set_len"),
+            ("snark_proof", "rest"),
+        ],
+        // [u32 k][u32 set_len][>= 1 u64][snark proof]
+        min_payload_len: 4 + 4 + 8 + 1,
+        commitment_len: 32,
+        // [u32 k][u32 set_len][set]
+        statement_len: |p| counted(p, 4, 8),
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_membership_index_below_payload,
+        prove: |i| {
+            set_membership::prove_membership_index_below(
+                required(i.value, "value")?,
+                i.set,
+                required(i.index, "index")?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(set_membership::verify_membership_index_below(
+                proof,
+                i.set,
+                required(i.index, "index")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 18,
+        name: "is_maximum",
+        verify_params: &[],
+        payload: &[
+            ("max_index", "4"),
+            ("n", "4"),
+            VALUE_COMMITMENTS,
+            RANGE_PROOF,
+        ],
+        // [u32 max_index][u32 n][value commitments 32n][u32 len][range proof]
+        min_payload_len: 4 + 4 + 64 + 4,
+        commitment_len: 32,
+        // [u32 max_index][u32 n]
+        statement_len: |_| 8,
+        pedersen_value: true,
+        verify_payload: proof_helpers::verify_is_maximum_payload,
+        prove: |i| threshold_proof::prove_is_maximum(i.values, required(i.index, "index")?),
+        verify: |proof, _| Ok(threshold_proof::verify_is_maximum(proof)),
+    },
+    SchemeInfo {
+        id: 19,
+        name: "exact_delta",
+        verify_params: &["old_commitment", "new_commitment", "delta_commitment"],
+        payload: &[
+            ("old_commitment", "32"),
+            ("delta_commitment", "32"),
+            ("dlog_proof", "64"),
+        ],
+        // [old commitment 32][delta commitment 32][dlog proof 64]
+        min_payload_len: 32 + 32 + 64,
+        commitment_len: 32,
+        // [old commitment 32][delta commitment 32]
+        statement_len: |_| 64,
+        pedersen_value: true,
+        verify_payload: proof_helpers::verify_exact_delta_payload,
+        prove: |i| {
+            improvement_proof::prove_exact_delta(
+                required_nth(&i.commitments, 0, "commitments")?,
+                required_nth(&i.commitments, 1, "commitments")?,
+                required_nth(&i.commitments, 2, "commitments")?,
+                &required_blindings::<3>(&i.blindings)?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(improvement_proof::verify_exact_delta(
+                proof,
+                required_nth(&i.commitments, 0, "commitments")?,
+                required_nth(&i.commitments, 1, "commitments")?,
+                required_nth(&i.commitments, 2, "commitments")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 20,
+        name: "disjoint",
+        verify_params: &["root_a", "root_b"],
+        payload: &[
+            ("root_b", "32"),
+            ("n_a", "4"),
+            ("n_b", "4"),
+            ("commitments_a", "32*n_a"),
+            ("commitments_b", "32*n_b"),
+            ("nonzero_proofs", "96*n_a*n_b"),
+        ],
+        // [root_b 32][u32 n_a][u32 n_b][commitments 32(n_a+n_b)][non-zero proofs 96 n_a n_b]
+        min_payload_len: 32 + 4 + 4 + 64 + 96,
+        commitment_len: 32,
+        // [root_b 32]
+        statement_len: |_| 32,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_disjoint_payload,
+        prove: |i| {
+            let mut blindings_a = i.blindings;
+            let blindings_b = blindings_a.split_off(i.set.len().min(blindings_a.len()));
+            set_membership::prove_disjoint(i.set, i.other_set, blindings_a, blindings_b)
+        },
+        verify: |proof, i| {
+            Ok(set_membership::verify_disjoint(
+                proof,
+                required_nth(&i.roots, 0, "roots")?.clone(),
+                required_nth(&i.roots, 1, "roots")?.clone(),
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 21,
+        name: "within_of_mean",
+        verify_params: &["max_deviation"],
+        payload: &[
+            ("max_deviation", "8"),
+            ("n", "4"),
+            VALUE_COMMITMENTS,
+            RANGE_PROOF,
+        ],
+        // [max_deviation 8][u32 n][value commitments 32n][u32 len][range proof]
+        min_payload_len: 8 + 4 + 32 + 4,
+        commitment_len: 32,
+        // [max_deviation 8][u32 n]
+        statement_len: |_| 12,
+        pedersen_value: true,
+        verify_payload: proof_helpers::verify_within_of_mean_payload,
+        prove: |i| {
+            range_proof::prove_within_of_mean(
+                i.values,
+                required(i.value, "value")?,
+                required(i.max_deviation, "max_deviation")?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(range_proof::verify_within_of_mean(
+                proof,
+                required(i.max_deviation, "max_deviation")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 22,
+        name: "membership_bloom",
+        verify_params: &["filter_commitment", "element"],
+        payload: &[
+            ("num_bits", "4"),
+            ("num_hashes", "4"),
+            ("element", "8"),
+            ("openings", "num_hashes*(32+32*ceil_log2(num_bits))"),
+        ],
+        // [num_bits 4][num_hashes 4][element 8][openings (32 + 32 depth) each]
+        min_payload_len: 4 + 4 + 8 + 32,
+        commitment_len: 32,
+        // [num_bits 4][num_hashes 4][element 8]
+        statement_len: |_| 16,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_membership_bloom_payload,
+        prove: |i| {
+            set_membership::prove_membership_bloom(
+                required(i.value, "value")?,
+                i.bloom
+                    .as_ref()
+                    .ok_or_else(|| ZkpError::InvalidInput("missing input: bloom".to_string()))?,
+                &i.filter,
+            )
+        },
+        verify: |proof, i| {
+            Ok(set_membership::verify_membership_bloom(
+                proof,
+                required_nth(&i.commitments, 0, "commitments")?,
+                required(i.value, "value")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 23,
+        name: "polynomial_nonneg",
+        verify_params: &["a", "b", "c"],
+        payload: &[("a", "8"), ("b", "8"), ("c", "8"), ("snark_proof", "rest")],
+        // [a 8][b 8][c 8][snark proof]
+        min_payload_len: 8 + 8 + 8 + 1,
+        commitment_len: 32,
+        // [a 8][b 8][c 8]
+        statement_len: |_| 24,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_polynomial_nonneg_payload,
+        prove: |i| {
+            range_proof::prove_polynomial_nonneg(
+                required(i.value, "value")?,
+                *required_nth(&i.coefficients, 0, "coefficients")?,
+                *required_nth(&i.coefficients, 1, "coefficients")?,
+                *required_nth(&i.coefficients, 2, "coefficients")?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(range_proof::verify_polynomial_nonneg(
+                proof,
+                *required_nth(&i.coefficients, 0, "coefficients")?,
+                *required_nth(&i.coefficients, 1, "coefficients")?,
+                *required_nth(&i.coefficients, 2, "coefficients")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 24,
+        name: "count_above",
+        verify_params: &["threshold", "min_count"],
+        payload: &[
+            ("threshold", "8"),
+            ("min_count", "4"),
+            ("n", "4"),
+            ("value_commitments", "32*n"),
+            ("selector_commitments", "32*n"),
+            RANGE_PROOF,
+        ],
+        // [threshold 8][u32 min_count][u32 n][value and selector commitments 64n][u32 len][range proof]
+        min_payload_len: 8 + 4 + 4 + 64 + 4,
+        commitment_len: 32,
+        // [threshold 8][u32 min_count]
+        statement_len: |_| 12,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_count_above_payload,
+        prove: |i| {
+            threshold_proof::prove_count_above(
+                i.values,
+                required(i.threshold, "threshold")?,
+                required(i.count, "count")?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(threshold_proof::verify_count_above(
+                proof,
+                required(i.threshold, "threshold")?,
+                required(i.count, "count")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 25,
+        name: "strictly_between",
+        verify_params: &["value_commitment", "low_commitment", "high_commitment"],
+        payload: &[
+            ("low_commitment", "32"),
+            ("high_commitment", "32"),
+            RANGE_PROOF,
+        ],
+        // [low commitment 32][high commitment 32][u32 len][range proof]
+        min_payload_len: 32 + 32 + 4,
+        commitment_len: 32,
+        // [low commitment 32][high commitment 32]
+        statement_len: |_| 64,
+        pedersen_value: true,
+        verify_payload: proof_helpers::verify_strictly_between_payload,
+        prove: |i| {
+            range_proof::prove_strictly_between(
+                required(i.value, "value")?,
+                required(i.min, "min")?,
+                required(i.max, "max")?,
+                required_nth(&i.commitments, 1, "commitments")?,
+                required_nth(&i.commitments, 2, "commitments")?,
+                &required_blindings::<3>(&i.blindings)?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(range_proof::verify_strictly_between(
+                proof,
+                required_nth(&i.commitments, 0, "commitments")?,
+                required_nth(&i.commitments, 1, "commitments")?,
+                required_nth(&i.commitments, 2, "commitments")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 26,
+        name: "difference",
+        verify_params: &["commitment_a", "commitment_b", "diff"],
+        payload: &[("commitment_b", "32"), ("diff", "8"), ("dlog_proof", "64")],
+        // [commitment b 32][diff 8][dlog proof 64]
+        min_payload_len: 32 + 8 + 64,
+        commitment_len: 32,
+        // [commitment b 32][diff 8]
+        statement_len: |_| 40,
+        pedersen_value: true,
+        verify_payload: proof_helpers::verify_difference_payload,
+        prove: |i| {
+            equality_proof::prove_difference(
+                required_nth(&i.commitments, 0, "commitments")?,
+                required_nth(&i.commitments, 1, "commitments")?,
+                required(i.amount, "amount")?,
+                &required_blindings::<2>(&i.blindings)?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(equality_proof::verify_difference(
+                proof,
+                required_nth(&i.commitments, 0, "commitments")?,
+                required_nth(&i.commitments, 1, "commitments")?,
+                required(i.amount, "amount")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 27,
+        name: "range_many",
+        verify_params: &["min", "max"],
+        payload: &[
+            ("min", "8"),
+            ("max", "8"),
+            ("n", "4"),
+            VALUE_COMMITMENTS,
+            RANGE_PROOF,
+        ],
+        // [min 8][max 8][u32 n][value commitments 32n][u32 len][range proof]
+        min_payload_len: 8 + 8 + 4 + 32 + 4,
+        commitment_len: 32,
+        // [min 8][max 8]; the commitment digests the value commitments
+        statement_len: |_| 16,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_range_many_payload,
+        prove: |i| {
+            range_proof::prove_range_many(
+                i.values,
+                required(i.min, "min")?,
+                required(i.max, "max")?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(range_proof::verify_range_many(
+                proof,
+                required(i.min, "min")?,
+                required(i.max, "max")?,
+            ))
+        },
+    },
+    #[cfg(feature = "halo2")]
+    SchemeInfo {
+        id: 28,
+        name: "range_halo2",
+        verify_params: &["min", "max"],
+        payload: &[("min", "8"), ("max", "8"), ("halo2_proof", "rest")],
+        // [min 8][max 8][halo2 proof]
+        min_payload_len: 8 + 8 + 1,
+        commitment_len: 32,
+        // [min 8][max 8]; the commitment binds the value
+        statement_len: |_| 16,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_range_halo2_payload,
+        prove: |i| {
+            range_proof::prove_range_with_backend(
+                required(i.value, "value")?,
+                required(i.min, "min")?,
+                required(i.max, "max")?,
+                BackendKind::Halo2,
+            )
+        },
+        verify: |proof, i| {
+            Ok(range_proof::verify_range_with_backend(
+                proof,
+                required(i.min, "min")?,
+                required(i.max, "max")?,
+                BackendKind::Halo2,
+            ))
+        },
+    },
+    #[cfg(feature = "halo2")]
+    SchemeInfo {
+        id: 29,
+        name: "equality_halo2",
+        verify_params: &["val1", "val2"],
+        payload: &[("halo2_proof", "rest")],
+        // Halo2 proof bytes
+        min_payload_len: 1,
+        commitment_len: 32,
+        statement_len: |_| 0,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_equality_halo2_payload,
+        prove: |i| {
+            equality_proof::prove_equality_with_backend(
+                required(i.value, "value")?,
+                required(i.other_value, "other_value")?,
+                BackendKind::Halo2,
+            )
+        },
+        verify: |proof, i| {
+            Ok(equality_proof::verify_equality_with_backend(
+                proof,
+                required(i.value, "value")?,
+                required(i.other_value, "other_value")?,
+                BackendKind::Halo2,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 30,
+        name: "non_membership",
+        verify_params: &["set"],
+        payload: &[("root", "32"), ("snark_proof", "rest")],
+        // [gap tree root 32][snark proof]
+        min_payload_len: 32 + 1,
+        commitment_len: 32,
+        // [root 32]
+        statement_len: |_| 32,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_non_membership_payload,
+        prove: |i| set_membership::prove_non_membership(required(i.value, "value")?, i.set),
+        verify: |proof, i| Ok(set_membership::verify_non_membership(proof, i.set)),
+    },
+    SchemeInfo {
+        id: 31,
+        name: "inequality",
+        verify_params: &["commitment_a", "commitment_b"],
+        payload: &[("commitment_b", "32"), ("nonzero_proof", "96")],
+        // [commitment b 32][non-zero proof 96]
+        min_payload_len: 32 + 96,
+        commitment_len: 32,
+        // [commitment b 32]
+        statement_len: |_| 32,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_inequality_payload,
+        prove: |i| {
+            inequality_proof::prove_inequality(
+                required(i.value, "value")?,
+                required(i.other_value, "other_value")?,
+                &required_blindings::<2>(&i.blindings)?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(inequality_proof::verify_inequality(
+                proof,
+                required_nth(&i.commitments, 0, "commitments")?,
+                required_nth(&i.commitments, 1, "commitments")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 32,
+        name: "greater_than",
+        verify_params: &["commitment_a", "commitment_b"],
+        payload: &[("commitment_b", "32"), RANGE_PROOF],
+        // [commitment b 32][u32 len][range proof]
+        min_payload_len: 32 + 4,
+        commitment_len: 32,
+        // [commitment b 32]
+        statement_len: |_| 32,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_greater_than_payload,
+        prove: |i| {
+            inequality_proof::prove_greater_than(
+                required(i.value, "value")?,
+                required(i.other_value, "other_value")?,
+                &required_blindings::<2>(&i.blindings)?,
+            )
+        },
+        verify: |proof, i| {
+            Ok(inequality_proof::verify_greater_than(
+                proof,
+                required_nth(&i.commitments, 0, "commitments")?,
+                required_nth(&i.commitments, 1, "commitments")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 33,
+        name: "average_threshold",
+        verify_params: &["threshold", "count"],
+        payload: &[("threshold", "8"), ("n", "4"), RANGE_PROOF],
+        // [threshold 8][u32 n][u32 len][range proof]
+        min_payload_len: 8 + 4 + 4,
+        commitment_len: 32,
+        // [threshold 8][u32 n]
+        statement_len: |_| 12,
+        pedersen_value: true,
+        verify_payload: proof_helpers::verify_average_threshold_payload,
+        prove: |i| {
+            threshold_proof::prove_average_threshold(i.values, required(i.threshold, "threshold")?)
+        },
+        verify: |proof, i| {
+            Ok(threshold_proof::verify_average_threshold(
+                proof,
+                required(i.threshold, "threshold")?,
+                required(i.count, "count")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 34,
+        name: "membership_merkle",
+        verify_params: &["root"],
+        payload: &[("root", "32"), ("snark_proof", "rest")],
+        // [root 32][snark proof]
+        min_payload_len: 32 + 1,
+        commitment_len: 32,
+        // [root 32]
+        statement_len: |_| 32,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_membership_merkle_payload,
+        prove: |i| set_membership::prove_membership_root(required(i.value, "value")?, i.set),
+        verify: |proof, i| {
+            Ok(set_membership::verify_membership_root(
+                proof,
+                required_nth(&i.roots, 0, "roots")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 35,
+        name: "solvency",
+        verify_params: &["asset_commitments", "liability_commitments"],
+        payload: &[
+            ("n_assets", "4"),
+            ("n_liabilities", "4"),
+            ("entry_commitments", "32*(n_assets+n_liabilities)"),
+            RANGE_PROOF,
+            ("entry_range_proofs", "rest"),
+        ],
+        // [u32 n_assets][u32 n_liabilities][entry commitments 32 each][u32 len][range proof]...
+        min_payload_len: 4 + 4 + 64 + 4 + 4,
+        commitment_len: 32,
+        // [u32 n_assets][u32 n_liabilities][entry commitments 32 each]
+        statement_len: |p| {
+            u32_at(p, 0)
+                .saturating_add(u32_at(p, 4))
+                .saturating_mul(32)
+                .saturating_add(8)
+        },
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_solvency_payload,
+        prove: |i| {
+            let mut asset_blindings = i.blindings;
+            let liability_blindings =
+                asset_blindings.split_off(i.values.len().min(asset_blindings.len()));
+            solvency::prove_solvency(
+                i.values,
+                asset_blindings,
+                i.other_values,
+                liability_blindings,
+            )
+        },
+        verify: |proof, i| {
+            let assets = required(i.count, "count")?.min(i.commitments.len());
+            let (asset_commitments, liability_commitments) = i.commitments.split_at(assets);
+            Ok(solvency::verify_solvency(
+                proof,
+                asset_commitments,
+                liability_commitments,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 36,
+        name: "shuffle",
+        verify_params: &["root_a", "list_b"],
+        payload: &[("n", "4"), ("list_b", "8*n"), ("dlog_proof", "64")],
+        // [u32 n][>= 1 u64][dlog proof 64]
+        min_payload_len: 4 + 8 + 64,
+        commitment_len: 32,
+        // [u32 n][list_b]
+        statement_len: |p| counted(p, 0, 4),
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_shuffle_payload,
+        prove: |i| {
+            set_membership::prove_shuffle(
+                i.set,
+                i.other_set,
+                &required_blindings::<1>(&i.blindings)?[0],
+            )
+        },
+        verify: |proof, i| {
+            Ok(set_membership::verify_shuffle(
+                proof,
+                required_nth(&i.roots, 0, "roots")?,
+                &i.other_set,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 37,
+        name: "vote",
+        verify_params: &["root", "election_id", "num_options"],
+        payload: &[
+            ("root", "32"),
+            ("election_id", "8"),
+            ("nullifier", "32"),
+            ("num_options", "4"),
+            ("snark_proof", "rest"),
+        ],
+        // [root 32][election_id 8][nullifier 32][u32 num_options][snark proof]
+        min_payload_len: 32 + 8 + 32 + 4 + 1,
+        commitment_len: 32,
+        // [root 32][election_id 8][nullifier 32][u32 num_options]
+        statement_len: |_| 76,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_vote_payload,
+        prove: |i| prove_with_blinding(ProofKind::Vote, i).map(|(proof, _)| proof),
+        verify: |proof, i| {
+            Ok(voting::verify_vote(
+                proof,
+                required_nth(&i.roots, 0, "roots")?,
+                required(i.election_id, "election_id")?,
+                required(i.count, "count")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 38,
+        name: "balance_update",
+        verify_params: &["old_commitment", "new_commitment", "amount"],
+        payload: &[
+            ("old_commitment", "32"),
+            ("amount", "8"),
+            ("dlog_proof", "64"),
+            RANGE_PROOF,
+        ],
+        // [old commitment 32][amount 8][dlog proof 64][u32 len][range proof]
+        min_payload_len: 32 + 8 + 64 + 4,
+        commitment_len: 32,
+        // [old commitment 32][amount 8]
+        statement_len: |_| 40,
+        pedersen_value: true,
+        verify_payload: proof_helpers::verify_balance_update_payload,
+        prove: |i| prove_with_blinding(ProofKind::BalanceUpdate, i).map(|(proof, _)| proof),
+        verify: |proof, i| {
+            Ok(improvement_proof::verify_balance_update(
+                proof,
+                required_nth(&i.commitments, 0, "commitments")?,
+                required_nth(&i.commitments, 1, "commitments")?,
+                required(i.amount, "amount")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 39,
+        name: "median_threshold",
+        verify_params: &["threshold"],
+        payload: &[
+            ("threshold", "8"),
+            ("n", "4"),
+            ("value_commitments", "32*n"),
+            RANGE_PROOF,
+        ],
+        // [threshold 8][u32 n][commitments 32n][u32 len][range proof]
+        min_payload_len: 8 + 4 + 32 + 4,
+        commitment_len: 32,
+        // [threshold 8][u32 n]
+        statement_len: |_| 12,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_median_threshold_payload,
+        prove: |i| {
+            threshold_proof::prove_median_threshold(i.values, required(i.threshold, "threshold")?)
+        },
+        verify: |proof, i| {
+            Ok(threshold_proof::verify_median_threshold(
+                proof,
+                required(i.threshold, "threshold")?,
+            ))
+        },
+    },
+    SchemeInfo {
+        id: 40,
+        name: "variance_below",
+        verify_params: &["bound"],
+        payload: &[("bound", "8"), ("n", "4"), ("snark_proof", "rest")],
+        // [bound 8][u32 n][snark proof]
+        min_payload_len: 8 + 4 + 1,
+        commitment_len: 32,
+        // [bound 8][u32 n]
+        statement_len: |_| 12,
+        pedersen_value: false,
+        verify_payload: proof_helpers::verify_variance_below_payload,
+        prove: |i| threshold_proof::prove_variance_below(i.values, required(i.max, "max")?),
+        verify: |proof, i| {
+            Ok(threshold_proof::verify_variance_below(
+                proof,
+                required(i.max, "max")?,
+            ))
+        },
+    },
+];
+
+/// Scheme id for a name from [`SCHEMES`].
+pub fn scheme_id(name: &str) -> Option<u8> {
+    SCHEMES.iter().find(|s| s.name == name).map(|s| s.id)
+}
+
+/// Entry of [`SCHEMES`] for a scheme id.
+pub fn scheme_info(scheme: u8) -> Option<&'static SchemeInfo> {
+    SCHEMES
+        .binary_search_by_key(&scheme, |s| s.id)
+        .ok()
+        .map(|i| &SCHEMES[i])
+}
+
+/// Little-endian u32 at offset `at` of a payload, or 0 if the payload is too short.
+fn u32_at(p: &[u8], at: usize) -> usize {
+    p.get(at..at + 4)
+        .and_then(|b| b.try_into().ok())
+        .map(u32::from_le_bytes)
+        .unwrap_or(0) as usize
+}
+
+/// `header` bytes followed by `8 * n` bytes, with the u32 `n` at offset `at`.
+fn counted(p: &[u8], at: usize, header: usize) -> usize {
+    u32_at(p, at).saturating_mul(8).saturating_add(header)
+}
+
+/// Capability descriptor for format negotiation:
+/// `[u8 n_versions][versions][u8 n_schemes][scheme ids]`, ascending. Peers on different library
+/// versions can exchange it before sending proofs.
+pub fn format_capabilities() -> Vec<u8> {
+    let versions = [PROOF_VERSION];
+    let schemes: Vec<u8> = (0..=u8::MAX)
+        .filter(|&s| scheme_length_requirements(s).is_some())
+        .collect();
+    let mut out = Vec::with_capacity(2 + versions.len() + schemes.len());
+    out.push(versions.len() as u8);
+    out.extend_from_slice(&versions);
+    out.push(schemes.len() as u8);
+    out.extend_from_slice(&schemes);
+    out
+}
+
+/// True if this build understands the version and scheme in the header of `proof_bytes`.
+/// Only the header is inspected; the proof itself is neither parsed nor verified.
+pub fn can_verify(proof_bytes: &[u8]) -> bool {
+    match Proof::parse_header(proof_bytes) {
+        Ok((version, scheme, ..)) => {
+            version == PROOF_VERSION && scheme_length_requirements(scheme).is_some()
+        }
+        Err(_) => false,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Proof {
     pub version: u8,
     pub scheme: u8,
     pub proof: Vec<u8>,
     pub commitment: Vec<u8>,
+    /// Byte order of the header length fields, kept so re-serialization round-trips.
+    byte_order: ByteOrder,
 }
 
 impl Proof {
@@ -17,19 +1285,67 @@ impl Proof {
             scheme,
             proof,
             commitment,
+            byte_order: ByteOrder::LittleEndian,
         }
     }
 
+    /// Byte order of the header length fields as parsed (little-endian for new proofs).
+    pub fn byte_order(&self) -> ByteOrder {
+        self.byte_order
+    }
+
+    /// The same proof serialized with `byte_order` header length fields.
+    pub fn with_byte_order(mut self, byte_order: ByteOrder) -> Self {
+        self.byte_order = byte_order;
+        self
+    }
+
+    /// Parse the 10-byte header: `(version, scheme, byte order, proof_len, commitment_len)`.
+    fn parse_header(data: &[u8]) -> ZkpResult<(u8, u8, ByteOrder, usize, usize)> {
+        let header: [u8; 10] = data
+            .get(..10)
+            .and_then(|h| h.try_into().ok())
+            .ok_or_else(|| {
+                ZkpError::InvalidProofFormat("proof too short for header".to_string())
+            })?;
+        let byte_order = if header[0] & BIG_ENDIAN_FLAG != 0 {
+            ByteOrder::BigEndian
+        } else {
+            ByteOrder::LittleEndian
+        };
+        let field = |i: usize| [header[i], header[i + 1], header[i + 2], header[i + 3]];
+        Ok((
+            header[0] & !BIG_ENDIAN_FLAG,
+            header[1],
+            byte_order,
+            byte_order.read_u32(field(2)) as usize,
+            byte_order.read_u32(field(6)) as usize,
+        ))
+    }
+
+    /// Total length `10 + proof_len + commitment_len` declared by the header of `data`,
+    /// honoring the byte-order flag. Trailing bytes beyond it are not inspected.
+    pub fn declared_len(data: &[u8]) -> ZkpResult<usize> {
+        let (_, _, _, proof_len, comm_len) = Self::parse_header(data)?;
+        10usize
+            .checked_add(proof_len)
+            .and_then(|t| t.checked_add(comm_len))
+            .ok_or_else(|| ZkpError::InvalidProofFormat("proof length overflow".to_string()))
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         // Avoid producing invalid encodings due to u32 truncation.
         if self.proof.len() > u32::MAX as usize || self.commitment.len() > u32::MAX as usize {
             return Vec::new();
         }
         let mut out = Vec::new();
-        out.push(self.version);
+        out.push(match self.byte_order {
+            ByteOrder::LittleEndian => self.version,
+            ByteOrder::BigEndian => self.version | BIG_ENDIAN_FLAG,
+        });
         out.push(self.scheme);
-        out.extend_from_slice(&(self.proof.len() as u32).to_le_bytes());
-        out.extend_from_slice(&(self.commitment.len() as u32).to_le_bytes());
+        out.extend_from_slice(&self.byte_order.write_u32(self.proof.len() as u32));
+        out.extend_from_slice(&self.byte_order.write_u32(self.commitment.len() as u32));
         out.extend_from_slice(&self.proof);
         out.extend_from_slice(&self.commitment);
         out
@@ -46,20 +1362,7 @@ impl Proof {
                 MAX_PROOF_TOTAL_BYTES
             )));
         }
-        if data.len() < 10 {
-            return Err(ZkpError::InvalidProofFormat(
-                "proof too short for header".to_string(),
-            ));
-        }
-        let version = data[0];
-        let scheme = data[1];
-        let proof_len =
-            u32::from_le_bytes(data[2..6].try_into().map_err(|_| {
-                ZkpError::InvalidProofFormat("invalid proof length field".to_string())
-            })?) as usize;
-        let comm_len = u32::from_le_bytes(data[6..10].try_into().map_err(|_| {
-            ZkpError::InvalidProofFormat("invalid commitment length field".to_string())
-        })?) as usize;
+        let (version, scheme, byte_order, proof_len, comm_len) = Self::parse_header(data)?;
         if proof_len > MAX_PROOF_PAYLOAD_BYTES || comm_len > MAX_COMMITMENT_BYTES {
             return Err(ZkpError::InvalidProofFormat(
                 "proof or commitment payload exceeds limit".to_string(),
@@ -81,13 +1384,318 @@ impl Proof {
             scheme,
             proof,
             commitment,
+            byte_order,
         })
     }
+
+    /// SHA-256 fingerprint of the statement this proof attests to: the scheme, its public
+    /// parameters (bounds, threshold, set, modulus, ...) and the commitment to the hidden
+    /// values. Two proofs share a fingerprint only if they make the same claim about the same
+    /// committed values; range proofs over the same bounds but freshly blinded values differ.
+    /// Unknown schemes fingerprint the whole payload and commitment.
+    pub fn statement_fingerprint(&self) -> [u8; 32] {
+        let p = &self.proof;
+        let public: &[u8] = match scheme_info(self.scheme) {
+            Some(s) => &p[..(s.statement_len)(p).min(p.len())],
+            None => p.as_slice(),
+        };
+
+        let mut hasher = Sha256::new();
+        hasher.update(b"libzkp_statement_v2");
+        hasher.update([self.scheme]);
+        hasher.update((public.len() as u32).to_le_bytes());
+        hasher.update(public);
+        hasher.update(&self.commitment);
+        hasher.finalize().into()
+    }
+
+    /// Like [`Self::from_bytes`], but also enforces the per-scheme length requirements from
+    /// [`scheme_length_requirements`] and rejects unknown schemes.
+    pub fn from_bytes_strict(data: &[u8]) -> ZkpResult<Self> {
+        let proof = Self::from_bytes(data)?;
+        let (min_proof_len, commitment_len) =
+            scheme_length_requirements(proof.scheme).ok_or_else(|| {
+                ZkpError::InvalidProofFormat(format!("unknown proof scheme: {}", proof.scheme))
+            })?;
+        if proof.proof.len() < min_proof_len {
+            return Err(ZkpError::InvalidProofFormat(format!(
+                "proof payload too short for scheme {}: expected at least {} bytes, got {}",
+                proof.scheme,
+                min_proof_len,
+                proof.proof.len()
+            )));
+        }
+        if proof.commitment.len() != commitment_len {
+            return Err(ZkpError::InvalidProofFormat(format!(
+                "invalid commitment length for scheme {}: expected {} bytes, got {}",
+                proof.scheme,
+                commitment_len,
+                proof.commitment.len()
+            )));
+        }
+        Ok(proof)
+    }
+}
+
+/// Proof kinds accepted by [`prove`] and [`verify`], one per scheme id. Each variant lists the
+/// [`ProofInputs`] fields it reads when proving, then (after `/`) when verifying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofKind {
+    /// `value`, `min`, `max` / `min`, `max`
+    Range = 1,
+    /// `value`, `other_value` / `value`, `other_value`
+    Equality = 2,
+    /// `values`, `threshold` / `threshold`
+    Threshold = 3,
+    /// `value`, `set` / `set`
+    Membership = 4,
+    /// `value` (old), `other_value` (new) / `value` (old)
+    Improvement = 5,
+    /// `values` / none
+    Consistency = 6,
+    /// `set`, `other_set`, `blindings` `[root_a, root_b]` / `roots` `[root_a, root_b]`
+    SetEquality = 7,
+    /// `value`, `modulus` / `modulus`
+    Divisible = 8,
+    /// `value`, `min`, `max`, `other_value`, `commitments` `[other]`, `blindings`
+    /// `[value, other]` / `min`, `max`, `commitments` `[other]`
+    RangeAndGreater = 9,
+    /// `values`, `weights`, `min`, `max` / `weights`, `min`, `max`
+    WeightedAverage = 10,
+    /// `value`, `min`, `max`, `commitments` `[value, min, max]`, `blindings`
+    /// `[value, min, max]` / `commitments` `[value, min, max]`
+    CommittedInterval = 11,
+    /// `value`, `option_a`, `option_b` / `option_a`, `option_b`
+    BinaryChoice = 12,
+    /// `values`, `threshold` / `threshold`
+    PositiveThreshold = 13,
+    /// `values`, `max_step` / `max_step`
+    BoundedConsistency = 14,
+    /// `values`, `total` / `total`
+    ExactSum = 15,
+    /// `value`, `set` (sorted), `max_rank` / `roots` `[set_root]`, `max_rank`
+    Rank = 16,
+    /// `value`, `set`, `index` (k) / `set`, `index`
+    MembershipIndexBelow = 17,
+    /// `values`, `index` (claimed maximum) / none
+    IsMaximum = 18,
+    /// `commitments` `[old, new, delta]`, `blindings` `[old, new, delta]` / `commitments`
+    ExactDelta = 19,
+    /// `set`, `other_set`, `blindings` (one per element of `set`, then of `other_set`) /
+    /// `roots` `[root_a, root_b]`
+    Disjoint = 20,
+    /// `values`, `value`, `max_deviation` / `max_deviation`
+    WithinOfMean = 21,
+    /// `value`, `bloom`, `filter` / `value`, `commitments` `[filter]`
+    MembershipBloom = 22,
+    /// `value`, `coefficients` `[a, b, c]` / `coefficients`
+    PolynomialNonneg = 23,
+    /// `values`, `threshold`, `count` (minimum count) / `threshold`, `count`
+    CountAbove = 24,
+    /// `value`, `min` (low), `max` (high), `commitments` `[value, low, high]`, `blindings`
+    /// `[value, low, high]` / `commitments` `[value, low, high]`
+    StrictlyBetween = 25,
+    /// `commitments` `[a, b]`, `amount` (diff), `blindings` `[a, b]` / `commitments`, `amount`
+    Difference = 26,
+    /// `values`, `min`, `max` / `min`, `max`
+    RangeMany = 27,
+    /// `value`, `min`, `max` / `min`, `max`
+    #[cfg(feature = "halo2")]
+    RangeHalo2 = 28,
+    /// `value`, `other_value` / `value`, `other_value`
+    #[cfg(feature = "halo2")]
+    EqualityHalo2 = 29,
+    /// `value`, `set` / `set`
+    NonMembership = 30,
+    /// `value`, `other_value`, `blindings` `[a, b]` / `commitments` `[a, b]`
+    Inequality = 31,
+    /// `value`, `other_value`, `blindings` `[a, b]` / `commitments` `[a, b]`
+    GreaterThan = 32,
+    /// `values`, `threshold` / `threshold`, `count`
+    AverageThreshold = 33,
+    /// `value`, `set` / `roots` `[set_root]`
+    MembershipMerkle = 34,
+    /// `values` (assets), `other_values` (liabilities), `blindings` (one per asset, then per
+    /// liability) / `commitments` (asset entries, then liability entries), `count` (assets)
+    Solvency = 35,
+    /// `set`, `other_set` (shuffled), `blindings` `[root_a]` / `other_set`, `roots` `[root_a]`
+    Shuffle = 36,
+    /// `secret`, `voter_keys`, `election_id`, `count` (options), `index` (choice) /
+    /// `roots` `[eligibility_root]`, `election_id`, `count`
+    Vote = 37,
+    /// `value` (old), `amount`, `blindings` `[old]` / `commitments` `[old, new]`, `amount`
+    BalanceUpdate = 38,
+    /// `values`, `threshold` / `threshold`
+    MedianThreshold = 39,
+    /// `values`, `max` (variance bound) / `max`
+    VarianceBelow = 40,
+}
+
+impl ProofKind {
+    /// Every kind, ordered by scheme id.
+    pub const ALL: &'static [ProofKind] = &[
+        ProofKind::Range,
+        ProofKind::Equality,
+        ProofKind::Threshold,
+        ProofKind::Membership,
+        ProofKind::Improvement,
+        ProofKind::Consistency,
+        ProofKind::SetEquality,
+        ProofKind::Divisible,
+        ProofKind::RangeAndGreater,
+        ProofKind::WeightedAverage,
+        ProofKind::CommittedInterval,
+        ProofKind::BinaryChoice,
+        ProofKind::PositiveThreshold,
+        ProofKind::BoundedConsistency,
+        ProofKind::ExactSum,
+        ProofKind::Rank,
+        ProofKind::MembershipIndexBelow,
+        ProofKind::IsMaximum,
+        ProofKind::ExactDelta,
+        ProofKind::Disjoint,
+        ProofKind::WithinOfMean,
+        ProofKind::MembershipBloom,
+        ProofKind::PolynomialNonneg,
+        ProofKind::CountAbove,
+        ProofKind::StrictlyBetween,
+        ProofKind::Difference,
+        ProofKind::RangeMany,
+        #[cfg(feature = "halo2")]
+        ProofKind::RangeHalo2,
+        #[cfg(feature = "halo2")]
+        ProofKind::EqualityHalo2,
+        ProofKind::NonMembership,
+        ProofKind::Inequality,
+        ProofKind::GreaterThan,
+        ProofKind::AverageThreshold,
+        ProofKind::MembershipMerkle,
+        ProofKind::Solvency,
+        ProofKind::Shuffle,
+        ProofKind::Vote,
+        ProofKind::BalanceUpdate,
+        ProofKind::MedianThreshold,
+        ProofKind::VarianceBelow,
+    ];
+
+    /// Scheme id written in the envelope of proofs of this kind.
+    pub fn scheme_id(self) -> u8 {
+        self as u8
+    }
+
+    /// Entry of [`SCHEMES`] for this kind.
+    pub fn info(self) -> &'static SchemeInfo {
+        scheme_info(self.scheme_id()).expect("every ProofKind has a SCHEMES entry")
+    }
+
+    /// Scheme name as listed in [`SCHEMES`].
+    pub fn name(self) -> &'static str {
+        self.info().name
+    }
+}
+
+/// Inputs for [`prove`] and [`verify`]. Only the fields named on the [`ProofKind`] are read;
+/// build with `..Default::default()`. A verifier fills in the public fields only.
+#[derive(Debug, Clone, Default)]
+pub struct ProofInputs {
+    pub value: Option<u64>,
+    pub other_value: Option<u64>,
+    pub values: Vec<u64>,
+    pub weights: Vec<u64>,
+    pub set: Vec<u64>,
+    pub other_set: Vec<u64>,
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+    pub threshold: Option<u64>,
+    pub total: Option<u64>,
+    pub modulus: Option<u64>,
+    pub max_step: Option<u64>,
+    pub max_deviation: Option<u64>,
+    pub option_a: Option<u64>,
+    pub option_b: Option<u64>,
+    pub max_rank: Option<usize>,
+    pub index: Option<usize>,
+    pub roots: Vec<Vec<u8>>,
+    pub commitments: Vec<Vec<u8>>,
+    pub blindings: Vec<[u8; 32]>,
+    pub other_values: Vec<u64>,
+    pub coefficients: Vec<i64>,
+    pub count: Option<usize>,
+    pub amount: Option<u64>,
+    pub bloom: Option<BloomFilterParams>,
+    pub filter: Vec<u8>,
+    pub secret: Vec<u8>,
+    pub voter_keys: Vec<Vec<u8>>,
+    pub election_id: Option<u64>,
+}
+
+fn required<T: Copy>(field: Option<T>, name: &str) -> ZkpResult<T> {
+    field.ok_or_else(|| ZkpError::InvalidInput(format!("missing input: {}", name)))
+}
+
+fn required_nth<'a, T>(items: &'a [T], index: usize, name: &str) -> ZkpResult<&'a T> {
+    items
+        .get(index)
+        .ok_or_else(|| ZkpError::InvalidInput(format!("missing input: {}[{}]", name, index)))
+}
+
+fn required_blindings<const N: usize>(blindings: &[[u8; 32]]) -> ZkpResult<[[u8; 32]; N]> {
+    blindings.try_into().map_err(|_| {
+        ZkpError::InvalidInput(format!("expected {} blindings, got {}", N, blindings.len()))
+    })
+}
+
+/// Create a proof of `kind` from `inputs` by dispatching to the scheme's `prove_*` function;
+/// the result is the same enveloped proof that function returns. Errors on missing inputs
+/// and on anything the underlying function rejects. [`ProofKind::Vote`] and
+/// [`ProofKind::BalanceUpdate`] also produce a blinding; use [`prove_with_blinding`] to keep it.
+pub fn prove(kind: ProofKind, inputs: ProofInputs) -> ZkpResult<Vec<u8>> {
+    (kind.info().prove)(inputs)
+}
+
+/// [`prove`], also returning the blinding the scheme hands back: the ballot's for
+/// [`ProofKind::Vote`] and the new balance's for [`ProofKind::BalanceUpdate`], `None` for
+/// every other kind.
+pub fn prove_with_blinding(
+    kind: ProofKind,
+    inputs: ProofInputs,
+) -> ZkpResult<(Vec<u8>, Option<[u8; 32]>)> {
+    let opened = |(proof, blinding)| (proof, Some(blinding));
+    let i = inputs;
+    match kind {
+        ProofKind::Vote => voting::prove_vote(
+            &i.secret,
+            i.voter_keys,
+            required(i.election_id, "election_id")?,
+            required(i.count, "count")?,
+            required(i.index, "index")?,
+        )
+        .map(opened),
+        ProofKind::BalanceUpdate => improvement_proof::prove_balance_update(
+            required(i.value, "value")?,
+            &required_blindings::<1>(&i.blindings)?[0],
+            required(i.amount, "amount")?,
+        )
+        .map(opened),
+        _ => prove(kind, i).map(|proof| (proof, None)),
+    }
+}
+
+/// Verify a proof of `kind` against the public fields of `inputs` by dispatching to the
+/// scheme's `verify_*` function. Missing inputs verify as `false`.
+pub fn verify(proof: Vec<u8>, kind: ProofKind, inputs: ProofInputs) -> bool {
+    (kind.info().verify)(proof, inputs).unwrap_or(false)
 }
 
+pub mod auction;
 pub mod consistency_proof;
+pub mod credentials;
 pub mod equality_proof;
 pub mod improvement_proof;
+pub mod inequality_proof;
 pub mod range_proof;
 pub mod set_membership;
+pub mod solvency;
 pub mod threshold_proof;
+pub mod time_range;
+pub mod voting;
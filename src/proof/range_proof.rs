@@ -1,11 +1,38 @@
-use crate::backend::bulletproofs::BulletproofsBackend;
+use crate::backend::bulletproofs::{
+    scalar_from_canonical, scalars_from_canonical, BulletproofsBackend,
+};
+use crate::backend::snark::SnarkBackend;
+use crate::backend::BackendKind;
 use crate::utils::{
+    commitment::{
+        commit_value_pedersen, commit_value_snark, random_pedersen_blinding, weights_hash,
+    },
+    encoding::read_u64_le,
     error_handling::{ZkpError, ZkpResult},
+    limits::MAX_AGGREGATED_RANGE_VALUES,
     proof_helpers::{create_proof, extract_bulletproofs_components},
-    validation::validate_range_params,
+    validation::{
+        validate_divisible_params, validate_greater_than, validate_polynomial_nonneg_params,
+        validate_range_many_params, validate_range_offset_params, validate_range_params,
+        validate_range_signed_params, validate_strictly_between_params,
+        validate_weighted_average_params, validate_within_of_mean_params,
+    },
 };
 
 const SCHEME_ID: u8 = 1;
+const DIVISIBLE_SCHEME_ID: u8 = 8;
+const RANGE_AND_GREATER_SCHEME_ID: u8 = 9;
+const WEIGHTED_AVERAGE_SCHEME_ID: u8 = 10;
+const COMMITTED_INTERVAL_SCHEME_ID: u8 = 11;
+const WITHIN_OF_MEAN_SCHEME_ID: u8 = 21;
+const POLYNOMIAL_NONNEG_SCHEME_ID: u8 = 23;
+const STRICTLY_BETWEEN_SCHEME_ID: u8 = 25;
+const RANGE_MANY_SCHEME_ID: u8 = 27;
+#[cfg(feature = "halo2")]
+const HALO2_RANGE_SCHEME_ID: u8 = 28;
+
+/// Most values one [`prove_range_many`] proof covers; each value takes two aggregated ranges.
+pub const MAX_RANGE_MANY_VALUES: usize = MAX_AGGREGATED_RANGE_VALUES / 2;
 
 pub fn prove_range(value: u64, min: u64, max: u64) -> ZkpResult<Vec<u8>> {
     prove_range_with_bits(value, min, max, 64)
@@ -24,7 +51,311 @@ pub fn prove_range_with_bits(value: u64, min: u64, max: u64, n_bits: usize) -> Z
     Ok(create_proof(SCHEME_ID, proof_bytes, commitment))
 }
 
+/// Range proof that also returns the blinding of its value commitment, so the caller can
+/// later open the commitment or link it to other proofs (e.g. `advanced::proofs_share_value`).
+/// The proof verifies with [`verify_range`]. Anyone holding the blinding can open the
+/// commitment and learn `value` by searching candidates, so store it like the value itself.
+pub fn prove_range_keyed(value: u64, min: u64, max: u64) -> ZkpResult<(Vec<u8>, [u8; 32])> {
+    let blinding = random_pedersen_blinding();
+    Ok((
+        prove_range_with_blinding(value, min, max, &blinding)?,
+        blinding,
+    ))
+}
+
+/// Range proof whose value commitment is `commit_value_pedersen(value, blinding)`, for values
+/// already committed elsewhere (e.g. a credential attribute). Verifies with [`verify_range`].
+pub fn prove_range_with_blinding(
+    value: u64,
+    min: u64,
+    max: u64,
+    blinding: &[u8; 32],
+) -> ZkpResult<Vec<u8>> {
+    validate_range_params(value, min, max)?;
+
+    let scalar = scalar_from_canonical(blinding)
+        .ok_or_else(|| ZkpError::InvalidInput("blinding is not a canonical scalar".to_string()))?;
+    let backend_proof =
+        BulletproofsBackend::prove_range_with_blinding(value, min, max, 64, &scalar)
+            .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(SCHEME_ID, proof_bytes, commitment))
+}
+
 pub fn verify_range(proof: Vec<u8>, min: u64, max: u64) -> bool {
+    verify_and_extract(proof, min, max).is_some()
+}
+
+/// Verify a range proof like [`verify_range`] and, if it is valid, return its 32-byte value
+/// commitment for chaining into other proofs, without parsing the proof a second time.
+pub fn verify_and_extract(proof: Vec<u8>, min: u64, max: u64) -> Option<[u8; 32]> {
+    use crate::utils::proof_helpers::{
+        parse_and_validate_proof, reconstruct_bulletproofs_proof, validate_standard_commitment,
+    };
+
+    if min > max {
+        return None;
+    }
+
+    let proof = parse_and_validate_proof(&proof, SCHEME_ID).ok()?;
+
+    validate_standard_commitment(&proof.commitment).ok()?;
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+
+    BulletproofsBackend::verify_range_with_bounds(&backend_proof, min, max)
+        .then(|| proof.commitment.as_slice().try_into().ok())
+        .flatten()
+}
+
+/// Verify many range proofs, each against its own `(min, max)`, in one batched check that is
+/// much faster than calling [`verify_range`] per proof. Returns true only if every proof is
+/// valid (an empty batch is valid); on false, fall back to [`verify_range`] to find the bad one.
+pub fn verify_range_batch(proofs: Vec<(Vec<u8>, u64, u64)>) -> bool {
+    use crate::utils::proof_helpers::{
+        parse_and_validate_proof, reconstruct_bulletproofs_proof, validate_standard_commitment,
+    };
+
+    let mut backend_proofs = Vec::with_capacity(proofs.len());
+    for (proof, min, max) in &proofs {
+        if min > max {
+            return false;
+        }
+        let proof = match parse_and_validate_proof(proof, SCHEME_ID) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+        if validate_standard_commitment(&proof.commitment).is_err() {
+            return false;
+        }
+        backend_proofs.push((
+            reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment),
+            *min,
+            *max,
+        ));
+    }
+
+    let batch: Vec<(&[u8], u64, u64)> = backend_proofs
+        .iter()
+        .map(|(proof, min, max)| (proof.as_slice(), *min, *max))
+        .collect();
+    BulletproofsBackend::verify_range_proofs_batch(&batch)
+}
+
+/// Range proof on a chosen backend. `Bulletproofs` is [`prove_range`]. `Halo2` needs no
+/// trusted setup and publishes `Halo2Backend::commit_value` under a fresh blinding that is then
+/// discarded. There is no Groth16 range circuit, so `Snark` is rejected.
+pub fn prove_range_with_backend(
+    value: u64,
+    min: u64,
+    max: u64,
+    backend: BackendKind,
+) -> ZkpResult<Vec<u8>> {
+    backend.ensure_available()?;
+    match backend {
+        BackendKind::Bulletproofs => prove_range(value, min, max),
+        #[cfg(feature = "halo2")]
+        BackendKind::Halo2 => prove_range_halo2(value, min, max),
+        _ => Err(backend.unsupported("range")),
+    }
+}
+
+/// Verify a proof from [`prove_range_with_backend`] made on `backend`.
+pub fn verify_range_with_backend(proof: Vec<u8>, min: u64, max: u64, backend: BackendKind) -> bool {
+    match backend {
+        BackendKind::Bulletproofs => verify_range(proof, min, max),
+        #[cfg(feature = "halo2")]
+        BackendKind::Halo2 => verify_range_halo2(&proof, min, max),
+        _ => false,
+    }
+}
+
+#[cfg(feature = "halo2")]
+fn prove_range_halo2(value: u64, min: u64, max: u64) -> ZkpResult<Vec<u8>> {
+    use crate::backend::halo2::Halo2Backend;
+    use zeroize::Zeroizing;
+
+    validate_range_params(value, min, max)?;
+    let blinding = Zeroizing::new(Halo2Backend::random_blinding());
+    let halo2_proof = Halo2Backend::prove_range(value, &blinding, min, max);
+    let commitment = match Halo2Backend::commit_value(value, &blinding) {
+        Some(commitment) if !halo2_proof.is_empty() => commitment,
+        _ => {
+            return Err(ZkpError::ProofGenerationFailed(
+                "Halo2 range proof generation failed".to_string(),
+            ))
+        }
+    };
+
+    let mut payload = Vec::with_capacity(16 + halo2_proof.len());
+    payload.extend_from_slice(&min.to_le_bytes());
+    payload.extend_from_slice(&max.to_le_bytes());
+    payload.extend_from_slice(&halo2_proof);
+    Ok(create_proof(
+        HALO2_RANGE_SCHEME_ID,
+        payload,
+        commitment.to_vec(),
+    ))
+}
+
+#[cfg(feature = "halo2")]
+fn verify_range_halo2(proof: &[u8], min: u64, max: u64) -> bool {
+    use crate::utils::proof_helpers::{parse_and_validate_proof, verify_proof_cryptographic};
+
+    let proof = match parse_and_validate_proof(proof, HALO2_RANGE_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+    if read_u64_le(&proof.proof, 0) != Some(min) || read_u64_le(&proof.proof, 8) != Some(max) {
+        return false;
+    }
+    verify_proof_cryptographic(&proof)
+}
+
+/// Bounds on `value` equivalent to `offset + value` in `[min, max]`; `None` if no `u64` fits.
+fn offset_bounds(offset: u64, min: u64, max: u64) -> Option<(u64, u64)> {
+    if min > max {
+        return None;
+    }
+    Some((min.saturating_sub(offset), max.checked_sub(offset)?))
+}
+
+/// Prove `offset + value` lies in `[min, max]` for a public `offset` (e.g. values stored
+/// relative to a base). The addition is overflow-checked; the proof itself is a plain range
+/// proof on `value` over the shifted bounds, so it carries those bounds rather than `min/max`.
+pub fn prove_range_offset(value: u64, offset: u64, min: u64, max: u64) -> ZkpResult<Vec<u8>> {
+    validate_range_offset_params(value, offset, min, max)?;
+    let (lower, upper) = offset_bounds(offset, min, max)
+        .ok_or_else(|| ZkpError::InvalidInput("offset exceeds max".to_string()))?;
+    prove_range(value, lower, upper)
+}
+
+pub fn verify_range_offset(proof: Vec<u8>, offset: u64, min: u64, max: u64) -> bool {
+    match offset_bounds(offset, min, max) {
+        Some((lower, upper)) => verify_range(proof, lower, upper),
+        None => false,
+    }
+}
+
+/// Order-preserving map from `i64` to `u64` (adds `2^63`), so `i64::MIN` encodes as 0.
+fn signed_offset(value: i64) -> u64 {
+    (value as u64) ^ (1 << 63)
+}
+
+/// Prove a signed `value` (e.g. a temperature or PnL) lies in `[min, max]`. All three are
+/// offset-encoded by `2^63` into `u64`, so the proof is a plain scheme-1 range proof over the
+/// encoded bounds: [`verify_range`] accepts it given those encoded bounds, and
+/// [`verify_range_signed`] accepts any range proof over them. Nothing in the proof marks it as
+/// signed; the verifier's choice of function decides how the bounds are read.
+pub fn prove_range_signed(value: i64, min: i64, max: i64) -> ZkpResult<Vec<u8>> {
+    validate_range_signed_params(value, min, max)?;
+    prove_range(signed_offset(value), signed_offset(min), signed_offset(max))
+}
+
+pub fn verify_range_signed(proof: Vec<u8>, min: i64, max: i64) -> bool {
+    verify_range(proof, signed_offset(min), signed_offset(max))
+}
+
+/// Verify a range proof against several sanctioned `(min, max)` policies and return the first
+/// one it is valid for, or `None`. Candidates whose bounds differ from the ones bound into
+/// the proof are rejected before any cryptographic work.
+pub fn verify_range_any(proof: Vec<u8>, candidates: Vec<(u64, u64)>) -> Option<(u64, u64)> {
+    candidates
+        .into_iter()
+        .find(|&(min, max)| verify_range(proof.clone(), min, max))
+}
+
+/// Prove that `value` is a multiple of `modulus` without revealing `value`.
+/// The proof commits to the quotient and shows `value == quotient * modulus`, with both
+/// `value` and the quotient range-checked to 64 bits. Errors if `modulus` is 0 or
+/// `value` is not divisible by it.
+pub fn prove_divisible(value: u64, modulus: u64) -> ZkpResult<Vec<u8>> {
+    validate_divisible_params(value, modulus)?;
+
+    let backend_proof =
+        BulletproofsBackend::prove_divisible(value, modulus).map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(DIVISIBLE_SCHEME_ID, proof_bytes, commitment))
+}
+
+pub fn verify_divisible(proof: Vec<u8>, modulus: u64) -> bool {
+    use crate::utils::proof_helpers::{
+        parse_and_validate_proof, reconstruct_bulletproofs_proof, validate_standard_commitment,
+    };
+
+    if modulus == 0 {
+        return false;
+    }
+
+    let proof = match parse_and_validate_proof(&proof, DIVISIBLE_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+
+    BulletproofsBackend::verify_divisible(&backend_proof, modulus)
+}
+
+/// Prove `min <= value <= max` and `value > other_value`, where `other_value` is only
+/// published as the Pedersen commitment `other_commitment` (see `commit_value_pedersen`).
+/// The prover must know the other value and its blinding, e.g. the auctioneer holding the
+/// current highest bid. The proof's commitment is the Pedersen commitment to `value` under
+/// `value_blinding`. Errors if `value` is out of range, not strictly greater, or if
+/// `other_commitment` does not open to `(other_value, other_blinding)`.
+pub fn prove_range_and_greater(
+    value: u64,
+    min: u64,
+    max: u64,
+    other_value: u64,
+    other_commitment: &[u8],
+    value_blinding: &[u8; 32],
+    other_blinding: &[u8; 32],
+) -> ZkpResult<Vec<u8>> {
+    validate_range_params(value, min, max)?;
+    validate_greater_than(value, other_value)?;
+    if commit_value_pedersen(other_value, other_blinding)? != other_commitment {
+        return Err(ZkpError::InvalidInput(
+            "other commitment does not open to the given value and blinding".to_string(),
+        ));
+    }
+    let invalid_blinding = || ZkpError::InvalidInput("blinding is not a canonical scalar".into());
+    let value_blinding = scalar_from_canonical(value_blinding).ok_or_else(invalid_blinding)?;
+    let other_blinding = scalar_from_canonical(other_blinding).ok_or_else(invalid_blinding)?;
+
+    let backend_proof = BulletproofsBackend::prove_range_and_greater(
+        value,
+        min,
+        max,
+        other_value,
+        &value_blinding,
+        &other_blinding,
+    )
+    .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(
+        RANGE_AND_GREATER_SCHEME_ID,
+        proof_bytes,
+        commitment,
+    ))
+}
+
+pub fn verify_range_and_greater(
+    proof: Vec<u8>,
+    min: u64,
+    max: u64,
+    other_commitment: &[u8],
+) -> bool {
     use crate::utils::proof_helpers::{
         parse_and_validate_proof, reconstruct_bulletproofs_proof, validate_standard_commitment,
     };
@@ -33,7 +364,7 @@ pub fn verify_range(proof: Vec<u8>, min: u64, max: u64) -> bool {
         return false;
     }
 
-    let proof = match parse_and_validate_proof(&proof, SCHEME_ID) {
+    let proof = match parse_and_validate_proof(&proof, RANGE_AND_GREATER_SCHEME_ID) {
         Ok(p) => p,
         Err(_) => return false,
     };
@@ -44,5 +375,363 @@ pub fn verify_range(proof: Vec<u8>, min: u64, max: u64) -> bool {
 
     let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
 
-    BulletproofsBackend::verify_range_with_bounds(&backend_proof, min, max)
+    BulletproofsBackend::verify_range_and_greater(&backend_proof, min, max, other_commitment)
+}
+
+/// Prove `min <= value <= max` when the bounds are only published as Pedersen commitments
+/// (see `commit_value_pedersen`). `blindings` are `[value, min, max]`; the proof's commitment
+/// is the Pedersen commitment to `value`. Errors if `value` is outside the interval or either
+/// bound commitment does not open to the given bound and blinding.
+pub fn prove_in_committed_interval(
+    value: u64,
+    min: u64,
+    max: u64,
+    min_commitment: &[u8],
+    max_commitment: &[u8],
+    blindings: &[[u8; 32]; 3],
+) -> ZkpResult<Vec<u8>> {
+    validate_range_params(value, min, max)?;
+    let [_, min_blinding, max_blinding] = blindings;
+    if commit_value_pedersen(min, min_blinding)? != min_commitment
+        || commit_value_pedersen(max, max_blinding)? != max_commitment
+    {
+        return Err(ZkpError::InvalidInput(
+            "bound commitments do not open to the given bounds and blindings".to_string(),
+        ));
+    }
+    let scalars = scalars_from_canonical(blindings)
+        .ok_or_else(|| ZkpError::InvalidInput("blinding is not a canonical scalar".into()))?;
+
+    let backend_proof = BulletproofsBackend::prove_in_committed_interval(value, min, max, &scalars)
+        .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(
+        COMMITTED_INTERVAL_SCHEME_ID,
+        proof_bytes,
+        commitment,
+    ))
+}
+
+/// Verify an interval proof against the value commitment and the two bound commitments.
+pub fn verify_in_committed_interval(
+    proof: Vec<u8>,
+    value_commitment: &[u8],
+    min_commitment: &[u8],
+    max_commitment: &[u8],
+) -> bool {
+    use crate::utils::proof_helpers::{
+        parse_and_validate_proof, reconstruct_bulletproofs_proof, validate_standard_commitment,
+    };
+
+    let proof = match parse_and_validate_proof(&proof, COMMITTED_INTERVAL_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+    if proof.commitment != value_commitment
+        || proof.proof.get(0..32) != Some(min_commitment)
+        || proof.proof.get(32..64) != Some(max_commitment)
+    {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+
+    BulletproofsBackend::verify_in_committed_interval(&backend_proof)
+}
+
+/// Prove `low < value < high` when the bounds are only published as Pedersen commitments.
+/// Works like [`prove_in_committed_interval`] on `value - low - 1` and `high - value - 1`;
+/// `blindings` are `[value, low, high]` and the proof's commitment is the commitment to
+/// `value`. Errors if `value` is not strictly inside, or a bound commitment does not open.
+pub fn prove_strictly_between(
+    value: u64,
+    low: u64,
+    high: u64,
+    low_commitment: &[u8],
+    high_commitment: &[u8],
+    blindings: &[[u8; 32]; 3],
+) -> ZkpResult<Vec<u8>> {
+    validate_strictly_between_params(value, low, high)?;
+    let [_, low_blinding, high_blinding] = blindings;
+    if commit_value_pedersen(low, low_blinding)? != low_commitment
+        || commit_value_pedersen(high, high_blinding)? != high_commitment
+    {
+        return Err(ZkpError::InvalidInput(
+            "bound commitments do not open to the given bounds and blindings".to_string(),
+        ));
+    }
+    let scalars = scalars_from_canonical(blindings)
+        .ok_or_else(|| ZkpError::InvalidInput("blinding is not a canonical scalar".into()))?;
+
+    let backend_proof = BulletproofsBackend::prove_strictly_between(value, low, high, &scalars)
+        .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(
+        STRICTLY_BETWEEN_SCHEME_ID,
+        proof_bytes,
+        commitment,
+    ))
+}
+
+/// Verify a strict ordering proof against the value commitment and the two bound commitments.
+pub fn verify_strictly_between(
+    proof: Vec<u8>,
+    value_commitment: &[u8],
+    low_commitment: &[u8],
+    high_commitment: &[u8],
+) -> bool {
+    use crate::utils::proof_helpers::{
+        parse_and_validate_proof, reconstruct_bulletproofs_proof, validate_standard_commitment,
+    };
+
+    let proof = match parse_and_validate_proof(&proof, STRICTLY_BETWEEN_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+    if proof.commitment != value_commitment
+        || proof.proof.get(0..32) != Some(low_commitment)
+        || proof.proof.get(32..64) != Some(high_commitment)
+    {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+
+    BulletproofsBackend::verify_strictly_between(&backend_proof)
+}
+
+/// Prove every one of the hidden `values` lies in `[min, max]` with a single aggregated
+/// Bulletproofs range proof, which is much smaller than one [`prove_range`] per value. The
+/// per-value commitments travel in the proof; the envelope commitment is their SHA-256 digest.
+/// Errors on an empty batch, more than [`MAX_RANGE_MANY_VALUES`] values or a value out of range.
+pub fn prove_range_many(values: Vec<u64>, min: u64, max: u64) -> ZkpResult<Vec<u8>> {
+    validate_range_many_params(&values, min, max)?;
+    if values.len() > MAX_RANGE_MANY_VALUES {
+        return Err(ZkpError::InvalidInput(format!(
+            "at most {} values supported",
+            MAX_RANGE_MANY_VALUES
+        )));
+    }
+
+    let backend_proof = BulletproofsBackend::prove_range_batch(&values, min, max)
+        .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(RANGE_MANY_SCHEME_ID, proof_bytes, commitment))
+}
+
+/// Verify a proof from [`prove_range_many`] for the public bounds.
+pub fn verify_range_many(proof: Vec<u8>, min: u64, max: u64) -> bool {
+    use crate::utils::proof_helpers::{
+        parse_and_validate_proof, reconstruct_bulletproofs_proof, validate_standard_commitment,
+    };
+
+    if min > max {
+        return false;
+    }
+
+    let proof = match parse_and_validate_proof(&proof, RANGE_MANY_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+
+    BulletproofsBackend::verify_range_batch(&backend_proof, min, max)
+}
+
+/// Prove the weighted average of hidden `values` under public `weights` lies in
+/// `[min, max]`. The comparison is exact (`min * W <= sum(w_i * v_i) <= max * W`, no
+/// division). The weights travel in the proof; verifiers identify them by `weights_hash`.
+/// Errors on mismatched lengths, zero total weight, overflow or an out-of-range average.
+pub fn prove_weighted_average_range(
+    values: Vec<u64>,
+    weights: Vec<u64>,
+    min: u64,
+    max: u64,
+) -> ZkpResult<Vec<u8>> {
+    validate_weighted_average_params(&values, &weights, min, max)?;
+    if values.len() + 2 > MAX_AGGREGATED_RANGE_VALUES {
+        return Err(ZkpError::InvalidInput(format!(
+            "at most {} values supported",
+            MAX_AGGREGATED_RANGE_VALUES - 2
+        )));
+    }
+
+    let backend_proof =
+        BulletproofsBackend::prove_weighted_average_range(&values, &weights, min, max)
+            .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(
+        WEIGHTED_AVERAGE_SCHEME_ID,
+        proof_bytes,
+        commitment,
+    ))
+}
+
+/// Verify a weighted average proof whose embedded weights hash to `expected_weights_hash`
+/// (see `utils::commitment::weights_hash`).
+pub fn verify_weighted_average_range(
+    proof: Vec<u8>,
+    expected_weights_hash: &[u8],
+    min: u64,
+    max: u64,
+) -> bool {
+    use crate::utils::proof_helpers::{
+        deserialize_embedded_set_prefix, parse_and_validate_proof, reconstruct_bulletproofs_proof,
+        validate_standard_commitment,
+    };
+
+    if min > max {
+        return false;
+    }
+
+    let proof = match parse_and_validate_proof(&proof, WEIGHTED_AVERAGE_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+
+    let weights = match proof
+        .proof
+        .get(16..)
+        .and_then(|rest| deserialize_embedded_set_prefix(rest, MAX_AGGREGATED_RANGE_VALUES - 2))
+    {
+        Some((w, _)) => w,
+        None => return false,
+    };
+    if weights_hash(&weights) != expected_weights_hash {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+
+    BulletproofsBackend::verify_weighted_average_range(&backend_proof, &weights, min, max)
+}
+
+/// Prove `my_value` lies within `max_deviation` of the mean of the hidden `values`. The
+/// comparison is exact (`|n * my_value - sum| <= n * max_deviation`), so a mean with a
+/// remainder is neither rounded nor truncated. The envelope commitment is to `my_value`; the
+/// per-value commitments travel in the proof. Errors on an empty dataset, overflow or a value
+/// outside the allowed deviation.
+pub fn prove_within_of_mean(
+    values: Vec<u64>,
+    my_value: u64,
+    max_deviation: u64,
+) -> ZkpResult<Vec<u8>> {
+    validate_within_of_mean_params(&values, my_value, max_deviation)?;
+    if values.len() + 3 > MAX_AGGREGATED_RANGE_VALUES {
+        return Err(ZkpError::InvalidInput(format!(
+            "at most {} values supported",
+            MAX_AGGREGATED_RANGE_VALUES - 3
+        )));
+    }
+
+    let backend_proof = BulletproofsBackend::prove_within_of_mean(&values, my_value, max_deviation)
+        .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(
+        WITHIN_OF_MEAN_SCHEME_ID,
+        proof_bytes,
+        commitment,
+    ))
+}
+
+pub fn verify_within_of_mean(proof: Vec<u8>, max_deviation: u64) -> bool {
+    use crate::utils::proof_helpers::{
+        parse_and_validate_proof, reconstruct_bulletproofs_proof, validate_standard_commitment,
+    };
+
+    let proof = match parse_and_validate_proof(&proof, WITHIN_OF_MEAN_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_within_of_mean(&backend_proof, max_deviation)
+}
+
+/// Prove `a*value^2 + b*value + c >= 0` for a hidden `value` and public coefficients, with a
+/// Groth16 circuit that evaluates the quadratic and range-checks the result to 64 bits. The
+/// commitment is the MiMC commitment to `value` (as in equality proofs). Errors if the
+/// predicate does not hold, or if the evaluation overflows or exceeds `u64::MAX`.
+pub fn prove_polynomial_nonneg(value: u64, a: i64, b: i64, c: i64) -> ZkpResult<Vec<u8>> {
+    validate_polynomial_nonneg_params(value, a, b, c)?;
+
+    let commitment = commit_value_snark(value);
+    let commitment_arr: [u8; 32] = commitment
+        .as_slice()
+        .try_into()
+        .map_err(|_| ZkpError::InvalidProofFormat("invalid commitment size".to_string()))?;
+    let snark_proof = SnarkBackend::prove_polynomial_nonneg_zk(value, [a, b, c], commitment_arr);
+    if snark_proof.is_empty() {
+        return Err(ZkpError::ProofGenerationFailed(
+            "SNARK proof generation failed".to_string(),
+        ));
+    }
+
+    let mut payload = Vec::with_capacity(24 + snark_proof.len());
+    for coefficient in [a, b, c] {
+        payload.extend_from_slice(&coefficient.to_le_bytes());
+    }
+    payload.extend_from_slice(&snark_proof);
+    Ok(create_proof(
+        POLYNOMIAL_NONNEG_SCHEME_ID,
+        payload,
+        commitment,
+    ))
+}
+
+/// Verify a proof from [`prove_polynomial_nonneg`] for the public coefficients `a`, `b`, `c`.
+pub fn verify_polynomial_nonneg(proof: Vec<u8>, a: i64, b: i64, c: i64) -> bool {
+    use crate::utils::proof_helpers::parse_and_validate_proof;
+
+    let proof = match parse_and_validate_proof(&proof, POLYNOMIAL_NONNEG_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+    match read_polynomial_coefficients(&proof.proof) {
+        Some(coefficients) if coefficients == [a, b, c] => {
+            SnarkBackend::verify_polynomial_nonneg_zk(
+                &proof.proof[24..],
+                coefficients,
+                &proof.commitment,
+            )
+        }
+        _ => false,
+    }
+}
+
+/// Coefficients `[a, b, c]` from the head of a polynomial proof payload.
+pub(crate) fn read_polynomial_coefficients(payload: &[u8]) -> Option<[i64; 3]> {
+    let read = |i: usize| read_u64_le(payload, 8 * i).map(|v| v as i64);
+    Some([read(0)?, read(1)?, read(2)?])
 }
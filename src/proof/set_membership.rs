@@ -1,25 +1,177 @@
+use crate::backend::bulletproofs::{
+    scalar_from_canonical, scalars_from_canonical, BulletproofsBackend,
+};
+use crate::backend::snark::merkle::{normalize_merkle_set, normalize_non_membership_set};
 use crate::backend::snark::{SnarkBackend, MAX_SET_SIZE};
 use crate::proof::Proof;
-use crate::utils::commitment::commit_value_snark;
+use crate::utils::bloom::{
+    bloom_commitment, bloom_positions, leaf_salt, verify_bloom_payload, BloomFilterParams,
+    BloomTree,
+};
+use crate::utils::commitment::{commit_value_snark, extend_set_chain};
 use crate::utils::error_handling::{ZkpError, ZkpResult};
+use crate::utils::limits::{MAX_DISJOINT_PAIRS, MAX_U64_VEC_LEN};
 use crate::utils::proof_helpers::{
-    deserialize_embedded_set_prefix, parse_and_validate_proof, validate_standard_commitment,
+    create_proof, deserialize_embedded_set_prefix, extract_bulletproofs_components,
+    parse_and_validate_proof, reconstruct_bulletproofs_proof, validate_standard_commitment,
 };
-use crate::utils::validation::{validate_membership_params, validate_set_size};
+use crate::utils::validation::{
+    validate_binary_choice_params, validate_disjoint_params, validate_membership_index_params,
+    validate_membership_params, validate_non_membership_params, validate_rank_params,
+    validate_set_equality_params, validate_set_size,
+};
+use std::collections::HashSet;
+use zeroize::Zeroizing;
 
 const SCHEME_ID: u8 = 4;
+const SET_EQUALITY_SCHEME_ID: u8 = 7;
+const BINARY_CHOICE_SCHEME_ID: u8 = 12;
+const RANK_SCHEME_ID: u8 = 16;
+const INDEX_BELOW_SCHEME_ID: u8 = 17;
+const DISJOINT_SCHEME_ID: u8 = 20;
+const BLOOM_SCHEME_ID: u8 = 22;
+const NON_MEMBERSHIP_SCHEME_ID: u8 = 30;
+const MERKLE_SCHEME_ID: u8 = 34;
+const SHUFFLE_SCHEME_ID: u8 = 36;
+
+/// A membership set validated and indexed once, for services that prove or verify against
+/// the same set repeatedly. Keeps the original order (it is embedded in proofs), a hash set
+/// for O(1) membership checks and a normalized copy (sorted, deduplicated) for verifier-side
+/// comparison.
+#[derive(Debug, Clone)]
+pub struct PreparedSet {
+    elements: Vec<u64>,
+    lookup: HashSet<u64>,
+    normalized: Vec<u64>,
+}
+
+impl PreparedSet {
+    /// Validate `set` (non-empty, at most `MAX_SET_SIZE` elements) and precompute its indexes.
+    pub fn new(set: Vec<u64>) -> ZkpResult<Self> {
+        if set.is_empty() {
+            return Err(ZkpError::InvalidInput("set cannot be empty".to_string()));
+        }
+        validate_set_size(&set, MAX_SET_SIZE)?;
+        let lookup = set.iter().copied().collect();
+        let normalized = SnarkBackend::normalize_set(set.clone())?;
+        Ok(Self {
+            elements: set,
+            lookup,
+            normalized,
+        })
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        self.lookup.contains(&value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Elements in their original order.
+    pub fn as_slice(&self) -> &[u64] {
+        &self.elements
+    }
+}
 
+/// Prove `value` is in `set` without revealing it. Sets of up to `MAX_SET_SIZE` distinct
+/// elements are embedded in the proof; larger ones (up to `MAX_MERKLE_SET_SIZE`) are proven
+/// by a Merkle path against the set's root, which is all the proof carries.
 pub fn prove_membership(value: u64, set: Vec<u64>) -> ZkpResult<Vec<u8>> {
     validate_membership_params(value, &set)?;
-    validate_set_size(&set, MAX_SET_SIZE)?;
+    if set.len() <= MAX_SET_SIZE {
+        return prove_membership_unchecked(value, &set);
+    }
+
+    let set = normalize_merkle_set(set)?;
+    if set.len() <= MAX_SET_SIZE {
+        return prove_membership_unchecked(value, &set);
+    }
+    prove_membership_merkle(value, set)
+}
+
+/// [`prove_membership`] that always proves by a Merkle path, whatever the size of `set`, so the
+/// proof verifies against [`merkle_root`] with [`verify_membership_root`].
+pub fn prove_membership_root(value: u64, set: Vec<u64>) -> ZkpResult<Vec<u8>> {
+    validate_membership_params(value, &set)?;
+    prove_membership_merkle(value, normalize_merkle_set(set)?)
+}
+
+/// Merkle membership: payload `[root 32][snark proof]`, commitment the value's MiMC commitment.
+fn prove_membership_merkle(value: u64, set: Vec<u64>) -> ZkpResult<Vec<u8>> {
+    let commitment = commit_value_snark(value);
+    let commitment_arr: [u8; 32] = commitment
+        .clone()
+        .try_into()
+        .map_err(|_| ZkpError::InvalidProofFormat("invalid commitment size".to_string()))?;
 
+    let (snark_proof, root) = SnarkBackend::prove_merkle_membership_zk(value, set, commitment_arr)
+        .ok_or_else(|| {
+            ZkpError::ProofGenerationFailed(
+                "SNARK Merkle membership proof generation failed".to_string(),
+            )
+        })?;
+
+    let mut payload = Vec::with_capacity(32 + snark_proof.len());
+    payload.extend_from_slice(&root);
+    payload.extend_from_slice(&snark_proof);
+    Ok(Proof::new(MERKLE_SCHEME_ID, payload, commitment).to_bytes())
+}
+
+/// Root of the Merkle tree `prove_membership` uses for sets of more than `MAX_SET_SIZE`
+/// distinct elements. Verifiers that check many proofs against one large set can compute it
+/// once and call [`verify_membership_root`].
+pub fn merkle_root(set: Vec<u64>) -> ZkpResult<Vec<u8>> {
+    Ok(SnarkBackend::merkle_set_root(set)?.to_vec())
+}
+
+/// Verify a Merkle membership proof against a root from [`merkle_root`].
+pub fn verify_membership_root(proof: Vec<u8>, root: &[u8]) -> bool {
+    let proof = match parse_and_validate_proof(&proof, MERKLE_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+    let (embedded_root, snark_bytes) = proof.proof.split_at(32);
+    embedded_root == root
+        && SnarkBackend::verify_merkle_membership_zk(snark_bytes, root, &proof.commitment)
+}
+
+/// Like [`prove_membership`], but against a [`PreparedSet`]: the set is not re-validated and
+/// the membership check is a hash lookup.
+pub fn prove_membership_prepared(value: u64, prepared: &PreparedSet) -> ZkpResult<Vec<u8>> {
+    if !prepared.contains(value) {
+        return Err(ZkpError::InvalidInput(format!(
+            "value {} is not in the provided set",
+            value
+        )));
+    }
+    prove_membership_unchecked(value, prepared.as_slice())
+}
+
+/// Shared proving path; `set` must already be validated and contain `value`.
+fn prove_membership_unchecked(value: u64, set: &[u64]) -> ZkpResult<Vec<u8>> {
+    let (payload, commitment) = membership_payload(value, set)?;
+    let proof = Proof::new(SCHEME_ID, payload, commitment);
+    Ok(proof.to_bytes())
+}
+
+/// Membership SNARK payload `[u32 set_len][set][snark proof]` and the value's MiMC commitment.
+fn membership_payload(value: u64, set: &[u64]) -> ZkpResult<(Vec<u8>, Vec<u8>)> {
     let commitment = commit_value_snark(value);
     let commitment_arr: [u8; 32] = commitment
         .clone()
         .try_into()
         .map_err(|_| ZkpError::InvalidProofFormat("invalid commitment size".to_string()))?;
 
-    let snark_proof = SnarkBackend::prove_membership_zk(value, set.clone(), commitment_arr);
+    let snark_proof = SnarkBackend::prove_membership_zk(value, set.to_vec(), commitment_arr);
     if snark_proof.is_empty() {
         return Err(ZkpError::ProofGenerationFailed(
             "SNARK membership proof generation failed".to_string(),
@@ -28,16 +180,87 @@ pub fn prove_membership(value: u64, set: Vec<u64>) -> ZkpResult<Vec<u8>> {
 
     let mut payload = Vec::with_capacity(4 + set.len() * 8 + snark_proof.len());
     payload.extend_from_slice(&(set.len() as u32).to_le_bytes());
-    for v in &set {
+    for v in set {
         payload.extend_from_slice(&v.to_le_bytes());
     }
     payload.extend_from_slice(&snark_proof);
 
-    let proof = Proof::new(SCHEME_ID, payload, commitment);
-    Ok(proof.to_bytes())
+    Ok((payload, commitment))
 }
 
+/// Verify a membership proof against `set`. Sets are compared in normalized form (see
+/// `SnarkBackend::normalize_set`), so order and duplicates do not matter. Sets of more than
+/// `MAX_SET_SIZE` distinct elements are checked through their Merkle root.
 pub fn verify_membership(proof: Vec<u8>, set: Vec<u64>) -> bool {
+    match normalize_merkle_set(set) {
+        Ok(set) if set.len() <= MAX_SET_SIZE => {
+            verify_membership_normalized(proof, &set, MAX_SET_SIZE)
+        }
+        Ok(set) => match SnarkBackend::merkle_set_root(set) {
+            Ok(root) => verify_membership_root(proof, &root),
+            Err(_) => false,
+        },
+        Err(_) => false,
+    }
+}
+
+/// Like [`verify_membership`], but for untrusted proofs: a proof whose embedded set declares
+/// more than `max_set_size` elements is rejected from its length prefix, before the set is
+/// read, sorted or any pairing check runs.
+pub fn verify_membership_bounded(proof: Vec<u8>, set: Vec<u64>, max_set_size: usize) -> bool {
+    if set.len() > max_set_size {
+        return false;
+    }
+    match SnarkBackend::normalize_set(set) {
+        Ok(set) => verify_membership_normalized(proof, &set, max_set_size.min(MAX_SET_SIZE)),
+        Err(_) => false,
+    }
+}
+
+/// Like [`verify_membership`], but compares against the prepared set's normalized form.
+pub fn verify_membership_prepared(proof: Vec<u8>, prepared: &PreparedSet) -> bool {
+    verify_membership_normalized(proof, &prepared.normalized, MAX_SET_SIZE)
+}
+
+/// Like [`verify_membership`], but reports a proof made over a set of a different length
+/// as `InvalidInput("set length mismatch between prove and verify")` instead of `false`.
+/// Other failures (wrong elements, bad proof) are `Ok(false)`; malformed bytes are errors.
+pub fn verify_membership_checked(proof: Vec<u8>, set: Vec<u64>) -> ZkpResult<bool> {
+    let proof = parse_and_validate_proof(&proof, SCHEME_ID)?;
+    validate_standard_commitment(&proof.commitment)?;
+
+    let (embedded_set, snark_bytes) = deserialize_embedded_set_prefix(&proof.proof, MAX_SET_SIZE)
+        .ok_or_else(|| {
+        ZkpError::InvalidProofFormat("invalid embedded membership set".to_string())
+    })?;
+    if snark_bytes.is_empty() {
+        return Err(ZkpError::InvalidProofFormat(
+            "missing SNARK membership proof".to_string(),
+        ));
+    }
+
+    let set = SnarkBackend::normalize_set(set)?;
+    let embedded_normalized = SnarkBackend::normalize_set(embedded_set.clone())?;
+    if embedded_normalized.len() == set.len() && embedded_normalized != set {
+        return Ok(false);
+    }
+
+    SnarkBackend::verify_membership_zk_with_len(
+        snark_bytes,
+        &embedded_set,
+        set.len(),
+        &proof.commitment,
+    )
+}
+
+/// `normalized_set` must come from `SnarkBackend::normalize_set`; the embedded set may be in
+/// any order or contain duplicates, and is rejected if it declares more than `max_set_len`
+/// elements.
+fn verify_membership_normalized(
+    proof: Vec<u8>,
+    normalized_set: &[u64],
+    max_set_len: usize,
+) -> bool {
     let proof = match parse_and_validate_proof(&proof, SCHEME_ID) {
         Ok(p) => p,
         Err(_) => return false,
@@ -48,7 +271,7 @@ pub fn verify_membership(proof: Vec<u8>, set: Vec<u64>) -> bool {
     }
 
     let (embedded_set, snark_bytes) =
-        match deserialize_embedded_set_prefix(&proof.proof, MAX_SET_SIZE) {
+        match deserialize_embedded_set_prefix(&proof.proof, max_set_len) {
             Some(p) => p,
             None => return false,
         };
@@ -56,16 +279,426 @@ pub fn verify_membership(proof: Vec<u8>, set: Vec<u64>) -> bool {
         return false;
     }
 
-    if set.len() != embedded_set.len() {
+    match SnarkBackend::normalize_set(embedded_set.clone()) {
+        Ok(embedded) if embedded == normalized_set => {}
+        _ => return false,
+    }
+
+    SnarkBackend::verify_membership_zk(snark_bytes, &embedded_set, &proof.commitment)
+}
+
+/// Prove `value` is *not* in `set` (e.g. not on a deny-list) without revealing it. The set is
+/// normalized (sorted, deduplicated), up to `MAX_NON_MEMBERSHIP_SET_SIZE` elements, and only
+/// the root of its gap tree is embedded: payload `[root 32][snark proof]`. The commitment is
+/// the value's MiMC commitment, as for [`prove_membership`].
+pub fn prove_non_membership(value: u64, set: Vec<u64>) -> ZkpResult<Vec<u8>> {
+    validate_non_membership_params(value, &set)?;
+    let set = normalize_non_membership_set(set)?;
+
+    let commitment = commit_value_snark(value);
+    let commitment_arr: [u8; 32] = commitment
+        .clone()
+        .try_into()
+        .map_err(|_| ZkpError::InvalidProofFormat("invalid commitment size".to_string()))?;
+    let (snark_proof, root) = SnarkBackend::prove_non_membership_zk(value, set, commitment_arr)
+        .ok_or_else(|| {
+            ZkpError::ProofGenerationFailed(
+                "SNARK non-membership proof generation failed".to_string(),
+            )
+        })?;
+
+    let mut payload = Vec::with_capacity(32 + snark_proof.len());
+    payload.extend_from_slice(&root);
+    payload.extend_from_slice(&snark_proof);
+    Ok(Proof::new(NON_MEMBERSHIP_SCHEME_ID, payload, commitment).to_bytes())
+}
+
+/// Root of the gap tree a non-membership proof for `set` embeds. Verifiers that check many
+/// proofs against one large set can compute it once and call [`verify_non_membership_root`].
+pub fn non_membership_root(set: Vec<u64>) -> ZkpResult<Vec<u8>> {
+    Ok(SnarkBackend::non_membership_set_root(set)?.to_vec())
+}
+
+/// Verify a proof from [`prove_non_membership`]. Like [`verify_membership`], order and
+/// duplicates in `set` do not matter.
+pub fn verify_non_membership(proof: Vec<u8>, set: Vec<u64>) -> bool {
+    match SnarkBackend::non_membership_set_root(set) {
+        Ok(root) => verify_non_membership_root(proof, &root),
+        Err(_) => false,
+    }
+}
+
+/// Verify a non-membership proof against a root from [`non_membership_root`].
+pub fn verify_non_membership_root(proof: Vec<u8>, root: &[u8]) -> bool {
+    let proof = match parse_and_validate_proof(&proof, NON_MEMBERSHIP_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+    let (embedded_root, snark_bytes) = proof.proof.split_at(32);
+    embedded_root == root
+        && SnarkBackend::verify_non_membership_zk(snark_bytes, root, &proof.commitment)
+}
+
+/// Prove `value` is in `set` at an index below `k` (e.g. "my pick is among the first `k`
+/// entries of this ordered list"), without revealing which. Uses the membership circuit with only
+/// the first `k` slots selectable. Errors if the value's first index is `k` or later; a `k` past
+/// the end of the set is clamped to its length.
+pub fn prove_membership_index_below(value: u64, set: Vec<u64>, k: usize) -> ZkpResult<Vec<u8>> {
+    validate_membership_index_params(value, &set, k)?;
+    validate_set_size(&set, MAX_SET_SIZE)?;
+    let limit = k.min(set.len());
+
+    let commitment = commit_value_snark(value);
+    let commitment_arr: [u8; 32] = commitment
+        .clone()
+        .try_into()
+        .map_err(|_| ZkpError::InvalidProofFormat("invalid commitment size".to_string()))?;
+    let snark_proof =
+        SnarkBackend::prove_membership_zk_below(value, set.clone(), limit, commitment_arr);
+    if snark_proof.is_empty() {
+        return Err(ZkpError::ProofGenerationFailed(
+            "SNARK membership proof generation failed".to_string(),
+        ));
+    }
+
+    let mut payload = Vec::with_capacity(8 + set.len() * 8 + snark_proof.len());
+    payload.extend_from_slice(&(limit as u32).to_le_bytes());
+    payload.extend_from_slice(&(set.len() as u32).to_le_bytes());
+    for v in &set {
+        payload.extend_from_slice(&v.to_le_bytes());
+    }
+    payload.extend_from_slice(&snark_proof);
+
+    Ok(Proof::new(INDEX_BELOW_SCHEME_ID, payload, commitment).to_bytes())
+}
+
+/// Verify a proof from [`prove_membership_index_below`]. Unlike [`verify_membership`], the set
+/// order matters: `set` must match the proven set element for element.
+pub fn verify_membership_index_below(proof: Vec<u8>, set: Vec<u64>, k: usize) -> bool {
+    let proof = match parse_and_validate_proof(&proof, INDEX_BELOW_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+
+    let limit = match proof.proof.get(0..4).and_then(|b| b.try_into().ok()) {
+        Some(b) => u32::from_le_bytes(b) as usize,
+        None => return false,
+    };
+    let (embedded_set, snark_bytes) =
+        match deserialize_embedded_set_prefix(&proof.proof[4..], MAX_SET_SIZE) {
+            Some(p) => p,
+            None => return false,
+        };
+    if snark_bytes.is_empty() || embedded_set != set || limit != k.min(set.len()) {
+        return false;
+    }
+
+    SnarkBackend::verify_membership_zk_below(snark_bytes, &embedded_set, limit, &proof.commitment)
+}
+
+/// Prove that `set_a` and `set_b` hold the same elements (as multisets) without revealing them,
+/// for roots published as `commit_multiset_pedersen(set, blinding)` with `blindings`
+/// `[r_a, r_b]`. The proof carries both roots: the commitment is `root_a`, and `root_b` is the
+/// first 32 bytes of the payload. Fails if the sets differ in any element or in cardinality.
+pub fn prove_set_equality(
+    set_a: Vec<u64>,
+    set_b: Vec<u64>,
+    blindings: &[[u8; 32]; 2],
+) -> ZkpResult<Vec<u8>> {
+    validate_set_equality_params(&set_a, &set_b)?;
+    validate_set_size(&set_a, MAX_U64_VEC_LEN)?;
+    let scalars = scalars_from_canonical(blindings)
+        .ok_or_else(|| ZkpError::InvalidInput("blinding is not a canonical scalar".into()))?;
+
+    let backend_proof = BulletproofsBackend::prove_set_equality(&set_a, &set_b, &scalars)
+        .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(
+        SET_EQUALITY_SCHEME_ID,
+        proof_bytes,
+        commitment,
+    ))
+}
+
+/// Verify a set equality proof against the two published set roots.
+pub fn verify_set_equality(proof: Vec<u8>, root_a: Vec<u8>, root_b: Vec<u8>) -> bool {
+    let proof = match parse_and_validate_proof(&proof, SET_EQUALITY_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
         return false;
     }
-    let mut a = set.clone();
-    let mut b = embedded_set.clone();
-    a.sort_unstable();
-    b.sort_unstable();
-    if a != b {
+    if proof.commitment != root_a || proof.proof.get(0..32) != Some(root_b.as_slice()) {
         return false;
     }
 
-    SnarkBackend::verify_membership_zk(snark_bytes, &embedded_set, &proof.commitment)
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_set_equality(&backend_proof)
+}
+
+/// Prove the public list `list_b` is a reordering of the hidden list `list_a` (e.g. records
+/// shuffled before publication), without revealing `list_a` or the permutation. `list_a`'s root
+/// must have been published earlier as `commit_multiset_pedersen(list_a, blinding)`; it is the
+/// proof commitment, and `list_b` is embedded in the proof.
+pub fn prove_shuffle(
+    list_a: Vec<u64>,
+    list_b: Vec<u64>,
+    blinding: &[u8; 32],
+) -> ZkpResult<Vec<u8>> {
+    validate_set_equality_params(&list_a, &list_b)?;
+    validate_set_size(&list_a, MAX_U64_VEC_LEN)?;
+    let blinding = scalar_from_canonical(blinding)
+        .ok_or_else(|| ZkpError::InvalidInput("blinding is not a canonical scalar".into()))?;
+
+    let backend_proof = BulletproofsBackend::prove_shuffle(&list_a, &list_b, &blinding)
+        .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(SHUFFLE_SCHEME_ID, proof_bytes, commitment))
+}
+
+/// Verify a shuffle proof against the root of the hidden list and the published list, in
+/// its published order.
+pub fn verify_shuffle(proof: Vec<u8>, root_a: &[u8], list_b: &[u64]) -> bool {
+    let proof = match parse_and_validate_proof(&proof, SHUFFLE_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() || proof.commitment != root_a {
+        return false;
+    }
+    match deserialize_embedded_set_prefix(&proof.proof, MAX_U64_VEC_LEN) {
+        Some((embedded, _)) if embedded == list_b => {}
+        _ => return false,
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_shuffle(&backend_proof)
+}
+
+/// Prove that `set_a` and `set_b` have no element in common without revealing either. Each
+/// element is committed separately under its blinding, one per element in `blindings_a` and
+/// `blindings_b`, and every cross pair gets a proof that the committed difference is non-zero,
+/// so the proof grows with `|set_a| * |set_b|` (at most `MAX_DISJOINT_PAIRS` pairs). The
+/// roots are those published with `commit_set_elements_pedersen`: the commitment is `root_a`
+/// and the first 32 bytes of the payload are `root_b`. Errors if either set is empty, the
+/// blindings do not match the sets or the sets share an element.
+pub fn prove_disjoint(
+    set_a: Vec<u64>,
+    set_b: Vec<u64>,
+    blindings_a: Vec<[u8; 32]>,
+    blindings_b: Vec<[u8; 32]>,
+) -> ZkpResult<Vec<u8>> {
+    validate_disjoint_params(&set_a, &set_b)?;
+    if set_a.len().saturating_mul(set_b.len()) > MAX_DISJOINT_PAIRS {
+        return Err(ZkpError::InvalidInput(format!(
+            "set sizes {} x {} exceed {} element pairs",
+            set_a.len(),
+            set_b.len(),
+            MAX_DISJOINT_PAIRS
+        )));
+    }
+
+    let parse = |blindings: &[[u8; 32]], set: &[u64]| {
+        if blindings.len() != set.len() {
+            return Err(ZkpError::InvalidInput(format!(
+                "expected {} blindings, got {}",
+                set.len(),
+                blindings.len()
+            )));
+        }
+        blindings
+            .iter()
+            .map(scalar_from_canonical)
+            .collect::<Option<Vec<_>>>()
+            .map(Zeroizing::new)
+            .ok_or_else(|| ZkpError::InvalidInput("blinding is not a canonical scalar".into()))
+    };
+    let blindings_a = parse(&blindings_a, &set_a)?;
+    let blindings_b = parse(&blindings_b, &set_b)?;
+
+    let backend_proof =
+        BulletproofsBackend::prove_disjoint(&set_a, &set_b, [&blindings_a, &blindings_b])
+            .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(DISJOINT_SCHEME_ID, proof_bytes, commitment))
+}
+
+/// Verify a disjointness proof against the two published set roots.
+pub fn verify_disjoint(proof: Vec<u8>, root_a: Vec<u8>, root_b: Vec<u8>) -> bool {
+    let proof = match parse_and_validate_proof(&proof, DISJOINT_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+    if proof.commitment != root_a || proof.proof.get(0..32) != Some(root_b.as_slice()) {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_disjoint(&backend_proof)
+}
+
+/// Prove that a hidden value is one of two public options (e.g. a yes/no vote) with a
+/// two-branch OR proof over a Pedersen commitment. Much smaller and faster than
+/// `prove_membership`, which needs the Groth16 setup. Errors if the options are equal or
+/// `value` is neither of them.
+pub fn prove_binary_choice(value: u64, option_a: u64, option_b: u64) -> ZkpResult<Vec<u8>> {
+    validate_binary_choice_params(value, option_a, option_b)?;
+
+    let backend_proof = BulletproofsBackend::prove_binary_choice(value, option_a, option_b)
+        .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(
+        BINARY_CHOICE_SCHEME_ID,
+        proof_bytes,
+        commitment,
+    ))
+}
+
+pub fn verify_binary_choice(proof: Vec<u8>, option_a: u64, option_b: u64) -> bool {
+    let proof = match parse_and_validate_proof(&proof, BINARY_CHOICE_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_binary_choice(&backend_proof, option_a, option_b)
+}
+
+/// Prove `value` is among the top `max_rank` elements of an ascending list (e.g. "my score is in
+/// the top 10") without revealing which. The proof embeds the top `max_rank` elements and the
+/// chain digest of the rest, so the verifier checks it against the published
+/// [`crate::utils::commitment::sorted_set_root`] without seeing the lower part of the list.
+/// `max_rank` is capped at `MAX_SET_SIZE` (the top part is proven with the membership circuit).
+pub fn prove_rank(value: u64, sorted_set: Vec<u64>, max_rank: usize) -> ZkpResult<Vec<u8>> {
+    let split = validate_rank_params(value, &sorted_set, max_rank)?;
+    let top = &sorted_set[split..];
+    validate_set_size(top, MAX_SET_SIZE)?;
+
+    let (membership, commitment) = membership_payload(value, top)?;
+    let mut payload = Vec::with_capacity(32 + membership.len());
+    payload.extend_from_slice(&extend_set_chain(None, &sorted_set[..split]));
+    payload.extend_from_slice(&membership);
+
+    Ok(Proof::new(RANK_SCHEME_ID, payload, commitment).to_bytes())
+}
+
+/// Verify a rank proof against the published list root and `max_rank`.
+pub fn verify_rank(proof: Vec<u8>, set_root: Vec<u8>, max_rank: usize) -> bool {
+    let proof = match parse_and_validate_proof(&proof, RANK_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+
+    let prefix_digest: [u8; 32] = match proof.proof.get(0..32).and_then(|b| b.try_into().ok()) {
+        Some(d) => d,
+        None => return false,
+    };
+    let (top, snark_bytes) = match deserialize_embedded_set_prefix(&proof.proof[32..], MAX_SET_SIZE)
+    {
+        Some(p) => p,
+        None => return false,
+    };
+    if snark_bytes.is_empty() || top.windows(2).any(|w| w[0] > w[1]) {
+        return false;
+    }
+    // Fewer than `max_rank` elements are only allowed when they are the whole list.
+    if top.len() > max_rank
+        || (top.len() < max_rank && prefix_digest != extend_set_chain(None, &[]))
+    {
+        return false;
+    }
+    if extend_set_chain(Some(&prefix_digest), &top).as_slice() != set_root.as_slice() {
+        return false;
+    }
+
+    SnarkBackend::verify_membership_zk(snark_bytes, &top, &proof.commitment)
+}
+
+/// Prove the committed Bloom filter `filter_bits` (see `utils::bloom`) accepts `element`, by
+/// opening the `num_hashes` bits it maps to; the rest of the filter, and so the set and its
+/// size, stay hidden. The element itself is revealed.
+///
+/// **False positives:** a Bloom filter also accepts elements that were never inserted, with
+/// probability `filter_params.false_positive_rate(n)` for `n` inserted elements, and anyone
+/// holding the filter can prove for such an element. Use this only where a small, known
+/// false-acceptance rate is tolerable (e.g. screening), never as proof of set membership.
+///
+/// Errors if the params or filter length are invalid, or the filter does not accept `element`.
+pub fn prove_membership_bloom(
+    element: u64,
+    filter_params: &BloomFilterParams,
+    filter_bits: &[u8],
+) -> ZkpResult<Vec<u8>> {
+    let tree = BloomTree::build(filter_params, filter_bits)?;
+    let positions = bloom_positions(element, filter_params.num_bits, filter_params.num_hashes);
+    if positions
+        .iter()
+        .any(|&pos| filter_bits[pos / 8] >> (pos % 8) & 1 == 0)
+    {
+        return Err(ZkpError::InvalidInput(
+            "element is not accepted by the filter".to_string(),
+        ));
+    }
+
+    let mut payload = Vec::new();
+    payload.extend_from_slice(&(filter_params.num_bits as u32).to_le_bytes());
+    payload.extend_from_slice(&(filter_params.num_hashes as u32).to_le_bytes());
+    payload.extend_from_slice(&element.to_le_bytes());
+    for pos in positions {
+        payload.extend_from_slice(&leaf_salt(&filter_params.salt, pos));
+        for sibling in tree.path(pos) {
+            payload.extend_from_slice(&sibling);
+        }
+    }
+
+    let commitment = bloom_commitment(
+        filter_params.num_bits,
+        filter_params.num_hashes,
+        tree.root(),
+    );
+    Ok(create_proof(BLOOM_SCHEME_ID, payload, commitment.to_vec()))
+}
+
+/// Verify a proof from [`prove_membership_bloom`] for `element` against the published
+/// `filter_commitment` (see `utils::bloom::commit_bloom_filter`). Subject to the filter's
+/// false-positive rate.
+pub fn verify_membership_bloom(proof: Vec<u8>, filter_commitment: &[u8], element: u64) -> bool {
+    let proof = match parse_and_validate_proof(&proof, BLOOM_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+    if proof.commitment != filter_commitment {
+        return false;
+    }
+    verify_bloom_payload(&proof.proof, &proof.commitment) == Some(element)
 }
@@ -0,0 +1,91 @@
+//! Proof of solvency: the assets of a private balance sheet sum to at least its liabilities.
+//! Every entry is published as a Pedersen commitment; the proof carries those commitments and
+//! commits to the two totals as their homomorphic sums, so the entries and the totals stay
+//! hidden. One aggregated range proof covers both totals and their difference, as for
+//! `inequality_proof::prove_greater_than`, and aggregated range proofs over the entries show
+//! none of them commits to a negative amount.
+
+use crate::backend::bulletproofs::{scalar_from_canonical, BulletproofsBackend};
+use crate::utils::error_handling::{ZkpError, ZkpResult};
+use crate::utils::proof_helpers::{
+    create_proof, extract_bulletproofs_components, parse_and_validate_proof,
+    reconstruct_bulletproofs_proof, validate_standard_commitment,
+};
+use crate::utils::validation::validate_solvency_params;
+use zeroize::Zeroizing;
+
+const SCHEME_ID: u8 = 35;
+
+/// Prove `sum(assets) >= sum(liabilities)` for entries published as
+/// `commit_value_pedersen(entry, blinding)`, given one blinding per entry. The proof commitment
+/// is the sum of the asset commitments; the payload carries the entry commitments, assets
+/// first.
+pub fn prove_solvency(
+    assets: Vec<u64>,
+    asset_blindings: Vec<[u8; 32]>,
+    liabilities: Vec<u64>,
+    liability_blindings: Vec<[u8; 32]>,
+) -> ZkpResult<Vec<u8>> {
+    validate_solvency_params(&assets, &liabilities)?;
+    let parse = |blindings: &[[u8; 32]], entries: usize, name: &str| {
+        if blindings.len() != entries {
+            return Err(ZkpError::InvalidInput(format!(
+                "expected {} {} blindings, got {}",
+                entries,
+                name,
+                blindings.len()
+            )));
+        }
+        blindings
+            .iter()
+            .map(scalar_from_canonical)
+            .collect::<Option<Vec<_>>>()
+            .map(Zeroizing::new)
+            .ok_or_else(|| ZkpError::InvalidInput("blinding is not a canonical scalar".into()))
+    };
+    let asset_blindings = parse(&asset_blindings, assets.len(), "asset")?;
+    let liability_blindings = parse(&liability_blindings, liabilities.len(), "liability")?;
+
+    let backend_proof = BulletproofsBackend::prove_solvency(
+        &assets,
+        &liabilities,
+        [&asset_blindings, &liability_blindings],
+    )
+    .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(SCHEME_ID, proof_bytes, commitment))
+}
+
+/// Verify a solvency proof against the published asset and liability entry commitments.
+pub fn verify_solvency(
+    proof: Vec<u8>,
+    asset_commitments: &[Vec<u8>],
+    liability_commitments: &[Vec<u8>],
+) -> bool {
+    let proof = match parse_and_validate_proof(&proof, SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+    let mut published =
+        Vec::with_capacity(8 + 32 * (asset_commitments.len() + liability_commitments.len()));
+    published.extend_from_slice(&(asset_commitments.len() as u32).to_le_bytes());
+    published.extend_from_slice(&(liability_commitments.len() as u32).to_le_bytes());
+    for c in asset_commitments.iter().chain(liability_commitments) {
+        if c.len() != 32 {
+            return false;
+        }
+        published.extend_from_slice(c);
+    }
+    if proof.proof.get(..published.len()) != Some(published.as_slice()) {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_solvency(&backend_proof)
+}
@@ -1,12 +1,26 @@
 use crate::backend::bulletproofs::BulletproofsBackend;
-use crate::utils::error_handling::ZkpResult;
+use crate::backend::snark::SnarkBackend;
+use crate::utils::encoding::read_u64_le;
+use crate::utils::error_handling::{ZkpError, ZkpResult};
+use crate::utils::limits::MAX_AGGREGATED_RANGE_VALUES;
 use crate::utils::proof_helpers::{
     create_proof, extract_bulletproofs_components, parse_and_validate_proof,
     reconstruct_bulletproofs_proof, validate_standard_commitment,
 };
-use crate::utils::validation::validate_threshold_params;
+use crate::utils::validation::{
+    validate_all_positive, validate_average_threshold_params, validate_count_above_params,
+    validate_exact_sum_params, validate_is_maximum_params, validate_median_threshold_params,
+    validate_threshold_params, validate_variance_below_params,
+};
 
 const SCHEME_ID: u8 = 3;
+const POSITIVE_THRESHOLD_SCHEME_ID: u8 = 13;
+const EXACT_SUM_SCHEME_ID: u8 = 15;
+const IS_MAXIMUM_SCHEME_ID: u8 = 18;
+const COUNT_ABOVE_SCHEME_ID: u8 = 24;
+const AVERAGE_THRESHOLD_SCHEME_ID: u8 = 33;
+const MEDIAN_THRESHOLD_SCHEME_ID: u8 = 39;
+const VARIANCE_BELOW_SCHEME_ID: u8 = 40;
 
 pub fn prove_threshold(values: Vec<u64>, threshold: u64) -> ZkpResult<Vec<u8>> {
     prove_threshold_with_bits(values, threshold, 64)
@@ -29,6 +43,40 @@ pub fn prove_threshold_with_bits(
     Ok(create_proof(SCHEME_ID, proof_bytes, commitment))
 }
 
+/// Threshold proof over values from an iterator, for inputs too large to collect: the sum is
+/// accumulated with overflow checks as the values arrive and only the sum is proven, so the
+/// result is the same kind of proof as [`prove_threshold`] and verifies with
+/// [`verify_threshold`].
+pub fn prove_threshold_streaming(
+    values: impl Iterator<Item = u64>,
+    threshold: u64,
+) -> ZkpResult<Vec<u8>> {
+    let mut count = 0usize;
+    let mut sum = 0u64;
+    for value in values {
+        sum = sum.checked_add(value).ok_or_else(|| {
+            ZkpError::InvalidInput("integer overflow in sum calculation".to_string())
+        })?;
+        count += 1;
+    }
+    if count == 0 {
+        return Err(ZkpError::InvalidInput("values cannot be empty".to_string()));
+    }
+    if sum < threshold {
+        return Err(ZkpError::InvalidInput(format!(
+            "sum {} is less than threshold {}",
+            sum, threshold
+        )));
+    }
+
+    let backend_proof = BulletproofsBackend::prove_threshold_sum(sum, threshold, 64)
+        .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(SCHEME_ID, proof_bytes, commitment))
+}
+
 pub fn verify_threshold(proof: Vec<u8>, threshold: u64) -> bool {
     let proof = match parse_and_validate_proof(&proof, SCHEME_ID) {
         Ok(p) => p,
@@ -42,3 +90,274 @@ pub fn verify_threshold(proof: Vec<u8>, threshold: u64) -> bool {
     let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
     BulletproofsBackend::verify_threshold(&backend_proof, threshold)
 }
+
+/// Threshold proof that also shows every contributing value is at least 1, so zero or padding
+/// entries cannot count toward the sum. Uses per-value commitments and one aggregated range
+/// proof, so at most `MAX_AGGREGATED_RANGE_VALUES - 1` values are supported.
+pub fn prove_positive_threshold(values: Vec<u64>, threshold: u64) -> ZkpResult<Vec<u8>> {
+    validate_threshold_params(&values, threshold)?;
+    validate_all_positive(&values)?;
+    if values.len() >= MAX_AGGREGATED_RANGE_VALUES {
+        return Err(ZkpError::InvalidInput(format!(
+            "at most {} values supported",
+            MAX_AGGREGATED_RANGE_VALUES - 1
+        )));
+    }
+
+    let backend_proof = BulletproofsBackend::prove_positive_threshold(&values, threshold)
+        .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(
+        POSITIVE_THRESHOLD_SCHEME_ID,
+        proof_bytes,
+        commitment,
+    ))
+}
+
+pub fn verify_positive_threshold(proof: Vec<u8>, threshold: u64) -> bool {
+    let proof = match parse_and_validate_proof(&proof, POSITIVE_THRESHOLD_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_positive_threshold(&backend_proof, threshold)
+}
+
+/// Prove hidden values sum to exactly the public `total` (e.g. ledger reconciliation). The proof
+/// carries one commitment per value and shows their sum opens to `total`; values themselves
+/// are not range-checked, so the statement holds modulo the group order.
+pub fn prove_exact_sum(values: Vec<u64>, total: u64) -> ZkpResult<Vec<u8>> {
+    validate_exact_sum_params(&values, total)?;
+
+    let backend_proof =
+        BulletproofsBackend::prove_exact_sum(&values, total).map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(EXACT_SUM_SCHEME_ID, proof_bytes, commitment))
+}
+
+pub fn verify_exact_sum(proof: Vec<u8>, total: u64) -> bool {
+    let proof = match parse_and_validate_proof(&proof, EXACT_SUM_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_exact_sum(&backend_proof, total)
+}
+
+/// Prove the mean of the hidden `values` is at least `threshold`, without revealing the values
+/// or their sum. The number of values is public and part of the statement; the commitment
+/// opens to the sum.
+pub fn prove_average_threshold(values: Vec<u64>, threshold: u64) -> ZkpResult<Vec<u8>> {
+    validate_average_threshold_params(&values, threshold)?;
+
+    let backend_proof = BulletproofsBackend::prove_average_threshold(&values, threshold)
+        .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(
+        AVERAGE_THRESHOLD_SCHEME_ID,
+        proof_bytes,
+        commitment,
+    ))
+}
+
+/// Verify an average-threshold proof over exactly `count` values.
+pub fn verify_average_threshold(proof: Vec<u8>, threshold: u64, count: usize) -> bool {
+    let proof = match parse_and_validate_proof(&proof, AVERAGE_THRESHOLD_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_average_threshold(&backend_proof, threshold, count)
+}
+
+/// Prove the median of the hidden values exceeds `threshold`; for an even count the lower of
+/// the two middle values must, so the usual median does too. The values are committed in
+/// sorted order, shown sorted by range proofs on adjacent differences as in the consistency
+/// proof. At most `MAX_AGGREGATED_RANGE_VALUES` values; the count is public.
+pub fn prove_median_threshold(values: Vec<u64>, threshold: u64) -> ZkpResult<Vec<u8>> {
+    let sorted = validate_median_threshold_params(&values, threshold)?;
+
+    let backend_proof = BulletproofsBackend::prove_median_threshold(&sorted, threshold)
+        .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(
+        MEDIAN_THRESHOLD_SCHEME_ID,
+        proof_bytes,
+        commitment,
+    ))
+}
+
+pub fn verify_median_threshold(proof: Vec<u8>, threshold: u64) -> bool {
+    let proof = match parse_and_validate_proof(&proof, MEDIAN_THRESHOLD_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_median_threshold(&backend_proof, threshold)
+}
+
+/// Prove the population variance of the hidden values is below `bound`, e.g. to attest to a
+/// series' stability without publishing it. At most
+/// [`crate::backend::snark::variance::MAX_VARIANCE_VALUES`] values; the count is public and the
+/// commitment is a blinded hash of the values.
+pub fn prove_variance_below(values: Vec<u64>, bound: u64) -> ZkpResult<Vec<u8>> {
+    validate_variance_below_params(&values, bound)?;
+
+    let (snark_proof, commitment) = SnarkBackend::prove_variance_below_zk(&values, bound)
+        .ok_or_else(|| {
+            ZkpError::ProofGenerationFailed("SNARK variance proof generation failed".to_string())
+        })?;
+
+    let mut payload = Vec::with_capacity(12 + snark_proof.len());
+    payload.extend_from_slice(&bound.to_le_bytes());
+    payload.extend_from_slice(&(values.len() as u32).to_le_bytes());
+    payload.extend_from_slice(&snark_proof);
+    Ok(create_proof(
+        VARIANCE_BELOW_SCHEME_ID,
+        payload,
+        commitment.to_vec(),
+    ))
+}
+
+pub fn verify_variance_below(proof: Vec<u8>, bound: u64) -> bool {
+    let proof = match parse_and_validate_proof(&proof, VARIANCE_BELOW_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+    read_u64_le(&proof.proof, 0) == Some(bound)
+        && verify_variance_payload(&proof.proof, &proof.commitment)
+}
+
+/// Check a variance payload `[bound 8][u32 n][snark proof]` against its commitment.
+pub(crate) fn verify_variance_payload(payload: &[u8], commitment: &[u8]) -> bool {
+    let (Some(bound), Some(count)) = (
+        read_u64_le(payload, 0),
+        payload
+            .get(8..12)
+            .and_then(|b| b.try_into().ok())
+            .map(u32::from_le_bytes),
+    ) else {
+        return false;
+    };
+    payload.len() > 12
+        && SnarkBackend::verify_variance_below_zk(&payload[12..], commitment, bound, count as usize)
+}
+
+/// Prove the standard deviation of the hidden values is below `bound`, as a variance proof
+/// against `bound²`; verify it with [`verify_std_dev_below`].
+pub fn prove_std_dev_below(values: Vec<u64>, bound: u64) -> ZkpResult<Vec<u8>> {
+    let variance_bound = bound.checked_mul(bound).ok_or_else(|| {
+        ZkpError::InvalidInput(format!("standard deviation bound {} is too large", bound))
+    })?;
+    prove_variance_below(values, variance_bound)
+}
+
+pub fn verify_std_dev_below(proof: Vec<u8>, bound: u64) -> bool {
+    bound
+        .checked_mul(bound)
+        .is_some_and(|variance_bound| verify_variance_below(proof, variance_bound))
+}
+
+/// Prove `values[claimed_max_index]` is the largest of the hidden values (e.g. the winning bid)
+/// without revealing any of them. The index is public; the values are committed. On ties any
+/// index holding the maximum may be claimed, since the proof only shows `values[i] <= max`.
+/// Errors if a larger value exists or fewer than two values are given; at most
+/// `MAX_AGGREGATED_RANGE_VALUES + 1` values are supported. The commitment opens to the maximum.
+pub fn prove_is_maximum(values: Vec<u64>, claimed_max_index: usize) -> ZkpResult<Vec<u8>> {
+    validate_is_maximum_params(&values, claimed_max_index)?;
+    if values.len() > MAX_AGGREGATED_RANGE_VALUES + 1 {
+        return Err(ZkpError::InvalidInput(format!(
+            "at most {} values supported",
+            MAX_AGGREGATED_RANGE_VALUES + 1
+        )));
+    }
+
+    let backend_proof = BulletproofsBackend::prove_is_maximum(&values, claimed_max_index)
+        .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(IS_MAXIMUM_SCHEME_ID, proof_bytes, commitment))
+}
+
+pub fn verify_is_maximum(proof: Vec<u8>) -> bool {
+    let proof = match parse_and_validate_proof(&proof, IS_MAXIMUM_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_is_maximum(&backend_proof)
+}
+
+/// Largest number of values [`prove_count_above`] accepts: each value takes four slots of
+/// one aggregated range proof, plus one for the count.
+pub const MAX_COUNT_ABOVE_VALUES: usize = (MAX_AGGREGATED_RANGE_VALUES - 1) / 4;
+
+/// Prove at least `min_count` of the hidden `values` are strictly greater than `threshold`,
+/// without revealing the values or which of them are above. Each value is committed with a
+/// hidden selector bit; selected values are range-proved above the threshold and the selectors
+/// must sum to at least `min_count`. The commitment opens to the actual count. Errors if fewer
+/// than `min_count` values exceed the threshold or more than [`MAX_COUNT_ABOVE_VALUES`] are given.
+pub fn prove_count_above(values: Vec<u64>, threshold: u64, min_count: usize) -> ZkpResult<Vec<u8>> {
+    validate_count_above_params(&values, threshold, min_count)?;
+    if values.len() > MAX_COUNT_ABOVE_VALUES {
+        return Err(ZkpError::InvalidInput(format!(
+            "at most {} values supported",
+            MAX_COUNT_ABOVE_VALUES
+        )));
+    }
+
+    let backend_proof = BulletproofsBackend::prove_count_above(&values, threshold, min_count)
+        .map_err(ZkpError::BackendError)?;
+
+    let (proof_bytes, commitment) = extract_bulletproofs_components(&backend_proof)?;
+
+    Ok(create_proof(COUNT_ABOVE_SCHEME_ID, proof_bytes, commitment))
+}
+
+pub fn verify_count_above(proof: Vec<u8>, threshold: u64, min_count: usize) -> bool {
+    let proof = match parse_and_validate_proof(&proof, COUNT_ABOVE_SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+
+    if validate_standard_commitment(&proof.commitment).is_err() {
+        return false;
+    }
+
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_count_above(&backend_proof, threshold, min_count)
+}
@@ -0,0 +1,171 @@
+//! Range proofs over hidden UNIX timestamps (seconds, UTC) with calendar-aware bounds. All
+//! proofs here are signed range proofs from [`crate::proof::range_proof`], so timestamps
+//! before 1970 are supported.
+
+use crate::proof::range_proof::{prove_range_signed, verify_range_signed};
+use crate::utils::error_handling::{ZkpError, ZkpResult};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// A proleptic Gregorian calendar date in UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CivilDate {
+    pub year: i32,
+    pub month: u8,
+    pub day: u8,
+}
+
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl CivilDate {
+    /// Errors if `month` or `day` does not name a real date (e.g. February 30).
+    pub fn new(year: i32, month: u8, day: u8) -> ZkpResult<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(ZkpError::InvalidInput(format!(
+                "{:04}-{:02}-{:02} is not a valid date",
+                year, month, day
+            )));
+        }
+        Ok(Self { year, month, day })
+    }
+
+    /// Days since 1970-01-01 (negative before it).
+    fn days_since_epoch(self) -> i64 {
+        let (month, day) = (i64::from(self.month), i64::from(self.day));
+        let year = i64::from(self.year) - i64::from(month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// The date containing `timestamp`.
+    pub fn from_unix_seconds(timestamp: i64) -> Self {
+        let days = timestamp.div_euclid(SECONDS_PER_DAY) + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Self {
+            year: year as i32,
+            month,
+            day,
+        }
+    }
+
+    /// First second of the day.
+    pub fn start_unix_seconds(self) -> i64 {
+        self.days_since_epoch() * SECONDS_PER_DAY
+    }
+
+    /// Last second of the day.
+    pub fn end_unix_seconds(self) -> i64 {
+        self.start_unix_seconds() + SECONDS_PER_DAY - 1
+    }
+
+    /// The same calendar date `years` earlier; February 29 maps to February 28 in a common year.
+    pub fn years_before(self, years: u32) -> ZkpResult<Self> {
+        let year = i32::try_from(years)
+            .ok()
+            .and_then(|y| self.year.checked_sub(y))
+            .ok_or_else(|| ZkpError::InvalidInput(format!("{} years is out of range", years)))?;
+        let day = self.day.min(days_in_month(year, self.month));
+        Self::new(year, self.month, day)
+    }
+}
+
+impl std::str::FromStr for CivilDate {
+    type Err = ZkpError;
+
+    /// `YYYY-MM-DD`.
+    fn from_str(s: &str) -> Result<Self, ZkpError> {
+        let invalid = || ZkpError::InvalidInput(format!("{} is not a YYYY-MM-DD date", s));
+        let mut parts = s.splitn(3, '-');
+        let (Some(year), Some(month), Some(day)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        Self::new(
+            year.parse().map_err(|_| invalid())?,
+            month.parse().map_err(|_| invalid())?,
+            day.parse().map_err(|_| invalid())?,
+        )
+    }
+}
+
+/// Prove a hidden `timestamp` lies in the public window `[not_before, not_after]`.
+pub fn prove_timestamp_in_window(
+    timestamp: i64,
+    not_before: i64,
+    not_after: i64,
+) -> ZkpResult<Vec<u8>> {
+    prove_range_signed(timestamp, not_before, not_after)
+}
+
+pub fn verify_timestamp_in_window(proof: Vec<u8>, not_before: i64, not_after: i64) -> bool {
+    verify_range_signed(proof, not_before, not_after)
+}
+
+/// Prove a hidden timestamp falls on a day in `[first_day, last_day]`, both days included.
+pub fn prove_timestamp_between_dates(
+    timestamp: i64,
+    first_day: CivilDate,
+    last_day: CivilDate,
+) -> ZkpResult<Vec<u8>> {
+    prove_timestamp_in_window(
+        timestamp,
+        first_day.start_unix_seconds(),
+        last_day.end_unix_seconds(),
+    )
+}
+
+pub fn verify_timestamp_between_dates(
+    proof: Vec<u8>,
+    first_day: CivilDate,
+    last_day: CivilDate,
+) -> bool {
+    verify_timestamp_in_window(
+        proof,
+        first_day.start_unix_seconds(),
+        last_day.end_unix_seconds(),
+    )
+}
+
+/// Latest birth timestamp that makes someone at least `years` old on `as_of`: the end of the
+/// same calendar day `years` earlier (see [`CivilDate::years_before`] for February 29).
+pub fn age_cutoff(years: u32, as_of: CivilDate) -> ZkpResult<i64> {
+    Ok(as_of.years_before(years)?.end_unix_seconds())
+}
+
+/// Prove the person born at the hidden `birth_timestamp` is at least `years` old on `as_of`.
+pub fn prove_age_at_least(
+    birth_timestamp: i64,
+    years: u32,
+    as_of: CivilDate,
+) -> ZkpResult<Vec<u8>> {
+    prove_timestamp_in_window(birth_timestamp, i64::MIN, age_cutoff(years, as_of)?)
+}
+
+pub fn verify_age_at_least(proof: Vec<u8>, years: u32, as_of: CivilDate) -> bool {
+    match age_cutoff(years, as_of) {
+        Ok(cutoff) => verify_timestamp_in_window(proof, i64::MIN, cutoff),
+        Err(_) => false,
+    }
+}
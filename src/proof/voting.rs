@@ -0,0 +1,161 @@
+//! Private voting over a public eligibility list. Each voter publishes a voter key derived
+//! from a 32-byte secret; a vote proves the key is in the list and that the sealed ballot
+//! selects exactly one option, revealing neither. The vote carries a nullifier, fixed per voter
+//! and election, so a tally counts each voter once. The ballot is opened to the tallier with
+//! the choice and the blinding returned by [`prove_vote`].
+
+use crate::backend::snark::SnarkBackend;
+use crate::proof::Proof;
+use crate::utils::commitment::blinding_from_slice;
+use crate::utils::encoding::read_u64_le;
+use crate::utils::error_handling::{ZkpError, ZkpResult};
+use crate::utils::proof_helpers::parse_and_validate_proof;
+use crate::utils::validation::validate_vote_params;
+
+const SCHEME_ID: u8 = 37;
+
+/// Public values of a vote payload `[root 32][election_id 8][nullifier 32][u32 num_options]
+/// [snark proof]`.
+pub(crate) struct VoteStatement<'a> {
+    root: &'a [u8],
+    election_id: u64,
+    nullifier: &'a [u8],
+    num_options: usize,
+    snark_proof: &'a [u8],
+}
+
+impl<'a> VoteStatement<'a> {
+    pub(crate) fn read(payload: &'a [u8]) -> Option<Self> {
+        let num_options = payload
+            .get(72..76)?
+            .try_into()
+            .ok()
+            .map(u32::from_le_bytes)?;
+        let snark_proof = &payload[76..];
+        if snark_proof.is_empty() {
+            return None;
+        }
+        Some(Self {
+            root: &payload[..32],
+            election_id: read_u64_le(payload, 32)?,
+            nullifier: &payload[40..72],
+            num_options: num_options as usize,
+            snark_proof,
+        })
+    }
+
+    /// Check the SNARK against the embedded statement and `ballot`.
+    pub(crate) fn verify(&self, ballot: &[u8]) -> bool {
+        SnarkBackend::verify_vote_zk(
+            self.snark_proof,
+            self.root,
+            self.election_id,
+            self.nullifier,
+            ballot,
+            self.num_options,
+        )
+    }
+}
+
+fn voter_secret(secret: &[u8]) -> ZkpResult<[u8; 32]> {
+    secret.try_into().map_err(|_| {
+        ZkpError::InvalidInput(format!(
+            "voter secret must be 32 bytes, got {}",
+            secret.len()
+        ))
+    })
+}
+
+fn parse_voter_keys(keys: Vec<Vec<u8>>) -> ZkpResult<Vec<[u8; 32]>> {
+    keys.into_iter()
+        .map(|k| {
+            <[u8; 32]>::try_from(k.as_slice()).map_err(|_| {
+                ZkpError::InvalidInput(format!("voter key must be 32 bytes, got {}", k.len()))
+            })
+        })
+        .collect()
+}
+
+/// Voter key for `secret` (32 random bytes kept by the voter), to publish in the eligibility
+/// list.
+pub fn voter_key(secret: &[u8]) -> ZkpResult<Vec<u8>> {
+    Ok(SnarkBackend::voter_key(&voter_secret(secret)?).to_vec())
+}
+
+/// Root of the eligibility list; the list's order and duplicates do not matter.
+pub fn eligibility_root(voter_keys: Vec<Vec<u8>>) -> ZkpResult<Vec<u8>> {
+    Ok(SnarkBackend::eligibility_root(parse_voter_keys(voter_keys)?)?.to_vec())
+}
+
+/// Vote for option `choice` (`0..num_options`) in election `election_id` as the holder of
+/// `secret`, whose voter key must be in `voter_keys`. Returns the vote and the ballot's
+/// blinding, which together with `choice` opens the ballot (see [`open_vote`]).
+pub fn prove_vote(
+    secret: &[u8],
+    voter_keys: Vec<Vec<u8>>,
+    election_id: u64,
+    num_options: usize,
+    choice: usize,
+) -> ZkpResult<(Vec<u8>, [u8; 32])> {
+    validate_vote_params(num_options, choice)?;
+    let secret = voter_secret(secret)?;
+    let keys = parse_voter_keys(voter_keys)?;
+    if !keys.contains(&SnarkBackend::voter_key(&secret)) {
+        return Err(ZkpError::InvalidInput(
+            "voter key is not in the eligibility list".to_string(),
+        ));
+    }
+
+    let vote = SnarkBackend::prove_vote_zk(&secret, keys, election_id, num_options, choice)
+        .ok_or_else(|| {
+            ZkpError::ProofGenerationFailed("SNARK vote proof generation failed".to_string())
+        })?;
+
+    let mut payload = Vec::with_capacity(76 + vote.proof.len());
+    payload.extend_from_slice(&vote.root);
+    payload.extend_from_slice(&election_id.to_le_bytes());
+    payload.extend_from_slice(&vote.nullifier);
+    payload.extend_from_slice(&(num_options as u32).to_le_bytes());
+    payload.extend_from_slice(&vote.proof);
+    let proof = Proof::new(SCHEME_ID, payload, vote.ballot.to_vec());
+    Ok((proof.to_bytes(), vote.blinding))
+}
+
+/// Verify a vote cast by an eligible voter in election `election_id` with `num_options`
+/// options, against the eligibility `root` from [`eligibility_root`].
+pub fn verify_vote(proof: Vec<u8>, root: &[u8], election_id: u64, num_options: usize) -> bool {
+    let proof = match parse_and_validate_proof(&proof, SCHEME_ID) {
+        Ok(p) => p,
+        Err(_) => return false,
+    };
+    match VoteStatement::read(&proof.proof) {
+        Some(statement) => {
+            statement.root == root
+                && statement.election_id == election_id
+                && statement.num_options == num_options
+                && statement.verify(&proof.commitment)
+        }
+        None => false,
+    }
+}
+
+/// The vote's nullifier: votes with equal nullifiers in one election come from the same
+/// voter, so a tally keeps only one of them.
+pub fn vote_nullifier(proof: &[u8]) -> ZkpResult<Vec<u8>> {
+    let proof = parse_and_validate_proof(proof, SCHEME_ID)?;
+    let statement = VoteStatement::read(&proof.proof)
+        .ok_or_else(|| ZkpError::InvalidProofFormat("invalid vote payload".to_string()))?;
+    Ok(statement.nullifier.to_vec())
+}
+
+/// Whether the vote's ballot seals `choice` under `blinding`. Checks the opening only; verify
+/// the vote itself with [`verify_vote`].
+pub fn open_vote(proof: &[u8], choice: usize, blinding: &[u8]) -> ZkpResult<bool> {
+    let proof = parse_and_validate_proof(proof, SCHEME_ID)?;
+    let blinding = blinding_from_slice(blinding)?;
+    Ok(SnarkBackend::ballot_opens(
+        &proof.commitment,
+        choice,
+        &blinding,
+    ))
+}
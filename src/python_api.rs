@@ -1,14 +1,37 @@
 //! Python bindings (PyO3). Built when the `python` feature is enabled.
 
+use crate::utils::error_handling::ZkpError;
 use pyo3::prelude::*;
 use pyo3::IntoPyObject;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Off by default: a Rust panic then surfaces as pyo3's `PanicException`, which derives from
+/// `BaseException` and escapes `except Exception`. [`set_panic_boundary`] turns it on.
+static PANIC_BOUNDARY: AtomicBool = AtomicBool::new(false);
+
+/// Run a binding body; with the panic boundary on, a panic becomes
+/// `ZkpError::BackendError("internal panic: ...")`, raised as `RuntimeError`.
+fn guarded<T>(f: impl FnOnce() -> PyResult<T>) -> PyResult<T> {
+    if !PANIC_BOUNDARY.load(Ordering::Relaxed) {
+        return f();
+    }
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let msg = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic payload".to_string());
+        Err(ZkpError::BackendError(format!("internal panic: {}", msg)).into())
+    })
+}
 
 macro_rules! py_zkp {
     ($name:ident, $ret:ty, $($arg:ident : $t:ty),* => $e:expr) => {
         #[pyfunction]
         fn $name($($arg: $t),*) -> PyResult<$ret> {
-            $e.map_err(Into::into)
+            guarded(|| $e.map_err(Into::into))
         }
     };
 }
@@ -17,49 +40,178 @@ macro_rules! py_ok {
     ($name:ident, $ret:ty, $($arg:ident : $t:ty),* => $e:expr) => {
         #[pyfunction]
         fn $name($($arg: $t),*) -> PyResult<$ret> {
-            Ok($e)
+            guarded(|| Ok($e))
         }
     };
 }
 
-py_zkp!(prove_range, Vec<u8>, value: u64, min: u64, max: u64 => crate::proof::range_proof::prove_range(value, min, max));
-py_ok!(verify_range, bool, proof: Vec<u8>, min: u64, max: u64 => crate::proof::range_proof::verify_range(proof, min, max));
+/// Enable or disable converting panics in binding calls into `RuntimeError`.
+#[pyfunction]
+fn set_panic_boundary(enabled: bool) {
+    PANIC_BOUNDARY.store(enabled, Ordering::Relaxed);
+}
+
+py_ok!(verify_and_extract, Option<Vec<u8>>, proof: Vec<u8>, min: u64, max: u64 => crate::proof::range_proof::verify_and_extract(proof, min, max).map(|c| c.to_vec()));
+py_zkp!(prove_range_keyed, (Vec<u8>, Vec<u8>), value: u64, min: u64, max: u64 => crate::proof::range_proof::prove_range_keyed(value, min, max).map(|(proof, blinding)| (proof, blinding.to_vec())));
+py_zkp!(prove_range_offset, Vec<u8>, value: u64, offset: u64, min: u64, max: u64 => crate::proof::range_proof::prove_range_offset(value, offset, min, max));
+py_zkp!(prove_range_signed, Vec<u8>, value: i64, min: i64, max: i64 => crate::proof::range_proof::prove_range_signed(value, min, max));
+py_ok!(verify_range_signed, bool, proof: Vec<u8>, min: i64, max: i64 => crate::proof::range_proof::verify_range_signed(proof, min, max));
+py_zkp!(prove_timestamp_in_window, Vec<u8>, timestamp: i64, not_before: i64, not_after: i64 => crate::proof::time_range::prove_timestamp_in_window(timestamp, not_before, not_after));
+py_ok!(verify_timestamp_in_window, bool, proof: Vec<u8>, not_before: i64, not_after: i64 => crate::proof::time_range::verify_timestamp_in_window(proof, not_before, not_after));
+py_zkp!(prove_timestamp_between_dates, Vec<u8>, timestamp: i64, first_day: String, last_day: String => crate::proof::time_range::prove_timestamp_between_dates(timestamp, first_day.parse()?, last_day.parse()?));
+py_ok!(verify_timestamp_between_dates, bool, proof: Vec<u8>, first_day: String, last_day: String => crate::proof::time_range::verify_timestamp_between_dates(proof, first_day.parse()?, last_day.parse()?));
+py_zkp!(prove_age_at_least, Vec<u8>, birth_timestamp: i64, years: u32, as_of: String => crate::proof::time_range::prove_age_at_least(birth_timestamp, years, as_of.parse()?));
+py_ok!(verify_age_at_least, bool, proof: Vec<u8>, years: u32, as_of: String => crate::proof::time_range::verify_age_at_least(proof, years, as_of.parse()?));
+py_zkp!(prove_bid_validity, (Vec<u8>, Vec<u8>), bid: u64, budget: u64, min_increment: u64 => crate::proof::auction::prove_bid_validity(bid, budget, min_increment).map(|(proof, blinding)| (proof, blinding.to_vec())));
+py_ok!(verify_bid_validity, bool, proof: Vec<u8>, budget: u64, min_increment: u64 => crate::proof::auction::verify_bid_validity(proof, budget, min_increment));
+py_zkp!(open_bid, bool, proof: Vec<u8>, bid: u64, blinding: Vec<u8> => crate::proof::auction::open_bid(&proof, bid, &blinding));
+py_ok!(verify_range_offset, bool, proof: Vec<u8>, offset: u64, min: u64, max: u64 => crate::proof::range_proof::verify_range_offset(proof, offset, min, max));
+py_ok!(verify_range_any, Option<(u64, u64)>, proof: Vec<u8>, candidates: Vec<(u64, u64)> => crate::proof::range_proof::verify_range_any(proof, candidates));
+py_ok!(verify_range_batch, bool, proofs: Vec<(Vec<u8>, u64, u64)> => crate::proof::range_proof::verify_range_batch(proofs));
+py_zkp!(prove_range_many, Vec<u8>, values: Vec<u64>, min: u64, max: u64 => crate::proof::range_proof::prove_range_many(values, min, max));
+py_ok!(verify_range_many, bool, proof: Vec<u8>, min: u64, max: u64 => crate::proof::range_proof::verify_range_many(proof, min, max));
+py_zkp!(prove_divisible, Vec<u8>, value: u64, modulus: u64 => crate::proof::range_proof::prove_divisible(value, modulus));
+py_ok!(verify_divisible, bool, proof: Vec<u8>, modulus: u64 => crate::proof::range_proof::verify_divisible(proof, modulus));
+
+py_zkp!(prove_weighted_average_range, Vec<u8>, values: Vec<u64>, weights: Vec<u64>, min: u64, max: u64 => crate::proof::range_proof::prove_weighted_average_range(values, weights, min, max));
+py_ok!(verify_weighted_average_range, bool, proof: Vec<u8>, weights_hash: Vec<u8>, min: u64, max: u64 => crate::proof::range_proof::verify_weighted_average_range(proof, &weights_hash, min, max));
+py_zkp!(prove_within_of_mean, Vec<u8>, values: Vec<u64>, my_value: u64, max_deviation: u64 => crate::proof::range_proof::prove_within_of_mean(values, my_value, max_deviation));
+py_ok!(verify_within_of_mean, bool, proof: Vec<u8>, max_deviation: u64 => crate::proof::range_proof::verify_within_of_mean(proof, max_deviation));
+py_zkp!(prove_polynomial_nonneg, Vec<u8>, value: u64, a: i64, b: i64, c: i64 => crate::proof::range_proof::prove_polynomial_nonneg(value, a, b, c));
+py_ok!(verify_polynomial_nonneg, bool, proof: Vec<u8>, a: i64, b: i64, c: i64 => crate::proof::range_proof::verify_polynomial_nonneg(proof, a, b, c));
+py_ok!(weights_hash, Vec<u8>, weights: Vec<u64> => crate::utils::commitment::weights_hash(&weights));
 
-py_zkp!(prove_equality, Vec<u8>, val1: u64, val2: u64 => crate::proof::equality_proof::prove_equality(val1, val2));
-py_ok!(verify_equality, bool, proof: Vec<u8>, val1: u64, val2: u64 => crate::proof::equality_proof::verify_equality(proof, val1, val2));
 py_ok!(verify_equality_with_commitment, bool, proof: Vec<u8>, expected_commitment: Vec<u8> => crate::proof::equality_proof::verify_equality_with_commitment(proof, expected_commitment));
 // MiMC-5 (BN254 Fr) commitment for Groth16 proofs; exposed for `verify_equality_with_commitment` callers.
 py_ok!(snark_commit_value, Vec<u8>, value: u64 => crate::utils::commitment::commit_value_snark(value));
+// Pedersen (Ristretto) commitment for proofs over another party's hidden value, e.g. `prove_range_and_greater`.
+py_zkp!(commit_value_pedersen, Vec<u8>, value: u64, blinding: Vec<u8> => crate::utils::commitment::blinding_from_slice(&blinding).and_then(|b| crate::utils::commitment::commit_value_pedersen(value, &b)));
+py_zkp!(commit_delta_pedersen, Vec<u8>, delta: i64, blinding: Vec<u8> => crate::utils::commitment::blinding_from_slice(&blinding).and_then(|b| crate::utils::commitment::commit_delta_pedersen(delta, &b)));
+py_zkp!(commit_multiset_pedersen, Vec<u8>, set: Vec<u64>, blinding: Vec<u8> => crate::utils::commitment::blinding_from_slice(&blinding).and_then(|b| crate::utils::commitment::commit_multiset_pedersen(&set, &b)));
+#[pyfunction]
+fn commit_set_elements_pedersen(set: Vec<u64>, blindings: Vec<Vec<u8>>) -> PyResult<Vec<u8>> {
+    guarded(|| {
+        let blindings = blindings
+            .iter()
+            .map(|b| crate::utils::commitment::blinding_from_slice(b))
+            .collect::<Result<Vec<_>, _>>()?;
+        crate::utils::commitment::commit_set_elements_pedersen(&set, &blindings).map_err(Into::into)
+    })
+}
+py_ok!(random_pedersen_blinding, Vec<u8>,  => crate::utils::commitment::random_pedersen_blinding().to_vec());
 
 py_zkp!(prove_threshold, Vec<u8>, values: Vec<u64>, threshold: u64 => crate::proof::threshold_proof::prove_threshold(values, threshold));
 py_ok!(verify_threshold, bool, proof: Vec<u8>, threshold: u64 => crate::proof::threshold_proof::verify_threshold(proof, threshold));
+py_zkp!(prove_positive_threshold, Vec<u8>, values: Vec<u64>, threshold: u64 => crate::proof::threshold_proof::prove_positive_threshold(values, threshold));
+py_ok!(verify_positive_threshold, bool, proof: Vec<u8>, threshold: u64 => crate::proof::threshold_proof::verify_positive_threshold(proof, threshold));
+py_zkp!(prove_exact_sum, Vec<u8>, values: Vec<u64>, total: u64 => crate::proof::threshold_proof::prove_exact_sum(values, total));
+py_ok!(verify_exact_sum, bool, proof: Vec<u8>, total: u64 => crate::proof::threshold_proof::verify_exact_sum(proof, total));
+py_ok!(verify_solvency, bool, proof: Vec<u8>, asset_commitments: Vec<Vec<u8>>, liability_commitments: Vec<Vec<u8>> => crate::proof::solvency::verify_solvency(proof, &asset_commitments, &liability_commitments));
+py_zkp!(prove_average_threshold, Vec<u8>, values: Vec<u64>, threshold: u64 => crate::proof::threshold_proof::prove_average_threshold(values, threshold));
+py_ok!(verify_average_threshold, bool, proof: Vec<u8>, threshold: u64, count: usize => crate::proof::threshold_proof::verify_average_threshold(proof, threshold, count));
+py_zkp!(prove_median_threshold, Vec<u8>, values: Vec<u64>, threshold: u64 => crate::proof::threshold_proof::prove_median_threshold(values, threshold));
+py_ok!(verify_median_threshold, bool, proof: Vec<u8>, threshold: u64 => crate::proof::threshold_proof::verify_median_threshold(proof, threshold));
+py_zkp!(prove_variance_below, Vec<u8>, values: Vec<u64>, bound: u64 => crate::proof::threshold_proof::prove_variance_below(values, bound));
+py_ok!(verify_variance_below, bool, proof: Vec<u8>, bound: u64 => crate::proof::threshold_proof::verify_variance_below(proof, bound));
+py_zkp!(prove_std_dev_below, Vec<u8>, values: Vec<u64>, bound: u64 => crate::proof::threshold_proof::prove_std_dev_below(values, bound));
+py_ok!(verify_std_dev_below, bool, proof: Vec<u8>, bound: u64 => crate::proof::threshold_proof::verify_std_dev_below(proof, bound));
+py_zkp!(prove_is_maximum, Vec<u8>, values: Vec<u64>, claimed_max_index: usize => crate::proof::threshold_proof::prove_is_maximum(values, claimed_max_index));
+py_ok!(verify_is_maximum, bool, proof: Vec<u8> => crate::proof::threshold_proof::verify_is_maximum(proof));
+py_zkp!(prove_count_above, Vec<u8>, values: Vec<u64>, threshold: u64, min_count: usize => crate::proof::threshold_proof::prove_count_above(values, threshold, min_count));
+py_ok!(verify_count_above, bool, proof: Vec<u8>, threshold: u64, min_count: usize => crate::proof::threshold_proof::verify_count_above(proof, threshold, min_count));
 
 py_zkp!(prove_membership, Vec<u8>, value: u64, set: Vec<u64> => crate::proof::set_membership::prove_membership(value, set));
 py_ok!(verify_membership, bool, proof: Vec<u8>, set: Vec<u64> => crate::proof::set_membership::verify_membership(proof, set));
+py_zkp!(verify_membership_checked, bool, proof: Vec<u8>, set: Vec<u64> => crate::proof::set_membership::verify_membership_checked(proof, set));
+py_ok!(verify_membership_bounded, bool, proof: Vec<u8>, set: Vec<u64>, max_set_size: usize => crate::proof::set_membership::verify_membership_bounded(proof, set, max_set_size));
+py_zkp!(merkle_set_root, Vec<u8>, set: Vec<u64> => crate::proof::set_membership::merkle_root(set));
+py_ok!(verify_membership_root, bool, proof: Vec<u8>, root: Vec<u8> => crate::proof::set_membership::verify_membership_root(proof, &root));
+py_zkp!(prove_non_membership, Vec<u8>, value: u64, set: Vec<u64> => crate::proof::set_membership::prove_non_membership(value, set));
+py_ok!(verify_non_membership, bool, proof: Vec<u8>, set: Vec<u64> => crate::proof::set_membership::verify_non_membership(proof, set));
+py_zkp!(non_membership_root, Vec<u8>, set: Vec<u64> => crate::proof::set_membership::non_membership_root(set));
+py_ok!(verify_non_membership_root, bool, proof: Vec<u8>, root: Vec<u8> => crate::proof::set_membership::verify_non_membership_root(proof, &root));
+py_zkp!(issue_credential, Vec<u8>, attributes: HashMap<String, u64> => crate::proof::credentials::Credential::issue(attributes.into_iter().collect()).map(|c| c.to_bytes()));
+py_zkp!(credential_commitment, Vec<u8>, credential: Vec<u8> => crate::proof::credentials::Credential::from_bytes(&credential)?.commitment().map(|c| c.to_vec()));
+py_zkp!(prove_credential_range, Vec<u8>, credential: Vec<u8>, name: String, min: u64, max: u64 => crate::proof::credentials::Credential::from_bytes(&credential)?.prove_range(&name, min, max));
+py_ok!(verify_credential_range, bool, presentation: Vec<u8>, commitment: Vec<u8>, name: String, min: u64, max: u64 => crate::proof::credentials::verify_attribute_range(presentation, &commitment, &name, min, max));
+py_zkp!(prove_credential_membership, Vec<u8>, credential: Vec<u8>, name: String, set: Vec<u64> => crate::proof::credentials::Credential::from_bytes(&credential)?.prove_membership(&name, set));
+py_ok!(verify_credential_membership, bool, presentation: Vec<u8>, commitment: Vec<u8>, name: String, set: Vec<u64> => crate::proof::credentials::verify_attribute_membership(presentation, &commitment, &name, set));
+py_ok!(verify_set_equality, bool, proof: Vec<u8>, root_a: Vec<u8>, root_b: Vec<u8> => crate::proof::set_membership::verify_set_equality(proof, root_a, root_b));
+py_zkp!(prove_shuffle, Vec<u8>, list_a: Vec<u64>, list_b: Vec<u64>, blinding: Vec<u8> => crate::utils::commitment::blinding_from_slice(&blinding).and_then(|b| crate::proof::set_membership::prove_shuffle(list_a, list_b, &b)));
+py_ok!(verify_shuffle, bool, proof: Vec<u8>, root_a: Vec<u8>, list_b: Vec<u64> => crate::proof::set_membership::verify_shuffle(proof, &root_a, &list_b));
+py_zkp!(voter_key, Vec<u8>, secret: Vec<u8> => crate::proof::voting::voter_key(&secret));
+py_zkp!(eligibility_root, Vec<u8>, voter_keys: Vec<Vec<u8>> => crate::proof::voting::eligibility_root(voter_keys));
+py_zkp!(prove_vote, (Vec<u8>, Vec<u8>), secret: Vec<u8>, voter_keys: Vec<Vec<u8>>, election_id: u64, num_options: usize, choice: usize => crate::proof::voting::prove_vote(&secret, voter_keys, election_id, num_options, choice).map(|(proof, blinding)| (proof, blinding.to_vec())));
+py_ok!(verify_vote, bool, proof: Vec<u8>, root: Vec<u8>, election_id: u64, num_options: usize => crate::proof::voting::verify_vote(proof, &root, election_id, num_options));
+py_zkp!(vote_nullifier, Vec<u8>, proof: Vec<u8> => crate::proof::voting::vote_nullifier(&proof));
+py_zkp!(open_vote, bool, proof: Vec<u8>, choice: usize, blinding: Vec<u8> => crate::proof::voting::open_vote(&proof, choice, &blinding));
+py_ok!(verify_disjoint, bool, proof: Vec<u8>, root_a: Vec<u8>, root_b: Vec<u8> => crate::proof::set_membership::verify_disjoint(proof, root_a, root_b));
+py_zkp!(prove_rank, Vec<u8>, value: u64, sorted_set: Vec<u64>, max_rank: usize => crate::proof::set_membership::prove_rank(value, sorted_set, max_rank));
+py_ok!(verify_rank, bool, proof: Vec<u8>, set_root: Vec<u8>, max_rank: usize => crate::proof::set_membership::verify_rank(proof, set_root, max_rank));
+py_zkp!(prove_membership_index_below, Vec<u8>, value: u64, set: Vec<u64>, k: usize => crate::proof::set_membership::prove_membership_index_below(value, set, k));
+py_ok!(verify_membership_index_below, bool, proof: Vec<u8>, set: Vec<u64>, k: usize => crate::proof::set_membership::verify_membership_index_below(proof, set, k));
+py_ok!(sorted_set_root, Vec<u8>, sorted_set: Vec<u64> => crate::utils::commitment::sorted_set_root(&sorted_set));
+py_zkp!(prove_binary_choice, Vec<u8>, value: u64, option_a: u64, option_b: u64 => crate::proof::set_membership::prove_binary_choice(value, option_a, option_b));
+py_ok!(verify_binary_choice, bool, proof: Vec<u8>, option_a: u64, option_b: u64 => crate::proof::set_membership::verify_binary_choice(proof, option_a, option_b));
 
 py_zkp!(prove_improvement, Vec<u8>, old: u64, new: u64 => crate::proof::improvement_proof::prove_improvement(old, new));
 py_ok!(verify_improvement, bool, proof: Vec<u8>, old: u64 => crate::proof::improvement_proof::verify_improvement(proof, old));
+py_ok!(verify_improvement_enveloped, Option<(u64, u64)>, proof: Vec<u8> => crate::proof::improvement_proof::verify_improvement_enveloped(proof));
+py_ok!(verify_history, bool, proofs: Vec<Vec<u8>>, values: Vec<u64> => crate::proof::improvement_proof::verify_history(proofs, values));
 
 py_zkp!(prove_consistency, Vec<u8>, data: Vec<u64> => crate::proof::consistency_proof::prove_consistency(data));
 py_ok!(verify_consistency, bool, proof: Vec<u8> => crate::proof::consistency_proof::verify_consistency(proof));
+py_zkp!(prove_bounded_consistency, Vec<u8>, data: Vec<u64>, max_step: u64 => crate::proof::consistency_proof::prove_bounded_consistency(data, max_step));
+py_ok!(verify_bounded_consistency, bool, proof: Vec<u8>, max_step: u64 => crate::proof::consistency_proof::verify_bounded_consistency(proof, max_step));
+py_zkp!(consistency_prefix_digest, Vec<u8>, proof: Vec<u8>, through_index: usize => crate::proof::consistency_proof::consistency_prefix_digest(proof, through_index));
+py_ok!(verify_consistency_suffix, bool, proof: Vec<u8>, from_index: usize, prefix_digest: Vec<u8> => crate::proof::consistency_proof::verify_consistency_suffix(proof, from_index, prefix_digest));
 
 py_zkp!(create_composite_proof, Vec<u8>, proof_list: Vec<Vec<u8>> => crate::advanced::create_composite_proof(proof_list));
 py_zkp!(verify_composite_proof, bool, composite_bytes: Vec<u8> => crate::advanced::verify_composite_proof(composite_bytes));
 py_zkp!(verify_composite_proof_integrity_only, bool, composite_bytes: Vec<u8> => crate::advanced::verify_composite_proof_integrity_only(composite_bytes));
+py_zkp!(composite_expected_hash, (String, String), composite_bytes: Vec<u8> => crate::advanced::composite_expected_hash(composite_bytes));
 py_zkp!(create_proof_with_metadata, Vec<u8>, proof_data: Vec<u8>, metadata: HashMap<String, Vec<u8>> => crate::advanced::create_proof_with_metadata(proof_data, metadata));
 py_zkp!(extract_proof_metadata, HashMap<String, Vec<u8>>, composite_bytes: Vec<u8> => crate::advanced::extract_proof_metadata(composite_bytes));
+py_zkp!(verify_range_with_freshness, bool, composite_bytes: Vec<u8>, min: u64, max: u64, max_age_seconds: u64 => crate::advanced::verify_range_with_freshness(composite_bytes, min, max, max_age_seconds));
 
 py_zkp!(clear_cache, (),  => crate::advanced::clear_cache());
 py_zkp!(get_cache_stats, HashMap<String, u64>,  => crate::advanced::get_cache_stats());
-py_zkp!(get_performance_metrics, HashMap<String, f64>,  => crate::advanced::get_performance_metrics());
+py_ok!(msm_acceleration_active, bool,  => crate::advanced::msm_acceleration_active());
+py_ok!(msm_acceleration_info, HashMap<String, bool>,  => crate::advanced::msm_acceleration_info());
+py_zkp!(get_performance_metrics, BTreeMap<String, f64>,  => crate::advanced::get_performance_metrics());
+py_zkp!(export_metrics_json, String,  => crate::advanced::export_metrics_json());
+py_zkp!(import_metrics_json, (), json: String => crate::advanced::import_metrics_json(json));
+py_zkp!(benchmark_proof_verification, HashMap<String, f64>, proof_type: String, iterations: u32 => crate::advanced::benchmark_proof_verification(proof_type, iterations));
 py_zkp!(benchmark_proof_generation_numeric, HashMap<String, f64>, proof_type: String, iterations: u32 => crate::advanced::benchmark_proof_generation_numeric(proof_type, iterations));
 py_zkp!(prove_range_cached, Vec<u8>, value: u64, min: u64, max: u64 => crate::advanced::prove_range_cached(value, min, max));
+py_zkp!(prove_range_with_receipt, (Vec<u8>, String), value: u64, min: u64, max: u64 => crate::advanced::prove_range_with_receipt(value, min, max));
 py_zkp!(prove_equality_advanced, Vec<u8>, val1: u64, val2: u64 => crate::advanced::prove_equality_advanced(val1, val2));
 py_zkp!(verify_proofs_parallel, Vec<bool>, proofs: Vec<(Vec<u8>, String)> => crate::advanced::verify_proofs_parallel(proofs));
-py_zkp!(prove_threshold_optimized, Vec<u8>, values: Vec<u64>, threshold: u64 => crate::advanced::prove_threshold_optimized(values, threshold));
 py_zkp!(validate_proof_chain, bool, proof_chain: Vec<Vec<u8>> => crate::advanced::validate_proof_chain(proof_chain));
+py_zkp!(sanitize_proof, Vec<u8>, bytes: Vec<u8> => crate::advanced::sanitize_proof(bytes));
+py_zkp!(scheme_registry, String,  => crate::advanced::scheme_registry());
+py_ok!(format_capabilities, Vec<u8>,  => crate::proof::format_capabilities());
+py_ok!(can_verify, bool, proof_bytes: Vec<u8> => crate::proof::can_verify(&proof_bytes));
 py_zkp!(get_proof_info, HashMap<String, u64>, proof_bytes: Vec<u8> => crate::advanced::get_proof_info(proof_bytes));
 py_zkp!(set_snark_key_dir, bool, path: String => crate::advanced::set_snark_key_dir(path));
+py_ok!(set_snark_proof_compression, (), enabled: bool => crate::advanced::set_snark_proof_compression(enabled));
+py_ok!(snark_proof_compression, bool,  => crate::advanced::snark_proof_compression());
+py_zkp!(set_snark_curve, (), name: String => crate::advanced::set_snark_curve(name));
+py_ok!(snark_curve, String,  => crate::advanced::snark_curve());
+py_zkp!(rotate_snark_keys, u32, circuit: String => crate::advanced::rotate_snark_keys(circuit));
+py_zkp!(retire_snark_key_version, (), circuit: String, version: u32 => crate::advanced::retire_snark_key_version(circuit, version));
+py_zkp!(snark_key_versions, Vec<u32>, circuit: String => crate::advanced::snark_key_versions(circuit));
+py_zkp!(export_snark_keys, (Vec<u8>, Vec<u8>), circuit: String => crate::advanced::export_snark_keys(circuit));
+py_zkp!(import_snark_keys, (), circuit: String, pk_bytes: Vec<u8>, vk_bytes: Vec<u8> => crate::advanced::import_snark_keys(circuit, pk_bytes, vk_bytes));
+py_zkp!(powers_of_tau_initialize, Vec<u8>, log_size: u32 => crate::advanced::powers_of_tau_initialize(log_size));
+py_zkp!(powers_of_tau_contribute, (Vec<u8>, Vec<u8>), params: Vec<u8> => crate::advanced::powers_of_tau_contribute(params));
+py_zkp!(powers_of_tau_verify, Vec<Vec<u8>>, params: Vec<u8> => crate::advanced::powers_of_tau_verify(params));
+py_zkp!(ceremony_initialize, Vec<u8>, circuit: String, phase1: Vec<u8> => crate::advanced::ceremony_initialize(circuit, phase1));
+py_zkp!(ceremony_contribute, (Vec<u8>, Vec<u8>), params: Vec<u8> => crate::advanced::ceremony_contribute(params));
+py_zkp!(ceremony_verify, Vec<Vec<u8>>, params: Vec<u8>, phase1: Vec<u8> => crate::advanced::ceremony_verify(params, phase1));
+py_zkp!(ceremony_finalize, u32, params: Vec<u8>, phase1: Vec<u8> => crate::advanced::ceremony_finalize(params, phase1));
+py_ok!(membership_constraint_count, usize,  => crate::backend::snark::SnarkBackend::membership_constraint_count());
+py_ok!(equality_constraint_count, usize,  => crate::backend::snark::SnarkBackend::equality_constraint_count());
 py_zkp!(is_snark_setup_initialized, bool,  => crate::advanced::is_snark_setup_initialized());
 py_zkp!(create_proof_batch, u64,  => crate::advanced::create_proof_batch());
 py_zkp!(batch_add_range_proof, (), batch_id: u64, value: u64, min: u64, max: u64 => crate::advanced::batch_add_range_proof(batch_id, value, min, max));
@@ -69,20 +221,23 @@ py_zkp!(batch_add_membership_proof, (), batch_id: u64, value: u64, set: Vec<u64>
 py_zkp!(batch_add_improvement_proof, (), batch_id: u64, old: u64, new: u64 => crate::advanced::batch_add_improvement_proof(batch_id, old, new));
 py_zkp!(batch_add_consistency_proof, (), batch_id: u64, data: Vec<u64> => crate::advanced::batch_add_consistency_proof(batch_id, data));
 py_zkp!(process_batch, Vec<Vec<u8>>, batch_id: u64 => crate::advanced::process_batch(batch_id));
+py_zkp!(validate_batch, Vec<(usize, Option<String>)>, batch_id: u64 => crate::advanced::validate_batch(batch_id));
 py_zkp!(get_batch_status, HashMap<String, usize>, batch_id: u64 => crate::advanced::get_batch_status(batch_id));
 py_zkp!(clear_batch, (), batch_id: u64 => crate::advanced::clear_batch(batch_id));
 
 #[cfg(feature = "batch-store")]
 #[pyfunction]
 fn set_batch_store_dir(path: String) -> PyResult<()> {
-    crate::advanced::batch_store::set_batch_store_dir(path).map_err(Into::into)
+    guarded(|| crate::advanced::batch_store::set_batch_store_dir(path).map_err(Into::into))
 }
 
 #[cfg(feature = "batch-store")]
 #[pyfunction]
 fn get_batch_store_dir() -> PyResult<Option<String>> {
-    Ok(crate::advanced::batch_store::get_batch_store_dir()
-        .map(|p| p.to_string_lossy().into_owned()))
+    guarded(|| {
+        Ok(crate::advanced::batch_store::get_batch_store_dir()
+            .map(|p| p.to_string_lossy().into_owned()))
+    })
 }
 
 #[cfg(feature = "batch-store")]
@@ -102,44 +257,561 @@ fn benchmark_proof_generation(
     proof_type: String,
     iterations: u32,
 ) -> PyResult<PyObject> {
-    let m = crate::advanced::benchmark_proof_generation(proof_type, iterations)?;
-    Ok(m.into_pyobject(py)?.into_any().unbind())
+    guarded(|| {
+        let m = crate::advanced::benchmark_proof_generation(proof_type, iterations)?;
+        Ok(m.into_pyobject(py)?.into_any().unbind())
+    })
+}
+
+#[pyfunction]
+fn prove_range_and_greater(
+    value: u64,
+    min: u64,
+    max: u64,
+    other_value: u64,
+    other_commitment: Vec<u8>,
+    value_blinding: Vec<u8>,
+    other_blinding: Vec<u8>,
+) -> PyResult<Vec<u8>> {
+    guarded(|| {
+        use crate::utils::commitment::blinding_from_slice;
+        crate::proof::range_proof::prove_range_and_greater(
+            value,
+            min,
+            max,
+            other_value,
+            &other_commitment,
+            &blinding_from_slice(&value_blinding)?,
+            &blinding_from_slice(&other_blinding)?,
+        )
+        .map_err(Into::into)
+    })
+}
+
+#[pyfunction]
+fn proofs_share_value(
+    proof_a: Vec<u8>,
+    proof_b: Vec<u8>,
+    blinding_a: Vec<u8>,
+    blinding_b: Vec<u8>,
+) -> PyResult<bool> {
+    guarded(|| {
+        use crate::utils::commitment::blinding_from_slice;
+        crate::advanced::proofs_share_value(
+            proof_a,
+            proof_b,
+            &blinding_from_slice(&blinding_a)?,
+            &blinding_from_slice(&blinding_b)?,
+        )
+        .map_err(Into::into)
+    })
+}
+
+py_ok!(verify_range_and_greater, bool, proof: Vec<u8>, min: u64, max: u64, other_commitment: Vec<u8> => crate::proof::range_proof::verify_range_and_greater(proof, min, max, &other_commitment));
+
+/// Parse the three 32-byte blindings `[value, low bound, high bound]` of an interval proof.
+fn interval_blindings(blindings: Vec<Vec<u8>>, expected: &str) -> PyResult<[[u8; 32]; 3]> {
+    use crate::utils::commitment::blinding_from_slice;
+    match blindings.as_slice() {
+        [v, lo, hi] => Ok([
+            blinding_from_slice(v)?,
+            blinding_from_slice(lo)?,
+            blinding_from_slice(hi)?,
+        ]),
+        _ => Err(ZkpError::InvalidInput(format!("expected 3 blindings: {}", expected)).into()),
+    }
+}
+
+#[pyfunction]
+fn prove_in_committed_interval(
+    value: u64,
+    min: u64,
+    max: u64,
+    min_commitment: Vec<u8>,
+    max_commitment: Vec<u8>,
+    blindings: Vec<Vec<u8>>,
+) -> PyResult<Vec<u8>> {
+    guarded(|| {
+        let blindings = interval_blindings(blindings, "[value, min, max]")?;
+        crate::proof::range_proof::prove_in_committed_interval(
+            value,
+            min,
+            max,
+            &min_commitment,
+            &max_commitment,
+            &blindings,
+        )
+        .map_err(Into::into)
+    })
+}
+
+#[pyfunction]
+fn prove_strictly_between(
+    value: u64,
+    low: u64,
+    high: u64,
+    low_commitment: Vec<u8>,
+    high_commitment: Vec<u8>,
+    blindings: Vec<Vec<u8>>,
+) -> PyResult<Vec<u8>> {
+    guarded(|| {
+        let blindings = interval_blindings(blindings, "[value, low, high]")?;
+        crate::proof::range_proof::prove_strictly_between(
+            value,
+            low,
+            high,
+            &low_commitment,
+            &high_commitment,
+            &blindings,
+        )
+        .map_err(Into::into)
+    })
+}
+
+#[pyfunction]
+fn prove_exact_delta(
+    old_commitment: Vec<u8>,
+    new_commitment: Vec<u8>,
+    delta_commitment: Vec<u8>,
+    blindings: Vec<Vec<u8>>,
+) -> PyResult<Vec<u8>> {
+    guarded(|| {
+        use crate::utils::commitment::blinding_from_slice;
+        let blindings: [[u8; 32]; 3] = match blindings.as_slice() {
+            [old, new, delta] => [
+                blinding_from_slice(old)?,
+                blinding_from_slice(new)?,
+                blinding_from_slice(delta)?,
+            ],
+            _ => {
+                return Err(ZkpError::InvalidInput(
+                    "expected 3 blindings: [old, new, delta]".to_string(),
+                )
+                .into())
+            }
+        };
+        crate::proof::improvement_proof::prove_exact_delta(
+            &old_commitment,
+            &new_commitment,
+            &delta_commitment,
+            &blindings,
+        )
+        .map_err(Into::into)
+    })
+}
+
+#[pyfunction]
+fn prove_set_equality(
+    set_a: Vec<u64>,
+    set_b: Vec<u64>,
+    blindings: Vec<Vec<u8>>,
+) -> PyResult<Vec<u8>> {
+    guarded(|| {
+        use crate::utils::commitment::blinding_from_slice;
+        let blindings: [[u8; 32]; 2] = match blindings.as_slice() {
+            [a, b] => [blinding_from_slice(a)?, blinding_from_slice(b)?],
+            _ => {
+                return Err(ZkpError::InvalidInput(
+                    "expected 2 blindings: [root_a, root_b]".to_string(),
+                )
+                .into())
+            }
+        };
+        crate::proof::set_membership::prove_set_equality(set_a, set_b, &blindings)
+            .map_err(Into::into)
+    })
+}
+
+#[pyfunction]
+fn prove_inequality(val1: u64, val2: u64, blindings: Vec<Vec<u8>>) -> PyResult<Vec<u8>> {
+    guarded(|| {
+        use crate::utils::commitment::blinding_from_slice;
+        let blindings: [[u8; 32]; 2] = match blindings.as_slice() {
+            [a, b] => [blinding_from_slice(a)?, blinding_from_slice(b)?],
+            _ => {
+                return Err(
+                    ZkpError::InvalidInput("expected 2 blindings: [a, b]".to_string()).into(),
+                )
+            }
+        };
+        crate::proof::inequality_proof::prove_inequality(val1, val2, &blindings).map_err(Into::into)
+    })
+}
+
+#[pyfunction]
+fn prove_greater_than(a: u64, b: u64, blindings: Vec<Vec<u8>>) -> PyResult<Vec<u8>> {
+    guarded(|| {
+        use crate::utils::commitment::blinding_from_slice;
+        let blindings: [[u8; 32]; 2] = match blindings.as_slice() {
+            [a, b] => [blinding_from_slice(a)?, blinding_from_slice(b)?],
+            _ => {
+                return Err(
+                    ZkpError::InvalidInput("expected 2 blindings: [a, b]".to_string()).into(),
+                )
+            }
+        };
+        crate::proof::inequality_proof::prove_greater_than(a, b, &blindings).map_err(Into::into)
+    })
+}
+
+#[pyfunction]
+fn prove_disjoint(
+    set_a: Vec<u64>,
+    set_b: Vec<u64>,
+    blindings_a: Vec<Vec<u8>>,
+    blindings_b: Vec<Vec<u8>>,
+) -> PyResult<Vec<u8>> {
+    guarded(|| {
+        use crate::utils::commitment::blinding_from_slice;
+        let parse = |blindings: &[Vec<u8>]| {
+            blindings
+                .iter()
+                .map(|b| blinding_from_slice(b))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        crate::proof::set_membership::prove_disjoint(
+            set_a,
+            set_b,
+            parse(&blindings_a)?,
+            parse(&blindings_b)?,
+        )
+        .map_err(Into::into)
+    })
+}
+
+#[pyfunction]
+fn prove_solvency(
+    assets: Vec<u64>,
+    asset_blindings: Vec<Vec<u8>>,
+    liabilities: Vec<u64>,
+    liability_blindings: Vec<Vec<u8>>,
+) -> PyResult<Vec<u8>> {
+    guarded(|| {
+        use crate::utils::commitment::blinding_from_slice;
+        let parse = |blindings: &[Vec<u8>]| {
+            blindings
+                .iter()
+                .map(|b| blinding_from_slice(b))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        crate::proof::solvency::prove_solvency(
+            assets,
+            parse(&asset_blindings)?,
+            liabilities,
+            parse(&liability_blindings)?,
+        )
+        .map_err(Into::into)
+    })
+}
+
+#[pyfunction]
+fn prove_difference(
+    commitment_a: Vec<u8>,
+    commitment_b: Vec<u8>,
+    diff: u64,
+    blindings: Vec<Vec<u8>>,
+) -> PyResult<Vec<u8>> {
+    guarded(|| {
+        use crate::utils::commitment::blinding_from_slice;
+        let blindings: [[u8; 32]; 2] = match blindings.as_slice() {
+            [a, b] => [blinding_from_slice(a)?, blinding_from_slice(b)?],
+            _ => {
+                return Err(
+                    ZkpError::InvalidInput("expected 2 blindings: [a, b]".to_string()).into(),
+                )
+            }
+        };
+        crate::proof::equality_proof::prove_difference(
+            &commitment_a,
+            &commitment_b,
+            diff,
+            &blindings,
+        )
+        .map_err(Into::into)
+    })
+}
+
+py_ok!(verify_difference, bool, proof: Vec<u8>, commitment_a: Vec<u8>, commitment_b: Vec<u8>, diff: u64 => crate::proof::equality_proof::verify_difference(proof, &commitment_a, &commitment_b, diff));
+
+py_zkp!(prove_balance_update, (Vec<u8>, Vec<u8>), old: u64, old_blinding: Vec<u8>, amount: u64 => crate::proof::improvement_proof::prove_balance_update(old, &crate::utils::commitment::blinding_from_slice(&old_blinding)?, amount).map(|(proof, blinding)| (proof, blinding.to_vec())));
+py_ok!(verify_balance_update, bool, proof: Vec<u8>, old_commitment: Vec<u8>, new_commitment: Vec<u8>, amount: u64 => crate::proof::improvement_proof::verify_balance_update(proof, &old_commitment, &new_commitment, amount));
+
+py_ok!(verify_inequality, bool, proof: Vec<u8>, commitment_a: Vec<u8>, commitment_b: Vec<u8> => crate::proof::inequality_proof::verify_inequality(proof, &commitment_a, &commitment_b));
+py_ok!(verify_greater_than, bool, proof: Vec<u8>, commitment_a: Vec<u8>, commitment_b: Vec<u8> => crate::proof::inequality_proof::verify_greater_than(proof, &commitment_a, &commitment_b));
+py_ok!(verify_exact_delta, bool, proof: Vec<u8>, old_commitment: Vec<u8>, new_commitment: Vec<u8>, delta_commitment: Vec<u8> => crate::proof::improvement_proof::verify_exact_delta(proof, &old_commitment, &new_commitment, &delta_commitment));
+py_ok!(verify_in_committed_interval, bool, proof: Vec<u8>, value_commitment: Vec<u8>, min_commitment: Vec<u8>, max_commitment: Vec<u8> => crate::proof::range_proof::verify_in_committed_interval(proof, &value_commitment, &min_commitment, &max_commitment));
+py_ok!(verify_strictly_between, bool, proof: Vec<u8>, value_commitment: Vec<u8>, low_commitment: Vec<u8>, high_commitment: Vec<u8> => crate::proof::range_proof::verify_strictly_between(proof, &value_commitment, &low_commitment, &high_commitment));
+
+#[pyfunction]
+#[pyo3(signature = (values, threshold, max_value_per_element=None))]
+fn prove_threshold_optimized(
+    values: Vec<u64>,
+    threshold: u64,
+    max_value_per_element: Option<u64>,
+) -> PyResult<Vec<u8>> {
+    guarded(|| {
+        crate::advanced::prove_threshold_optimized(values, threshold, max_value_per_element)
+            .map_err(Into::into)
+    })
+}
+
+// `backend=None` keeps each statement's default backend (Bulletproofs for range, Groth16 for
+// equality); a name picks one per call, see `BackendKind`.
+#[pyfunction]
+#[pyo3(signature = (value, min, max, backend=None))]
+fn prove_range(value: u64, min: u64, max: u64, backend: Option<&str>) -> PyResult<Vec<u8>> {
+    guarded(|| {
+        let proof = match backend {
+            Some(name) => {
+                crate::proof::range_proof::prove_range_with_backend(value, min, max, name.parse()?)
+            }
+            None => crate::proof::range_proof::prove_range(value, min, max),
+        };
+        proof.map_err(Into::into)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (proof, min, max, backend=None))]
+fn verify_range(proof: Vec<u8>, min: u64, max: u64, backend: Option<&str>) -> PyResult<bool> {
+    guarded(|| {
+        Ok(match backend {
+            Some(name) => {
+                crate::proof::range_proof::verify_range_with_backend(proof, min, max, name.parse()?)
+            }
+            None => crate::proof::range_proof::verify_range(proof, min, max),
+        })
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (val1, val2, backend=None))]
+fn prove_equality(val1: u64, val2: u64, backend: Option<&str>) -> PyResult<Vec<u8>> {
+    guarded(|| {
+        let proof = match backend {
+            Some(name) => {
+                crate::proof::equality_proof::prove_equality_with_backend(val1, val2, name.parse()?)
+            }
+            None => crate::proof::equality_proof::prove_equality(val1, val2),
+        };
+        proof.map_err(Into::into)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (proof, val1, val2, backend=None))]
+fn verify_equality(proof: Vec<u8>, val1: u64, val2: u64, backend: Option<&str>) -> PyResult<bool> {
+    guarded(|| {
+        Ok(match backend {
+            Some(name) => crate::proof::equality_proof::verify_equality_with_backend(
+                proof,
+                val1,
+                val2,
+                name.parse()?,
+            ),
+            None => crate::proof::equality_proof::verify_equality(proof, val1, val2),
+        })
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (snark_key_dir=None, cache_max_size=crate::config::DEFAULT_CACHE_MAX_SIZE, cache_ttl_seconds=crate::config::DEFAULT_CACHE_TTL_SECONDS, batch_store_dir=None, num_threads=None, compress_snark_proofs=false, snark_curve="bn254"))]
+fn configure(
+    snark_key_dir: Option<String>,
+    cache_max_size: usize,
+    cache_ttl_seconds: u64,
+    batch_store_dir: Option<String>,
+    num_threads: Option<usize>,
+    compress_snark_proofs: bool,
+    snark_curve: &str,
+) -> PyResult<()> {
+    guarded(|| {
+        crate::config::configure(crate::config::LibzkpConfig {
+            snark_key_dir: snark_key_dir.map(Into::into),
+            cache_max_size,
+            cache_ttl_seconds,
+            batch_store_dir: batch_store_dir.map(Into::into),
+            num_threads,
+            compress_snark_proofs,
+            snark_curve: snark_curve.parse()?,
+        })
+        .map_err(Into::into)
+    })
 }
 
 /// Registers all Python-callable functions on the module `m`.
 pub fn register_module(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(configure, m)?)?;
+    m.add_function(wrap_pyfunction!(set_panic_boundary, m)?)?;
     m.add_function(wrap_pyfunction!(prove_range, m)?)?;
     m.add_function(wrap_pyfunction!(verify_range, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_and_extract, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_range_keyed, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_range_offset, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_range_offset, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_range_signed, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_range_signed, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_timestamp_in_window, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_timestamp_in_window, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_timestamp_between_dates, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_timestamp_between_dates, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_age_at_least, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_age_at_least, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_bid_validity, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_bid_validity, m)?)?;
+    m.add_function(wrap_pyfunction!(open_bid, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_range_any, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_range_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_range_many, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_range_many, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_divisible, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_divisible, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_range_and_greater, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_range_and_greater, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_in_committed_interval, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_in_committed_interval, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_strictly_between, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_strictly_between, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_difference, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_balance_update, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_balance_update, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_difference, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_inequality, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_inequality, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_greater_than, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_greater_than, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_exact_delta, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_exact_delta, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_weighted_average_range, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_weighted_average_range, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_within_of_mean, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_within_of_mean, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_polynomial_nonneg, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_polynomial_nonneg, m)?)?;
+    m.add_function(wrap_pyfunction!(weights_hash, m)?)?;
     m.add_function(wrap_pyfunction!(prove_equality, m)?)?;
     m.add_function(wrap_pyfunction!(verify_equality, m)?)?;
     m.add_function(wrap_pyfunction!(verify_equality_with_commitment, m)?)?;
     m.add_function(wrap_pyfunction!(snark_commit_value, m)?)?;
+    m.add_function(wrap_pyfunction!(commit_value_pedersen, m)?)?;
+    m.add_function(wrap_pyfunction!(commit_delta_pedersen, m)?)?;
+    m.add_function(wrap_pyfunction!(commit_multiset_pedersen, m)?)?;
+    m.add_function(wrap_pyfunction!(commit_set_elements_pedersen, m)?)?;
+    m.add_function(wrap_pyfunction!(random_pedersen_blinding, m)?)?;
     m.add_function(wrap_pyfunction!(prove_threshold, m)?)?;
     m.add_function(wrap_pyfunction!(verify_threshold, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_positive_threshold, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_positive_threshold, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_exact_sum, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_exact_sum, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_average_threshold, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_solvency, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_solvency, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_average_threshold, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_median_threshold, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_median_threshold, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_variance_below, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_variance_below, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_std_dev_below, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_std_dev_below, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_is_maximum, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_is_maximum, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_count_above, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_count_above, m)?)?;
     m.add_function(wrap_pyfunction!(prove_membership, m)?)?;
     m.add_function(wrap_pyfunction!(verify_membership, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_membership_checked, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_membership_bounded, m)?)?;
+    m.add_function(wrap_pyfunction!(merkle_set_root, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_membership_root, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_non_membership, m)?)?;
+    m.add_function(wrap_pyfunction!(issue_credential, m)?)?;
+    m.add_function(wrap_pyfunction!(credential_commitment, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_credential_range, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_credential_range, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_credential_membership, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_credential_membership, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_non_membership, m)?)?;
+    m.add_function(wrap_pyfunction!(non_membership_root, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_non_membership_root, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_rank, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_rank, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_membership_index_below, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_membership_index_below, m)?)?;
+    m.add_function(wrap_pyfunction!(sorted_set_root, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_set_equality, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_set_equality, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_shuffle, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_shuffle, m)?)?;
+    m.add_function(wrap_pyfunction!(voter_key, m)?)?;
+    m.add_function(wrap_pyfunction!(eligibility_root, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_vote, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_vote, m)?)?;
+    m.add_function(wrap_pyfunction!(vote_nullifier, m)?)?;
+    m.add_function(wrap_pyfunction!(open_vote, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_disjoint, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_disjoint, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_binary_choice, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_binary_choice, m)?)?;
     m.add_function(wrap_pyfunction!(prove_improvement, m)?)?;
     m.add_function(wrap_pyfunction!(verify_improvement, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_improvement_enveloped, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_history, m)?)?;
     m.add_function(wrap_pyfunction!(prove_consistency, m)?)?;
     m.add_function(wrap_pyfunction!(verify_consistency, m)?)?;
+    m.add_function(wrap_pyfunction!(consistency_prefix_digest, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_consistency_suffix, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_bounded_consistency, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_bounded_consistency, m)?)?;
     m.add_function(wrap_pyfunction!(create_composite_proof, m)?)?;
     m.add_function(wrap_pyfunction!(verify_composite_proof, m)?)?;
     m.add_function(wrap_pyfunction!(verify_composite_proof_integrity_only, m)?)?;
+    m.add_function(wrap_pyfunction!(composite_expected_hash, m)?)?;
     m.add_function(wrap_pyfunction!(create_proof_with_metadata, m)?)?;
     m.add_function(wrap_pyfunction!(extract_proof_metadata, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_range_with_freshness, m)?)?;
     m.add_function(wrap_pyfunction!(clear_cache, m)?)?;
     m.add_function(wrap_pyfunction!(get_cache_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(msm_acceleration_active, m)?)?;
+    m.add_function(wrap_pyfunction!(msm_acceleration_info, m)?)?;
     m.add_function(wrap_pyfunction!(get_performance_metrics, m)?)?;
+    m.add_function(wrap_pyfunction!(export_metrics_json, m)?)?;
+    m.add_function(wrap_pyfunction!(import_metrics_json, m)?)?;
     m.add_function(wrap_pyfunction!(benchmark_proof_generation_numeric, m)?)?;
+    m.add_function(wrap_pyfunction!(benchmark_proof_verification, m)?)?;
     m.add_function(wrap_pyfunction!(prove_range_cached, m)?)?;
+    m.add_function(wrap_pyfunction!(prove_range_with_receipt, m)?)?;
     m.add_function(wrap_pyfunction!(prove_equality_advanced, m)?)?;
     m.add_function(wrap_pyfunction!(verify_proofs_parallel, m)?)?;
     m.add_function(wrap_pyfunction!(benchmark_proof_generation, m)?)?;
     m.add_function(wrap_pyfunction!(prove_threshold_optimized, m)?)?;
     m.add_function(wrap_pyfunction!(validate_proof_chain, m)?)?;
+    m.add_function(wrap_pyfunction!(scheme_registry, m)?)?;
+    m.add_function(wrap_pyfunction!(format_capabilities, m)?)?;
+    m.add_function(wrap_pyfunction!(can_verify, m)?)?;
     m.add_function(wrap_pyfunction!(get_proof_info, m)?)?;
+    m.add_function(wrap_pyfunction!(sanitize_proof, m)?)?;
+    m.add_function(wrap_pyfunction!(proofs_share_value, m)?)?;
     m.add_function(wrap_pyfunction!(set_snark_key_dir, m)?)?;
+    m.add_function(wrap_pyfunction!(set_snark_proof_compression, m)?)?;
+    m.add_function(wrap_pyfunction!(snark_proof_compression, m)?)?;
+    m.add_function(wrap_pyfunction!(set_snark_curve, m)?)?;
+    m.add_function(wrap_pyfunction!(snark_curve, m)?)?;
+    m.add_function(wrap_pyfunction!(rotate_snark_keys, m)?)?;
+    m.add_function(wrap_pyfunction!(retire_snark_key_version, m)?)?;
+    m.add_function(wrap_pyfunction!(snark_key_versions, m)?)?;
+    m.add_function(wrap_pyfunction!(export_snark_keys, m)?)?;
+    m.add_function(wrap_pyfunction!(import_snark_keys, m)?)?;
+    m.add_function(wrap_pyfunction!(powers_of_tau_initialize, m)?)?;
+    m.add_function(wrap_pyfunction!(powers_of_tau_contribute, m)?)?;
+    m.add_function(wrap_pyfunction!(powers_of_tau_verify, m)?)?;
+    m.add_function(wrap_pyfunction!(ceremony_initialize, m)?)?;
+    m.add_function(wrap_pyfunction!(ceremony_contribute, m)?)?;
+    m.add_function(wrap_pyfunction!(ceremony_verify, m)?)?;
+    m.add_function(wrap_pyfunction!(ceremony_finalize, m)?)?;
     m.add_function(wrap_pyfunction!(is_snark_setup_initialized, m)?)?;
+    m.add_function(wrap_pyfunction!(membership_constraint_count, m)?)?;
+    m.add_function(wrap_pyfunction!(equality_constraint_count, m)?)?;
     m.add_function(wrap_pyfunction!(create_proof_batch, m)?)?;
     m.add_function(wrap_pyfunction!(batch_add_range_proof, m)?)?;
     m.add_function(wrap_pyfunction!(batch_add_equality_proof, m)?)?;
@@ -149,6 +821,7 @@ pub fn register_module(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()>
     m.add_function(wrap_pyfunction!(batch_add_consistency_proof, m)?)?;
     m.add_function(wrap_pyfunction!(process_batch, m)?)?;
     m.add_function(wrap_pyfunction!(get_batch_status, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_batch, m)?)?;
     m.add_function(wrap_pyfunction!(clear_batch, m)?)?;
     #[cfg(feature = "batch-store")]
     {
@@ -162,3 +835,34 @@ pub fn register_module(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()>
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::exceptions::PyRuntimeError;
+
+    /// Stand-in for a binding with an unguarded index on malformed input.
+    #[pyfunction]
+    fn read_scheme_byte(proof: Vec<u8>) -> PyResult<u8> {
+        guarded(|| Ok(proof[1]))
+    }
+
+    #[test]
+    fn panic_boundary_raises_python_exception() {
+        set_panic_boundary(true);
+        Python::with_gil(|py| {
+            let f = wrap_pyfunction!(read_scheme_byte, py).unwrap();
+            assert_eq!(
+                f.call1((vec![1u8, 2],)).unwrap().extract::<u8>().unwrap(),
+                2
+            );
+
+            let err = f.call1((vec![1u8],)).unwrap_err();
+            assert!(err.is_instance_of::<PyRuntimeError>(py));
+            assert!(err
+                .to_string()
+                .contains("internal panic: index out of bounds"));
+        });
+        set_panic_boundary(false);
+    }
+}
@@ -0,0 +1,267 @@
+//! Salted Bloom filters committed as a Merkle tree over their bits, for membership proofs that
+//! hide the allow-list. A proof opens only the `num_hashes` bits an element maps to; every other
+//! bit stays hidden behind its salted leaf. Bloom filters have false positives: an element that
+//! was never inserted passes with probability about `(1 - e^(-k*n/m))^k` (see
+//! [`BloomFilterParams::false_positive_rate`]), so a proof shows "the filter accepts this
+//! element", not "this element is in the set".
+
+use crate::utils::encoding::read_u64_le;
+use crate::utils::error_handling::{ZkpError, ZkpResult};
+use crate::utils::limits::{MAX_BLOOM_FILTER_BITS, MAX_BLOOM_HASHES};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+/// Shape of a committed Bloom filter plus the secret salt its leaves are blinded with. The
+/// filter owner keeps the whole struct (the salt is needed to prove again under the same
+/// commitment); verifiers only ever see `num_bits` and `num_hashes`, inside proofs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BloomFilterParams {
+    pub num_bits: usize,
+    pub num_hashes: usize,
+    pub salt: [u8; 32],
+}
+
+impl BloomFilterParams {
+    /// Params with a fresh random salt; errors outside `1..=MAX_BLOOM_FILTER_BITS` bits or
+    /// `1..=MAX_BLOOM_HASHES` hashes.
+    pub fn new(num_bits: usize, num_hashes: usize) -> ZkpResult<Self> {
+        let mut salt = [0u8; 32];
+        OsRng.fill_bytes(&mut salt);
+        let params = Self {
+            num_bits,
+            num_hashes,
+            salt,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// Size a filter for `expected_items` at the target false-positive rate, using the
+    /// standard optimum `m = -n ln p / (ln 2)^2`, `k = (m / n) ln 2`.
+    pub fn for_capacity(expected_items: usize, false_positive_rate: f64) -> ZkpResult<Self> {
+        if expected_items == 0 || !(false_positive_rate > 0.0 && false_positive_rate < 1.0) {
+            return Err(ZkpError::InvalidInput(
+                "expected items must be positive and the false-positive rate in (0, 1)".to_string(),
+            ));
+        }
+        let ln2 = std::f64::consts::LN_2;
+        let n = expected_items as f64;
+        let bits = (-n * false_positive_rate.ln() / (ln2 * ln2)).ceil();
+        if bits > MAX_BLOOM_FILTER_BITS as f64 {
+            return Err(ZkpError::InvalidInput(format!(
+                "filter would need {} bits; at most {} supported",
+                bits, MAX_BLOOM_FILTER_BITS
+            )));
+        }
+        let hashes = ((bits / n) * ln2)
+            .round()
+            .clamp(1.0, MAX_BLOOM_HASHES as f64);
+        Self::new(bits as usize, hashes as usize)
+    }
+
+    /// Expected false-positive rate once `inserted_items` elements are in the filter.
+    pub fn false_positive_rate(&self, inserted_items: usize) -> f64 {
+        let k = self.num_hashes as f64;
+        let exponent = -k * inserted_items as f64 / self.num_bits as f64;
+        (1.0 - exponent.exp()).powf(k)
+    }
+
+    fn validate(&self) -> ZkpResult<()> {
+        if self.num_bits == 0 || self.num_bits > MAX_BLOOM_FILTER_BITS {
+            return Err(ZkpError::InvalidInput(format!(
+                "filter size must be between 1 and {} bits",
+                MAX_BLOOM_FILTER_BITS
+            )));
+        }
+        if self.num_hashes == 0 || self.num_hashes > MAX_BLOOM_HASHES {
+            return Err(ZkpError::InvalidInput(format!(
+                "number of hashes must be between 1 and {}",
+                MAX_BLOOM_HASHES
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Bit positions `element` maps to: `SHA-256(domain || i || element) mod num_bits`.
+pub fn bloom_positions(element: u64, num_bits: usize, num_hashes: usize) -> Vec<usize> {
+    (0..num_hashes as u32)
+        .map(|i| {
+            let mut hasher = Sha256::new();
+            hasher.update(b"libzkp_bloom_position");
+            hasher.update(i.to_le_bytes());
+            hasher.update(element.to_le_bytes());
+            let digest = hasher.finalize();
+            let word = u64::from_le_bytes(digest[..8].try_into().expect("8-byte slice"));
+            (word % num_bits as u64) as usize
+        })
+        .collect()
+}
+
+/// Build the filter bits (packed LSB-first, `ceil(num_bits / 8)` bytes) for `set`.
+pub fn build_bloom_filter(set: &[u64], params: &BloomFilterParams) -> ZkpResult<Vec<u8>> {
+    params.validate()?;
+    let mut bits = vec![0u8; params.num_bits.div_ceil(8)];
+    for &element in set {
+        for pos in bloom_positions(element, params.num_bits, params.num_hashes) {
+            bits[pos / 8] |= 1 << (pos % 8);
+        }
+    }
+    Ok(bits)
+}
+
+/// 32-byte commitment to `filter_bits` under `params`; publish it in place of the set.
+pub fn commit_bloom_filter(params: &BloomFilterParams, filter_bits: &[u8]) -> ZkpResult<Vec<u8>> {
+    let tree = BloomTree::build(params, filter_bits)?;
+    Ok(bloom_commitment(params.num_bits, params.num_hashes, tree.root()).to_vec())
+}
+
+/// Merkle tree over the salted bit leaves, padded to a power of two with zero bits.
+pub(crate) struct BloomTree {
+    /// `levels[0]` are the leaves, the last level is the root.
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl BloomTree {
+    pub(crate) fn build(params: &BloomFilterParams, filter_bits: &[u8]) -> ZkpResult<Self> {
+        params.validate()?;
+        if filter_bits.len() != params.num_bits.div_ceil(8) {
+            return Err(ZkpError::InvalidInput(format!(
+                "filter must be {} bytes for {} bits, got {}",
+                params.num_bits.div_ceil(8),
+                params.num_bits,
+                filter_bits.len()
+            )));
+        }
+        let leaves: Vec<[u8; 32]> = (0..params.num_bits.next_power_of_two())
+            .map(|j| {
+                let bit = filter_bits
+                    .get(j / 8)
+                    .is_some_and(|b| b >> (j % 8) & 1 == 1)
+                    && j < params.num_bits;
+                leaf_hash(&leaf_salt(&params.salt, j), j, bit)
+            })
+            .collect();
+        let mut levels = vec![leaves];
+        while levels.last().map_or(0, Vec::len) > 1 {
+            let next = levels
+                .last()
+                .expect("non-empty levels")
+                .chunks(2)
+                .map(|pair| node_hash(&pair[0], &pair[1]))
+                .collect();
+            levels.push(next);
+        }
+        Ok(Self { levels })
+    }
+
+    pub(crate) fn root(&self) -> &[u8; 32] {
+        &self.levels.last().expect("non-empty levels")[0]
+    }
+
+    /// Sibling hashes from leaf `index` up to (excluding) the root.
+    pub(crate) fn path(&self, index: usize) -> Vec<[u8; 32]> {
+        let mut path = Vec::with_capacity(self.levels.len() - 1);
+        let mut i = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            path.push(level[i ^ 1]);
+            i /= 2;
+        }
+        path
+    }
+}
+
+/// Per-leaf salt, derived from the filter's secret salt so only opened leaves reveal theirs.
+pub(crate) fn leaf_salt(salt: &[u8; 32], index: usize) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"libzkp_bloom_leaf_salt");
+    hasher.update(salt);
+    hasher.update((index as u64).to_le_bytes());
+    hasher.finalize().into()
+}
+
+fn leaf_hash(leaf_salt: &[u8; 32], index: usize, bit: bool) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0u8]);
+    hasher.update(leaf_salt);
+    hasher.update((index as u64).to_le_bytes());
+    hasher.update([bit as u8]);
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([1u8]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Commitment binding the tree root to the filter shape.
+pub(crate) fn bloom_commitment(num_bits: usize, num_hashes: usize, root: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"libzkp_bloom_filter_v1");
+    hasher.update((num_bits as u32).to_le_bytes());
+    hasher.update((num_hashes as u32).to_le_bytes());
+    hasher.update(root);
+    hasher.finalize().into()
+}
+
+/// Tree depth for `num_bits` leaves.
+pub(crate) fn bloom_tree_depth(num_bits: usize) -> usize {
+    num_bits.next_power_of_two().trailing_zeros() as usize
+}
+
+/// Check a Bloom membership payload `[u32 num_bits][u32 num_hashes][element 8]` followed by
+/// one `[leaf salt 32][path 32*depth]` opening per hash, against `commitment`. Returns the
+/// embedded element if every opened bit is set and all paths lead to the committed root.
+pub(crate) fn verify_bloom_payload(payload: &[u8], commitment: &[u8]) -> Option<u64> {
+    let read_u32 = |at: usize| {
+        payload
+            .get(at..at + 4)
+            .and_then(|b| b.try_into().ok())
+            .map(|b| u32::from_le_bytes(b) as usize)
+    };
+    let num_bits = read_u32(0)?;
+    let num_hashes = read_u32(4)?;
+    if num_bits == 0 || num_bits > MAX_BLOOM_FILTER_BITS {
+        return None;
+    }
+    if num_hashes == 0 || num_hashes > MAX_BLOOM_HASHES {
+        return None;
+    }
+    let element = read_u64_le(payload, 8)?;
+    let depth = bloom_tree_depth(num_bits);
+    let opening_len = 32 + 32 * depth;
+    let openings = payload.get(16..)?;
+    if openings.len() != num_hashes * opening_len {
+        return None;
+    }
+
+    let mut root: Option<[u8; 32]> = None;
+    for (pos, opening) in bloom_positions(element, num_bits, num_hashes)
+        .into_iter()
+        .zip(openings.chunks(opening_len))
+    {
+        let salt: [u8; 32] = opening[..32].try_into().ok()?;
+        let mut node = leaf_hash(&salt, pos, true);
+        let mut i = pos;
+        for sibling in opening[32..].chunks(32) {
+            let sibling: [u8; 32] = sibling.try_into().ok()?;
+            node = if i % 2 == 0 {
+                node_hash(&node, &sibling)
+            } else {
+                node_hash(&sibling, &node)
+            };
+            i /= 2;
+        }
+        if root.is_some_and(|r| r != node) {
+            return None;
+        }
+        root = Some(node);
+    }
+
+    let root = root?;
+    (bloom_commitment(num_bits, num_hashes, &root)[..] == *commitment).then_some(element)
+}
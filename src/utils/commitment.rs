@@ -1,4 +1,8 @@
-use crate::backend::snark::{fr_to_commitment, mimc_hash_native};
+use crate::backend::bulletproofs::{
+    element_commitments, multiset_root, pedersen_commit, pedersen_commit_signed, random_blinding,
+    scalar_from_canonical,
+};
+use crate::backend::snark::mimc_commitment;
 use crate::utils::error_handling::{ZkpError, ZkpResult};
 use sha2::{Digest, Sha256};
 
@@ -10,9 +14,66 @@ pub fn commit_value(value: u64) -> Vec<u8> {
 }
 
 /// Generate a MiMC-5 commitment for a single value (used by SNARK-based proofs).
-/// Returns 32 bytes: the canonical little-endian serialization of MiMC5(value) over BN254 Fr.
+/// Returns 32 bytes: the canonical little-endian serialization of MiMC5(value) over the Fr of
+/// the configured SNARK curve (BN254 by default).
 pub fn commit_value_snark(value: u64) -> Vec<u8> {
-    fr_to_commitment(mimc_hash_native(value)).to_vec()
+    mimc_commitment(value).to_vec()
+}
+
+/// Fresh random Pedersen blinding factor (canonical 32-byte scalar encoding).
+/// Keep it secret: together with the value it opens `commit_value_pedersen`.
+pub fn random_pedersen_blinding() -> [u8; 32] {
+    random_blinding().to_bytes()
+}
+
+/// Generate a 32-byte Pedersen commitment (compressed Ristretto point) to `value` under
+/// the Bulletproofs default generators. Used by proofs over other parties' hidden values.
+pub fn commit_value_pedersen(value: u64, blinding: &[u8; 32]) -> ZkpResult<Vec<u8>> {
+    let blinding = scalar_from_canonical(blinding)
+        .ok_or_else(|| ZkpError::InvalidInput("blinding is not a canonical scalar".to_string()))?;
+    Ok(pedersen_commit(value, &blinding).to_bytes().to_vec())
+}
+
+/// Pedersen commitment to a signed change, e.g. the `delta` of
+/// [`crate::proof::improvement_proof::prove_exact_delta`]; negative deltas commit to `-|delta|`.
+pub fn commit_delta_pedersen(delta: i64, blinding: &[u8; 32]) -> ZkpResult<Vec<u8>> {
+    let blinding = scalar_from_canonical(blinding)
+        .ok_or_else(|| ZkpError::InvalidInput("blinding is not a canonical scalar".to_string()))?;
+    Ok(pedersen_commit_signed(delta, &blinding).to_bytes().to_vec())
+}
+
+/// 32-byte blinded root of a multiset (order ignored, duplicates counted), as published for
+/// `prove_set_equality` and `prove_shuffle`. Hides the elements as long as `blinding` is secret.
+pub fn commit_multiset_pedersen(set: &[u64], blinding: &[u8; 32]) -> ZkpResult<Vec<u8>> {
+    let blinding = scalar_from_canonical(blinding)
+        .ok_or_else(|| ZkpError::InvalidInput("blinding is not a canonical scalar".to_string()))?;
+    Ok(multiset_root(set, &blinding).compress().to_bytes().to_vec())
+}
+
+/// 32-byte root of a set committed element by element, as published for `prove_disjoint`: the
+/// SHA-256 digest of `commit_value_pedersen(element, blinding)` over the elements in order,
+/// with one blinding per element.
+pub fn commit_set_elements_pedersen(set: &[u64], blindings: &[[u8; 32]]) -> ZkpResult<Vec<u8>> {
+    if set.len() != blindings.len() {
+        return Err(ZkpError::InvalidInput(format!(
+            "expected {} blindings, got {}",
+            set.len(),
+            blindings.len()
+        )));
+    }
+    let blindings = blindings
+        .iter()
+        .map(scalar_from_canonical)
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| ZkpError::InvalidInput("blinding is not a canonical scalar".to_string()))?;
+    Ok(element_commitments(set, &blindings).1.to_vec())
+}
+
+/// Convert a byte slice into a 32-byte blinding, e.g. from the Python bindings.
+pub fn blinding_from_slice(bytes: &[u8]) -> ZkpResult<[u8; 32]> {
+    bytes.try_into().map_err(|_| {
+        ZkpError::InvalidInput(format!("blinding must be 32 bytes, got {}", bytes.len()))
+    })
 }
 
 /// Generate a SHA256 commitment for multiple values
@@ -34,6 +95,40 @@ pub fn commit_with_context(values: &[u64], context: &[u8]) -> Vec<u8> {
     hasher.finalize().to_vec()
 }
 
+/// 32-byte SHA-256 digest identifying a weight vector for weighted average proofs.
+pub fn weights_hash(weights: &[u64]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(b"libzkp_weights_v1");
+    hasher.update((weights.len() as u32).to_le_bytes());
+    for &w in weights {
+        hasher.update(w.to_le_bytes());
+    }
+    hasher.finalize().to_vec()
+}
+
+/// Extend the sorted-set hash chain `h' = SHA-256(h || element)` from `start` (or from the
+/// chain's initial digest when `None`) over `elements`.
+pub(crate) fn extend_set_chain(start: Option<&[u8; 32]>, elements: &[u64]) -> [u8; 32] {
+    let mut digest: [u8; 32] = match start {
+        Some(d) => *d,
+        None => Sha256::digest(b"libzkp_sorted_set_v1").into(),
+    };
+    for &e in elements {
+        let mut hasher = Sha256::new();
+        hasher.update(digest);
+        hasher.update(e.to_le_bytes());
+        digest = hasher.finalize().into();
+    }
+    digest
+}
+
+/// 32-byte root of an ascending list, chained from its smallest element upward, for rank
+/// proofs. Any suffix of the list can be checked against the root given the chain digest of
+/// the preceding prefix. The root binds the list but does not hide low-entropy elements.
+pub fn sorted_set_root(sorted_set: &[u64]) -> Vec<u8> {
+    extend_set_chain(None, sorted_set).to_vec()
+}
+
 /// Create a 32-byte SHA-256 commitment binding `(old, new)` for improvement proofs.
 pub fn commit_improvement(old: u64, new: u64) -> ZkpResult<Vec<u8>> {
     if new <= old {
@@ -1,9 +1,15 @@
-use crate::proof::Proof;
+use crate::proof::{Proof, ProofInputs, ProofKind};
 use crate::utils::error_handling::{ZkpError, ZkpResult};
 use crate::utils::limits::MAX_COMPOSITE_PROOF_BYTES;
 use crate::utils::proof_helpers::verify_proof_cryptographic;
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+
+/// Maximum number of proofs or metadata entries in one composite.
+const MAX_COMPOSITE_ITEMS: usize = 1000;
+const MAX_METADATA_KEY_BYTES: usize = 1024;
+const MAX_METADATA_VALUE_BYTES: usize = 65536;
 
 /// Composite proof that combines multiple individual proofs.
 ///
@@ -46,6 +52,19 @@ impl CompositeProof {
         })
     }
 
+    /// Like [`Self::new`], but drops proofs whose [`Proof::statement_fingerprint`] was already
+    /// seen (the first one is kept). Returns the composite and the number of proofs removed.
+    pub fn new_deduped(proofs: Vec<Proof>) -> ZkpResult<(Self, usize)> {
+        let original_len = proofs.len();
+        let mut seen = HashSet::new();
+        let unique: Vec<Proof> = proofs
+            .into_iter()
+            .filter(|p| seen.insert(p.statement_fingerprint()))
+            .collect();
+        let removed = original_len - unique.len();
+        Ok((Self::new(unique)?, removed))
+    }
+
     /// Add metadata to the composite proof
     pub fn add_metadata(&mut self, key: String, value: Vec<u8>) {
         self.metadata.insert(key, value);
@@ -111,202 +130,138 @@ impl CompositeProof {
 
     /// Deserialize composite proof from bytes
     pub fn from_bytes(data: &[u8]) -> ZkpResult<Self> {
-        if data.len() > MAX_COMPOSITE_PROOF_BYTES {
+        Self::from_bytes_with_limit(data, MAX_COMPOSITE_PROOF_BYTES)
+    }
+
+    /// Like [`Self::from_bytes`], but with a caller-chosen total size cap instead of
+    /// [`MAX_COMPOSITE_PROOF_BYTES`], checked before any parsing. Memory-bounded verifiers can
+    /// pass a smaller value; per-item limits still apply.
+    pub fn from_bytes_with_limit(data: &[u8], max_bytes: usize) -> ZkpResult<Self> {
+        let composite = Self::parse_with_limit(data, max_bytes)?;
+        if composite.composition_hash != composite.expected_composition_hash() {
+            return Err(ZkpError::InvalidProofFormat(
+                "composition hash mismatch".to_string(),
+            ));
+        }
+        Ok(composite)
+    }
+
+    /// Parse a composite but keep the stored composition hash as-is instead of rejecting a
+    /// mismatch. Diagnostic use only: compare [`Self::composition_hash`] with
+    /// [`Self::expected_composition_hash`] to see whether integrity broke.
+    pub fn from_bytes_unverified(data: &[u8]) -> ZkpResult<Self> {
+        Self::parse_with_limit(data, MAX_COMPOSITE_PROOF_BYTES)
+    }
+
+    /// Recompute the composition hash from the current proofs and metadata.
+    pub fn expected_composition_hash(&self) -> Vec<u8> {
+        Self::compute_composition_hash(&self.proofs, &self.metadata)
+    }
+
+    fn parse_with_limit(data: &[u8], max_bytes: usize) -> ZkpResult<Self> {
+        if data.len() > max_bytes {
             return Err(ZkpError::InvalidProofFormat(format!(
                 "composite proof too large: max {} bytes",
-                MAX_COMPOSITE_PROOF_BYTES
+                max_bytes
             )));
         }
-        if data.len() < 12 {
-            return Err(ZkpError::InvalidProofFormat(format!(
-                "composite proof too short: expected at least 12 bytes, got {}",
-                data.len()
-            )));
+        Self::parse_items(SliceSource { data, offset: 0 })
+    }
+
+    /// Parse a composite from a byte stream without buffering the whole blob first.
+    /// Applies the same limits as [`Self::from_bytes`] (total size, item counts, key/value sizes)
+    /// while reading, and rejects trailing bytes after the composition hash.
+    pub fn from_reader<R: Read>(reader: R) -> ZkpResult<Self> {
+        Self::from_reader_with_limit(reader, MAX_COMPOSITE_PROOF_BYTES)
+    }
+
+    /// Like [`Self::from_reader`], but stops reading once more than `max_bytes` arrive.
+    pub fn from_reader_with_limit<R: Read>(reader: R, max_bytes: usize) -> ZkpResult<Self> {
+        // One byte past the cap lets trailing data and oversized blobs be told apart.
+        let composite = Self::parse_items(StreamSource {
+            reader: reader.take(max_bytes as u64 + 1),
+            max_bytes,
+        })?;
+        if composite.composition_hash != composite.expected_composition_hash() {
+            return Err(ZkpError::InvalidProofFormat(
+                "composition hash mismatch".to_string(),
+            ));
         }
+        Ok(composite)
+    }
 
-        if &data[0..4] != b"COMP" {
+    /// Read the header, proofs, metadata and composition hash item by item, checking each
+    /// declared length before reading it. Shared by the slice and stream parsers.
+    fn parse_items<S: ItemSource>(mut source: S) -> ZkpResult<Self> {
+        use crate::utils::limits::MAX_PROOF_TOTAL_BYTES;
+
+        let mut header = [0u8; 12];
+        source.read_exact(&mut header, "composite proof header")?;
+        if &header[0..4] != b"COMP" {
             return Err(ZkpError::InvalidProofFormat(format!(
                 "invalid composite proof header: expected 'COMP', got '{:?}'",
-                &data[0..4]
+                &header[0..4]
             )));
         }
-
-        let num_proofs = match data[4..8].try_into() {
-            Ok(arr) => u32::from_le_bytes(arr) as usize,
-            Err(_) => {
-                return Err(ZkpError::InvalidProofFormat(
-                    "invalid proofs count".to_string(),
-                ))
-            }
-        };
-        let num_metadata = match data[8..12].try_into() {
-            Ok(arr) => u32::from_le_bytes(arr) as usize,
-            Err(_) => {
-                return Err(ZkpError::InvalidProofFormat(
-                    "invalid metadata count".to_string(),
-                ))
-            }
-        };
-
-        // Validate reasonable limits
-        if num_proofs > 1000 || num_metadata > 1000 {
+        let num_proofs = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        let num_metadata =
+            u32::from_le_bytes([header[8], header[9], header[10], header[11]]) as usize;
+        if num_proofs > MAX_COMPOSITE_ITEMS || num_metadata > MAX_COMPOSITE_ITEMS {
             return Err(ZkpError::InvalidProofFormat(format!(
                 "composite proof has too many items: proofs={}, metadata={}",
                 num_proofs, num_metadata
             )));
         }
 
-        let mut offset = 12;
-        let mut proofs = Vec::new();
-
-        // Read proofs
-        for _ in 0..num_proofs {
-            if offset + 4 > data.len() {
-                return Err(ZkpError::InvalidProofFormat(
-                    "truncated proof length".to_string(),
-                ));
-            }
-
-            let proof_len = match data[offset..offset + 4].try_into() {
-                Ok(arr) => u32::from_le_bytes(arr) as usize,
-                Err(_) => {
-                    return Err(ZkpError::InvalidProofFormat(
-                        "invalid proof length".to_string(),
-                    ))
-                }
-            };
-            offset += 4;
-
-            let next = offset
-                .checked_add(proof_len)
-                .ok_or_else(|| ZkpError::InvalidProofFormat("proof length overflow".to_string()))?;
-            if next > data.len() {
-                return Err(ZkpError::InvalidProofFormat(
-                    "truncated proof data".to_string(),
-                ));
+        let mut proofs = Vec::with_capacity(num_proofs);
+        for i in 0..num_proofs {
+            let proof_len = source.read_u32(&format!("proof length at index {}", i))? as usize;
+            if proof_len > MAX_PROOF_TOTAL_BYTES {
+                return Err(ZkpError::InvalidProofFormat(format!(
+                    "proof {} too large: max {} bytes",
+                    i, MAX_PROOF_TOTAL_BYTES
+                )));
             }
-
-            let proof = Proof::from_bytes(&data[offset..next])?;
-
-            proofs.push(proof);
-            offset = next;
+            let mut buf = vec![0u8; proof_len];
+            source.read_exact(&mut buf, &format!("proof data at index {}", i))?;
+            proofs.push(Proof::from_bytes(&buf)?);
         }
 
-        // Read metadata
+        // Encoding (see `to_bytes`): [u32 key_len][key_bytes][u32 value_len][value_bytes]
         let mut metadata = HashMap::new();
         for i in 0..num_metadata {
-            // Encoding (see `to_bytes`):
-            // [u32 key_len][key_bytes][u32 value_len][value_bytes]
-            if offset + 4 > data.len() {
-                return Err(ZkpError::InvalidProofFormat(format!(
-                    "truncated metadata header at index {}: offset={}, data_len={}",
-                    i,
-                    offset,
-                    data.len()
-                )));
-            }
-
-            let key_len = match data[offset..offset + 4].try_into() {
-                Ok(arr) => u32::from_le_bytes(arr) as usize,
-                Err(_) => {
-                    return Err(ZkpError::InvalidProofFormat(
-                        "invalid metadata key length".to_string(),
-                    ))
-                }
-            };
-            offset += 4;
-
-            // Validate key and value lengths
-            if key_len > 1024 {
+            let key_len = source.read_u32(&format!("metadata key length at index {}", i))? as usize;
+            if key_len > MAX_METADATA_KEY_BYTES {
                 return Err(ZkpError::InvalidProofFormat(format!(
                     "metadata key too large at index {}: key_len={}",
                     i, key_len
                 )));
             }
-
-            if offset + key_len > data.len() {
-                return Err(ZkpError::InvalidProofFormat(format!(
-                    "truncated metadata key at index {}: offset={}, key_len={}, data_len={}",
-                    i,
-                    offset,
-                    key_len,
-                    data.len()
-                )));
-            }
-            let key = String::from_utf8(data[offset..offset + key_len].to_vec()).map_err(|_| {
+            let mut key = vec![0u8; key_len];
+            source.read_exact(&mut key, &format!("metadata key at index {}", i))?;
+            let key = String::from_utf8(key).map_err(|_| {
                 ZkpError::InvalidProofFormat(format!(
                     "invalid metadata key at index {}: non-utf8 bytes",
                     i
                 ))
             })?;
-            offset += key_len;
 
-            if offset + 4 > data.len() {
-                return Err(ZkpError::InvalidProofFormat(format!(
-                    "truncated metadata value length at index {}: offset={}, data_len={}",
-                    i,
-                    offset,
-                    data.len()
-                )));
-            }
-            let value_len = match data[offset..offset + 4].try_into() {
-                Ok(arr) => u32::from_le_bytes(arr) as usize,
-                Err(_) => {
-                    return Err(ZkpError::InvalidProofFormat(
-                        "invalid metadata value length".to_string(),
-                    ))
-                }
-            };
-            offset += 4;
-
-            if value_len > 65536 {
+            let value_len =
+                source.read_u32(&format!("metadata value length at index {}", i))? as usize;
+            if value_len > MAX_METADATA_VALUE_BYTES {
                 return Err(ZkpError::InvalidProofFormat(format!(
                     "metadata value too large at index {}: value_len={}",
                     i, value_len
                 )));
             }
-            if offset + value_len > data.len() {
-                return Err(ZkpError::InvalidProofFormat(format!(
-                    "truncated metadata value at index {}: offset={}, value_len={}, data_len={}",
-                    i,
-                    offset,
-                    value_len,
-                    data.len()
-                )));
-            }
-
-            let value = data[offset..offset + value_len].to_vec();
-            offset += value_len;
-
+            let mut value = vec![0u8; value_len];
+            source.read_exact(&mut value, &format!("metadata value at index {}", i))?;
             metadata.insert(key, value);
         }
 
-        // Read composition hash
-        let end = offset
-            .checked_add(32)
-            .ok_or_else(|| ZkpError::InvalidProofFormat("hash length overflow".to_string()))?;
-        if end > data.len() {
-            return Err(ZkpError::InvalidProofFormat(
-                "missing composition hash".to_string(),
-            ));
-        }
-
-        let composition_hash = data[offset..end].to_vec();
-
-        if end != data.len() {
-            return Err(ZkpError::InvalidProofFormat(format!(
-                "trailing bytes after composition hash: {} extra byte(s)",
-                data.len().saturating_sub(end)
-            )));
-        }
-
-        // Verify composition hash
-        let expected_hash = Self::compute_composition_hash(&proofs, &metadata);
-        if composition_hash != expected_hash {
-            return Err(ZkpError::InvalidProofFormat(
-                "composition hash mismatch".to_string(),
-            ));
-        }
+        let mut composition_hash = vec![0u8; 32];
+        source.read_exact(&mut composition_hash, "composition hash")?;
+        source.finish()?;
 
         Ok(CompositeProof {
             proofs,
@@ -330,6 +285,254 @@ impl CompositeProof {
     pub fn verify_full(&self) -> bool {
         self.verify_integrity() && self.verify_cryptographic()
     }
+
+    /// Verify inner proof `i` against the statement in `params[i]`, in parallel when the
+    /// `parallel` feature is on. Unlike [`Self::verify_cryptographic`], this checks the public
+    /// parameters too. A proof whose scheme differs from its params, or that has no params
+    /// (`params` shorter than [`Self::proofs`]), is `false`; extra params are ignored.
+    pub fn verify_all(&self, params: Vec<VerifyParams>) -> Vec<bool> {
+        let mut params: Vec<Option<VerifyParams>> = params.into_iter().map(Some).collect();
+        params.resize(self.proofs.len(), None);
+        let pairs: Vec<(&Proof, Option<VerifyParams>)> = self.proofs.iter().zip(params).collect();
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            pairs
+                .into_par_iter()
+                .map(|(proof, params)| params.is_some_and(|p| p.verify(proof)))
+                .collect()
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            pairs
+                .into_iter()
+                .map(|(proof, params)| params.is_some_and(|p| p.verify(proof)))
+                .collect()
+        }
+    }
+}
+
+/// Public parameters for one inner proof of a [`CompositeProof`], see
+/// [`CompositeProof::verify_all`]. Schemes without a dedicated variant go through `Other`,
+/// which takes the same inputs as [`crate::proof::verify`].
+#[derive(Debug, Clone)]
+pub enum VerifyParams {
+    Range {
+        min: u64,
+        max: u64,
+    },
+    Equality {
+        value: u64,
+        other_value: u64,
+    },
+    Threshold {
+        threshold: u64,
+    },
+    Membership {
+        set: Vec<u64>,
+    },
+    Improvement {
+        old: u64,
+    },
+    Consistency,
+    Divisible {
+        modulus: u64,
+    },
+    BinaryChoice {
+        option_a: u64,
+        option_b: u64,
+    },
+    PositiveThreshold {
+        threshold: u64,
+    },
+    BoundedConsistency {
+        max_step: u64,
+    },
+    ExactSum {
+        total: u64,
+    },
+    WithinOfMean {
+        max_deviation: u64,
+    },
+    Other {
+        kind: ProofKind,
+        inputs: Box<ProofInputs>,
+    },
+}
+
+impl VerifyParams {
+    /// Scheme these params verify.
+    pub fn kind(&self) -> ProofKind {
+        match self {
+            VerifyParams::Range { .. } => ProofKind::Range,
+            VerifyParams::Equality { .. } => ProofKind::Equality,
+            VerifyParams::Threshold { .. } => ProofKind::Threshold,
+            VerifyParams::Membership { .. } => ProofKind::Membership,
+            VerifyParams::Improvement { .. } => ProofKind::Improvement,
+            VerifyParams::Consistency => ProofKind::Consistency,
+            VerifyParams::Divisible { .. } => ProofKind::Divisible,
+            VerifyParams::BinaryChoice { .. } => ProofKind::BinaryChoice,
+            VerifyParams::PositiveThreshold { .. } => ProofKind::PositiveThreshold,
+            VerifyParams::BoundedConsistency { .. } => ProofKind::BoundedConsistency,
+            VerifyParams::ExactSum { .. } => ProofKind::ExactSum,
+            VerifyParams::WithinOfMean { .. } => ProofKind::WithinOfMean,
+            VerifyParams::Other { kind, .. } => *kind,
+        }
+    }
+
+    fn into_inputs(self) -> ProofInputs {
+        let d = ProofInputs::default();
+        match self {
+            VerifyParams::Range { min, max } => ProofInputs {
+                min: Some(min),
+                max: Some(max),
+                ..d
+            },
+            VerifyParams::Equality { value, other_value } => ProofInputs {
+                value: Some(value),
+                other_value: Some(other_value),
+                ..d
+            },
+            VerifyParams::Threshold { threshold }
+            | VerifyParams::PositiveThreshold { threshold } => ProofInputs {
+                threshold: Some(threshold),
+                ..d
+            },
+            VerifyParams::Membership { set } => ProofInputs { set, ..d },
+            VerifyParams::Improvement { old } => ProofInputs {
+                value: Some(old),
+                ..d
+            },
+            VerifyParams::Consistency => d,
+            VerifyParams::Divisible { modulus } => ProofInputs {
+                modulus: Some(modulus),
+                ..d
+            },
+            VerifyParams::BinaryChoice { option_a, option_b } => ProofInputs {
+                option_a: Some(option_a),
+                option_b: Some(option_b),
+                ..d
+            },
+            VerifyParams::BoundedConsistency { max_step } => ProofInputs {
+                max_step: Some(max_step),
+                ..d
+            },
+            VerifyParams::ExactSum { total } => ProofInputs {
+                total: Some(total),
+                ..d
+            },
+            VerifyParams::WithinOfMean { max_deviation } => ProofInputs {
+                max_deviation: Some(max_deviation),
+                ..d
+            },
+            VerifyParams::Other { inputs, .. } => *inputs,
+        }
+    }
+
+    fn verify(self, proof: &Proof) -> bool {
+        let kind = self.kind();
+        proof.scheme == kind.scheme_id()
+            && crate::proof::verify(proof.to_bytes(), kind, self.into_inputs())
+    }
+}
+
+/// Input for [`CompositeProof::parse_items`]: an in-memory blob or a size-capped stream.
+trait ItemSource {
+    /// Fill `buf` from the next bytes; `what` names the item in error messages.
+    fn read_exact(&mut self, buf: &mut [u8], what: &str) -> ZkpResult<()>;
+
+    /// Reject anything left after the composition hash.
+    fn finish(self) -> ZkpResult<()>;
+
+    fn read_u32(&mut self, what: &str) -> ZkpResult<u32> {
+        let mut buf = [0u8; 4];
+        self.read_exact(&mut buf, what)?;
+        Ok(u32::from_le_bytes(buf))
+    }
+}
+
+struct SliceSource<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl ItemSource for SliceSource<'_> {
+    fn read_exact(&mut self, buf: &mut [u8], what: &str) -> ZkpResult<()> {
+        let bytes = self
+            .offset
+            .checked_add(buf.len())
+            .and_then(|end| self.data.get(self.offset..end))
+            .ok_or_else(|| {
+                ZkpError::InvalidProofFormat(format!(
+                    "truncated {}: offset={}, len={}, data_len={}",
+                    what,
+                    self.offset,
+                    buf.len(),
+                    self.data.len()
+                ))
+            })?;
+        buf.copy_from_slice(bytes);
+        self.offset += buf.len();
+        Ok(())
+    }
+
+    fn finish(self) -> ZkpResult<()> {
+        if self.offset != self.data.len() {
+            return Err(ZkpError::InvalidProofFormat(format!(
+                "trailing bytes after composition hash: {} extra byte(s)",
+                self.data.len() - self.offset
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A reader capped one byte past `max_bytes`; using that byte means the blob is too large.
+struct StreamSource<R: Read> {
+    reader: std::io::Take<R>,
+    max_bytes: usize,
+}
+
+impl<R: Read> StreamSource<R> {
+    fn too_large(&self) -> ZkpError {
+        ZkpError::InvalidProofFormat(format!(
+            "composite proof too large: max {} bytes",
+            self.max_bytes
+        ))
+    }
+}
+
+impl<R: Read> ItemSource for StreamSource<R> {
+    fn read_exact(&mut self, buf: &mut [u8], what: &str) -> ZkpResult<()> {
+        self.reader.read_exact(buf).map_err(|e| {
+            if self.reader.limit() == 0 {
+                self.too_large()
+            } else if e.kind() == std::io::ErrorKind::UnexpectedEof {
+                ZkpError::InvalidProofFormat(format!("truncated {}", what))
+            } else {
+                ZkpError::InvalidProofFormat(format!("failed to read {}: {}", what, e))
+            }
+        })
+    }
+
+    fn finish(mut self) -> ZkpResult<()> {
+        if self.reader.limit() == 0 {
+            return Err(self.too_large());
+        }
+        let mut probe = [0u8; 1];
+        match self.reader.read(&mut probe) {
+            Ok(0) => Ok(()),
+            Ok(_) if self.reader.limit() == 0 => Err(self.too_large()),
+            Ok(_) => Err(ZkpError::InvalidProofFormat(
+                "trailing bytes after composition hash".to_string(),
+            )),
+            Err(e) => Err(ZkpError::InvalidProofFormat(format!(
+                "failed to read composite proof: {}",
+                e
+            ))),
+        }
+    }
 }
 
 /// Batch proof operations for improved performance
@@ -23,5 +23,23 @@ pub const MAX_BACKEND_OPERATION_LEN: usize = 64;
 /// Maximum size (in bytes) accepted for a serialized `CompositeProof`.
 pub const MAX_COMPOSITE_PROOF_BYTES: usize = 4 * 1024 * 1024; // 4 MiB
 
+/// Maximum number of values covered by one aggregated Bulletproofs range proof.
+pub const MAX_AGGREGATED_RANGE_VALUES: usize = 64;
+
+/// Maximum number of element pairs in a set disjointness proof (96 bytes each).
+pub const MAX_DISJOINT_PAIRS: usize = 4096;
+
+/// Maximum number of bits in a committed Bloom filter (tree depth 20).
+pub const MAX_BLOOM_FILTER_BITS: usize = 1 << 20;
+
+/// Maximum number of hash functions (opened bits per proof) in a Bloom filter.
+pub const MAX_BLOOM_HASHES: usize = 32;
+
 /// Maximum size (in bytes) accepted for bulletproofs backend proofs.
 pub const MAX_BULLETPROOFS_BACKEND_PROOF_BYTES: usize = 2 * 1024 * 1024; // 2 MiB
+
+/// Maximum number of attributes in one credential.
+pub const MAX_CREDENTIAL_ATTRIBUTES: usize = 64;
+
+/// Maximum length (in bytes) of a credential attribute name.
+pub const MAX_ATTRIBUTE_NAME_BYTES: usize = 64;
@@ -1,3 +1,4 @@
+pub mod bloom;
 pub mod commitment;
 pub mod composition;
 pub mod encoding;
@@ -8,6 +9,7 @@ pub mod proof_helpers;
 pub mod serialization;
 pub mod validation;
 
+pub use bloom::*;
 pub use commitment::*;
 pub use composition::*;
 pub use encoding::*;
@@ -1,3 +1,4 @@
+use crate::utils::error_handling::{ZkpError, ZkpResult};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
@@ -21,6 +22,20 @@ fn now_ms() -> f64 {
     js_sys::Date::now()
 }
 
+/// Wall-clock time in whole seconds since the Unix epoch (0 if the clock is before the epoch).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn unix_time_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn unix_time_secs() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
+
 /// Simple TTL cache with LFU-style eviction when at capacity (not LRU).
 pub struct ProofCache {
     cache: Arc<Mutex<HashMap<String, CacheEntry>>>,
@@ -93,6 +108,11 @@ impl ProofCache {
         }
     }
 
+    /// Maximum number of entries kept before eviction.
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
     pub fn size(&self) -> usize {
         match self.cache.lock() {
             Ok(cache) => cache.len(),
@@ -107,7 +127,33 @@ static GLOBAL_CACHE: OnceLock<ProofCache> = OnceLock::new();
 static GLOBAL_METRICS: OnceLock<Arc<Mutex<PerformanceMetrics>>> = OnceLock::new();
 
 pub fn get_global_cache() -> &'static ProofCache {
-    GLOBAL_CACHE.get_or_init(|| ProofCache::new(1000, 3600))
+    GLOBAL_CACHE.get_or_init(|| {
+        ProofCache::new(
+            crate::config::DEFAULT_CACHE_MAX_SIZE,
+            crate::config::DEFAULT_CACHE_TTL_SECONDS,
+        )
+    })
+}
+
+/// Create the global cache with explicit limits, and the metrics store, for
+/// [`crate::config::configure`]. Errors if either was already created by first use.
+pub(crate) fn init_global_state(max_size: usize, ttl_seconds: u64) -> ZkpResult<()> {
+    let in_use = || {
+        ZkpError::ConfigError(
+            "proof cache is already initialized; call configure before first use".to_string(),
+        )
+    };
+    GLOBAL_CACHE
+        .set(ProofCache::new(max_size, ttl_seconds))
+        .map_err(|_| in_use())?;
+    GLOBAL_METRICS
+        .set(Arc::new(Mutex::new(PerformanceMetrics::new())))
+        .map_err(|_| in_use())
+}
+
+/// Whether the global cache or metrics store has been created.
+pub(crate) fn global_state_initialized() -> bool {
+    GLOBAL_CACHE.get().is_some() || GLOBAL_METRICS.get().is_some()
 }
 
 pub fn get_global_metrics() -> Arc<Mutex<PerformanceMetrics>> {
@@ -194,10 +240,12 @@ impl PerformanceMetrics {
     }
 
     pub fn get_average_time(&self, operation: &str) -> Option<Duration> {
-        self.operation_times.get(operation).map(|times| {
-            let total: Duration = times.iter().sum();
-            total / times.len() as u32
-        })
+        let times = self.operation_times.get(operation)?;
+        if times.is_empty() {
+            return None;
+        }
+        let total: Duration = times.iter().sum();
+        Some(total / times.len() as u32)
     }
 
     pub fn get_cache_hit_rate(&self) -> f64 {
@@ -208,6 +256,85 @@ impl PerformanceMetrics {
             self.cache_hits as f64 / total as f64
         }
     }
+
+    /// Fold another collector into this one (e.g. metrics shipped from a worker process).
+    pub fn merge(&mut self, other: &PerformanceMetrics) {
+        for (op, count) in &other.operation_counts {
+            let total = self.operation_counts.entry(op.clone()).or_insert(0);
+            *total = total.saturating_add(*count);
+        }
+        for (op, times) in &other.operation_times {
+            self.operation_times
+                .entry(op.clone())
+                .or_default()
+                .extend_from_slice(times);
+        }
+        self.cache_hits = self.cache_hits.saturating_add(other.cache_hits);
+        self.cache_misses = self.cache_misses.saturating_add(other.cache_misses);
+    }
+
+    /// JSON form used to ship metrics between processes; times are integer nanoseconds.
+    pub fn to_json(&self) -> String {
+        let times: HashMap<&String, Vec<u64>> = self
+            .operation_times
+            .iter()
+            .map(|(op, ts)| {
+                let ns = ts
+                    .iter()
+                    .map(|d| u64::try_from(d.as_nanos()).unwrap_or(u64::MAX))
+                    .collect();
+                (op, ns)
+            })
+            .collect();
+        serde_json::json!({
+            "operation_counts": self.operation_counts,
+            "operation_times_ns": times,
+            "cache_hits": self.cache_hits,
+            "cache_misses": self.cache_misses,
+        })
+        .to_string()
+    }
+
+    /// Parse metrics produced by [`Self::to_json`].
+    pub fn from_json(json: &str) -> ZkpResult<Self> {
+        let bad =
+            |what: &str| ZkpError::SerializationError(format!("invalid metrics JSON: {}", what));
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| bad(&e.to_string()))?;
+
+        let mut metrics = PerformanceMetrics::new();
+        if let Some(counts) = value.get("operation_counts") {
+            let counts = counts.as_object().ok_or_else(|| bad("operation_counts"))?;
+            for (op, n) in counts {
+                let n = n.as_u64().ok_or_else(|| bad("operation_counts"))?;
+                metrics.operation_counts.insert(op.clone(), n);
+            }
+        }
+        if let Some(times) = value.get("operation_times_ns") {
+            let times = times.as_object().ok_or_else(|| bad("operation_times_ns"))?;
+            for (op, ns) in times {
+                let ns = ns
+                    .as_array()
+                    .filter(|ns| !ns.is_empty())
+                    .ok_or_else(|| bad("operation_times_ns"))?;
+                let durations = ns
+                    .iter()
+                    .map(|t| t.as_u64().map(Duration::from_nanos))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| bad("operation_times_ns"))?;
+                metrics.operation_times.insert(op.clone(), durations);
+            }
+        }
+        metrics.cache_hits = value
+            .get("cache_hits")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        metrics.cache_misses = value
+            .get("cache_misses")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        Ok(metrics)
+    }
 }
 
 impl Default for PerformanceMetrics {
@@ -242,6 +369,47 @@ impl Default for Timer {
     }
 }
 
+/// Multi-scalar multiplication speedups in use by this build on this CPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MsmAcceleration {
+    /// Assembly Montgomery multiplication for the SNARK fields (Groth16 proving). Needs the
+    /// `msm-accel` feature and an x86_64 build with the `bmi2` and `adx` target features.
+    pub field_asm: bool,
+    /// AVX2 Ristretto arithmetic for Bulletproofs, picked at runtime by `curve25519-dalek` on
+    /// x86_64 (unless it was built with `curve25519_dalek_backend="serial"`).
+    pub ristretto_simd: bool,
+}
+
+impl MsmAcceleration {
+    /// Whether the `msm-accel` speedup is in effect; `ristretto_simd` does not depend on it.
+    pub fn is_active(&self) -> bool {
+        self.field_asm
+    }
+}
+
+/// Report which MSM speedups apply. Groth16 MSMs are multi-threaded on the rayon pool either way.
+pub fn msm_acceleration() -> MsmAcceleration {
+    MsmAcceleration {
+        field_asm: cfg!(all(
+            feature = "msm-accel",
+            target_arch = "x86_64",
+            target_feature = "bmi2",
+            target_feature = "adx"
+        )),
+        ristretto_simd: ristretto_simd_detected(),
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn ristretto_simd_detected() -> bool {
+    std::is_x86_feature_detected!("avx2")
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn ristretto_simd_detected() -> bool {
+    false
+}
+
 /// Parallel processing utilities for batch operations
 pub mod parallel {
     use crate::proof::{Proof, PROOF_VERSION};
@@ -277,14 +445,9 @@ pub mod parallel {
             return false;
         }
 
-        let expected_scheme = match proof_type {
-            "range" => 1,
-            "equality" => 2,
-            "threshold" => 3,
-            "membership" => 4,
-            "improvement" => 5,
-            "consistency" => 6,
-            _ => return false,
+        let expected_scheme = match crate::proof::scheme_id(proof_type) {
+            Some(id) => id,
+            None => return false,
         };
         if proof.scheme != expected_scheme {
             return false;
@@ -3,9 +3,12 @@ use crate::backend::{
     bulletproofs::BulletproofsBackend, snark::SnarkBackend, snark::MAX_SET_SIZE,
     stark::StarkBackend,
 };
-use crate::proof::{Proof, PROOF_VERSION};
+use crate::proof::{scheme_info, Proof, PROOF_VERSION};
+use crate::utils::encoding::read_u64_le;
 use crate::utils::error_handling::{ZkpError, ZkpResult};
-use crate::utils::limits::{MAX_BULLETPROOFS_BACKEND_PROOF_BYTES, MAX_PROOF_TOTAL_BYTES};
+use crate::utils::limits::{
+    MAX_AGGREGATED_RANGE_VALUES, MAX_BULLETPROOFS_BACKEND_PROOF_BYTES, MAX_PROOF_TOTAL_BYTES,
+};
 
 /// Common proof parsing and validation logic
 pub fn parse_and_validate_proof(proof_bytes: &[u8], expected_scheme: u8) -> ZkpResult<Proof> {
@@ -15,7 +18,7 @@ pub fn parse_and_validate_proof(proof_bytes: &[u8], expected_scheme: u8) -> ZkpR
             MAX_PROOF_TOTAL_BYTES
         )));
     }
-    let proof = Proof::from_bytes(proof_bytes)?;
+    let proof = Proof::from_bytes_strict(proof_bytes)?;
 
     if proof.version != PROOF_VERSION {
         return Err(ZkpError::InvalidProofFormat(format!(
@@ -34,6 +37,19 @@ pub fn parse_and_validate_proof(proof_bytes: &[u8], expected_scheme: u8) -> ZkpR
     Ok(proof)
 }
 
+/// Return the Pedersen value commitment carried by `proof_bytes`; errors for schemes whose
+/// commitment is a hash, a MiMC output, or a set root.
+pub fn extract_value_commitment(proof_bytes: &[u8]) -> ZkpResult<Vec<u8>> {
+    let proof = Proof::from_bytes_strict(proof_bytes)?;
+    if !scheme_info(proof.scheme).is_some_and(|s| s.pedersen_value) {
+        return Err(ZkpError::InvalidInput(format!(
+            "scheme {} does not carry a Pedersen value commitment",
+            proof.scheme
+        )));
+    }
+    Ok(proof.commitment)
+}
+
 /// Extract proof body and commitment from bulletproofs backend output
 /// (`[u32 len][proof_body][u32=32][32 byte commit]`).
 pub fn extract_bulletproofs_components(backend_proof: &[u8]) -> ZkpResult<(Vec<u8>, Vec<u8>)> {
@@ -152,96 +168,466 @@ pub fn safe_sum(values: &[u64]) -> ZkpResult<u64> {
     })
 }
 
-/// Cryptographically verify a single [`Proof`] using its `scheme` field (backends: Bulletproofs, SNARK, STARK).
+/// Cryptographically verify a single [`Proof`] with the `verify_payload` of its scheme's
+/// [`crate::proof::SCHEMES`] entry (backends: Bulletproofs, SNARK, STARK).
 pub fn verify_proof_cryptographic(proof: &Proof) -> bool {
     if proof.version != PROOF_VERSION {
         return false;
     }
-    match proof.scheme {
-        1 => {
-            // New format: [min:8][max:8][n_bits:4][...] — minimum 20 bytes
-            if proof.proof.len() < 20 || proof.commitment.len() != 32 {
-                return false;
-            }
-            let min_bytes: [u8; 8] = match proof.proof[0..8].try_into() {
-                Ok(arr) => arr,
-                Err(_) => return false,
-            };
-            let max_bytes: [u8; 8] = match proof.proof[8..16].try_into() {
-                Ok(arr) => arr,
-                Err(_) => return false,
-            };
-            let min = u64::from_le_bytes(min_bytes);
-            let max = u64::from_le_bytes(max_bytes);
-            if min > max {
-                return false;
-            }
-            let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
-            BulletproofsBackend::verify_range_with_bounds(&backend_proof, min, max)
-        }
-        2 => {
-            if proof.commitment.len() != 32 {
-                return false;
-            }
-            SnarkBackend::verify(&proof.proof, &proof.commitment)
-        }
-        3 => {
-            // New format: [threshold:8][n_bits:4][...] — minimum 12 bytes
-            if proof.proof.len() < 12 || proof.commitment.len() != 32 {
-                return false;
-            }
-            let threshold_bytes: [u8; 8] = match proof.proof[0..8].try_into() {
-                Ok(arr) => arr,
-                Err(_) => return false,
-            };
-            let threshold = u64::from_le_bytes(threshold_bytes);
-            let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
-            BulletproofsBackend::verify_threshold(&backend_proof, threshold)
-        }
-        4 => {
-            if proof.commitment.len() != 32 {
-                return false;
-            }
-            let (set, snark_bytes) =
-                match deserialize_embedded_set_prefix(&proof.proof, MAX_SET_SIZE) {
-                    Some(p) => p,
-                    None => return false,
-                };
-            if snark_bytes.is_empty() {
-                return false;
-            }
-            SnarkBackend::verify_membership_zk(snark_bytes, &set, &proof.commitment)
-        }
-        5 => {
-            if proof.commitment.len() != 32 || proof.proof.len() < 16 {
-                return false;
-            }
-            let old = match proof.proof[0..8].try_into() {
-                Ok(arr) => u64::from_le_bytes(arr),
-                Err(_) => return false,
-            };
-            let new = match proof.proof[8..16].try_into() {
-                Ok(arr) => u64::from_le_bytes(arr),
-                Err(_) => return false,
-            };
-            if crate::utils::commitment::validate_improvement_commitment(
-                &proof.commitment,
-                old,
-                new,
-            )
-            .is_err()
-            {
-                return false;
-            }
-            let mut data = Vec::with_capacity(16);
-            data.extend_from_slice(&old.to_le_bytes());
-            data.extend_from_slice(&new.to_le_bytes());
-            StarkBackend::verify(&proof.proof[16..], &data)
-        }
-        6 => {
-            let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
-            BulletproofsBackend::verify_consistency(&backend_proof)
-        }
-        _ => false,
+    scheme_info(proof.scheme).is_some_and(|s| (s.verify_payload)(proof))
+}
+
+// Per-scheme payload checks, referenced by the `verify_payload` field of each `SCHEMES` entry.
+
+pub(crate) fn verify_range_payload(proof: &Proof) -> bool {
+    // New format: [min:8][max:8][n_bits:4][...] — minimum 20 bytes
+    if proof.proof.len() < 20 || proof.commitment.len() != 32 {
+        return false;
+    }
+    let min_bytes: [u8; 8] = match proof.proof[0..8].try_into() {
+        Ok(arr) => arr,
+        Err(_) => return false,
+    };
+    let max_bytes: [u8; 8] = match proof.proof[8..16].try_into() {
+        Ok(arr) => arr,
+        Err(_) => return false,
+    };
+    let min = u64::from_le_bytes(min_bytes);
+    let max = u64::from_le_bytes(max_bytes);
+    if min > max {
+        return false;
+    }
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_range_with_bounds(&backend_proof, min, max)
+}
+
+pub(crate) fn verify_equality_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    SnarkBackend::verify(&proof.proof, &proof.commitment)
+}
+
+pub(crate) fn verify_threshold_payload(proof: &Proof) -> bool {
+    // New format: [threshold:8][n_bits:4][...] — minimum 12 bytes
+    if proof.proof.len() < 12 || proof.commitment.len() != 32 {
+        return false;
+    }
+    let threshold_bytes: [u8; 8] = match proof.proof[0..8].try_into() {
+        Ok(arr) => arr,
+        Err(_) => return false,
+    };
+    let threshold = u64::from_le_bytes(threshold_bytes);
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_threshold(&backend_proof, threshold)
+}
+
+pub(crate) fn verify_membership_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let (set, snark_bytes) = match deserialize_embedded_set_prefix(&proof.proof, MAX_SET_SIZE) {
+        Some(p) => p,
+        None => return false,
+    };
+    if snark_bytes.is_empty() {
+        return false;
+    }
+    SnarkBackend::verify_membership_zk(snark_bytes, &set, &proof.commitment)
+}
+
+pub(crate) fn verify_improvement_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 || proof.proof.len() < 16 {
+        return false;
+    }
+    let old = match proof.proof[0..8].try_into() {
+        Ok(arr) => u64::from_le_bytes(arr),
+        Err(_) => return false,
+    };
+    let new = match proof.proof[8..16].try_into() {
+        Ok(arr) => u64::from_le_bytes(arr),
+        Err(_) => return false,
+    };
+    if crate::utils::commitment::validate_improvement_commitment(&proof.commitment, old, new)
+        .is_err()
+    {
+        return false;
+    }
+    let mut data = Vec::with_capacity(16);
+    data.extend_from_slice(&old.to_le_bytes());
+    data.extend_from_slice(&new.to_le_bytes());
+    StarkBackend::verify(&proof.proof[16..], &data)
+}
+
+pub(crate) fn verify_consistency_payload(proof: &Proof) -> bool {
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_consistency(&backend_proof)
+}
+
+pub(crate) fn verify_set_equality_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_set_equality(&backend_proof)
+}
+
+pub(crate) fn verify_divisible_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let modulus = match read_u64_le(&proof.proof, 0) {
+        Some(m) => m,
+        None => return false,
+    };
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_divisible(&backend_proof, modulus)
+}
+
+pub(crate) fn verify_range_and_greater_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let (min, max) = match (read_u64_le(&proof.proof, 0), read_u64_le(&proof.proof, 8)) {
+        (Some(min), Some(max)) => (min, max),
+        _ => return false,
+    };
+    let other_commitment = match proof.proof.get(16..48) {
+        Some(c) => c,
+        None => return false,
+    };
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_range_and_greater(&backend_proof, min, max, other_commitment)
+}
+
+pub(crate) fn verify_weighted_average_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let (min, max) = match (read_u64_le(&proof.proof, 0), read_u64_le(&proof.proof, 8)) {
+        (Some(min), Some(max)) => (min, max),
+        _ => return false,
+    };
+    let weights = match proof
+        .proof
+        .get(16..)
+        .and_then(|rest| deserialize_embedded_set_prefix(rest, MAX_AGGREGATED_RANGE_VALUES - 2))
+    {
+        Some((w, _)) => w,
+        None => return false,
+    };
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_weighted_average_range(&backend_proof, &weights, min, max)
+}
+
+pub(crate) fn verify_committed_interval_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_in_committed_interval(&backend_proof)
+}
+
+pub(crate) fn verify_binary_choice_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let (option_a, option_b) = match (read_u64_le(&proof.proof, 0), read_u64_le(&proof.proof, 8)) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return false,
+    };
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_binary_choice(&backend_proof, option_a, option_b)
+}
+
+pub(crate) fn verify_positive_threshold_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let threshold = match read_u64_le(&proof.proof, 0) {
+        Some(t) => t,
+        None => return false,
+    };
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_positive_threshold(&backend_proof, threshold)
+}
+
+pub(crate) fn verify_bounded_consistency_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let max_step = match read_u64_le(&proof.proof, 0) {
+        Some(m) => m,
+        None => return false,
+    };
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_bounded_consistency(&backend_proof, max_step)
+}
+
+pub(crate) fn verify_exact_sum_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
     }
+    let total = match read_u64_le(&proof.proof, 0) {
+        Some(t) => t,
+        None => return false,
+    };
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_exact_sum(&backend_proof, total)
+}
+
+pub(crate) fn verify_rank_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let (top, snark_bytes) = match proof
+        .proof
+        .get(32..)
+        .and_then(|rest| deserialize_embedded_set_prefix(rest, MAX_SET_SIZE))
+    {
+        Some(p) => p,
+        None => return false,
+    };
+    if snark_bytes.is_empty() {
+        return false;
+    }
+    SnarkBackend::verify_membership_zk(snark_bytes, &top, &proof.commitment)
+}
+
+pub(crate) fn verify_membership_index_below_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let limit = match proof.proof.get(0..4).and_then(|b| b.try_into().ok()) {
+        Some(b) => u32::from_le_bytes(b) as usize,
+        None => return false,
+    };
+    let (set, snark_bytes) = match deserialize_embedded_set_prefix(&proof.proof[4..], MAX_SET_SIZE)
+    {
+        Some(p) => p,
+        None => return false,
+    };
+    if snark_bytes.is_empty() {
+        return false;
+    }
+    SnarkBackend::verify_membership_zk_below(snark_bytes, &set, limit, &proof.commitment)
+}
+
+pub(crate) fn verify_is_maximum_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_is_maximum(&backend_proof)
+}
+
+pub(crate) fn verify_exact_delta_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_exact_delta(&backend_proof)
+}
+
+pub(crate) fn verify_disjoint_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_disjoint(&backend_proof)
+}
+
+pub(crate) fn verify_within_of_mean_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let max_deviation = match read_u64_le(&proof.proof, 0) {
+        Some(d) => d,
+        None => return false,
+    };
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_within_of_mean(&backend_proof, max_deviation)
+}
+
+pub(crate) fn verify_membership_bloom_payload(proof: &Proof) -> bool {
+    crate::utils::bloom::verify_bloom_payload(&proof.proof, &proof.commitment).is_some()
+}
+
+pub(crate) fn verify_polynomial_nonneg_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    match crate::proof::range_proof::read_polynomial_coefficients(&proof.proof) {
+        Some(coefficients) => SnarkBackend::verify_polynomial_nonneg_zk(
+            &proof.proof[24..],
+            coefficients,
+            &proof.commitment,
+        ),
+        None => false,
+    }
+}
+
+pub(crate) fn verify_count_above_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let (threshold, min_count) = match (
+        read_u64_le(&proof.proof, 0),
+        proof.proof.get(8..12).and_then(|b| b.try_into().ok()),
+    ) {
+        (Some(t), Some(k)) => (t, u32::from_le_bytes(k) as usize),
+        _ => return false,
+    };
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_count_above(&backend_proof, threshold, min_count)
+}
+
+pub(crate) fn verify_strictly_between_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_strictly_between(&backend_proof)
+}
+
+pub(crate) fn verify_difference_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let diff = match read_u64_le(&proof.proof, 32) {
+        Some(d) => d,
+        None => return false,
+    };
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_difference(&backend_proof, diff)
+}
+
+pub(crate) fn verify_range_many_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let (min, max) = match (read_u64_le(&proof.proof, 0), read_u64_le(&proof.proof, 8)) {
+        (Some(min), Some(max)) => (min, max),
+        _ => return false,
+    };
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_range_batch(&backend_proof, min, max)
+}
+
+#[cfg(feature = "halo2")]
+pub(crate) fn verify_range_halo2_payload(proof: &Proof) -> bool {
+    let (min, max) = match (read_u64_le(&proof.proof, 0), read_u64_le(&proof.proof, 8)) {
+        (Some(min), Some(max)) => (min, max),
+        _ => return false,
+    };
+    match proof.proof.get(16..) {
+        Some(halo2_proof) => crate::backend::halo2::Halo2Backend::verify_range(
+            halo2_proof,
+            &proof.commitment,
+            min,
+            max,
+        ),
+        None => false,
+    }
+}
+
+#[cfg(feature = "halo2")]
+pub(crate) fn verify_equality_halo2_payload(proof: &Proof) -> bool {
+    crate::backend::halo2::Halo2Backend::verify_equality(&proof.proof, &proof.commitment)
+}
+
+pub(crate) fn verify_non_membership_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 || proof.proof.len() <= 32 {
+        return false;
+    }
+    let (root, snark_bytes) = proof.proof.split_at(32);
+    SnarkBackend::verify_non_membership_zk(snark_bytes, root, &proof.commitment)
+}
+
+pub(crate) fn verify_inequality_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_inequality(&backend_proof)
+}
+
+pub(crate) fn verify_greater_than_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_greater_than(&backend_proof)
+}
+
+pub(crate) fn verify_average_threshold_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let (threshold, count) = match (
+        read_u64_le(&proof.proof, 0),
+        proof.proof.get(8..12).and_then(|b| b.try_into().ok()),
+    ) {
+        (Some(t), Some(n)) => (t, u32::from_le_bytes(n) as usize),
+        _ => return false,
+    };
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_average_threshold(&backend_proof, threshold, count)
+}
+
+pub(crate) fn verify_membership_merkle_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 || proof.proof.len() <= 32 {
+        return false;
+    }
+    let (root, snark_bytes) = proof.proof.split_at(32);
+    SnarkBackend::verify_merkle_membership_zk(snark_bytes, root, &proof.commitment)
+}
+
+pub(crate) fn verify_solvency_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_solvency(&backend_proof)
+}
+
+pub(crate) fn verify_shuffle_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_shuffle(&backend_proof)
+}
+
+pub(crate) fn verify_vote_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    crate::proof::voting::VoteStatement::read(&proof.proof)
+        .is_some_and(|statement| statement.verify(&proof.commitment))
+}
+
+pub(crate) fn verify_balance_update_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_balance_update(&backend_proof)
+}
+
+pub(crate) fn verify_median_threshold_payload(proof: &Proof) -> bool {
+    if proof.commitment.len() != 32 {
+        return false;
+    }
+    let threshold = match read_u64_le(&proof.proof, 0) {
+        Some(t) => t,
+        None => return false,
+    };
+    let backend_proof = reconstruct_bulletproofs_proof(&proof.proof, &proof.commitment);
+    BulletproofsBackend::verify_median_threshold(&backend_proof, threshold)
+}
+
+pub(crate) fn verify_variance_below_payload(proof: &Proof) -> bool {
+    proof.commitment.len() == 32
+        && crate::proof::threshold_proof::verify_variance_payload(&proof.proof, &proof.commitment)
 }
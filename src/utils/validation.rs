@@ -1,4 +1,10 @@
+use crate::backend::snark::variance::{variance_spread, MAX_VARIANCE_VALUES};
+use crate::backend::snark::voting::MAX_VOTE_OPTIONS;
 use crate::utils::error_handling::{ZkpError, ZkpResult};
+use crate::utils::limits::{
+    MAX_AGGREGATED_RANGE_VALUES, MAX_ATTRIBUTE_NAME_BYTES, MAX_CREDENTIAL_ATTRIBUTES,
+    MAX_U64_VEC_LEN,
+};
 use crate::utils::proof_helpers::{is_ascending_order, safe_sum};
 
 /// Validate range parameters
@@ -25,6 +31,41 @@ pub fn validate_equality_params(val1: u64, val2: u64) -> ZkpResult<()> {
     Ok(())
 }
 
+/// Validate inequality parameters
+pub fn validate_inequality_params(val1: u64, val2: u64) -> ZkpResult<()> {
+    if val1 == val2 {
+        return Err(ZkpError::InvalidInput("values are equal".to_string()));
+    }
+    Ok(())
+}
+
+/// Validate signed range parameters
+pub fn validate_range_signed_params(value: i64, min: i64, max: i64) -> ZkpResult<()> {
+    if min > max {
+        return Err(ZkpError::InvalidInput(
+            "min cannot be greater than max".to_string(),
+        ));
+    }
+    if value < min || value > max {
+        return Err(ZkpError::InvalidInput(format!(
+            "value {} is not in range [{}, {}]",
+            value, min, max
+        )));
+    }
+    Ok(())
+}
+
+/// Validate that `offset + value` lies in `[min, max]`, rejecting an overflowing sum
+pub fn validate_range_offset_params(value: u64, offset: u64, min: u64, max: u64) -> ZkpResult<()> {
+    let shifted = value.checked_add(offset).ok_or_else(|| {
+        ZkpError::InvalidInput(format!(
+            "integer overflow adding offset {} to value",
+            offset
+        ))
+    })?;
+    validate_range_params(shifted, min, max)
+}
+
 /// Validate threshold parameters
 pub fn validate_threshold_params(values: &[u64], threshold: u64) -> ZkpResult<u64> {
     if values.is_empty() {
@@ -43,6 +84,136 @@ pub fn validate_threshold_params(values: &[u64], threshold: u64) -> ZkpResult<u6
     Ok(sum)
 }
 
+/// Validate that the mean of `values` is at least `threshold` (`sum >= threshold * n`)
+pub fn validate_average_threshold_params(values: &[u64], threshold: u64) -> ZkpResult<()> {
+    if values.is_empty() {
+        return Err(ZkpError::InvalidInput("values cannot be empty".to_string()));
+    }
+    let sum = safe_sum(values)?;
+    if (sum as u128) < threshold as u128 * values.len() as u128 {
+        return Err(ZkpError::InvalidInput(format!(
+            "average of {} values is below threshold {}",
+            values.len(),
+            threshold
+        )));
+    }
+    Ok(())
+}
+
+/// Validate that the median of 1 to `MAX_AGGREGATED_RANGE_VALUES` values (the lower median
+/// for an even count) exceeds `threshold`; returns the values sorted ascending
+pub fn validate_median_threshold_params(values: &[u64], threshold: u64) -> ZkpResult<Vec<u64>> {
+    if values.is_empty() {
+        return Err(ZkpError::InvalidInput("values cannot be empty".to_string()));
+    }
+    if values.len() > MAX_AGGREGATED_RANGE_VALUES {
+        return Err(ZkpError::InvalidInput(format!(
+            "{} values exceed maximum {}",
+            values.len(),
+            MAX_AGGREGATED_RANGE_VALUES
+        )));
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let median = sorted[(sorted.len() - 1) / 2];
+    if median <= threshold {
+        return Err(ZkpError::InvalidInput(format!(
+            "median {} does not exceed threshold {}",
+            median, threshold
+        )));
+    }
+    Ok(sorted)
+}
+
+/// Validate that `assets` sum to at least the sum of `liabilities`; returns both sums
+pub fn validate_solvency_params(assets: &[u64], liabilities: &[u64]) -> ZkpResult<(u64, u64)> {
+    if assets.is_empty() || liabilities.is_empty() {
+        return Err(ZkpError::InvalidInput(
+            "assets and liabilities cannot be empty".to_string(),
+        ));
+    }
+    if assets.len() + liabilities.len() > MAX_U64_VEC_LEN {
+        return Err(ZkpError::InvalidInput(format!(
+            "at most {} asset and liability entries are supported",
+            MAX_U64_VEC_LEN
+        )));
+    }
+    let total_assets = safe_sum(assets)?;
+    let total_liabilities = safe_sum(liabilities)?;
+    if total_assets < total_liabilities {
+        return Err(ZkpError::InvalidInput(
+            "total assets are less than total liabilities".to_string(),
+        ));
+    }
+    Ok((total_assets, total_liabilities))
+}
+
+/// Validate that `amount` can be taken from the balance `old`; returns the new balance
+pub fn validate_balance_update_params(old: u64, amount: u64) -> ZkpResult<u64> {
+    old.checked_sub(amount)
+        .ok_or_else(|| ZkpError::InvalidInput(format!("amount {} exceeds balance {}", amount, old)))
+}
+
+/// Validate that `values` sum to exactly `total`
+pub fn validate_exact_sum_params(values: &[u64], total: u64) -> ZkpResult<()> {
+    if values.is_empty() {
+        return Err(ZkpError::InvalidInput("values cannot be empty".to_string()));
+    }
+    let sum = safe_sum(values)?;
+    if sum != total {
+        return Err(ZkpError::InvalidInput(format!(
+            "sum {} does not equal total {}",
+            sum, total
+        )));
+    }
+    Ok(())
+}
+
+/// Validate that `values[max_index]` is a maximum of `values` (ties allowed)
+pub fn validate_is_maximum_params(values: &[u64], max_index: usize) -> ZkpResult<()> {
+    if values.len() < 2 {
+        return Err(ZkpError::InvalidInput(
+            "at least two values are required".to_string(),
+        ));
+    }
+    let claimed = *values.get(max_index).ok_or_else(|| {
+        ZkpError::InvalidInput(format!(
+            "max index {} is out of range for {} values",
+            max_index,
+            values.len()
+        ))
+    })?;
+    if let Some(i) = values.iter().position(|&v| v > claimed) {
+        return Err(ZkpError::InvalidInput(format!(
+            "value at index {} is not the maximum: index {} is larger",
+            max_index, i
+        )));
+    }
+    Ok(())
+}
+
+/// Validate that every value is at most `max_value`; the error names the first offending index.
+pub fn validate_value_bounds(values: &[u64], max_value: u64) -> ZkpResult<()> {
+    if let Some((i, v)) = values.iter().enumerate().find(|(_, &v)| v > max_value) {
+        return Err(ZkpError::InvalidInput(format!(
+            "value {} at index {} exceeds per-element maximum {}",
+            v, i, max_value
+        )));
+    }
+    Ok(())
+}
+
+/// Validate that every value is at least 1; the error names the first zero index.
+pub fn validate_all_positive(values: &[u64]) -> ZkpResult<()> {
+    if let Some(i) = values.iter().position(|&v| v == 0) {
+        return Err(ZkpError::InvalidInput(format!(
+            "value at index {} must be positive",
+            i
+        )));
+    }
+    Ok(())
+}
+
 /// Validate set membership parameters
 pub fn validate_membership_params(value: u64, set: &[u64]) -> ZkpResult<()> {
     if set.is_empty() {
@@ -59,6 +230,39 @@ pub fn validate_membership_params(value: u64, set: &[u64]) -> ZkpResult<()> {
     Ok(())
 }
 
+/// Validate non-membership parameters: a non-empty set that does not contain `value`.
+pub fn validate_non_membership_params(value: u64, set: &[u64]) -> ZkpResult<()> {
+    if set.is_empty() {
+        return Err(ZkpError::InvalidInput("set cannot be empty".to_string()));
+    }
+
+    if set.contains(&value) {
+        return Err(ZkpError::InvalidInput(format!(
+            "value {} is in the provided set",
+            value
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate membership parameters where the value's first index in `set` must be below `k`
+pub fn validate_membership_index_params(value: u64, set: &[u64], k: usize) -> ZkpResult<()> {
+    validate_membership_params(value, set)?;
+    if k == 0 {
+        return Err(ZkpError::InvalidInput(
+            "k must be greater than zero".to_string(),
+        ));
+    }
+    if let Some(i) = set.iter().position(|&x| x == value).filter(|&i| i >= k) {
+        return Err(ZkpError::InvalidInput(format!(
+            "value index {} is not below {}",
+            i, k
+        )));
+    }
+    Ok(())
+}
+
 /// Validate improvement parameters
 pub fn validate_improvement_params(old: u64, new: u64) -> ZkpResult<u64> {
     if new <= old {
@@ -85,6 +289,340 @@ pub fn validate_consistency_params(data: &[u64]) -> ZkpResult<()> {
     Ok(())
 }
 
+/// Validate consistency parameters where every step is also at most `max_step`
+pub fn validate_bounded_consistency_params(data: &[u64], max_step: u64) -> ZkpResult<()> {
+    validate_consistency_params(data)?;
+    if let Some(i) = data.windows(2).position(|w| w[1] - w[0] > max_step) {
+        return Err(ZkpError::InvalidInput(format!(
+            "step {} at index {} exceeds maximum step {}",
+            data[i + 1] - data[i],
+            i + 1,
+            max_step
+        )));
+    }
+    Ok(())
+}
+
+/// Validate rank parameters: `value` must be among the top `max_rank` elements of the ascending
+/// `sorted_set`. Returns the index where the top `max_rank` elements start.
+pub fn validate_rank_params(value: u64, sorted_set: &[u64], max_rank: usize) -> ZkpResult<usize> {
+    if sorted_set.is_empty() {
+        return Err(ZkpError::InvalidInput("set cannot be empty".to_string()));
+    }
+    if max_rank == 0 {
+        return Err(ZkpError::InvalidInput(
+            "max_rank must be greater than zero".to_string(),
+        ));
+    }
+    if !is_ascending_order(sorted_set) {
+        return Err(ZkpError::InvalidInput(
+            "set must be sorted in ascending order".to_string(),
+        ));
+    }
+    let split = sorted_set.len().saturating_sub(max_rank);
+    if !sorted_set[split..].contains(&value) {
+        return Err(ZkpError::InvalidInput(format!(
+            "value is not among the top {} elements",
+            max_rank
+        )));
+    }
+    Ok(split)
+}
+
+/// Validate disjointness parameters: both sets non-empty and no element in common
+pub fn validate_disjoint_params(set_a: &[u64], set_b: &[u64]) -> ZkpResult<()> {
+    if set_a.is_empty() || set_b.is_empty() {
+        return Err(ZkpError::InvalidInput("sets cannot be empty".to_string()));
+    }
+    let lookup: std::collections::HashSet<u64> = set_a.iter().copied().collect();
+    if set_b.iter().any(|v| lookup.contains(v)) {
+        return Err(ZkpError::InvalidInput(
+            "sets share at least one element".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validate set equality parameters (multisets: order is ignored, duplicates count)
+pub fn validate_set_equality_params(set_a: &[u64], set_b: &[u64]) -> ZkpResult<()> {
+    if set_a.is_empty() || set_b.is_empty() {
+        return Err(ZkpError::InvalidInput("sets cannot be empty".to_string()));
+    }
+    if set_a.len() != set_b.len() {
+        return Err(ZkpError::InvalidInput(format!(
+            "set sizes differ: {} vs {}",
+            set_a.len(),
+            set_b.len()
+        )));
+    }
+    let mut a = set_a.to_vec();
+    let mut b = set_b.to_vec();
+    a.sort_unstable();
+    b.sort_unstable();
+    if a != b {
+        return Err(ZkpError::InvalidInput("sets are not equal".to_string()));
+    }
+    Ok(())
+}
+
+/// Validate weighted average parameters; the average is compared as `min*W <= S <= max*W`
+pub fn validate_weighted_average_params(
+    values: &[u64],
+    weights: &[u64],
+    min: u64,
+    max: u64,
+) -> ZkpResult<()> {
+    if values.is_empty() {
+        return Err(ZkpError::InvalidInput("values cannot be empty".to_string()));
+    }
+    if values.len() != weights.len() {
+        return Err(ZkpError::InvalidInput(format!(
+            "values and weights lengths differ: {} vs {}",
+            values.len(),
+            weights.len()
+        )));
+    }
+    if min > max {
+        return Err(ZkpError::InvalidInput(
+            "min cannot be greater than max".to_string(),
+        ));
+    }
+    let overflow =
+        || ZkpError::InvalidInput("integer overflow in weighted sum calculation".to_string());
+    let total_weight = weights
+        .iter()
+        .try_fold(0u64, |acc, &w| acc.checked_add(w))
+        .ok_or_else(overflow)?;
+    if total_weight == 0 {
+        return Err(ZkpError::InvalidInput(
+            "total weight must be greater than zero".to_string(),
+        ));
+    }
+    let weighted_sum = values
+        .iter()
+        .zip(weights)
+        .try_fold(0u64, |acc, (&v, &w)| acc.checked_add(v.checked_mul(w)?))
+        .ok_or_else(overflow)?;
+    let lower = min.checked_mul(total_weight).ok_or_else(overflow)?;
+    let upper = max.checked_mul(total_weight).ok_or_else(overflow)?;
+    if weighted_sum < lower || weighted_sum > upper {
+        return Err(ZkpError::InvalidInput(format!(
+            "weighted average {}/{} is outside range [{}, {}]",
+            weighted_sum, total_weight, min, max
+        )));
+    }
+    Ok(())
+}
+
+/// Validate that `my_value` is within `max_deviation` of the mean of `values`, compared
+/// without division as `|n*my_value - S| <= n*max_deviation`
+pub fn validate_within_of_mean_params(
+    values: &[u64],
+    my_value: u64,
+    max_deviation: u64,
+) -> ZkpResult<()> {
+    if values.is_empty() {
+        return Err(ZkpError::InvalidInput("values cannot be empty".to_string()));
+    }
+    let overflow = || ZkpError::InvalidInput("integer overflow in mean calculation".to_string());
+    let n = values.len() as u64;
+    let sum = safe_sum(values)?;
+    let scaled_value = my_value.checked_mul(n).ok_or_else(overflow)?;
+    let scaled_deviation = max_deviation.checked_mul(n).ok_or_else(overflow)?;
+    if scaled_value.abs_diff(sum) > scaled_deviation {
+        return Err(ZkpError::InvalidInput(format!(
+            "value deviates from the mean {}/{} by more than {}",
+            sum, n, max_deviation
+        )));
+    }
+    // The proof commits to both `n*x + n*D - S` and `S + n*D - n*x`, which must fit in u64.
+    scaled_value
+        .max(sum)
+        .checked_add(scaled_deviation)
+        .ok_or_else(overflow)?;
+    Ok(())
+}
+
+/// Validate that at least `min_count` of `values` are strictly greater than `threshold`.
+pub fn validate_count_above_params(
+    values: &[u64],
+    threshold: u64,
+    min_count: usize,
+) -> ZkpResult<()> {
+    if values.is_empty() {
+        return Err(ZkpError::InvalidInput("values cannot be empty".to_string()));
+    }
+    let count = values.iter().filter(|&&v| v > threshold).count();
+    if count < min_count {
+        return Err(ZkpError::InvalidInput(format!(
+            "only {} of {} values exceed {}; at least {} required",
+            count,
+            values.len(),
+            threshold,
+            min_count
+        )));
+    }
+    Ok(())
+}
+
+/// `a*x^2 + b*x + c` for `coefficients = [a, b, c]`, or `None` on `i128` overflow.
+pub fn evaluate_quadratic(x: u64, coefficients: [i64; 3]) -> Option<i128> {
+    let [a, b, c] = coefficients.map(i128::from);
+    let x = i128::from(x);
+    x.checked_mul(x)
+        .and_then(|x2| a.checked_mul(x2))
+        .and_then(|ax2| ax2.checked_add(b * x))
+        .and_then(|v| v.checked_add(c))
+}
+
+/// Validate that `a*value^2 + b*value + c` is non-negative and fits in a `u64`, the width the
+/// polynomial proof range-checks.
+pub fn validate_polynomial_nonneg_params(value: u64, a: i64, b: i64, c: i64) -> ZkpResult<()> {
+    let result = evaluate_quadratic(value, [a, b, c]).ok_or_else(|| {
+        ZkpError::InvalidInput("integer overflow in polynomial evaluation".to_string())
+    })?;
+    if result < 0 {
+        return Err(ZkpError::InvalidInput(format!(
+            "polynomial {}*x^2 + {}*x + {} is negative at the value",
+            a, b, c
+        )));
+    }
+    if result > u64::MAX as i128 {
+        return Err(ZkpError::InvalidInput(
+            "integer overflow in polynomial evaluation: result exceeds u64".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validate `low < value < high`.
+pub fn validate_strictly_between_params(value: u64, low: u64, high: u64) -> ZkpResult<()> {
+    if value <= low || value >= high {
+        return Err(ZkpError::InvalidInput(format!(
+            "value must be strictly between {} and {}",
+            low, high
+        )));
+    }
+    Ok(())
+}
+
+/// Validate a non-empty batch of values that all lie in `[min, max]`
+pub fn validate_range_many_params(values: &[u64], min: u64, max: u64) -> ZkpResult<()> {
+    if values.is_empty() {
+        return Err(ZkpError::InvalidInput("values cannot be empty".to_string()));
+    }
+    values
+        .iter()
+        .try_for_each(|&value| validate_range_params(value, min, max))
+}
+
+/// Validate that `value` is strictly greater than `other`
+pub fn validate_greater_than(value: u64, other: u64) -> ZkpResult<()> {
+    if value <= other {
+        return Err(ZkpError::InvalidInput(
+            "value must be greater than the other value".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validate binary choice parameters: distinct options and `value` equal to one of them
+pub fn validate_binary_choice_params(value: u64, option_a: u64, option_b: u64) -> ZkpResult<()> {
+    if option_a == option_b {
+        return Err(ZkpError::InvalidInput("options must differ".to_string()));
+    }
+    if value != option_a && value != option_b {
+        return Err(ZkpError::InvalidInput(format!(
+            "value is neither {} nor {}",
+            option_a, option_b
+        )));
+    }
+    Ok(())
+}
+
+/// Validate divisibility parameters
+pub fn validate_divisible_params(value: u64, modulus: u64) -> ZkpResult<()> {
+    if modulus == 0 {
+        return Err(ZkpError::InvalidInput(
+            "modulus must be greater than zero".to_string(),
+        ));
+    }
+    if !value.is_multiple_of(modulus) {
+        return Err(ZkpError::InvalidInput(format!(
+            "value is not divisible by {}",
+            modulus
+        )));
+    }
+    Ok(())
+}
+
+/// Validate credential attribute names: between 1 and `MAX_CREDENTIAL_ATTRIBUTES` of them,
+/// each non-empty and at most `MAX_ATTRIBUTE_NAME_BYTES` long
+pub fn validate_credential_attributes<'a>(
+    names: impl ExactSizeIterator<Item = &'a str>,
+) -> ZkpResult<()> {
+    if names.len() == 0 {
+        return Err(ZkpError::InvalidInput(
+            "credential needs at least one attribute".to_string(),
+        ));
+    }
+    if names.len() > MAX_CREDENTIAL_ATTRIBUTES {
+        return Err(ZkpError::InvalidInput(format!(
+            "{} attributes exceed maximum {}",
+            names.len(),
+            MAX_CREDENTIAL_ATTRIBUTES
+        )));
+    }
+    for name in names {
+        if name.is_empty() || name.len() > MAX_ATTRIBUTE_NAME_BYTES {
+            return Err(ZkpError::InvalidInput(format!(
+                "attribute name {:?} must be 1 to {} bytes",
+                name, MAX_ATTRIBUTE_NAME_BYTES
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validate a vote: between 1 and `MAX_VOTE_OPTIONS` options and `choice < num_options`
+pub fn validate_vote_params(num_options: usize, choice: usize) -> ZkpResult<()> {
+    if !(1..=MAX_VOTE_OPTIONS).contains(&num_options) {
+        return Err(ZkpError::InvalidInput(format!(
+            "number of options must be in 1..={}, got {}",
+            MAX_VOTE_OPTIONS, num_options
+        )));
+    }
+    if choice >= num_options {
+        return Err(ZkpError::InvalidInput(format!(
+            "choice {} is not one of {} options",
+            choice, num_options
+        )));
+    }
+    Ok(())
+}
+
+/// Validate that the population variance of 1 to `MAX_VARIANCE_VALUES` values is below `bound`
+pub fn validate_variance_below_params(values: &[u64], bound: u64) -> ZkpResult<()> {
+    if values.is_empty() {
+        return Err(ZkpError::InvalidInput("values cannot be empty".to_string()));
+    }
+    if values.len() > MAX_VARIANCE_VALUES {
+        return Err(ZkpError::InvalidInput(format!(
+            "{} values exceed maximum {}",
+            values.len(),
+            MAX_VARIANCE_VALUES
+        )));
+    }
+    let n = values.len() as u128;
+    match variance_spread(values) {
+        Some(spread) if spread < bound as u128 * n * n => Ok(()),
+        _ => Err(ZkpError::InvalidInput(format!(
+            "variance is not below bound {}",
+            bound
+        ))),
+    }
+}
+
 /// Validate maximum set size
 pub fn validate_set_size(set: &[u64], max_size: usize) -> ZkpResult<()> {
     if set.len() > max_size {
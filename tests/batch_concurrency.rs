@@ -0,0 +1,56 @@
+//! Concurrent `process_batch` calls. Kept in its own test binary so the batch-store
+//! directory set by other tests cannot interfere with batch creation, and so this test can
+//! size the global rayon pool.
+#![cfg(feature = "parallel")]
+
+use libzkp::advanced::{
+    batch_add_range_proof, create_proof_batch, get_batch_status, process_batch,
+};
+use libzkp::utils::error_handling::{ZkpError, ZkpResult};
+use std::sync::{mpsc, Arc, Barrier};
+
+fn in_progress<T>(result: &ZkpResult<T>) -> bool {
+    matches!(result, Err(ZkpError::InvalidInput(msg)) if msg.contains("already being processed"))
+}
+
+#[test]
+fn concurrent_process_batch_reports_in_progress() {
+    let id = create_proof_batch().unwrap();
+    for v in 0..4 {
+        batch_add_range_proof(id, v, 0, 100).unwrap();
+    }
+
+    // Park the pool's only worker so the first `process_batch` holds the batch until released.
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build_global()
+        .unwrap();
+    let barrier = Arc::new(Barrier::new(2));
+    let (release, parked) = mpsc::channel::<()>();
+    {
+        let barrier = Arc::clone(&barrier);
+        rayon::spawn(move || {
+            barrier.wait();
+            parked.recv().ok();
+        });
+    }
+    barrier.wait();
+
+    let first = std::thread::spawn(move || process_batch(id));
+    while !in_progress(&get_batch_status(id)) {
+        std::thread::yield_now();
+    }
+    let second = process_batch(id);
+    assert!(
+        in_progress(&second),
+        "unexpected result: {:?}",
+        second.err()
+    );
+
+    release.send(()).unwrap();
+    assert_eq!(first.join().unwrap().unwrap().len(), 4);
+    assert!(matches!(
+        process_batch(id),
+        Err(ZkpError::InvalidInput(msg)) if msg.contains("Invalid batch ID")
+    ));
+}
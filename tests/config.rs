@@ -0,0 +1,63 @@
+//! `configure` before any proving. Kept in its own test binary so no other test has touched
+//! the global cache or SNARK setup first.
+
+use libzkp::advanced::{set_snark_key_dir, snark_curve};
+use libzkp::backend::snark::SnarkCurve;
+use libzkp::config::is_configured;
+use libzkp::proof::range_proof::{prove_range, verify_range};
+use libzkp::utils::error_handling::ZkpError;
+use libzkp::utils::performance::get_global_cache;
+use libzkp::{configure, LibzkpConfig};
+
+#[test]
+fn configure_before_use_applies_settings_once() {
+    let key_dir = std::env::temp_dir().join(format!("libzkp_config_keys_{}", std::process::id()));
+    assert!(matches!(
+        configure(LibzkpConfig {
+            cache_max_size: 0,
+            ..LibzkpConfig::default()
+        }),
+        Err(ZkpError::ConfigError(_))
+    ));
+    assert!(!is_configured());
+
+    // A bad key dir must not leave the curve fixed to the rejected config's choice.
+    set_snark_key_dir(key_dir.to_string_lossy().into_owned()).unwrap();
+    assert!(matches!(
+        configure(LibzkpConfig {
+            snark_key_dir: Some(key_dir.with_extension("other")),
+            snark_curve: SnarkCurve::Bls12_381,
+            ..LibzkpConfig::default()
+        }),
+        Err(ZkpError::ConfigError(_))
+    ));
+    assert_eq!(snark_curve(), "bn254");
+    assert!(!is_configured());
+
+    configure(LibzkpConfig {
+        snark_key_dir: Some(key_dir.clone()),
+        cache_max_size: 7,
+        cache_ttl_seconds: 60,
+        ..LibzkpConfig::default()
+    })
+    .unwrap();
+    assert!(is_configured());
+    assert_eq!(get_global_cache().max_size(), 7);
+
+    let proof = prove_range(5, 0, 10).unwrap();
+    assert!(verify_range(proof, 0, 10));
+
+    assert!(matches!(
+        configure(LibzkpConfig::default()),
+        Err(ZkpError::ConfigError(msg)) if msg.contains("already configured")
+    ));
+    assert!(matches!(
+        set_snark_key_dir(
+            key_dir
+                .with_extension("other")
+                .to_string_lossy()
+                .into_owned()
+        ),
+        Err(ZkpError::ConfigError(_))
+    ));
+}
@@ -0,0 +1,25 @@
+//! `configure` after subsystems have initialized with their defaults. Kept in its own test
+//! binary so the SNARK setup and cache are created here, before `configure` is attempted.
+
+use libzkp::advanced::prove_range_cached;
+use libzkp::config::is_configured;
+use libzkp::proof::equality_proof::prove_equality;
+use libzkp::utils::error_handling::ZkpError;
+use libzkp::{configure, LibzkpConfig};
+
+fn assert_in_use(result: Result<(), ZkpError>) {
+    assert!(matches!(
+        result,
+        Err(ZkpError::ConfigError(msg)) if msg.contains("already in use")
+    ));
+}
+
+#[test]
+fn configure_after_use_is_rejected() {
+    prove_equality(3, 3).unwrap();
+    assert_in_use(configure(LibzkpConfig::default()));
+
+    prove_range_cached(5, 0, 10).unwrap();
+    assert_in_use(configure(LibzkpConfig::default()));
+    assert!(!is_configured());
+}
@@ -1,14 +1,16 @@
 //! Prove/verify round-trips for all schemes.
 
 use libzkp::advanced::{
-    benchmark_proof_generation_numeric, create_composite_proof, verify_composite_proof,
-    verify_composite_proof_integrity_only,
+    benchmark_proof_generation_numeric, create_composite_proof, prove_range_with_receipt,
+    prove_threshold_optimized, verify_composite_proof, verify_composite_proof_integrity_only,
 };
+use libzkp::proof::Proof;
 use libzkp::proof::{
     consistency_proof, equality_proof, improvement_proof, range_proof, set_membership,
     threshold_proof,
 };
 use libzkp::utils::commitment::commit_value_snark;
+use libzkp::utils::error_handling::ZkpError;
 
 #[test]
 fn range_prove_verify() {
@@ -69,6 +71,25 @@ fn benchmark_numeric_smoke() {
     assert!(m.contains_key("avg_time_ms"));
 }
 
+#[test]
+fn benchmark_verification_reports_timings() {
+    use libzkp::advanced::benchmark_proof_verification;
+
+    let m = benchmark_proof_verification("range".to_string(), 3).expect("bench");
+    assert_eq!(m["successful_iterations"], 3.0);
+    for key in [
+        "avg_time_ms",
+        "min_time_ms",
+        "max_time_ms",
+        "p95_time_ms",
+        "verifications_per_second",
+    ] {
+        assert!(m[key] > 0.0, "{} should be positive", key);
+    }
+    assert!(m["min_time_ms"] <= m["p95_time_ms"] && m["p95_time_ms"] <= m["max_time_ms"]);
+    assert!(benchmark_proof_verification("bogus".to_string(), 1).is_err());
+}
+
 #[test]
 fn range_prove_rejects_out_of_range() {
     assert!(range_proof::prove_range(100, 0, 10).is_err());
@@ -97,6 +118,3103 @@ fn composite_rejects_trailing_bytes() {
     assert!(verify_composite_proof(bytes).is_err());
 }
 
+#[test]
+fn set_equality_ignores_order() {
+    use libzkp::utils::commitment::{commit_multiset_pedersen, random_pedersen_blinding};
+
+    let blindings = [random_pedersen_blinding(), random_pedersen_blinding()];
+    let root_a = commit_multiset_pedersen(&[4, 1, 9, 1], &blindings[0]).unwrap();
+    let root_b = commit_multiset_pedersen(&[1, 9, 1, 4], &blindings[1]).unwrap();
+    let proof = set_membership::prove_set_equality(vec![4, 1, 9, 1], vec![1, 9, 1, 4], &blindings)
+        .expect("prove");
+    assert!(set_membership::verify_set_equality(
+        proof.clone(),
+        root_a.clone(),
+        root_b.clone()
+    ));
+    assert!(!set_membership::verify_set_equality(
+        proof.clone(),
+        root_b.clone(),
+        root_a.clone()
+    ));
+    // Bound to the published roots.
+    let other = commit_multiset_pedersen(&[4, 1, 9, 1], &random_pedersen_blinding()).unwrap();
+    assert!(!set_membership::verify_set_equality(proof, other, root_b));
+}
+
+#[test]
+fn set_equality_rejects_different_sets() {
+    use libzkp::utils::commitment::random_pedersen_blinding;
+
+    let blindings = [random_pedersen_blinding(), random_pedersen_blinding()];
+    assert!(set_membership::prove_set_equality(vec![1, 2, 3], vec![1, 2, 4], &blindings).is_err());
+    assert!(
+        set_membership::prove_set_equality(vec![1, 2, 3], vec![1, 2, 3, 3], &blindings).is_err()
+    );
+    assert!(matches!(
+        set_membership::prove_set_equality(vec![1, 2], vec![2, 1], &[[0xff; 32], blindings[1]]),
+        Err(ZkpError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn range_receipt_describes_public_bounds() {
+    let (proof, receipt) = prove_range_with_receipt(7, 3, 20).expect("prove");
+    let json: serde_json::Value = serde_json::from_str(&receipt).expect("receipt json");
+    assert_eq!(json["type"], "range");
+    assert_eq!(json["min"], 3);
+    assert_eq!(json["max"], 20);
+    assert_eq!(json["proof_size"], proof.len());
+    assert!(json.get("value").is_none());
+    assert!(range_proof::verify_range(proof, 3, 20));
+}
+
+#[test]
+fn composite_from_reader_matches_from_bytes() {
+    use libzkp::utils::composition::CompositeProof;
+
+    let a = range_proof::prove_range(5, 0, 10).unwrap();
+    let b = threshold_proof::prove_threshold(vec![3, 4], 5).unwrap();
+    let mut composite = CompositeProof::new(vec![
+        Proof::from_bytes(&a).unwrap(),
+        Proof::from_bytes(&b).unwrap(),
+    ])
+    .unwrap();
+    composite.add_metadata("issuer".to_string(), b"test".to_vec());
+    let bytes = composite.to_bytes();
+
+    let from_bytes = CompositeProof::from_bytes(&bytes).expect("from_bytes");
+    let from_reader =
+        CompositeProof::from_reader(std::io::Cursor::new(bytes.clone())).expect("from_reader");
+    assert_eq!(from_reader.to_bytes(), from_bytes.to_bytes());
+    assert_eq!(from_reader.metadata, from_bytes.metadata);
+
+    let mut trailing = bytes;
+    trailing.push(0);
+    assert!(CompositeProof::from_reader(std::io::Cursor::new(trailing)).is_err());
+
+    // A bad item count is rejected after the header, without reading the rest of the stream.
+    let mut oversized = b"COMP".to_vec();
+    oversized.extend_from_slice(&5000u32.to_le_bytes());
+    oversized.extend_from_slice(&0u32.to_le_bytes());
+    oversized.extend_from_slice(&[0u8; 1000]);
+    let mut rest = &oversized[..];
+    assert!(CompositeProof::from_reader(&mut rest).is_err());
+    assert_eq!(rest.len(), 1000);
+}
+
+#[test]
+fn threshold_optimized_enforces_per_element_bound() {
+    let proof = prove_threshold_optimized(vec![3, 4, 5], 10, Some(5)).expect("within bound");
+    assert!(threshold_proof::verify_threshold(proof, 10));
+
+    let err = prove_threshold_optimized(vec![3, 40, 5], 10, Some(5)).unwrap_err();
+    assert!(matches!(err, ZkpError::InvalidInput(ref m) if m.contains("index 1")));
+}
+
+#[test]
+fn performance_metrics_merge_combines_workers() {
+    use libzkp::utils::performance::PerformanceMetrics;
+    use std::time::Duration;
+
+    let mut a = PerformanceMetrics::new();
+    a.record_operation("range_proof", Duration::from_millis(10));
+    a.record_cache_hit();
+    let mut b = PerformanceMetrics::new();
+    b.record_operation("range_proof", Duration::from_millis(30));
+    b.record_operation("threshold_proof", Duration::from_millis(5));
+    b.record_cache_miss();
+
+    let shipped = PerformanceMetrics::from_json(&b.to_json()).expect("json roundtrip");
+    a.merge(&shipped);
+    assert_eq!(a.operation_counts["range_proof"], 2);
+    assert_eq!(a.operation_counts["threshold_proof"], 1);
+    assert_eq!(
+        a.get_average_time("range_proof"),
+        Some(Duration::from_millis(20))
+    );
+    assert_eq!((a.cache_hits, a.cache_misses), (1, 1));
+
+    let empty = r#"{"operation_counts":{},"operation_times_ns":{"range_proof":[]}}"#;
+    assert!(PerformanceMetrics::from_json(empty).is_err());
+    assert_eq!(a.get_average_time("missing"), None);
+
+    let mut full = PerformanceMetrics::new();
+    full.cache_hits = u64::MAX;
+    full.operation_counts.insert("range_proof".into(), u64::MAX);
+    full.merge(&a);
+    assert_eq!(full.cache_hits, u64::MAX);
+    assert_eq!(full.operation_counts["range_proof"], u64::MAX);
+}
+
+#[test]
+fn strict_parse_rejects_missing_commitment() {
+    let proof = range_proof::prove_range(7, 0, 10).expect("prove");
+    let parsed = Proof::from_bytes(&proof).unwrap();
+    // Move the commitment into the payload and declare a zero-length commitment.
+    let mut payload = parsed.proof.clone();
+    payload.extend_from_slice(&parsed.commitment);
+    let mangled = Proof::new(parsed.scheme, payload, Vec::new()).to_bytes();
+
+    assert!(Proof::from_bytes(&mangled).is_ok());
+    assert!(Proof::from_bytes_strict(&mangled).is_err());
+    assert!(Proof::from_bytes_strict(&proof).is_ok());
+    assert!(!range_proof::verify_range(mangled, 0, 10));
+}
+
+#[test]
+fn divisible_prove_verify() {
+    let proof = range_proof::prove_divisible(84, 7).expect("prove");
+    assert!(range_proof::verify_divisible(proof.clone(), 7));
+    assert!(!range_proof::verify_divisible(proof.clone(), 5));
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &Proof::from_bytes(&proof).unwrap()
+    ));
+}
+
+#[test]
+fn divisible_rejects_bad_inputs() {
+    assert!(matches!(
+        range_proof::prove_divisible(85, 7),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        range_proof::prove_divisible(84, 0),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    let proof = range_proof::prove_divisible(0, 3).expect("zero is divisible");
+    assert!(!range_proof::verify_divisible(proof, 0));
+}
+
+#[test]
+fn range_and_greater_against_committed_bid() {
+    use libzkp::utils::commitment::{commit_value_pedersen, random_pedersen_blinding};
+
+    let other_blinding = random_pedersen_blinding();
+    let highest = commit_value_pedersen(120, &other_blinding).unwrap();
+    let bid_blinding = random_pedersen_blinding();
+
+    let proof = range_proof::prove_range_and_greater(
+        150,
+        100,
+        1000,
+        120,
+        &highest,
+        &bid_blinding,
+        &other_blinding,
+    )
+    .expect("prove");
+    assert_eq!(
+        Proof::from_bytes(&proof).unwrap().commitment,
+        commit_value_pedersen(150, &bid_blinding).unwrap()
+    );
+    assert!(range_proof::verify_range_and_greater(
+        proof.clone(),
+        100,
+        1000,
+        &highest
+    ));
+    assert!(!range_proof::verify_range_and_greater(
+        proof.clone(),
+        100,
+        140,
+        &highest
+    ));
+    let other = commit_value_pedersen(130, &other_blinding).unwrap();
+    assert!(!range_proof::verify_range_and_greater(
+        proof, 100, 1000, &other
+    ));
+}
+
+#[test]
+fn range_and_greater_rejects_bad_bids() {
+    use libzkp::utils::commitment::{commit_value_pedersen, random_pedersen_blinding};
+
+    let other_blinding = random_pedersen_blinding();
+    let highest = commit_value_pedersen(120, &other_blinding).unwrap();
+    let r = random_pedersen_blinding();
+    for (value, min, max) in [(120, 100, 1000), (110, 100, 1000), (1500, 100, 1000)] {
+        assert!(matches!(
+            range_proof::prove_range_and_greater(
+                value,
+                min,
+                max,
+                120,
+                &highest,
+                &r,
+                &other_blinding
+            ),
+            Err(ZkpError::InvalidInput(_))
+        ));
+    }
+    // Commitment that does not open to the claimed other value.
+    assert!(range_proof::prove_range_and_greater(
+        150,
+        100,
+        1000,
+        119,
+        &highest,
+        &r,
+        &other_blinding
+    )
+    .is_err());
+}
+
+#[test]
+fn weighted_average_range_prove_verify() {
+    use libzkp::utils::commitment::weights_hash;
+
+    // (700*2 + 650*1 + 800*1) / 4 = 712.5
+    let weights = vec![2, 1, 1];
+    let proof =
+        range_proof::prove_weighted_average_range(vec![700, 650, 800], weights.clone(), 700, 720)
+            .expect("prove");
+    let hash = weights_hash(&weights);
+    assert!(range_proof::verify_weighted_average_range(
+        proof.clone(),
+        &hash,
+        700,
+        720
+    ));
+    assert!(!range_proof::verify_weighted_average_range(
+        proof.clone(),
+        &hash,
+        713,
+        720
+    ));
+    assert!(!range_proof::verify_weighted_average_range(
+        proof,
+        &weights_hash(&[1, 1, 1]),
+        700,
+        720
+    ));
+}
+
+#[test]
+fn weighted_average_range_rejects_bad_inputs() {
+    // Average 712.5 is above 712.
+    assert!(range_proof::prove_weighted_average_range(
+        vec![700, 650, 800],
+        vec![2, 1, 1],
+        600,
+        712
+    )
+    .is_err());
+    assert!(range_proof::prove_weighted_average_range(vec![1, 2], vec![1], 0, 10).is_err());
+    assert!(range_proof::prove_weighted_average_range(vec![1, 2], vec![0, 0], 0, 10).is_err());
+    assert!(matches!(
+        range_proof::prove_weighted_average_range(vec![u64::MAX, 1], vec![2, 1], 0, u64::MAX),
+        Err(ZkpError::InvalidInput(msg)) if msg.contains("overflow")
+    ));
+}
+
+#[test]
+fn composite_dedup_drops_repeated_statement() {
+    use libzkp::utils::composition::CompositeProof;
+
+    let a = Proof::from_bytes(&range_proof::prove_range(5, 0, 10).unwrap()).unwrap();
+    let b = Proof::from_bytes(&range_proof::prove_range(5, 0, 10).unwrap()).unwrap();
+    let c = Proof::from_bytes(&range_proof::prove_range(7, 0, 20).unwrap()).unwrap();
+    // Same bounds, but each proof commits to the value under a fresh blinding.
+    assert_ne!(a.statement_fingerprint(), b.statement_fingerprint());
+    assert_ne!(a.statement_fingerprint(), c.statement_fingerprint());
+
+    // Only the same commitment with the same public parameters is a repeated statement.
+    let mut rebounded = a.clone();
+    rebounded.proof[8..16].copy_from_slice(&20u64.to_le_bytes());
+    assert_ne!(a.statement_fingerprint(), rebounded.statement_fingerprint());
+
+    let (composite, removed) =
+        CompositeProof::new_deduped(vec![a.clone(), b, a.clone(), c]).expect("composite");
+    assert_eq!(removed, 1);
+    assert_eq!(composite.proofs.len(), 3);
+    assert_eq!(composite.proofs[0].to_bytes(), a.to_bytes());
+    assert!(composite.verify_integrity());
+}
+
+#[test]
+fn committed_interval_hides_bounds() {
+    use libzkp::utils::commitment::{commit_value_pedersen, random_pedersen_blinding};
+
+    let blindings = [
+        random_pedersen_blinding(),
+        random_pedersen_blinding(),
+        random_pedersen_blinding(),
+    ];
+    let min_c = commit_value_pedersen(18, &blindings[1]).unwrap();
+    let max_c = commit_value_pedersen(65, &blindings[2]).unwrap();
+    let value_c = commit_value_pedersen(30, &blindings[0]).unwrap();
+
+    let proof = range_proof::prove_in_committed_interval(30, 18, 65, &min_c, &max_c, &blindings)
+        .expect("prove");
+    assert!(range_proof::verify_in_committed_interval(
+        proof.clone(),
+        &value_c,
+        &min_c,
+        &max_c
+    ));
+    assert!(!range_proof::verify_in_committed_interval(
+        proof, &value_c, &max_c, &min_c
+    ));
+
+    assert!(matches!(
+        range_proof::prove_in_committed_interval(70, 18, 65, &min_c, &max_c, &blindings),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(
+        range_proof::prove_in_committed_interval(30, 18, 66, &min_c, &max_c, &blindings).is_err()
+    );
+}
+
+#[test]
+fn composite_size_limit_rejects_before_parsing() {
+    use libzkp::utils::composition::CompositeProof;
+
+    let a = Proof::from_bytes(&range_proof::prove_range(5, 0, 10).unwrap()).unwrap();
+    let bytes = CompositeProof::new(vec![a]).unwrap().to_bytes();
+    assert!(CompositeProof::from_bytes_with_limit(&bytes, bytes.len()).is_ok());
+    assert!(CompositeProof::from_reader_with_limit(
+        std::io::Cursor::new(bytes.clone()),
+        bytes.len()
+    )
+    .is_ok());
+
+    // Garbage that would fail header parsing is reported as oversized first.
+    let garbage = vec![0xAAu8; 4096];
+    match CompositeProof::from_bytes_with_limit(&garbage, 1024) {
+        Err(ZkpError::InvalidProofFormat(msg)) => assert!(msg.contains("too large"), "{}", msg),
+        other => panic!("unexpected: {:?}", other.map(|c| c.proofs.len())),
+    }
+    match CompositeProof::from_reader_with_limit(
+        std::io::Cursor::new(bytes.clone()),
+        bytes.len() - 1,
+    ) {
+        Err(ZkpError::InvalidProofFormat(msg)) => assert!(msg.contains("too large"), "{}", msg),
+        other => panic!("unexpected: {:?}", other.map(|c| c.proofs.len())),
+    }
+}
+
+#[test]
+fn binary_choice_accepts_either_option() {
+    for value in [0, 1] {
+        let proof = set_membership::prove_binary_choice(value, 0, 1).expect("prove");
+        assert!(set_membership::verify_binary_choice(proof.clone(), 0, 1));
+        assert!(!set_membership::verify_binary_choice(proof.clone(), 1, 0));
+        assert!(!set_membership::verify_binary_choice(proof, 0, 2));
+    }
+}
+
+#[test]
+fn binary_choice_rejects_other_values() {
+    assert!(matches!(
+        set_membership::prove_binary_choice(2, 0, 1),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(set_membership::prove_binary_choice(5, 5, 5).is_err());
+}
+
+#[test]
+fn composite_expected_hash_reports_mismatch() {
+    use libzkp::advanced::composite_expected_hash;
+
+    let a = range_proof::prove_range(5, 0, 10).unwrap();
+    let bytes = create_composite_proof(vec![a]).unwrap();
+    let (stored, recomputed) = composite_expected_hash(bytes.clone()).expect("intact");
+    assert_eq!(stored.len(), 64);
+    assert_eq!(stored, recomputed);
+
+    // Flip a byte inside the inner proof payload (after the 12-byte composite header,
+    // 4-byte length and 10-byte proof header).
+    let mut tampered = bytes;
+    tampered[12 + 4 + 10 + 3] ^= 0x01;
+    assert!(verify_composite_proof_integrity_only(tampered.clone()).is_err());
+    let (stored2, recomputed2) = composite_expected_hash(tampered).expect("tampered");
+    assert_eq!(stored2, stored);
+    assert_ne!(stored2, recomputed2);
+}
+
+#[test]
+fn positive_threshold_requires_nonzero_values() {
+    let proof = threshold_proof::prove_positive_threshold(vec![1, 2, 3], 6).expect("prove");
+    assert!(threshold_proof::verify_positive_threshold(proof.clone(), 6));
+    assert!(!threshold_proof::verify_positive_threshold(
+        proof.clone(),
+        7
+    ));
+    assert!(!threshold_proof::verify_threshold(proof, 6));
+
+    assert!(matches!(
+        threshold_proof::prove_positive_threshold(vec![4, 0, 3], 5),
+        Err(ZkpError::InvalidInput(msg)) if msg.contains("index 1")
+    ));
+    assert!(matches!(
+        threshold_proof::prove_positive_threshold(vec![1, 2], 4),
+        Err(ZkpError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn verify_range_any_picks_matching_policy() {
+    let proof = range_proof::prove_range(30, 18, 65).unwrap();
+    assert_eq!(
+        range_proof::verify_range_any(proof.clone(), vec![(0, 17), (18, 65), (0, 100)]),
+        Some((18, 65))
+    );
+    assert_eq!(
+        range_proof::verify_range_any(proof.clone(), vec![(0, 17), (18, 64), (0, 100)]),
+        None
+    );
+    assert_eq!(range_proof::verify_range_any(proof, vec![]), None);
+}
+
+#[test]
+fn prepared_set_matches_unprepared_membership() {
+    use set_membership::PreparedSet;
+
+    let set: Vec<u64> = (1..=40).map(|i| i * 7).collect();
+    let prepared = PreparedSet::new(set.clone()).expect("prepare");
+    assert_eq!(prepared.len(), 40);
+
+    // Each proof against the prepared set must match the unprepared path; timing lives in
+    // examples/prepared_set.rs.
+    for value in [7, 140, 280] {
+        let proof = set_membership::prove_membership_prepared(value, &prepared).expect("prove");
+        assert!(set_membership::verify_membership_prepared(
+            proof.clone(),
+            &prepared
+        ));
+        assert!(set_membership::verify_membership(proof, set.clone()));
+    }
+    let unprepared = set_membership::prove_membership(14, set.clone()).unwrap();
+    assert!(set_membership::verify_membership_prepared(
+        unprepared, &prepared
+    ));
+
+    assert!(matches!(
+        set_membership::prove_membership_prepared(8, &prepared),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(PreparedSet::new(vec![]).is_err());
+    assert!(PreparedSet::new((0..65).collect()).is_err());
+}
+
+#[test]
+fn improvement_enveloped_returns_bound_values() {
+    let proof = improvement_proof::prove_improvement(3, 11).expect("prove");
+    assert_eq!(
+        improvement_proof::verify_improvement_enveloped(proof.clone()),
+        Some((3, 11))
+    );
+    assert!(improvement_proof::verify_improvement(proof.clone(), 3));
+    assert!(!improvement_proof::verify_improvement(proof.clone(), 4));
+
+    // Rewrite the embedded `new` (payload bytes 8..16, after the 10-byte header).
+    let mut tampered = proof;
+    tampered[10 + 8..10 + 16].copy_from_slice(&12u64.to_le_bytes());
+    assert_eq!(
+        improvement_proof::verify_improvement_enveloped(tampered),
+        None
+    );
+}
+
+#[test]
+fn range_offset_shifts_bounds() {
+    // Stored 250 relative to a base of 10_000 cents: effective 10_250 in [10_000, 20_000].
+    let proof = range_proof::prove_range_offset(250, 10_000, 10_000, 20_000).expect("prove");
+    assert!(range_proof::verify_range_offset(
+        proof.clone(),
+        10_000,
+        10_000,
+        20_000
+    ));
+    assert!(!range_proof::verify_range_offset(
+        proof, 9_000, 10_000, 20_000
+    ));
+
+    assert!(matches!(
+        range_proof::prove_range_offset(15_000, 10_000, 10_000, 20_000),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        range_proof::prove_range_offset(2, u64::MAX, 0, u64::MAX),
+        Err(ZkpError::InvalidInput(msg)) if msg.contains("overflow")
+    ));
+}
+
+#[test]
+fn proofs_share_value_links_commitments() {
+    use libzkp::advanced::proofs_share_value;
+    use libzkp::utils::commitment::{commit_value_pedersen, random_pedersen_blinding};
+
+    let [r_bid, r_other, r_min, r_max, r_bid2] = [(); 5].map(|_| random_pedersen_blinding());
+    let other_c = commit_value_pedersen(120, &r_other).unwrap();
+    let bid = range_proof::prove_range_and_greater(150, 100, 1000, 120, &other_c, &r_bid, &r_other)
+        .expect("prove bid");
+
+    let min_c = commit_value_pedersen(100, &r_min).unwrap();
+    let max_c = commit_value_pedersen(200, &r_max).unwrap();
+    let same = range_proof::prove_in_committed_interval(
+        150,
+        100,
+        200,
+        &min_c,
+        &max_c,
+        &[r_bid2, r_min, r_max],
+    )
+    .expect("prove interval");
+    let different = range_proof::prove_in_committed_interval(
+        140,
+        100,
+        200,
+        &min_c,
+        &max_c,
+        &[r_bid2, r_min, r_max],
+    )
+    .expect("prove interval");
+
+    assert!(proofs_share_value(bid.clone(), same, &r_bid, &r_bid2).unwrap());
+    assert!(!proofs_share_value(bid.clone(), different, &r_bid, &r_bid2).unwrap());
+
+    let equality = equality_proof::prove_equality(150, 150).unwrap();
+    assert!(matches!(
+        proofs_share_value(bid, equality, &r_bid, &r_bid2),
+        Err(ZkpError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn consistency_suffix_verifies_tail_only() {
+    use consistency_proof::{consistency_prefix_digest, verify_consistency_suffix};
+
+    let proof = consistency_proof::prove_consistency(vec![1, 3, 3, 8, 10]).expect("prove");
+    assert!(consistency_proof::verify_consistency(proof.clone()));
+    let prefix = consistency_prefix_digest(proof.clone(), 2).unwrap();
+    assert!(verify_consistency_suffix(proof.clone(), 2, prefix.clone()));
+    let all = consistency_prefix_digest(proof.clone(), 4).unwrap();
+    assert_eq!(all, Proof::from_bytes(&proof).unwrap().commitment);
+    assert!(verify_consistency_suffix(proof.clone(), 4, all.clone()));
+    assert!(!verify_consistency_suffix(proof.clone(), 2, all));
+    assert!(!verify_consistency_suffix(proof.clone(), 5, prefix.clone()));
+    assert!(consistency_prefix_digest(proof.clone(), 5).is_err());
+
+    // Trailing layout: [... diff commitments 32 each][envelope commitment 32].
+    let last_diff = proof.len() - 64;
+    let mut tampered_tail = proof.clone();
+    tampered_tail[last_diff] ^= 1;
+    assert!(!verify_consistency_suffix(tampered_tail, 2, prefix.clone()));
+
+    // A tampered prefix step is rejected even though its range proof is skipped.
+    let first_diff = proof.len() - 32 - 4 * 32;
+    let mut tampered_head = proof;
+    tampered_head[first_diff] ^= 1;
+    assert!(!verify_consistency_suffix(tampered_head, 2, prefix.clone()));
+
+    // So is a different log whose prefix was never verified.
+    let forged = consistency_proof::prove_consistency(vec![1, 3, 3, 8, 10]).expect("prove");
+    assert!(!verify_consistency_suffix(forged, 2, prefix));
+}
+
+#[test]
+fn range_keyed_blinding_opens_commitment() {
+    use libzkp::utils::commitment::commit_value_pedersen;
+
+    let (proof, blinding) = range_proof::prove_range_keyed(42, 10, 100).expect("prove");
+    assert!(range_proof::verify_range(proof.clone(), 10, 100));
+
+    let commitment = Proof::from_bytes(&proof).unwrap().commitment;
+    assert_eq!(commit_value_pedersen(42, &blinding).unwrap(), commitment);
+    assert_ne!(commit_value_pedersen(43, &blinding).unwrap(), commitment);
+}
+
+#[test]
+fn sanitize_proof_strips_trailing_bytes() {
+    use libzkp::advanced::sanitize_proof;
+
+    let proof = range_proof::prove_range(5, 0, 10).unwrap();
+    let mut legacy = proof.clone();
+    legacy.extend_from_slice(b"ctx:legacy");
+    assert!(!range_proof::verify_range(legacy.clone(), 0, 10));
+
+    let clean = sanitize_proof(legacy).expect("sanitize");
+    assert_eq!(clean, proof);
+    assert!(range_proof::verify_range(clean, 0, 10));
+
+    assert!(matches!(
+        sanitize_proof(proof[..proof.len() - 1].to_vec()),
+        Err(ZkpError::InvalidProofFormat(_))
+    ));
+}
+
+#[test]
+fn bounded_consistency_limits_steps() {
+    let proof = consistency_proof::prove_bounded_consistency(vec![3, 10, 10, 20], 10).unwrap();
+    assert!(consistency_proof::verify_bounded_consistency(
+        proof.clone(),
+        10
+    ));
+    assert!(!consistency_proof::verify_bounded_consistency(
+        proof.clone(),
+        9
+    ));
+    assert!(!consistency_proof::verify_consistency(proof));
+
+    assert!(matches!(
+        consistency_proof::prove_bounded_consistency(vec![3, 10, 21], 10),
+        Err(ZkpError::InvalidInput(msg)) if msg.contains("index 2")
+    ));
+    assert!(matches!(
+        consistency_proof::prove_bounded_consistency(vec![3, 10, 9], 10),
+        Err(ZkpError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn exact_sum_matches_public_total() {
+    let proof = threshold_proof::prove_exact_sum(vec![120, 30, 850], 1000).expect("prove");
+    assert!(threshold_proof::verify_exact_sum(proof.clone(), 1000));
+    assert!(!threshold_proof::verify_exact_sum(proof.clone(), 999));
+    assert!(!threshold_proof::verify_threshold(proof, 1000));
+
+    assert!(matches!(
+        threshold_proof::prove_exact_sum(vec![120, 30, 850], 1001),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(threshold_proof::prove_exact_sum(vec![u64::MAX, 1], 0).is_err());
+}
+
+#[test]
+fn rank_proves_top_k_against_root() {
+    use libzkp::utils::commitment::sorted_set_root;
+
+    let scores = vec![3, 15, 22, 40, 58, 71, 90];
+    let root = sorted_set_root(&scores);
+
+    let top = set_membership::prove_rank(90, scores.clone(), 3).expect("top-ranked");
+    assert!(set_membership::verify_rank(top.clone(), root.clone(), 3));
+    assert!(!set_membership::verify_rank(top.clone(), root.clone(), 2));
+    assert!(!set_membership::verify_rank(
+        top,
+        sorted_set_root(&[3, 15, 22, 41, 58, 71, 90]),
+        3
+    ));
+
+    let borderline = set_membership::prove_rank(58, scores.clone(), 3).expect("borderline");
+    assert!(set_membership::verify_rank(borderline, root, 3));
+
+    assert!(matches!(
+        set_membership::prove_rank(40, scores, 3),
+        Err(ZkpError::InvalidInput(msg)) if msg.contains("top 3")
+    ));
+}
+
+#[test]
+fn improvement_history_links_each_step() {
+    let values = vec![10, 25, 40, 70];
+    let proofs: Vec<Vec<u8>> = values
+        .windows(2)
+        .map(|w| improvement_proof::prove_improvement(w[0], w[1]).unwrap())
+        .collect();
+    assert!(improvement_proof::verify_history(
+        proofs.clone(),
+        values.clone()
+    ));
+
+    let mut missing = proofs.clone();
+    missing.remove(1);
+    assert!(!improvement_proof::verify_history(missing, values.clone()));
+    assert!(!improvement_proof::verify_history(
+        proofs[1..].to_vec(),
+        vec![25, 40, 70, 80]
+    ));
+
+    let mut reordered = proofs.clone();
+    reordered.swap(0, 1);
+    assert!(!improvement_proof::verify_history(reordered, values));
+    assert!(!improvement_proof::verify_history(
+        proofs,
+        vec![10, 40, 25, 70]
+    ));
+}
+
+#[test]
+fn big_endian_header_parses_lengths() {
+    use libzkp::proof::{ByteOrder, BIG_ENDIAN_FLAG};
+
+    let bytes = range_proof::prove_range(5, 0, 10).unwrap();
+    let parsed = Proof::from_bytes(&bytes).unwrap();
+    assert_eq!(parsed.byte_order(), ByteOrder::LittleEndian);
+    assert_eq!(parsed.to_bytes(), bytes);
+
+    let be_bytes = parsed
+        .clone()
+        .with_byte_order(ByteOrder::BigEndian)
+        .to_bytes();
+    assert_eq!(be_bytes[0], parsed.version | BIG_ENDIAN_FLAG);
+    assert_eq!(
+        be_bytes[2..6],
+        (parsed.proof.len() as u32).to_be_bytes()[..]
+    );
+
+    let reparsed = Proof::from_bytes(&be_bytes).unwrap();
+    assert_eq!(reparsed.byte_order(), ByteOrder::BigEndian);
+    assert_eq!(reparsed.version, parsed.version);
+    assert_eq!(reparsed.proof, parsed.proof);
+    assert_eq!(reparsed.commitment, parsed.commitment);
+    assert!(range_proof::verify_range(be_bytes.clone(), 0, 10));
+    assert_eq!(libzkp::advanced::sanitize_proof(be_bytes).unwrap(), bytes);
+}
+
+#[test]
+fn membership_index_below_bounds_selection() {
+    let set = vec![7, 11, 13, 17, 19];
+
+    let early = set_membership::prove_membership_index_below(7, set.clone(), 3).expect("index 0");
+    assert!(set_membership::verify_membership_index_below(
+        early.clone(),
+        set.clone(),
+        3
+    ));
+    assert!(!set_membership::verify_membership_index_below(
+        early.clone(),
+        set.clone(),
+        4
+    ));
+    assert!(!set_membership::verify_membership_index_below(
+        early,
+        vec![11, 7, 13, 17, 19],
+        3
+    ));
+
+    let last = set_membership::prove_membership_index_below(13, set.clone(), 3).expect("index k-1");
+    assert!(set_membership::verify_membership_index_below(
+        last,
+        set.clone(),
+        3
+    ));
+
+    assert!(matches!(
+        set_membership::prove_membership_index_below(17, set, 3),
+        Err(ZkpError::InvalidInput(msg)) if msg.contains("not below 3")
+    ));
+}
+
+#[test]
+fn can_verify_checks_scheme_and_version() {
+    use libzkp::proof::{can_verify, format_capabilities, PROOF_VERSION};
+
+    let caps = format_capabilities();
+    assert_eq!(&caps[..2], &[1, PROOF_VERSION]);
+    assert_eq!(caps[2] as usize, caps.len() - 3);
+    assert!(caps[3..].contains(&1) && caps[3..].contains(&17));
+    // The Halo2 schemes are only advertised by builds that can verify them.
+    assert_eq!(caps[3..].contains(&28), cfg!(feature = "halo2"));
+    assert_eq!(
+        can_verify(&Proof::new(29, vec![0; 8], vec![0; 32]).to_bytes()),
+        cfg!(feature = "halo2")
+    );
+
+    let range = range_proof::prove_range(25, 18, 65).unwrap();
+    assert!(can_verify(&range));
+    assert!(can_verify(
+        &Proof::new(4, vec![0; 13], vec![0; 32]).to_bytes()
+    ));
+
+    assert!(!can_verify(
+        &Proof::new(200, vec![0; 8], vec![0; 32]).to_bytes()
+    ));
+    let mut newer = range.clone();
+    newer[0] = PROOF_VERSION + 1;
+    assert!(!can_verify(&newer));
+    assert!(!can_verify(&range[..4]));
+}
+
+#[test]
+fn is_maximum_checks_claimed_index() {
+    let bids = vec![120, 340, 95, 210];
+    let proof = threshold_proof::prove_is_maximum(bids.clone(), 1).expect("highest bid");
+    assert!(threshold_proof::verify_is_maximum(proof.clone()));
+    assert_eq!(
+        Proof::from_bytes(&proof).unwrap().proof[0..4],
+        1u32.to_le_bytes()
+    );
+
+    assert!(matches!(
+        threshold_proof::prove_is_maximum(bids.clone(), 3),
+        Err(ZkpError::InvalidInput(msg)) if msg.contains("index 1 is larger")
+    ));
+    assert!(threshold_proof::prove_is_maximum(bids, 4).is_err());
+
+    // Ties: either index holding the maximum can be claimed.
+    let tied = vec![50, 80, 80];
+    for index in [1, 2] {
+        let proof = threshold_proof::prove_is_maximum(tied.clone(), index).unwrap();
+        assert!(threshold_proof::verify_is_maximum(proof));
+    }
+    assert!(threshold_proof::prove_is_maximum(tied, 0).is_err());
+}
+
+#[test]
+fn scheme_registry_describes_every_scheme() {
+    use libzkp::proof::scheme_length_requirements;
+
+    let json: serde_json::Value =
+        serde_json::from_str(&libzkp::advanced::scheme_registry().unwrap()).unwrap();
+    let schemes = json["schemes"].as_array().unwrap();
+    let by_name = |name: &str| {
+        schemes
+            .iter()
+            .find(|s| s["name"] == name)
+            .unwrap_or_else(|| panic!("missing scheme {}", name))
+    };
+    for (name, id) in [
+        ("range", 1),
+        ("equality", 2),
+        ("threshold", 3),
+        ("membership", 4),
+        ("improvement", 5),
+        ("consistency", 6),
+    ] {
+        assert_eq!(by_name(name)["id"], id);
+    }
+    let listed: Vec<u64> = schemes.iter().map(|s| s["id"].as_u64().unwrap()).collect();
+    let known: Vec<u64> = (0..=255u8)
+        .filter(|&id| scheme_length_requirements(id).is_some())
+        .map(u64::from)
+        .collect();
+    assert_eq!(listed, known);
+
+    let range = by_name("range");
+    assert_eq!(range["verify_params"], serde_json::json!(["min", "max"]));
+    assert_eq!(range["payload"][2]["name"], "n_bits");
+    assert_eq!(range["payload"][2]["offset"], 16);
+    let consistency = &by_name("consistency")["payload"];
+    assert_eq!(consistency[1]["offset"], 4);
+    assert!(consistency[2]["offset"].is_null());
+}
+
+#[test]
+fn exact_delta_links_three_commitments() {
+    use libzkp::utils::commitment::{
+        commit_delta_pedersen, commit_value_pedersen, random_pedersen_blinding,
+    };
+
+    let blindings = [
+        random_pedersen_blinding(),
+        random_pedersen_blinding(),
+        random_pedersen_blinding(),
+    ];
+    let [r_old, r_new, r_delta] = &blindings;
+    let old = commit_value_pedersen(500, r_old).unwrap();
+
+    // Withdrawal: 500 - 120 = 380.
+    let new = commit_value_pedersen(380, r_new).unwrap();
+    let delta = commit_delta_pedersen(-120, r_delta).unwrap();
+    let proof = improvement_proof::prove_exact_delta(&old, &new, &delta, &blindings).unwrap();
+    assert!(improvement_proof::verify_exact_delta(
+        proof.clone(),
+        &old,
+        &new,
+        &delta
+    ));
+    let other_delta = commit_delta_pedersen(-120, &random_pedersen_blinding()).unwrap();
+    assert!(!improvement_proof::verify_exact_delta(
+        proof,
+        &old,
+        &new,
+        &other_delta
+    ));
+
+    // Deposit with a wrong delta: 500 + 100 != 380.
+    let wrong = commit_delta_pedersen(100, r_delta).unwrap();
+    assert!(matches!(
+        improvement_proof::prove_exact_delta(&old, &new, &wrong, &blindings),
+        Err(ZkpError::InvalidInput(msg)) if msg.contains("new == old + delta")
+    ));
+}
+
+#[test]
+fn disjoint_sets_prove_and_overlaps_fail() {
+    use libzkp::utils::commitment::{commit_set_elements_pedersen, random_pedersen_blinding};
+
+    let blindings = |n: usize| {
+        (0..n)
+            .map(|_| random_pedersen_blinding())
+            .collect::<Vec<_>>()
+    };
+    let (set_a, set_b) = (vec![3, 8, 21], vec![4, 9, 22, 100]);
+    let (blindings_a, blindings_b) = (blindings(3), blindings(4));
+    // The roots are published before proving, from the caller's blindings.
+    let root_a = commit_set_elements_pedersen(&set_a, &blindings_a).unwrap();
+    let root_b = commit_set_elements_pedersen(&set_b, &blindings_b).unwrap();
+    let proof = set_membership::prove_disjoint(
+        set_a.clone(),
+        set_b.clone(),
+        blindings_a.clone(),
+        blindings_b.clone(),
+    )
+    .unwrap();
+    let parsed = Proof::from_bytes(&proof).unwrap();
+    assert_eq!(parsed.commitment, root_a);
+    assert_eq!(parsed.proof[0..32], root_b[..]);
+    assert!(set_membership::verify_disjoint(
+        proof.clone(),
+        root_a.clone(),
+        root_b.clone()
+    ));
+    let other = commit_set_elements_pedersen(&set_a, &blindings(3)).unwrap();
+    assert!(!set_membership::verify_disjoint(
+        proof.clone(),
+        other,
+        root_b.clone()
+    ));
+    assert!(set_membership::verify_disjoint(
+        proof.clone(),
+        root_a.clone(),
+        root_b.clone()
+    ));
+    assert!(!set_membership::verify_disjoint(
+        proof.clone(),
+        root_b.clone(),
+        root_a.clone()
+    ));
+    // Flip a byte in the last pair proof (the 32-byte commitment follows the payload).
+    let mut tampered = proof;
+    let at = tampered.len() - 32 - 80;
+    tampered[at] ^= 1;
+    assert!(!set_membership::verify_disjoint(tampered, root_a, root_b));
+
+    let overlapping = set_membership::prove_disjoint(
+        set_a.clone(),
+        vec![4, 8],
+        blindings_a.clone(),
+        blindings(2),
+    );
+    assert!(matches!(overlapping, Err(ZkpError::InvalidInput(msg)) if msg.contains("share")));
+    assert!(
+        set_membership::prove_disjoint(vec![5, 6], vec![6, 5], blindings(2), blindings(2)).is_err()
+    );
+    let short = set_membership::prove_disjoint(
+        set_a.clone(),
+        set_b.clone(),
+        blindings_a.clone(),
+        blindings(3),
+    );
+    assert!(
+        matches!(short, Err(ZkpError::InvalidInput(msg)) if msg.contains("expected 4 blindings"))
+    );
+    assert!(matches!(
+        set_membership::prove_disjoint(set_a, set_b, blindings_a, vec![[0xff; 32]; 4]),
+        Err(ZkpError::InvalidInput(msg)) if msg.contains("canonical")
+    ));
+}
+
+#[test]
+fn range_freshness_distinguishes_expired_and_invalid() {
+    use libzkp::advanced::{
+        create_proof_with_metadata, verify_range_with_freshness, ISSUED_AT_METADATA_KEY,
+    };
+    use libzkp::utils::performance::unix_time_secs;
+
+    let wrap = |proof: Vec<u8>, issued_at: u64| {
+        let metadata = std::collections::HashMap::from([(
+            ISSUED_AT_METADATA_KEY.to_string(),
+            issued_at.to_le_bytes().to_vec(),
+        )]);
+        create_proof_with_metadata(proof, metadata).unwrap()
+    };
+    let now = unix_time_secs();
+    let proof = range_proof::prove_range(25, 18, 65).unwrap();
+
+    let fresh = wrap(proof.clone(), now - 10);
+    assert!(verify_range_with_freshness(fresh, 18, 65, 60).unwrap());
+
+    let expired = wrap(proof.clone(), now - 3600);
+    assert!(matches!(
+        verify_range_with_freshness(expired, 18, 65, 60),
+        Err(ZkpError::VerificationFailed(msg)) if msg.contains("expired")
+    ));
+
+    let mut tampered = proof;
+    let mid = tampered.len() / 2;
+    tampered[mid] ^= 1;
+    let invalid = wrap(tampered, now);
+    assert!(matches!(
+        verify_range_with_freshness(invalid, 18, 65, 60),
+        Err(ZkpError::VerificationFailed(msg)) if msg.contains("invalid")
+    ));
+}
+
+#[test]
+fn threshold_streaming_matches_vector_path() {
+    let threshold = 5_000_000_000;
+    let streamed = threshold_proof::prove_threshold_streaming(1..=200_000u64, threshold).unwrap();
+    let values: Vec<u64> = (1..=200_000u64).collect();
+    let collected = threshold_proof::prove_threshold(values, threshold).unwrap();
+
+    assert!(threshold_proof::verify_threshold(
+        streamed.clone(),
+        threshold
+    ));
+    assert!(threshold_proof::verify_threshold(
+        collected.clone(),
+        threshold
+    ));
+    assert!(!threshold_proof::verify_threshold(
+        streamed.clone(),
+        threshold + 1
+    ));
+    assert_eq!(streamed.len(), collected.len());
+    // Each proof commits to the sum under its own blinding, so the statements differ.
+    assert_ne!(
+        Proof::from_bytes(&streamed)
+            .unwrap()
+            .statement_fingerprint(),
+        Proof::from_bytes(&collected)
+            .unwrap()
+            .statement_fingerprint()
+    );
+
+    assert!(threshold_proof::prove_threshold_streaming(std::iter::empty(), 0).is_err());
+    assert!(threshold_proof::prove_threshold_streaming([u64::MAX, 1].into_iter(), 0).is_err());
+    assert!(threshold_proof::prove_threshold_streaming(1..=10u64, 56).is_err());
+}
+
+#[test]
+fn membership_checked_reports_set_length_mismatch() {
+    let proof = set_membership::prove_membership(11, vec![5, 11, 17]).unwrap();
+    assert!(set_membership::verify_membership_checked(proof.clone(), vec![17, 5, 11]).unwrap());
+    assert!(!set_membership::verify_membership_checked(proof.clone(), vec![5, 11, 18]).unwrap());
+    assert!(matches!(
+        set_membership::verify_membership_checked(proof.clone(), vec![5, 11, 17, 23]),
+        Err(ZkpError::InvalidInput(msg)) if msg == "set length mismatch between prove and verify"
+    ));
+    assert!(!set_membership::verify_membership(
+        proof,
+        vec![5, 11, 17, 23]
+    ));
+}
+
+#[test]
+fn within_of_mean_prove_verify() {
+    // mean = 31/3 (non-integer); 12 is 5/3 away, so D = 1 fails and D = 2 holds
+    let values = vec![10, 10, 11];
+    let proof = range_proof::prove_within_of_mean(values.clone(), 12, 2).unwrap();
+    assert!(range_proof::verify_within_of_mean(proof.clone(), 2));
+    assert!(!range_proof::verify_within_of_mean(proof.clone(), 1));
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &Proof::from_bytes(&proof).unwrap()
+    ));
+
+    assert!(range_proof::prove_within_of_mean(values.clone(), 12, 1).is_err());
+    assert!(range_proof::prove_within_of_mean(values, 8, 2).is_err());
+}
+
+#[test]
+fn within_of_mean_rejects_overflow() {
+    let err = range_proof::prove_within_of_mean(vec![u64::MAX, 1], u64::MAX, 0).unwrap_err();
+    assert!(err.to_string().contains("overflow"));
+    assert!(range_proof::prove_within_of_mean(vec![5, 5], u64::MAX / 2 + 1, 0).is_err());
+    assert!(range_proof::prove_within_of_mean(vec![], 0, 0).is_err());
+}
+
+#[test]
+fn proof_kinds_cover_every_scheme() {
+    use libzkp::proof::{ProofKind, SCHEMES};
+
+    let kinds: Vec<(u8, &str)> = ProofKind::ALL
+        .iter()
+        .map(|k| (k.scheme_id(), k.name()))
+        .collect();
+    let schemes: Vec<(u8, &str)> = SCHEMES.iter().map(|s| (s.id, s.name)).collect();
+    assert_eq!(kinds, schemes);
+}
+
+#[test]
+fn facade_round_trips_every_kind() {
+    use libzkp::proof::set_membership::merkle_root;
+    use libzkp::proof::voting::{eligibility_root, voter_key};
+    use libzkp::proof::{prove_with_blinding, scheme_id, verify, ProofInputs, ProofKind};
+    use libzkp::utils::bloom::{build_bloom_filter, commit_bloom_filter, BloomFilterParams};
+    use libzkp::utils::commitment::{
+        commit_delta_pedersen, commit_multiset_pedersen, commit_set_elements_pedersen,
+        commit_value_pedersen, random_pedersen_blinding, sorted_set_root,
+    };
+
+    let r: Vec<[u8; 32]> = (0..3).map(|_| random_pedersen_blinding()).collect();
+    let interval_commits = vec![
+        commit_value_pedersen(50, &r[0]).unwrap(),
+        commit_value_pedersen(10, &r[1]).unwrap(),
+        commit_value_pedersen(90, &r[2]).unwrap(),
+    ];
+    let delta_commits = vec![
+        commit_value_pedersen(500, &r[0]).unwrap(),
+        commit_value_pedersen(380, &r[1]).unwrap(),
+        commit_delta_pedersen(-120, &r[2]).unwrap(),
+    ];
+    let other_commit = commit_value_pedersen(40, &r[1]).unwrap();
+    let solvency_commits = vec![
+        commit_value_pedersen(300, &r[0]).unwrap(),
+        commit_value_pedersen(200, &r[1]).unwrap(),
+        commit_value_pedersen(400, &r[2]).unwrap(),
+    ];
+    let bloom = BloomFilterParams::for_capacity(3, 0.01).unwrap();
+    let filter = build_bloom_filter(&[5, 6, 7], &bloom).unwrap();
+    let filter_commit = commit_bloom_filter(&bloom, &filter).unwrap();
+    let secrets: Vec<[u8; 32]> = (1..=3u8).map(|i| [i; 32]).collect();
+    let voter_keys: Vec<Vec<u8>> = secrets.iter().map(|s| voter_key(s).unwrap()).collect();
+    let d = ProofInputs::default;
+
+    let cases = vec![
+        (
+            ProofKind::Range,
+            ProofInputs {
+                value: Some(25),
+                min: Some(18),
+                max: Some(65),
+                ..d()
+            },
+            ProofInputs {
+                min: Some(18),
+                max: Some(65),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::Equality,
+            ProofInputs {
+                value: Some(7),
+                other_value: Some(7),
+                ..d()
+            },
+            ProofInputs {
+                value: Some(7),
+                other_value: Some(7),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::Threshold,
+            ProofInputs {
+                values: vec![10, 20],
+                threshold: Some(25),
+                ..d()
+            },
+            ProofInputs {
+                threshold: Some(25),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::Membership,
+            ProofInputs {
+                value: Some(3),
+                set: vec![1, 3, 5],
+                ..d()
+            },
+            ProofInputs {
+                set: vec![1, 3, 5],
+                ..d()
+            },
+        ),
+        (
+            ProofKind::Improvement,
+            ProofInputs {
+                value: Some(10),
+                other_value: Some(15),
+                ..d()
+            },
+            ProofInputs {
+                value: Some(10),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::Consistency,
+            ProofInputs {
+                values: vec![1, 2, 2, 5],
+                ..d()
+            },
+            d(),
+        ),
+        (
+            ProofKind::SetEquality,
+            ProofInputs {
+                set: vec![4, 1],
+                other_set: vec![1, 4],
+                blindings: vec![r[0], r[1]],
+                ..d()
+            },
+            ProofInputs {
+                roots: vec![
+                    commit_multiset_pedersen(&[4, 1], &r[0]).unwrap(),
+                    commit_multiset_pedersen(&[1, 4], &r[1]).unwrap(),
+                ],
+                ..d()
+            },
+        ),
+        (
+            ProofKind::Divisible,
+            ProofInputs {
+                value: Some(84),
+                modulus: Some(7),
+                ..d()
+            },
+            ProofInputs {
+                modulus: Some(7),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::RangeAndGreater,
+            ProofInputs {
+                value: Some(50),
+                min: Some(10),
+                max: Some(90),
+                other_value: Some(40),
+                commitments: vec![other_commit.clone()],
+                blindings: vec![r[0], r[1]],
+                ..d()
+            },
+            ProofInputs {
+                min: Some(10),
+                max: Some(90),
+                commitments: vec![other_commit],
+                ..d()
+            },
+        ),
+        (
+            ProofKind::WeightedAverage,
+            ProofInputs {
+                values: vec![700, 650, 800],
+                weights: vec![2, 1, 1],
+                min: Some(700),
+                max: Some(720),
+                ..d()
+            },
+            ProofInputs {
+                weights: vec![2, 1, 1],
+                min: Some(700),
+                max: Some(720),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::CommittedInterval,
+            ProofInputs {
+                value: Some(50),
+                min: Some(10),
+                max: Some(90),
+                commitments: interval_commits.clone(),
+                blindings: r.clone(),
+                ..d()
+            },
+            ProofInputs {
+                commitments: interval_commits.clone(),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::BinaryChoice,
+            ProofInputs {
+                value: Some(1),
+                option_a: Some(0),
+                option_b: Some(1),
+                ..d()
+            },
+            ProofInputs {
+                option_a: Some(0),
+                option_b: Some(1),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::PositiveThreshold,
+            ProofInputs {
+                values: vec![3, 4],
+                threshold: Some(5),
+                ..d()
+            },
+            ProofInputs {
+                threshold: Some(5),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::BoundedConsistency,
+            ProofInputs {
+                values: vec![1, 3, 4],
+                max_step: Some(2),
+                ..d()
+            },
+            ProofInputs {
+                max_step: Some(2),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::ExactSum,
+            ProofInputs {
+                values: vec![3, 4],
+                total: Some(7),
+                ..d()
+            },
+            ProofInputs {
+                total: Some(7),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::Rank,
+            ProofInputs {
+                value: Some(90),
+                set: vec![3, 15, 90],
+                max_rank: Some(1),
+                ..d()
+            },
+            ProofInputs {
+                roots: vec![sorted_set_root(&[3, 15, 90])],
+                max_rank: Some(1),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::MembershipIndexBelow,
+            ProofInputs {
+                value: Some(3),
+                set: vec![1, 3, 5],
+                index: Some(2),
+                ..d()
+            },
+            ProofInputs {
+                set: vec![1, 3, 5],
+                index: Some(2),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::IsMaximum,
+            ProofInputs {
+                values: vec![4, 9, 2],
+                index: Some(1),
+                ..d()
+            },
+            d(),
+        ),
+        (
+            ProofKind::ExactDelta,
+            ProofInputs {
+                commitments: delta_commits.clone(),
+                blindings: r.clone(),
+                ..d()
+            },
+            ProofInputs {
+                commitments: delta_commits,
+                ..d()
+            },
+        ),
+        (
+            ProofKind::Disjoint,
+            ProofInputs {
+                set: vec![1, 2],
+                other_set: vec![3],
+                blindings: r.to_vec(),
+                ..d()
+            },
+            ProofInputs {
+                roots: vec![
+                    commit_set_elements_pedersen(&[1, 2], &r[0..2]).unwrap(),
+                    commit_set_elements_pedersen(&[3], &r[2..3]).unwrap(),
+                ],
+                ..d()
+            },
+        ),
+        (
+            ProofKind::WithinOfMean,
+            ProofInputs {
+                values: vec![10, 10, 11],
+                value: Some(12),
+                max_deviation: Some(2),
+                ..d()
+            },
+            ProofInputs {
+                max_deviation: Some(2),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::MembershipBloom,
+            ProofInputs {
+                value: Some(6),
+                bloom: Some(bloom),
+                filter,
+                ..d()
+            },
+            ProofInputs {
+                value: Some(6),
+                commitments: vec![filter_commit],
+                ..d()
+            },
+        ),
+        (
+            ProofKind::PolynomialNonneg,
+            ProofInputs {
+                value: Some(8),
+                coefficients: vec![1, -10, 21],
+                ..d()
+            },
+            ProofInputs {
+                coefficients: vec![1, -10, 21],
+                ..d()
+            },
+        ),
+        (
+            ProofKind::CountAbove,
+            ProofInputs {
+                values: vec![5, 20, 30, 10],
+                threshold: Some(10),
+                count: Some(2),
+                ..d()
+            },
+            ProofInputs {
+                threshold: Some(10),
+                count: Some(2),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::StrictlyBetween,
+            ProofInputs {
+                value: Some(50),
+                min: Some(10),
+                max: Some(90),
+                commitments: interval_commits.clone(),
+                blindings: r.clone(),
+                ..d()
+            },
+            ProofInputs {
+                commitments: interval_commits.clone(),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::Difference,
+            ProofInputs {
+                commitments: interval_commits[0..2].to_vec(),
+                amount: Some(40),
+                blindings: vec![r[0], r[1]],
+                ..d()
+            },
+            ProofInputs {
+                commitments: interval_commits[0..2].to_vec(),
+                amount: Some(40),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::RangeMany,
+            ProofInputs {
+                values: vec![20, 30],
+                min: Some(18),
+                max: Some(65),
+                ..d()
+            },
+            ProofInputs {
+                min: Some(18),
+                max: Some(65),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::NonMembership,
+            ProofInputs {
+                value: Some(1234),
+                set: vec![1001, 4242],
+                ..d()
+            },
+            ProofInputs {
+                set: vec![1001, 4242],
+                ..d()
+            },
+        ),
+        (
+            ProofKind::Inequality,
+            ProofInputs {
+                value: Some(50),
+                other_value: Some(10),
+                blindings: vec![r[0], r[1]],
+                ..d()
+            },
+            ProofInputs {
+                commitments: interval_commits[0..2].to_vec(),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::GreaterThan,
+            ProofInputs {
+                value: Some(50),
+                other_value: Some(10),
+                blindings: vec![r[0], r[1]],
+                ..d()
+            },
+            ProofInputs {
+                commitments: interval_commits[0..2].to_vec(),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::AverageThreshold,
+            ProofInputs {
+                values: vec![70, 85, 90],
+                threshold: Some(80),
+                ..d()
+            },
+            ProofInputs {
+                threshold: Some(80),
+                count: Some(3),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::MembershipMerkle,
+            ProofInputs {
+                value: Some(3),
+                set: vec![1, 2, 3],
+                ..d()
+            },
+            ProofInputs {
+                roots: vec![merkle_root(vec![1, 2, 3]).unwrap()],
+                ..d()
+            },
+        ),
+        (
+            ProofKind::Solvency,
+            ProofInputs {
+                values: vec![300, 200],
+                other_values: vec![400],
+                blindings: r.clone(),
+                ..d()
+            },
+            ProofInputs {
+                commitments: solvency_commits,
+                count: Some(2),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::Shuffle,
+            ProofInputs {
+                set: vec![4, 1, 9],
+                other_set: vec![9, 4, 1],
+                blindings: vec![r[0]],
+                ..d()
+            },
+            ProofInputs {
+                other_set: vec![9, 4, 1],
+                roots: vec![commit_multiset_pedersen(&[4, 1, 9], &r[0]).unwrap()],
+                ..d()
+            },
+        ),
+        (
+            ProofKind::Vote,
+            ProofInputs {
+                secret: secrets[1].to_vec(),
+                voter_keys: voter_keys.clone(),
+                election_id: Some(7),
+                count: Some(3),
+                index: Some(2),
+                ..d()
+            },
+            ProofInputs {
+                roots: vec![eligibility_root(voter_keys).unwrap()],
+                election_id: Some(7),
+                count: Some(3),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::BalanceUpdate,
+            ProofInputs {
+                value: Some(500),
+                amount: Some(120),
+                blindings: vec![r[0]],
+                ..d()
+            },
+            // The new commitment is appended once the new blinding is known.
+            ProofInputs {
+                commitments: vec![commit_value_pedersen(500, &r[0]).unwrap()],
+                amount: Some(120),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::MedianThreshold,
+            ProofInputs {
+                values: vec![9, 1, 7, 3, 8],
+                threshold: Some(6),
+                ..d()
+            },
+            ProofInputs {
+                threshold: Some(6),
+                ..d()
+            },
+        ),
+        (
+            ProofKind::VarianceBelow,
+            ProofInputs {
+                values: vec![2, 4, 6, 8],
+                max: Some(6),
+                ..d()
+            },
+            ProofInputs {
+                max: Some(6),
+                ..d()
+            },
+        ),
+    ];
+    #[cfg(feature = "halo2")]
+    let cases = [
+        cases,
+        vec![
+            (
+                ProofKind::RangeHalo2,
+                ProofInputs {
+                    value: Some(25),
+                    min: Some(18),
+                    max: Some(65),
+                    ..d()
+                },
+                ProofInputs {
+                    min: Some(18),
+                    max: Some(65),
+                    ..d()
+                },
+            ),
+            (
+                ProofKind::EqualityHalo2,
+                ProofInputs {
+                    value: Some(7),
+                    other_value: Some(7),
+                    ..d()
+                },
+                ProofInputs {
+                    value: Some(7),
+                    other_value: Some(7),
+                    ..d()
+                },
+            ),
+        ],
+    ]
+    .concat();
+    assert_eq!(cases.len(), ProofKind::ALL.len());
+
+    for (kind, prove_inputs, mut verify_inputs) in cases {
+        let (proof, blinding) =
+            prove_with_blinding(kind, prove_inputs).unwrap_or_else(|e| panic!("{:?}: {}", kind, e));
+        assert_eq!(
+            blinding.is_some(),
+            matches!(kind, ProofKind::Vote | ProofKind::BalanceUpdate),
+            "{:?}",
+            kind
+        );
+        if kind == ProofKind::BalanceUpdate {
+            let new_commitment = commit_value_pedersen(380, &blinding.unwrap()).unwrap();
+            verify_inputs.commitments.push(new_commitment);
+        }
+        let parsed = Proof::from_bytes(&proof).unwrap();
+        assert_eq!(parsed.scheme, kind.scheme_id(), "{:?}", kind);
+        assert_eq!(scheme_id(kind.name()), Some(kind.scheme_id()));
+        assert!(verify(proof, kind, verify_inputs), "{:?}", kind);
+    }
+}
+
+#[test]
+fn facade_matches_direct_functions() {
+    use libzkp::proof::{prove, verify, ProofInputs, ProofKind};
+
+    let direct = range_proof::prove_range(25, 18, 65).unwrap();
+    let bounds = ProofInputs {
+        min: Some(18),
+        max: Some(65),
+        ..Default::default()
+    };
+    assert!(verify(direct, ProofKind::Range, bounds.clone()));
+    let via_facade = prove(
+        ProofKind::Range,
+        ProofInputs {
+            value: Some(25),
+            ..bounds.clone()
+        },
+    )
+    .unwrap();
+    assert!(range_proof::verify_range(via_facade.clone(), 18, 65));
+    assert!(!range_proof::verify_range(via_facade.clone(), 30, 65));
+    // A proof checked as the wrong kind fails like the direct function would.
+    assert!(!verify(via_facade, ProofKind::Divisible, bounds));
+
+    let direct = threshold_proof::prove_exact_sum(vec![3, 4], 7).unwrap();
+    let total = |t| ProofInputs {
+        total: Some(t),
+        ..Default::default()
+    };
+    assert!(verify(direct.clone(), ProofKind::ExactSum, total(7)));
+    assert!(!verify(direct, ProofKind::ExactSum, total(8)));
+
+    // Errors match the direct function's, and missing inputs are reported by name.
+    let err = prove(
+        ProofKind::Range,
+        ProofInputs {
+            value: Some(5),
+            min: Some(10),
+            max: Some(20),
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        range_proof::prove_range(5, 10, 20).unwrap_err().to_string()
+    );
+    let err = prove(ProofKind::Threshold, ProofInputs::default()).unwrap_err();
+    assert!(err.to_string().contains("threshold"));
+    assert!(!verify(vec![], ProofKind::Range, ProofInputs::default()));
+}
+
+#[test]
+fn membership_bounded_rejects_oversized_embedded_set() {
+    let set = vec![2, 3, 5, 7, 11];
+    let proof = set_membership::prove_membership(7, set.clone()).unwrap();
+    assert!(set_membership::verify_membership_bounded(
+        proof.clone(),
+        set.clone(),
+        5
+    ));
+    assert!(!set_membership::verify_membership_bounded(
+        proof.clone(),
+        set.clone(),
+        4
+    ));
+
+    // A declared length past the bound is rejected without reading the (absent) elements.
+    let mut parsed = Proof::from_bytes(&proof).unwrap();
+    parsed.proof[0..4].copy_from_slice(&u32::MAX.to_le_bytes());
+    parsed.proof.truncate(4);
+    assert!(!set_membership::verify_membership_bounded(
+        parsed.to_bytes(),
+        set,
+        5
+    ));
+}
+
+#[test]
+fn membership_bloom_prove_verify() {
+    use libzkp::utils::bloom::{build_bloom_filter, commit_bloom_filter, BloomFilterParams};
+
+    let set: Vec<u64> = (0..50).map(|i| 1_000 + i * 7).collect();
+    let params = BloomFilterParams::for_capacity(set.len(), 0.01).expect("params");
+    let bits = build_bloom_filter(&set, &params).expect("filter");
+    let commitment = commit_bloom_filter(&params, &bits).expect("commit");
+
+    let proof = set_membership::prove_membership_bloom(1_014, &params, &bits).expect("prove");
+    assert!(set_membership::verify_membership_bloom(
+        proof.clone(),
+        &commitment,
+        1_014
+    ));
+    assert!(!set_membership::verify_membership_bloom(
+        proof.clone(),
+        &commitment,
+        1_021
+    ));
+    assert!(!set_membership::verify_membership_bloom(
+        proof.clone(),
+        &[0u8; 32],
+        1_014
+    ));
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &Proof::from_bytes(&proof).unwrap()
+    ));
+
+    let mut tampered = proof.clone();
+    let last = tampered.len() - 40;
+    tampered[last] ^= 1;
+    assert!(!set_membership::verify_membership_bloom(
+        tampered,
+        &commitment,
+        1_014
+    ));
+
+    let empty = vec![0u8; bits.len()];
+    assert!(set_membership::prove_membership_bloom(1_014, &params, &empty).is_err());
+    assert!(set_membership::prove_membership_bloom(1_014, &params, &bits[1..]).is_err());
+}
+
+#[test]
+fn membership_bloom_false_positive_rate_is_bounded() {
+    use libzkp::utils::bloom::{build_bloom_filter, commit_bloom_filter, BloomFilterParams};
+
+    let set: Vec<u64> = (0..200).map(|i| i * 2).collect();
+    let params = BloomFilterParams::for_capacity(set.len(), 0.01).expect("params");
+    let bits = build_bloom_filter(&set, &params).expect("filter");
+    let commitment = commit_bloom_filter(&params, &bits).expect("commit");
+
+    // No false negatives: every inserted element proves and verifies.
+    for &element in set.iter().step_by(20) {
+        let proof =
+            set_membership::prove_membership_bloom(element, &params, &bits).expect("member");
+        assert!(set_membership::verify_membership_bloom(
+            proof,
+            &commitment,
+            element
+        ));
+    }
+
+    // Non-members (odd values) are only accepted at about the predicted rate.
+    let trials = 1_000;
+    let accepted = (0..trials)
+        .map(|i| 1_000_001 + 2 * i as u64)
+        .filter(|&x| set_membership::prove_membership_bloom(x, &params, &bits).is_ok())
+        .count();
+    let expected = params.false_positive_rate(set.len()) * trials as f64;
+    assert!(expected < 20.0, "expected {expected}");
+    assert!(
+        (accepted as f64) <= 3.0 * expected + 5.0,
+        "accepted {accepted} of {trials}, expected about {expected}"
+    );
+}
+
+#[test]
+fn composite_verify_all_with_typed_params() {
+    use libzkp::utils::composition::{CompositeProof, VerifyParams};
+
+    let range = range_proof::prove_range(7, 0, 10).expect("range");
+    let threshold = threshold_proof::prove_threshold(vec![3, 4, 5], 10).expect("threshold");
+    let membership = set_membership::prove_membership(2, vec![1, 2, 3]).expect("membership");
+    let composite = CompositeProof::new(
+        [range, threshold, membership]
+            .iter()
+            .map(|p| Proof::from_bytes(p).unwrap())
+            .collect(),
+    )
+    .expect("composite");
+
+    let params = vec![
+        VerifyParams::Range { min: 0, max: 10 },
+        VerifyParams::Threshold { threshold: 10 },
+        VerifyParams::Membership { set: vec![1, 2, 3] },
+    ];
+    assert_eq!(composite.verify_all(params), vec![true, true, true]);
+
+    // Params in the wrong order name the wrong scheme for every proof.
+    let swapped = vec![
+        VerifyParams::Threshold { threshold: 10 },
+        VerifyParams::Range { min: 0, max: 10 },
+        VerifyParams::Membership { set: vec![1, 2, 3] },
+    ];
+    assert_eq!(composite.verify_all(swapped), vec![false, false, true]);
+
+    // Right scheme, wrong statement; missing params fail too.
+    let wrong = vec![
+        VerifyParams::Range { min: 0, max: 5 },
+        VerifyParams::Threshold { threshold: 11 },
+    ];
+    assert_eq!(composite.verify_all(wrong), vec![false, false, false]);
+}
+
+#[test]
+fn performance_metrics_keys_are_namespaced_and_sorted() {
+    use libzkp::advanced::get_performance_metrics;
+    use libzkp::utils::performance::record_operation_metric;
+    use std::time::Duration;
+
+    record_operation_metric("metrics_key_test_op", Duration::from_millis(4));
+    record_operation_metric("metrics_key_test_op", Duration::from_millis(6));
+    // An operation literally named like a timing key must not clobber it.
+    record_operation_metric("metrics_key_test_op_avg_ms", Duration::from_millis(1));
+
+    let metrics = get_performance_metrics().expect("metrics");
+    assert_eq!(metrics["count_metrics_key_test_op"], 2.0);
+    assert_eq!(metrics["time_metrics_key_test_op_avg_ms"], 5.0);
+    assert_eq!(metrics["count_metrics_key_test_op_avg_ms"], 1.0);
+    assert_eq!(metrics["time_metrics_key_test_op_avg_ms_avg_ms"], 1.0);
+    assert!(metrics.contains_key("total_operations"));
+    assert!(metrics.contains_key("cache_hit_rate"));
+    assert!(!metrics.contains_key("metrics_key_test_op_count"));
+
+    let keys: Vec<&String> = metrics.keys().collect();
+    let mut sorted = keys.clone();
+    sorted.sort();
+    assert_eq!(keys, sorted);
+}
+
+#[test]
+fn polynomial_nonneg_prove_verify() {
+    // x^2 - 10x + 21 = (x - 3)(x - 7): non-negative at 8, negative at 5.
+    let proof = range_proof::prove_polynomial_nonneg(8, 1, -10, 21).expect("prove");
+    assert!(range_proof::verify_polynomial_nonneg(
+        proof.clone(),
+        1,
+        -10,
+        21
+    ));
+    assert!(!range_proof::verify_polynomial_nonneg(
+        proof.clone(),
+        1,
+        -10,
+        20
+    ));
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &Proof::from_bytes(&proof).unwrap()
+    ));
+
+    // Rewriting the embedded coefficients breaks the SNARK check.
+    let mut parsed = Proof::from_bytes(&proof).unwrap();
+    parsed.proof[16..24].copy_from_slice(&0i64.to_le_bytes());
+    assert!(!range_proof::verify_polynomial_nonneg(
+        parsed.to_bytes(),
+        1,
+        -10,
+        0
+    ));
+
+    // Zero is a valid result.
+    let root = range_proof::prove_polynomial_nonneg(7, 1, -10, 21).expect("root");
+    assert!(range_proof::verify_polynomial_nonneg(root, 1, -10, 21));
+}
+
+#[test]
+fn polynomial_nonneg_rejects_negative_and_overflow() {
+    assert!(matches!(
+        range_proof::prove_polynomial_nonneg(5, 1, -10, 21),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    // x^2 does not fit in i128 at u64::MAX.
+    let err = range_proof::prove_polynomial_nonneg(u64::MAX, i64::MAX, 0, 0).unwrap_err();
+    assert!(err.to_string().contains("overflow"), "{err}");
+    // Fits in i128 but not in the 64-bit range gadget.
+    let err = range_proof::prove_polynomial_nonneg(1 << 40, 1, 0, 0).unwrap_err();
+    assert!(err.to_string().contains("overflow"), "{err}");
+}
+
+#[test]
+fn verify_and_extract_returns_commitment() {
+    let proof = range_proof::prove_range(7, 0, 10).expect("prove");
+    let commitment = range_proof::verify_and_extract(proof.clone(), 0, 10).expect("valid");
+    assert_eq!(
+        commitment.as_slice(),
+        Proof::from_bytes(&proof).unwrap().commitment.as_slice()
+    );
+
+    assert_eq!(range_proof::verify_and_extract(proof.clone(), 0, 9), None);
+    assert_eq!(range_proof::verify_and_extract(proof.clone(), 10, 0), None);
+    let mut tampered = proof;
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    assert_eq!(range_proof::verify_and_extract(tampered, 0, 10), None);
+}
+
+#[test]
+fn count_above_prove_verify() {
+    // Exactly min_count above the threshold.
+    let proof = threshold_proof::prove_count_above(vec![5, 20, 30, 10], 10, 2).expect("exact");
+    assert!(threshold_proof::verify_count_above(proof.clone(), 10, 2));
+    assert!(!threshold_proof::verify_count_above(proof.clone(), 10, 3));
+    assert!(!threshold_proof::verify_count_above(proof.clone(), 9, 2));
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &Proof::from_bytes(&proof).unwrap()
+    ));
+
+    // More than min_count above.
+    let proof = threshold_proof::prove_count_above(vec![11, 20, 30, 10], 10, 2).expect("more");
+    assert!(threshold_proof::verify_count_above(proof, 10, 2));
+}
+
+#[test]
+fn count_above_rejects_too_few() {
+    assert!(matches!(
+        threshold_proof::prove_count_above(vec![5, 20, 10], 10, 2),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(threshold_proof::prove_count_above(vec![], 10, 0).is_err());
+    let too_many = vec![100; threshold_proof::MAX_COUNT_ABOVE_VALUES + 1];
+    assert!(threshold_proof::prove_count_above(too_many, 10, 1).is_err());
+}
+
+#[test]
+fn strictly_between_committed_bounds() {
+    use libzkp::utils::commitment::{commit_value_pedersen, random_pedersen_blinding};
+
+    let blindings = [
+        random_pedersen_blinding(),
+        random_pedersen_blinding(),
+        random_pedersen_blinding(),
+    ];
+    let low_c = commit_value_pedersen(10, &blindings[1]).unwrap();
+    let high_c = commit_value_pedersen(20, &blindings[2]).unwrap();
+    let value_c = commit_value_pedersen(11, &blindings[0]).unwrap();
+
+    let proof = range_proof::prove_strictly_between(11, 10, 20, &low_c, &high_c, &blindings)
+        .expect("prove");
+    assert!(range_proof::verify_strictly_between(
+        proof.clone(),
+        &value_c,
+        &low_c,
+        &high_c
+    ));
+    assert!(!range_proof::verify_strictly_between(
+        proof.clone(),
+        &value_c,
+        &high_c,
+        &low_c
+    ));
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &Proof::from_bytes(&proof).unwrap()
+    ));
+
+    // Equal to either bound, or outside, is rejected.
+    for value in [10, 20, 5, 25] {
+        assert!(matches!(
+            range_proof::prove_strictly_between(value, 10, 20, &low_c, &high_c, &blindings),
+            Err(ZkpError::InvalidInput(_))
+        ));
+    }
+}
+
+#[test]
+fn membership_sets_are_normalized_for_prove_and_verify() {
+    use libzkp::backend::snark::{SnarkBackend, MAX_SET_SIZE};
+
+    assert_eq!(
+        SnarkBackend::normalize_set(vec![3, 1, 2, 2, 3]).unwrap(),
+        vec![1, 2, 3]
+    );
+    assert!(SnarkBackend::normalize_set(vec![]).is_err());
+    assert!(SnarkBackend::normalize_set((0..MAX_SET_SIZE as u64 + 1).collect()).is_err());
+    // Duplicates do not count towards the size limit.
+    let mut padded: Vec<u64> = (0..MAX_SET_SIZE as u64).collect();
+    padded.push(0);
+    assert_eq!(
+        SnarkBackend::normalize_set(padded).unwrap().len(),
+        MAX_SET_SIZE
+    );
+
+    let proof = set_membership::prove_membership(2, vec![3, 1, 2, 2, 3]).expect("prove");
+    assert!(set_membership::verify_membership(
+        proof.clone(),
+        vec![3, 1, 2, 2, 3]
+    ));
+    assert!(set_membership::verify_membership(
+        proof.clone(),
+        vec![1, 2, 3]
+    ));
+    assert!(set_membership::verify_membership(
+        proof.clone(),
+        vec![2, 3, 3, 1]
+    ));
+    assert!(!set_membership::verify_membership(
+        proof.clone(),
+        vec![1, 2, 4]
+    ));
+    assert!(matches!(
+        set_membership::verify_membership_checked(proof.clone(), vec![1, 1, 2, 3]),
+        Ok(true)
+    ));
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &Proof::from_bytes(&proof).unwrap()
+    ));
+
+    let deduped = set_membership::prove_membership(2, vec![1, 2, 3]).expect("prove");
+    assert!(set_membership::verify_membership(deduped, vec![3, 2, 2, 1]));
+
+    // Proofs from before normalization bound the set in the prover's order, duplicates included.
+    let legacy_set = vec![3, 1, 2, 2, 3];
+    let commitment = libzkp::utils::commitment::commit_value_snark(2);
+    let snark = SnarkBackend::prove_membership_zk_below(
+        2,
+        legacy_set.clone(),
+        legacy_set.len(),
+        commitment.clone().try_into().unwrap(),
+    );
+    let mut payload = (legacy_set.len() as u32).to_le_bytes().to_vec();
+    for v in &legacy_set {
+        payload.extend_from_slice(&v.to_le_bytes());
+    }
+    payload.extend_from_slice(&snark);
+    let legacy = Proof::new(4, payload, commitment).to_bytes();
+    assert!(set_membership::verify_membership(
+        legacy.clone(),
+        vec![1, 2, 3]
+    ));
+    assert!(matches!(
+        set_membership::verify_membership_checked(legacy.clone(), vec![3, 2, 1]),
+        Ok(true)
+    ));
+    assert!(!set_membership::verify_membership(legacy, vec![1, 2, 4]));
+}
+
+#[test]
+fn difference_of_committed_values() {
+    use libzkp::utils::commitment::{commit_value_pedersen, random_pedersen_blinding};
+
+    let blindings = [random_pedersen_blinding(), random_pedersen_blinding()];
+    let a = commit_value_pedersen(1_250, &blindings[0]).unwrap();
+    let b = commit_value_pedersen(1_000, &blindings[1]).unwrap();
+
+    let proof = equality_proof::prove_difference(&a, &b, 250, &blindings).unwrap();
+    assert!(equality_proof::verify_difference(
+        proof.clone(),
+        &a,
+        &b,
+        250
+    ));
+    assert_eq!(
+        libzkp::advanced::verify_proofs_parallel(vec![(proof.clone(), "difference".to_string())])
+            .unwrap(),
+        vec![true]
+    );
+    assert!(!equality_proof::verify_difference(
+        proof.clone(),
+        &a,
+        &b,
+        251
+    ));
+    assert!(!equality_proof::verify_difference(proof, &b, &a, 250));
+
+    assert!(matches!(
+        equality_proof::prove_difference(&a, &b, 249, &blindings),
+        Err(ZkpError::InvalidInput(msg)) if msg.contains("a - b == diff")
+    ));
+    // b > a: 1000 - 1250 underflows, so no u64 difference can be proven.
+    let swapped = [blindings[1], blindings[0]];
+    assert!(equality_proof::prove_difference(&b, &a, 250, &swapped).is_err());
+    assert!(equality_proof::prove_difference(&b, &a, u64::MAX - 249, &swapped).is_err());
+}
+
+#[cfg(feature = "halo2")]
+#[test]
+fn halo2_range_and_equality_round_trip() {
+    use libzkp::backend::halo2::Halo2Backend;
+    use libzkp::backend::ZkpBackend;
+
+    let r = Halo2Backend::random_blinding();
+    let commitment = Halo2Backend::commit_value(42, &r).unwrap();
+    assert_ne!(
+        commitment,
+        Halo2Backend::commit_value(42, &Halo2Backend::random_blinding()).unwrap()
+    );
+    let proof = Halo2Backend::prove_range(42, &r, 10, 100);
+    assert!(!proof.is_empty());
+    assert!(Halo2Backend::verify_range(&proof, &commitment, 10, 100));
+    assert!(!Halo2Backend::verify_range(&proof, &commitment, 10, 99));
+    assert!(!Halo2Backend::verify_range(
+        &proof,
+        &Halo2Backend::commit_value(43, &r).unwrap(),
+        10,
+        100
+    ));
+    assert!(Halo2Backend::prove_range(101, &r, 10, 100).is_empty());
+    assert!(Halo2Backend::commit_value(42, &[0xff; 32]).is_none());
+    assert!(Halo2Backend::prove_range(42, &[0xff; 32], 10, 100).is_empty());
+
+    let proof = Halo2Backend::prove_equality(7, 7, &r);
+    assert!(Halo2Backend::verify_equality(
+        &proof,
+        &Halo2Backend::commit_value(7, &r).unwrap()
+    ));
+    assert!(!Halo2Backend::verify_equality(
+        &proof,
+        &Halo2Backend::commit_value(8, &r).unwrap()
+    ));
+    assert!(!Halo2Backend::verify_equality(
+        &proof,
+        &Halo2Backend::commit_value(7, &Halo2Backend::random_blinding()).unwrap()
+    ));
+    assert!(Halo2Backend::prove_equality(7, 8, &r).is_empty());
+
+    let mut data = 5u64.to_le_bytes().to_vec();
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.extend_from_slice(&u64::MAX.to_le_bytes());
+    let proof = Halo2Backend::prove(&data);
+    assert!(Halo2Backend::verify(&proof, &data[8..]));
+    assert!(!Halo2Backend::verify(&proof, &[]));
+}
+
+#[test]
+fn range_many_aggregates_all_values() {
+    let values = vec![18, 25, 40, 65];
+    let proof = range_proof::prove_range_many(values.clone(), 18, 65).unwrap();
+    assert!(range_proof::verify_range_many(proof.clone(), 18, 65));
+    assert_eq!(
+        libzkp::advanced::verify_proofs_parallel(vec![(proof.clone(), "range_many".to_string())])
+            .unwrap(),
+        vec![true]
+    );
+    assert!(!range_proof::verify_range_many(proof.clone(), 18, 64));
+    assert!(!range_proof::verify_range_many(proof.clone(), 0, 65));
+    assert!(!range_proof::verify_range(proof.clone(), 18, 65));
+
+    // One aggregate is far smaller than a range proof per value.
+    let singles: usize = values
+        .iter()
+        .map(|&v| range_proof::prove_range(v, 18, 65).unwrap().len())
+        .sum();
+    assert!(proof.len() < singles);
+
+    let mut tampered = proof;
+    let last = tampered.len() - 40;
+    tampered[last] ^= 1;
+    assert!(!range_proof::verify_range_many(tampered, 18, 65));
+
+    assert!(range_proof::prove_range_many(vec![18, 66], 18, 65).is_err());
+    assert!(range_proof::prove_range_many(vec![], 18, 65).is_err());
+    assert!(matches!(
+        range_proof::prove_range_many(vec![20; range_proof::MAX_RANGE_MANY_VALUES + 1], 18, 65),
+        Err(ZkpError::InvalidInput(msg)) if msg.contains("at most")
+    ));
+}
+
+#[test]
+fn range_batch_verification() {
+    let proofs: Vec<(Vec<u8>, u64, u64)> = [(25, 18, 65), (0, 0, 10), (200, 100, 255)]
+        .iter()
+        .map(|&(value, min, max)| (range_proof::prove_range(value, min, max).unwrap(), min, max))
+        .chain([(
+            range_proof::prove_range_with_bits(7, 0, 200, 8).unwrap(),
+            0,
+            200,
+        )])
+        .collect();
+    assert!(range_proof::verify_range_batch(proofs.clone()));
+    assert!(range_proof::verify_range_batch(vec![]));
+
+    let mut wrong_bounds = proofs.clone();
+    wrong_bounds[1].2 = 11;
+    assert!(!range_proof::verify_range_batch(wrong_bounds));
+
+    let mut tampered = proofs.clone();
+    let at = tampered[2].0.len() / 2;
+    tampered[2].0[at] ^= 1;
+    assert!(!range_proof::verify_range(tampered[2].0.clone(), 100, 255));
+    assert!(!range_proof::verify_range_batch(tampered));
+
+    let mut other_scheme = proofs;
+    other_scheme.push((
+        range_proof::prove_range_many(vec![30], 18, 65).unwrap(),
+        18,
+        65,
+    ));
+    assert!(!range_proof::verify_range_batch(other_scheme));
+}
+
+#[test]
+fn per_call_backend_selection() {
+    use libzkp::backend::BackendKind;
+
+    let bp = "bulletproofs".parse().unwrap();
+    let proof = range_proof::prove_range_with_backend(7, 0, 10, bp).unwrap();
+    assert!(range_proof::verify_range(proof.clone(), 0, 10));
+    assert!(range_proof::verify_range_with_backend(
+        proof.clone(),
+        0,
+        10,
+        bp
+    ));
+    assert!(!range_proof::verify_range_with_backend(
+        proof,
+        0,
+        10,
+        BackendKind::Snark
+    ));
+
+    let proof = equality_proof::prove_equality_with_backend(3, 3, BackendKind::Snark).unwrap();
+    assert!(equality_proof::verify_equality_with_backend(
+        proof,
+        3,
+        3,
+        BackendKind::Snark
+    ));
+
+    assert!(matches!(
+        range_proof::prove_range_with_backend(7, 0, 10, BackendKind::Snark),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        equality_proof::prove_equality_with_backend(3, 3, bp),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        "plonky2".parse::<BackendKind>(),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    #[cfg(not(feature = "halo2"))]
+    assert!(matches!(
+        range_proof::prove_range_with_backend(7, 0, 10, BackendKind::Halo2),
+        Err(ZkpError::ConfigError(_))
+    ));
+    #[cfg(feature = "halo2")]
+    {
+        let proof = range_proof::prove_range_with_backend(42, 10, 100, BackendKind::Halo2).unwrap();
+        assert!(range_proof::verify_range_with_backend(
+            proof.clone(),
+            10,
+            100,
+            BackendKind::Halo2
+        ));
+        assert!(!range_proof::verify_range_with_backend(
+            proof.clone(),
+            10,
+            99,
+            BackendKind::Halo2
+        ));
+        assert!(!range_proof::verify_range(proof.clone(), 10, 100));
+        assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+            &Proof::from_bytes(&proof).unwrap()
+        ));
+
+        let proof = equality_proof::prove_equality_with_backend(7, 7, BackendKind::Halo2).unwrap();
+        assert!(equality_proof::verify_equality_with_backend(
+            proof.clone(),
+            7,
+            7,
+            BackendKind::Halo2
+        ));
+        assert!(!equality_proof::verify_equality_with_backend(
+            proof.clone(),
+            7,
+            8,
+            BackendKind::Halo2
+        ));
+        assert!(!equality_proof::verify_equality(proof.clone(), 7, 7));
+        // Fresh blinding per proof: the same value gives unlinkable commitments.
+        let again = equality_proof::prove_equality_with_backend(7, 7, BackendKind::Halo2).unwrap();
+        let (first, second) = (
+            Proof::from_bytes(&proof).unwrap(),
+            Proof::from_bytes(&again).unwrap(),
+        );
+        assert_ne!(first.commitment, second.commitment);
+        let mut swapped = first;
+        swapped.commitment = second.commitment;
+        assert!(!equality_proof::verify_equality_with_backend(
+            swapped.to_bytes(),
+            7,
+            7,
+            BackendKind::Halo2
+        ));
+    }
+}
+
+#[test]
+fn msm_acceleration_reports_build_and_cpu() {
+    use libzkp::advanced::{msm_acceleration_active, msm_acceleration_info};
+
+    let info = msm_acceleration_info();
+    assert_eq!(info.len(), 2);
+    assert_eq!(msm_acceleration_active(), info["field_asm"]);
+    assert_eq!(
+        info["field_asm"],
+        cfg!(all(
+            feature = "msm-accel",
+            target_arch = "x86_64",
+            target_feature = "bmi2",
+            target_feature = "adx"
+        ))
+    );
+}
+
+#[test]
+fn non_membership_proves_value_outside_deny_list() {
+    use libzkp::proof::set_membership::{
+        non_membership_root, prove_non_membership, verify_non_membership,
+        verify_non_membership_root,
+    };
+
+    let deny_list = vec![1001, 1002, 4242, 9000];
+    let proof = prove_non_membership(1234, deny_list.clone()).unwrap();
+    assert!(verify_non_membership(proof.clone(), deny_list.clone()));
+    assert!(verify_non_membership(
+        proof.clone(),
+        vec![9000, 4242, 1002, 1001, 4242]
+    ));
+    assert!(!verify_non_membership(
+        proof.clone(),
+        vec![1001, 1002, 4242]
+    ));
+    assert!(!verify_non_membership(
+        proof.clone(),
+        vec![1001, 1002, 4242, 1234]
+    ));
+
+    let parsed = Proof::from_bytes(&proof).unwrap();
+    assert_eq!(parsed.scheme, 30);
+    assert_eq!(parsed.commitment, commit_value_snark(1234));
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &parsed
+    ));
+
+    assert!(matches!(
+        prove_non_membership(4242, deny_list),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(prove_non_membership(1, vec![]).is_err());
+
+    // Beyond the old 64-element limit, with values below, between and above the elements.
+    let large: Vec<u64> = (1..=1000).map(|i| i * 10).collect();
+    let root = non_membership_root(large.clone()).unwrap();
+    for value in [0, 4321, u64::MAX] {
+        let proof = prove_non_membership(value, large.clone()).unwrap();
+        assert!(verify_non_membership_root(proof.clone(), &root));
+        assert!(verify_non_membership(proof.clone(), large.clone()));
+        assert!(!verify_non_membership_root(
+            proof,
+            &non_membership_root(vec![value]).unwrap()
+        ));
+    }
+    assert!(prove_non_membership(5000, large).is_err());
+}
+
+#[test]
+fn inequality_proves_values_differ_without_revealing_them() {
+    use libzkp::proof::inequality_proof::{prove_inequality, verify_inequality};
+    use libzkp::utils::commitment::{commit_value_pedersen, random_pedersen_blinding};
+
+    let blindings = [random_pedersen_blinding(), random_pedersen_blinding()];
+    let commit_a = commit_value_pedersen(17, &blindings[0]).unwrap();
+    let commit_b = commit_value_pedersen(42, &blindings[1]).unwrap();
+    let bytes = prove_inequality(17, 42, &blindings).unwrap();
+    let proof = Proof::from_bytes(&bytes).unwrap();
+    assert_eq!(proof.scheme, 31);
+    assert!(verify_inequality(bytes.clone(), &commit_a, &commit_b));
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &proof
+    ));
+
+    // Bound to both published commitments, in order.
+    assert!(!verify_inequality(bytes.clone(), &commit_b, &commit_a));
+    let other = commit_value_pedersen(17, &random_pedersen_blinding()).unwrap();
+    assert!(!verify_inequality(bytes.clone(), &other, &commit_b));
+
+    let mut tampered = bytes.clone();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    assert!(!verify_inequality(tampered, &commit_a, &commit_b));
+
+    assert!(matches!(
+        prove_inequality(7, 7, &blindings),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        prove_inequality(7, 8, &[blindings[0], [0xff; 32]]),
+        Err(ZkpError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn greater_than_compares_hidden_values() {
+    use libzkp::proof::inequality_proof::{prove_greater_than, verify_greater_than};
+    use libzkp::utils::commitment::{commit_value_pedersen, random_pedersen_blinding};
+
+    let blindings = [random_pedersen_blinding(), random_pedersen_blinding()];
+    let commit_a = commit_value_pedersen(1_000, &blindings[0]).unwrap();
+    let commit_b = commit_value_pedersen(999, &blindings[1]).unwrap();
+    let bytes = prove_greater_than(1_000, 999, &blindings).unwrap();
+    let proof = Proof::from_bytes(&bytes).unwrap();
+    assert_eq!(proof.scheme, 32);
+    assert!(verify_greater_than(bytes.clone(), &commit_a, &commit_b));
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &proof
+    ));
+    assert!(!verify_greater_than(bytes.clone(), &commit_b, &commit_a));
+    // Bound to the published commitments, not to fresh ones.
+    let other = commit_value_pedersen(1_000, &random_pedersen_blinding()).unwrap();
+    assert!(!verify_greater_than(bytes.clone(), &other, &commit_b));
+
+    // Neither value appears in the payload.
+    assert!(!proof.proof.windows(8).any(|w| w == 1_000u64.to_le_bytes()));
+
+    let wide = prove_greater_than(u64::MAX, 0, &blindings).unwrap();
+    assert!(verify_greater_than(
+        wide,
+        &commit_value_pedersen(u64::MAX, &blindings[0]).unwrap(),
+        &commit_value_pedersen(0, &blindings[1]).unwrap()
+    ));
+
+    for (a, b) in [(5, 5), (4, 5)] {
+        assert!(matches!(
+            prove_greater_than(a, b, &blindings),
+            Err(ZkpError::InvalidInput(_))
+        ));
+    }
+    assert!(matches!(
+        prove_greater_than(2, 1, &[[0xff; 32], blindings[1]]),
+        Err(ZkpError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn average_threshold_scales_by_count() {
+    let proof = threshold_proof::prove_average_threshold(vec![70, 85, 90], 80).unwrap();
+    assert!(threshold_proof::verify_average_threshold(
+        proof.clone(),
+        80,
+        3
+    ));
+    assert!(!threshold_proof::verify_average_threshold(
+        proof.clone(),
+        81,
+        3
+    ));
+    // A shorter claimed count would inflate the mean.
+    assert!(!threshold_proof::verify_average_threshold(
+        proof.clone(),
+        80,
+        2
+    ));
+    assert!(!threshold_proof::verify_threshold(proof.clone(), 80));
+    let parsed = Proof::from_bytes(&proof).unwrap();
+    assert_eq!(parsed.scheme, 33);
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &parsed
+    ));
+
+    // Mean of 245 / 3 is below 82; a sum check alone would pass.
+    assert!(matches!(
+        threshold_proof::prove_average_threshold(vec![70, 85, 90], 82),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(threshold_proof::prove_average_threshold(vec![], 0).is_err());
+    let big =
+        threshold_proof::prove_average_threshold(vec![u64::MAX / 2; 2], u64::MAX / 2).unwrap();
+    assert!(threshold_proof::verify_average_threshold(
+        big,
+        u64::MAX / 2,
+        2
+    ));
+}
+
+#[test]
+fn signed_range_covers_negative_values() {
+    let proof = range_proof::prove_range_signed(-12, -40, 5).unwrap();
+    assert!(range_proof::verify_range_signed(proof.clone(), -40, 5));
+    assert!(!range_proof::verify_range_signed(proof.clone(), -40, 6));
+    assert!(!range_proof::verify_range(proof.clone(), 0, 5));
+    // The proof is a plain range proof over the offset-encoded bounds.
+    let encode = |v: i64| (v as u64) ^ (1 << 63);
+    assert!(range_proof::verify_range(proof, encode(-40), encode(5)));
+
+    let extremes = range_proof::prove_range_signed(i64::MIN, i64::MIN, i64::MAX).unwrap();
+    assert!(range_proof::verify_range_signed(
+        extremes,
+        i64::MIN,
+        i64::MAX
+    ));
+
+    for (value, min, max) in [(-41, -40, 5), (6, -40, 5), (0, 1, -1)] {
+        assert!(matches!(
+            range_proof::prove_range_signed(value, min, max),
+            Err(ZkpError::InvalidInput(_))
+        ));
+    }
+}
+
+#[test]
+fn time_range_uses_calendar_dates() {
+    use libzkp::proof::time_range::{self, CivilDate};
+
+    let date = |s: &str| s.parse::<CivilDate>().unwrap();
+    assert_eq!(date("1970-01-01").start_unix_seconds(), 0);
+    assert_eq!(date("2000-03-01").start_unix_seconds(), 951_868_800);
+    assert_eq!(date("1969-12-31").end_unix_seconds(), -1);
+    assert_eq!(
+        CivilDate::from_unix_seconds(951_868_799),
+        date("2000-02-29")
+    );
+    assert_eq!(CivilDate::from_unix_seconds(-1), date("1969-12-31"));
+    assert!("2023-02-29".parse::<CivilDate>().is_err());
+    assert!("2024-13-01".parse::<CivilDate>().is_err());
+    assert_eq!(
+        date("2024-02-29").years_before(1).unwrap(),
+        date("2023-02-28")
+    );
+
+    // Born 2008-10-16 23:00 UTC: 18 on 2026-10-16, not on 2026-10-15.
+    let birth = date("2008-10-16").start_unix_seconds() + 23 * 3600;
+    let as_of = date("2026-10-16");
+    let proof = time_range::prove_age_at_least(birth, 18, as_of).unwrap();
+    assert!(time_range::verify_age_at_least(proof.clone(), 18, as_of));
+    assert!(!time_range::verify_age_at_least(proof, 19, as_of));
+    assert!(matches!(
+        time_range::prove_age_at_least(birth, 18, date("2026-10-15")),
+        Err(ZkpError::InvalidInput(_))
+    ));
+
+    // Pre-epoch birth dates work too.
+    let old =
+        time_range::prove_age_at_least(date("1950-06-01").start_unix_seconds(), 65, as_of).unwrap();
+    assert!(time_range::verify_age_at_least(old, 65, as_of));
+
+    let (first, last) = (date("2026-01-01"), date("2026-12-31"));
+    let ts = last.end_unix_seconds();
+    let window = time_range::prove_timestamp_between_dates(ts, first, last).unwrap();
+    assert!(time_range::verify_timestamp_between_dates(
+        window, first, last
+    ));
+    assert!(time_range::prove_timestamp_between_dates(ts + 1, first, last).is_err());
+}
+
+#[test]
+fn membership_above_max_set_size_uses_merkle_root() {
+    use libzkp::backend::snark::MAX_SET_SIZE;
+
+    let set: Vec<u64> = (0..300).map(|i| i * 7 + 3).collect();
+    let proof = set_membership::prove_membership(7 * 211 + 3, set.clone()).unwrap();
+    assert_eq!(Proof::from_bytes(&proof).unwrap().scheme, 34);
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &Proof::from_bytes(&proof).unwrap()
+    ));
+
+    let mut shuffled = set.clone();
+    shuffled.reverse();
+    shuffled.push(3);
+    assert!(set_membership::verify_membership(proof.clone(), shuffled));
+    let root = set_membership::merkle_root(set.clone()).unwrap();
+    assert!(set_membership::verify_membership_root(proof.clone(), &root));
+
+    let mut other = set.clone();
+    other[0] = 1;
+    assert!(!set_membership::verify_membership(
+        proof.clone(),
+        other.clone()
+    ));
+    let other_root = set_membership::merkle_root(other).unwrap();
+    assert!(!set_membership::verify_membership_root(proof, &other_root));
+
+    assert!(set_membership::prove_membership(5, set).is_err());
+
+    // Duplicates do not push a small set onto the Merkle path.
+    let repeated: Vec<u64> = (0..MAX_SET_SIZE as u64 * 2).map(|i| i % 10).collect();
+    let proof = set_membership::prove_membership(4, repeated.clone()).unwrap();
+    assert_eq!(Proof::from_bytes(&proof).unwrap().scheme, 4);
+    assert!(set_membership::verify_membership(proof, repeated));
+}
+
+#[test]
+fn credential_attributes_prove_against_issuer_commitment() {
+    use libzkp::proof::credentials::{
+        verify_attribute_membership, verify_attribute_range, Credential,
+    };
+    use std::collections::BTreeMap;
+
+    let attributes = BTreeMap::from([("age".to_string(), 34), ("country".to_string(), 392)]);
+    let credential = Credential::issue(attributes).unwrap();
+    let commitment = credential.commitment().unwrap();
+    let stored = Credential::from_bytes(&credential.to_bytes()).unwrap();
+    assert_eq!(stored.commitment().unwrap(), commitment);
+
+    let range = stored.prove_range("age", 18, 120).unwrap();
+    assert!(verify_attribute_range(
+        range.clone(),
+        &commitment,
+        "age",
+        18,
+        120
+    ));
+    assert!(!verify_attribute_range(
+        range.clone(),
+        &commitment,
+        "age",
+        40,
+        120
+    ));
+    assert!(!verify_attribute_range(
+        range.clone(),
+        &commitment,
+        "country",
+        18,
+        120
+    ));
+    assert!(!verify_attribute_range(range, &[0u8; 32], "age", 18, 120));
+
+    let countries = vec![250, 276, 392, 840];
+    let membership = stored
+        .prove_membership("country", countries.clone())
+        .unwrap();
+    assert!(verify_attribute_membership(
+        membership.clone(),
+        &commitment,
+        "country",
+        countries
+    ));
+    assert!(verify_attribute_membership(
+        membership.clone(),
+        &commitment,
+        "country",
+        vec![840, 392, 250, 276, 392]
+    ));
+    assert!(!verify_attribute_membership(
+        membership.clone(),
+        &commitment,
+        "country",
+        vec![250, 276, 840, 392, 1]
+    ));
+    assert!(!verify_attribute_membership(
+        membership.clone(),
+        &commitment,
+        "age",
+        vec![250, 276, 392, 840]
+    ));
+    // The presentation carries the Pedersen commitment only, never the value's MiMC commitment.
+    let mimc = commit_value_snark(392);
+    assert!(!membership.windows(32).any(|w| w == mimc.as_slice()));
+    assert!(matches!(
+        stored.prove_membership("country", vec![250, 276]),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(stored.prove_membership("country", vec![]).is_err());
+
+    // A self-made credential with the same values does not match the issuer's commitment.
+    let forged = Credential::issue(BTreeMap::from([
+        ("age".to_string(), 34),
+        ("country".to_string(), 392),
+    ]))
+    .unwrap();
+    let range = forged.prove_range("age", 18, 120).unwrap();
+    assert!(!verify_attribute_range(range, &commitment, "age", 18, 120));
+
+    assert!(matches!(
+        stored.prove_range("age", 40, 120),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        stored.prove_range("name", 0, 1),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(Credential::issue(BTreeMap::new()).is_err());
+    assert!(Credential::from_bytes(&[1, 0, 0]).is_err());
+}
+
+#[test]
+fn solvency_proves_assets_cover_liabilities() {
+    use libzkp::proof::solvency::{prove_solvency, verify_solvency};
+    use libzkp::utils::commitment::{commit_value_pedersen, random_pedersen_blinding};
+
+    let entries = |values: &[u64]| {
+        let blindings: Vec<[u8; 32]> = values.iter().map(|_| random_pedersen_blinding()).collect();
+        let commitments: Vec<Vec<u8>> = values
+            .iter()
+            .zip(&blindings)
+            .map(|(v, r)| commit_value_pedersen(*v, r).unwrap())
+            .collect();
+        (blindings, commitments)
+    };
+    let (assets, liabilities) = (vec![500, 300, 200], vec![400, 600]);
+    let (asset_blindings, asset_commits) = entries(&assets);
+    let (liability_blindings, liability_commits) = entries(&liabilities);
+
+    let proof = prove_solvency(
+        assets.clone(),
+        asset_blindings.clone(),
+        liabilities.clone(),
+        liability_blindings.clone(),
+    )
+    .unwrap();
+    assert!(verify_solvency(
+        proof.clone(),
+        &asset_commits,
+        &liability_commits
+    ));
+    let parsed = Proof::from_bytes(&proof).unwrap();
+    assert_eq!(parsed.scheme, 35);
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &parsed
+    ));
+
+    // The proof is bound to the published entries.
+    assert!(!verify_solvency(
+        proof.clone(),
+        &asset_commits[..2],
+        &liability_commits
+    ));
+    let (_, other_commits) = entries(&liabilities);
+    assert!(!verify_solvency(
+        proof.clone(),
+        &asset_commits,
+        &other_commits
+    ));
+    assert!(!verify_solvency(proof.clone(), &[], &liability_commits));
+
+    // Swapping the two lists claims the reverse inequality.
+    assert!(!verify_solvency(
+        proof.clone(),
+        &liability_commits,
+        &asset_commits
+    ));
+    let mut moved = asset_commits.clone();
+    moved.push(liability_commits[0].clone());
+    assert!(!verify_solvency(
+        proof.clone(),
+        &moved,
+        &liability_commits[1..]
+    ));
+
+    // Entries beyond one aggregated range proof are covered by further chunks.
+    let many_assets = vec![20; 70];
+    let (many_blindings, many_commits) = entries(&many_assets);
+    let many = prove_solvency(
+        many_assets,
+        many_blindings,
+        liabilities.clone(),
+        liability_blindings.clone(),
+    )
+    .unwrap();
+    assert!(verify_solvency(
+        many.clone(),
+        &many_commits,
+        &liability_commits
+    ));
+    // Drop the last chunk: skip the entry commitments, the totals proof and the first chunk.
+    let mut truncated = Proof::from_bytes(&many).unwrap();
+    let mut end = 8 + 32 * 72;
+    for _ in 0..2 {
+        let len = u32::from_le_bytes(truncated.proof[end..end + 4].try_into().unwrap());
+        end += 4 + len as usize;
+    }
+    assert!(end < truncated.proof.len());
+    truncated.proof.truncate(end);
+    assert!(!verify_solvency(
+        truncated.to_bytes(),
+        &many_commits,
+        &liability_commits
+    ));
+
+    assert!(matches!(
+        prove_solvency(
+            vec![500],
+            vec![random_pedersen_blinding()],
+            vec![400, 101],
+            vec![random_pedersen_blinding(); 2]
+        ),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        prove_solvency(assets, asset_blindings, liabilities, vec![[0; 32]]),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(prove_solvency(vec![], vec![], vec![1], liability_blindings[..1].to_vec()).is_err());
+    assert!(prove_solvency(
+        vec![u64::MAX, 1],
+        vec![random_pedersen_blinding(); 2],
+        vec![1],
+        vec![random_pedersen_blinding()]
+    )
+    .is_err());
+}
+
+#[test]
+fn shuffle_proves_published_list_is_a_permutation() {
+    use libzkp::utils::commitment::{commit_multiset_pedersen, random_pedersen_blinding};
+
+    let original = vec![17, 4, 99, 4, 23];
+    let shuffled = vec![4, 23, 17, 4, 99];
+    // The root of the hidden list is published before the shuffle.
+    let blinding = random_pedersen_blinding();
+    let root = commit_multiset_pedersen(&original, &blinding).unwrap();
+    let proof =
+        set_membership::prove_shuffle(original.clone(), shuffled.clone(), &blinding).unwrap();
+    let parsed = Proof::from_bytes(&proof).unwrap();
+    assert_eq!(parsed.scheme, 36);
+    assert!(set_membership::verify_shuffle(
+        proof.clone(),
+        &root,
+        &shuffled
+    ));
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &parsed
+    ));
+
+    // The published order is part of the statement.
+    assert!(!set_membership::verify_shuffle(
+        proof.clone(),
+        &root,
+        &[4, 4, 17, 23, 99]
+    ));
+    assert!(!set_membership::verify_shuffle(
+        proof.clone(),
+        &[7u8; 32],
+        &shuffled
+    ));
+    // A root published under another blinding is not this proof's.
+    let other = commit_multiset_pedersen(&original, &random_pedersen_blinding()).unwrap();
+    assert!(!set_membership::verify_shuffle(
+        proof.clone(),
+        &other,
+        &shuffled
+    ));
+
+    // Editing the embedded list breaks the multiset relation.
+    let mut tampered = parsed.clone();
+    tampered.proof[4..12].copy_from_slice(&5u64.to_le_bytes());
+    assert!(!set_membership::verify_shuffle(
+        tampered.to_bytes(),
+        &root,
+        &[5, 23, 17, 4, 99]
+    ));
+
+    assert!(matches!(
+        set_membership::prove_shuffle(original.clone(), vec![4, 23, 17, 17, 99], &blinding),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        set_membership::prove_shuffle(original, shuffled, &[0xff; 32]),
+        Err(ZkpError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn vote_proves_eligibility_and_one_choice() {
+    use libzkp::proof::voting::{
+        eligibility_root, open_vote, prove_vote, verify_vote, vote_nullifier, voter_key,
+    };
+
+    let secrets: Vec<[u8; 32]> = (1..=5u8).map(|i| [i; 32]).collect();
+    let keys: Vec<Vec<u8>> = secrets.iter().map(|s| voter_key(s).unwrap()).collect();
+    let root = eligibility_root(keys.clone()).unwrap();
+    let mut reordered = keys.clone();
+    reordered.reverse();
+    assert_eq!(eligibility_root(reordered).unwrap(), root);
+
+    let (vote, blinding) = prove_vote(&secrets[2], keys.clone(), 7, 4, 3).unwrap();
+    assert_eq!(Proof::from_bytes(&vote).unwrap().scheme, 37);
+    assert!(verify_vote(vote.clone(), &root, 7, 4));
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &Proof::from_bytes(&vote).unwrap()
+    ));
+    assert!(!verify_vote(vote.clone(), &root, 8, 4));
+    assert!(!verify_vote(vote.clone(), &root, 7, 5));
+    assert!(open_vote(&vote, 3, &blinding).unwrap());
+    assert!(!open_vote(&vote, 2, &blinding).unwrap());
+
+    // One nullifier per voter and election.
+    let (again, _) = prove_vote(&secrets[2], keys.clone(), 7, 4, 1).unwrap();
+    let (elsewhere, _) = prove_vote(&secrets[2], keys.clone(), 9, 4, 1).unwrap();
+    let (other_voter, _) = prove_vote(&secrets[0], keys.clone(), 7, 4, 1).unwrap();
+    let nullifier = vote_nullifier(&vote).unwrap();
+    assert_eq!(vote_nullifier(&again).unwrap(), nullifier);
+    assert_ne!(vote_nullifier(&elsewhere).unwrap(), nullifier);
+    assert_ne!(vote_nullifier(&other_voter).unwrap(), nullifier);
+
+    let outsider = [9u8; 32];
+    assert!(matches!(
+        prove_vote(&outsider, keys.clone(), 7, 4, 0),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(prove_vote(&secrets[0], keys.clone(), 7, 4, 4).is_err());
+    assert!(prove_vote(&secrets[0], keys, 7, 17, 0).is_err());
+}
+
+#[test]
+fn bid_validity_seals_bid_between_minimum_and_budget() {
+    use libzkp::proof::auction::{open_bid, prove_bid_validity, verify_bid_validity};
+
+    let (proof, blinding) = prove_bid_validity(1_250, 2_000, 1_100).unwrap();
+    assert!(verify_bid_validity(proof.clone(), 2_000, 1_100));
+    assert!(!verify_bid_validity(proof.clone(), 2_000, 1_200));
+    assert!(!verify_bid_validity(proof.clone(), 1_500, 1_100));
+    assert!(open_bid(&proof, 1_250, &blinding).unwrap());
+    assert!(!open_bid(&proof, 1_300, &blinding).unwrap());
+
+    assert!(prove_bid_validity(1_050, 2_000, 1_100).is_err());
+    assert!(prove_bid_validity(2_001, 2_000, 1_100).is_err());
+    assert!(prove_bid_validity(1_500, 1_000, 1_100).is_err());
+}
+
+#[test]
+fn balance_update_links_commitments_and_stays_non_negative() {
+    use libzkp::proof::improvement_proof::{prove_balance_update, verify_balance_update};
+    use libzkp::utils::commitment::{commit_value_pedersen, random_pedersen_blinding};
+
+    let blinding = random_pedersen_blinding();
+    let old_commitment = commit_value_pedersen(500, &blinding).unwrap();
+    let (proof, new_blinding) = prove_balance_update(500, &blinding, 120).unwrap();
+    let new_commitment = commit_value_pedersen(380, &new_blinding).unwrap();
+    assert!(verify_balance_update(
+        proof.clone(),
+        &old_commitment,
+        &new_commitment,
+        120
+    ));
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &Proof::from_bytes(&proof).unwrap()
+    ));
+    assert!(!verify_balance_update(
+        proof.clone(),
+        &old_commitment,
+        &new_commitment,
+        121
+    ));
+    let other_old = commit_value_pedersen(501, &blinding).unwrap();
+    assert!(!verify_balance_update(
+        proof,
+        &other_old,
+        &new_commitment,
+        120
+    ));
+
+    // The new blinding chains into the next update, which may empty the balance.
+    let (next, _) = prove_balance_update(380, &new_blinding, 380).unwrap();
+    assert_eq!(Proof::from_bytes(&next).unwrap().scheme, 38);
+    assert!(matches!(
+        prove_balance_update(380, &new_blinding, 381),
+        Err(ZkpError::InvalidInput(_))
+    ));
+}
+
+#[test]
+fn median_threshold_uses_sorted_commitments() {
+    // Sorted: [1, 3, 7, 8, 9]; the median is 7.
+    let proof = threshold_proof::prove_median_threshold(vec![9, 1, 7, 3, 8], 6).unwrap();
+    assert!(threshold_proof::verify_median_threshold(proof.clone(), 6));
+    assert!(!threshold_proof::verify_median_threshold(proof.clone(), 5));
+    assert!(!threshold_proof::verify_median_threshold(proof.clone(), 7));
+    let parsed = Proof::from_bytes(&proof).unwrap();
+    assert_eq!(parsed.scheme, 39);
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &parsed
+    ));
+
+    assert!(threshold_proof::prove_median_threshold(vec![9, 1, 7, 3, 8], 7).is_err());
+    assert!(threshold_proof::prove_median_threshold(vec![], 0).is_err());
+    // Even count: the lower middle value (4) must exceed the threshold.
+    assert!(threshold_proof::prove_median_threshold(vec![10, 4, 2, 20], 4).is_err());
+    let proof = threshold_proof::prove_median_threshold(vec![10, 4, 2, 20], 3).unwrap();
+    assert!(threshold_proof::verify_median_threshold(proof, 3));
+}
+
+#[test]
+fn variance_below_bounds_hidden_spread() {
+    // Mean 5, population variance (9 + 1 + 1 + 9) / 4 = 5.
+    let values = vec![2, 4, 6, 8];
+    let proof = threshold_proof::prove_variance_below(values.clone(), 6).unwrap();
+    assert!(threshold_proof::verify_variance_below(proof.clone(), 6));
+    assert!(!threshold_proof::verify_variance_below(proof.clone(), 7));
+    let parsed = Proof::from_bytes(&proof).unwrap();
+    assert_eq!(parsed.scheme, 40);
+    assert!(libzkp::utils::proof_helpers::verify_proof_cryptographic(
+        &parsed
+    ));
+
+    // The bound is strict.
+    assert!(threshold_proof::prove_variance_below(values.clone(), 5).is_err());
+    assert!(threshold_proof::prove_variance_below(vec![], 1).is_err());
+    assert!(threshold_proof::prove_variance_below(vec![7], 0).is_err());
+
+    // Standard deviation sqrt(5) is below 3 but not below 2.
+    let proof = threshold_proof::prove_std_dev_below(values.clone(), 3).unwrap();
+    assert!(threshold_proof::verify_std_dev_below(proof.clone(), 3));
+    assert!(threshold_proof::verify_variance_below(proof, 9));
+    assert!(threshold_proof::prove_std_dev_below(values, 2).is_err());
+    assert!(threshold_proof::prove_std_dev_below(vec![1], u64::MAX).is_err());
+}
+
 #[cfg(feature = "batch-store")]
 mod batch_store_tests {
     use std::sync::Mutex;
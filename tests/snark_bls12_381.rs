@@ -0,0 +1,44 @@
+//! SNARK proofs on BLS12-381. The curve is process-wide and fixed by the first SNARK use, so
+//! this runs in its own test binary with the curve selected before anything else.
+
+use libzkp::backend::snark::{set_proof_compression, set_snark_curve, snark_curve, SnarkCurve};
+use libzkp::proof::equality_proof::{prove_equality, verify_equality};
+use libzkp::proof::set_membership::{prove_membership, verify_membership};
+use libzkp::proof::Proof;
+use libzkp::utils::commitment::commit_value_snark;
+use libzkp::utils::error_handling::ZkpError;
+
+#[test]
+fn bls12_381_proofs_roundtrip() {
+    assert_eq!(snark_curve(), SnarkCurve::Bn254);
+    set_snark_curve("bls12-381".parse().unwrap()).unwrap();
+    assert_eq!(snark_curve(), SnarkCurve::Bls12_381);
+
+    let proof = prove_equality(42, 42).unwrap();
+    assert!(verify_equality(proof.clone(), 42, 42));
+    assert!(!verify_equality(proof.clone(), 43, 43));
+    let parsed = Proof::from_bytes(&proof).unwrap();
+    assert_eq!(parsed.proof.len(), 384);
+    assert_eq!(parsed.commitment, commit_value_snark(42));
+
+    set_proof_compression(true);
+    let compressed = prove_equality(7, 7).unwrap();
+    set_proof_compression(false);
+    assert_eq!(Proof::from_bytes(&compressed).unwrap().proof.len(), 192);
+    assert!(verify_equality(compressed, 7, 7));
+
+    let set = vec![3, 9, 27];
+    let membership = prove_membership(9, set.clone()).unwrap();
+    assert!(verify_membership(membership.clone(), set));
+    assert!(!verify_membership(membership, vec![3, 9, 28]));
+
+    assert!(set_snark_curve(SnarkCurve::Bls12_381).is_ok());
+    assert!(matches!(
+        set_snark_curve(SnarkCurve::Bn254),
+        Err(ZkpError::ConfigError(_))
+    ));
+    assert!(matches!(
+        "secp256k1".parse::<SnarkCurve>(),
+        Err(ZkpError::ConfigError(_))
+    ));
+}
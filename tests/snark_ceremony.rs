@@ -0,0 +1,91 @@
+//! Groth16 setup ceremony end to end. Finalizing writes keys into the process-wide key
+//! directory, which must happen before the first equality proof, so this runs in its own
+//! test binary.
+
+use libzkp::backend::snark::ceremony;
+use libzkp::backend::snark::powers_of_tau;
+use libzkp::backend::snark::set_snark_key_dir;
+use libzkp::backend::snark::SnarkCircuit;
+use libzkp::proof::equality_proof::{prove_equality, verify_equality};
+use libzkp::utils::error_handling::ZkpError;
+
+#[test]
+fn ceremony_contributions_verify_and_finalize() {
+    let key_dir = std::env::temp_dir().join(format!("libzkp_ceremony_keys_{}", std::process::id()));
+    set_snark_key_dir(&key_dir.to_string_lossy()).unwrap();
+
+    assert_eq!(
+        "equality_w16".parse::<SnarkCircuit>().unwrap(),
+        SnarkCircuit::EqualityBits(16)
+    );
+    assert!(matches!(
+        "equality_w0".parse::<SnarkCircuit>(),
+        Err(ZkpError::InvalidInput(_))
+    ));
+
+    // Phase 1: the starting accumulator (tau = 1) cannot seed a circuit.
+    let tau0 = powers_of_tau::initialize(9).unwrap();
+    assert!(powers_of_tau::verify(&tau0).unwrap().is_empty());
+    assert!(ceremony::initialize(SnarkCircuit::Equality, &tau0).is_err());
+    let (tau1, tau1_hash) = powers_of_tau::contribute(&tau0).unwrap();
+    let (phase1, phase1_hash) = powers_of_tau::contribute(&tau1).unwrap();
+    assert_eq!(
+        powers_of_tau::verify(&phase1).unwrap(),
+        vec![tau1_hash, phase1_hash]
+    );
+    // Flip a byte of the last contribution's last Schnorr response.
+    let mut tampered = phase1.clone();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    assert!(powers_of_tau::verify(&tampered).is_err());
+    // A point of the accumulator replaced by another valid point.
+    let mut tampered = phase1.clone();
+    let point = tampered[10 + 64 * 3..10 + 64 * 4].to_vec();
+    tampered[10 + 64 * 2..10 + 64 * 3].copy_from_slice(&point);
+    assert!(powers_of_tau::verify(&tampered).is_err());
+    // Too small for the circuit.
+    let (small, _) = powers_of_tau::contribute(&powers_of_tau::initialize(1).unwrap()).unwrap();
+    assert!(matches!(
+        ceremony::initialize(SnarkCircuit::Equality, &small),
+        Err(ZkpError::InvalidInput(_))
+    ));
+
+    let initial = ceremony::initialize(SnarkCircuit::Equality, &phase1).unwrap();
+    assert!(ceremony::verify(&initial, &phase1).unwrap().is_empty());
+    assert!(matches!(
+        ceremony::finalize(&initial, &phase1),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    // The initial key is tied to the accumulator it was derived from.
+    assert!(ceremony::verify(&initial, &tau1).is_err());
+
+    let (first, first_hash) = ceremony::contribute(&initial).unwrap();
+    let (second, second_hash) = ceremony::contribute(&first).unwrap();
+    assert_eq!(
+        ceremony::verify(&second, &phase1).unwrap(),
+        vec![first_hash, second_hash]
+    );
+    assert_eq!(
+        ceremony::circuit_of(&second).unwrap(),
+        SnarkCircuit::Equality
+    );
+
+    // Flip a byte of the last contribution's Schnorr response.
+    let mut tampered = second.clone();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    assert!(ceremony::verify(&tampered, &phase1).is_err());
+    assert!(ceremony::verify(&second[..second.len() - 40], &phase1).is_err());
+
+    assert_eq!(ceremony::finalize(&second, &phase1).unwrap(), 1);
+    assert!(key_dir.join("equality_mimc_vk.bin").exists());
+    assert!(key_dir.join("equality_mimc_pk.bin").exists());
+
+    let proof = prove_equality(42, 42).unwrap();
+    assert!(verify_equality(proof, 42, 42));
+    assert!(matches!(
+        ceremony::finalize(&second, &phase1),
+        Err(ZkpError::ConfigError(_))
+    ));
+    let _ = std::fs::remove_dir_all(&key_dir);
+}
@@ -0,0 +1,37 @@
+//! `set_snark_key_dir` racing the first SNARK setup. Kept in its own test binary so no other
+//! test has initialized the setup or set the key directory first.
+
+use libzkp::advanced::set_snark_key_dir;
+use libzkp::proof::equality_proof::prove_equality;
+use libzkp::utils::error_handling::ZkpError;
+use std::fs;
+use std::sync::{Arc, Barrier};
+
+#[test]
+fn key_dir_change_races_setup_consistently() {
+    let dir = std::env::temp_dir().join(format!("libzkp_keydir_race_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let barrier = Arc::new(Barrier::new(2));
+
+    let b = Arc::clone(&barrier);
+    let prover = std::thread::spawn(move || {
+        b.wait();
+        prove_equality(7, 7)
+    });
+    let b = Arc::clone(&barrier);
+    let dir_str = dir.to_string_lossy().into_owned();
+    let setter = std::thread::spawn(move || {
+        b.wait();
+        set_snark_key_dir(dir_str)
+    });
+
+    let proof = prover.join().unwrap();
+    let set_result = setter.join().unwrap();
+    assert!(!proof.unwrap().is_empty());
+    match set_result {
+        // The override won the race, so the setup must have used (and persisted to) it.
+        Ok(_) => assert!(dir.join("equality_mimc_vk.bin").exists()),
+        Err(e) => assert!(matches!(e, ZkpError::ConfigError(_))),
+    }
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,61 @@
+//! Shipping SNARK keys between processes without a key directory. Imported keys must be in
+//! place before a process first uses them, so the importing side runs as a child run of this
+//! binary (`import_child`).
+
+use libzkp::backend::snark::{export_keys, import_keys, SnarkCircuit};
+use libzkp::proof::equality_proof::{prove_equality, verify_equality};
+use libzkp::utils::error_handling::ZkpError;
+use std::process::Command;
+
+const DIR_VAR: &str = "LIBZKP_IMPORT_TEST_DIR";
+
+#[test]
+fn import_child() {
+    let Ok(dir) = std::env::var(DIR_VAR) else {
+        return;
+    };
+    let dir = std::path::PathBuf::from(dir);
+    let pk = std::fs::read(dir.join("pk.bin")).unwrap();
+    let vk = std::fs::read(dir.join("vk.bin")).unwrap();
+    import_keys(SnarkCircuit::Equality, &pk, &vk).unwrap();
+    let proof = prove_equality(42, 42).unwrap();
+    assert!(verify_equality(proof.clone(), 42, 42));
+    std::fs::write(dir.join("proof.bin"), proof).unwrap();
+}
+
+#[test]
+fn exported_keys_prove_in_another_process() {
+    if std::env::var(DIR_VAR).is_ok() {
+        return;
+    }
+    let (pk, vk) = export_keys(SnarkCircuit::Equality).unwrap();
+    assert!(matches!(
+        import_keys(SnarkCircuit::Equality, &pk, &vk),
+        Err(ZkpError::ConfigError(_))
+    ));
+    assert!(matches!(
+        import_keys(SnarkCircuit::EqualityBits(8), &pk, &vk),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        import_keys(SnarkCircuit::Membership, &pk[..pk.len() - 1], &vk),
+        Err(ZkpError::InvalidInput(_))
+    ));
+
+    let dir = std::env::temp_dir().join(format!("libzkp_key_import_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("pk.bin"), &pk).unwrap();
+    std::fs::write(dir.join("vk.bin"), &vk).unwrap();
+    let status = Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", "import_child"])
+        .env(DIR_VAR, &dir)
+        .env_remove("LIBZKP_SNARK_KEY_DIR")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    // The child proved with our keys, so our verifier accepts its proof.
+    let proof = std::fs::read(dir.join("proof.bin")).unwrap();
+    assert!(verify_equality(proof, 42, 42));
+    let _ = std::fs::remove_dir_all(&dir);
+}
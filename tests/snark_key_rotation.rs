@@ -0,0 +1,93 @@
+//! SNARK key rotation. Keys are loaded once per process and rotation must happen before that,
+//! so the test drives proving and verifying through child runs of this binary
+//! (`rotation_child`) while the parent only manages key versions.
+
+use libzkp::backend::snark::{
+    key_versions, retire_key_version, rotate_keys, set_snark_key_dir, SnarkCircuit,
+};
+use libzkp::proof::equality_proof::{prove_equality, verify_equality};
+use libzkp::utils::error_handling::ZkpError;
+use std::path::Path;
+use std::process::Command;
+
+const STEP_VAR: &str = "LIBZKP_ROTATION_STEP";
+
+/// Run `rotation_child` with `step` against the key directory; panics if the child fails.
+fn run_child(key_dir: &Path, step: &str) {
+    let status = Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", "rotation_child", "--test-threads=1"])
+        .env("LIBZKP_SNARK_KEY_DIR", key_dir)
+        .env(STEP_VAR, step)
+        .status()
+        .unwrap();
+    assert!(status.success(), "child step {} failed", step);
+}
+
+#[test]
+fn rotation_child() {
+    let Ok(step) = std::env::var(STEP_VAR) else {
+        return;
+    };
+    let key_dir = std::path::PathBuf::from(std::env::var("LIBZKP_SNARK_KEY_DIR").unwrap());
+    let proof_path = key_dir.join("old_proof.bin");
+    match step.as_str() {
+        "prove" => std::fs::write(&proof_path, prove_equality(42, 42).unwrap()).unwrap(),
+        "accepts_old" => {
+            assert!(verify_equality(std::fs::read(&proof_path).unwrap(), 42, 42));
+            assert!(verify_equality(prove_equality(7, 7).unwrap(), 7, 7));
+        }
+        "rejects_old" => assert!(!verify_equality(
+            std::fs::read(&proof_path).unwrap(),
+            42,
+            42
+        )),
+        _ => panic!("unknown step {}", step),
+    }
+}
+
+#[test]
+fn rotated_keys_keep_old_proofs_until_retired() {
+    if std::env::var(STEP_VAR).is_ok() {
+        return;
+    }
+    let key_dir = std::env::temp_dir().join(format!("libzkp_rotation_keys_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&key_dir);
+    set_snark_key_dir(&key_dir.to_string_lossy()).unwrap();
+    let circuit = SnarkCircuit::Equality;
+    assert!(key_versions(circuit).unwrap().is_empty());
+
+    run_child(&key_dir, "prove");
+    assert_eq!(key_versions(circuit).unwrap(), vec![1]);
+    assert!(key_dir.join("equality_mimc_vk.bin").exists());
+
+    assert_eq!(rotate_keys(circuit).unwrap(), 2);
+    assert!(key_dir.join("equality_mimc_v2_vk.bin").exists());
+    assert_eq!(key_versions(circuit).unwrap(), vec![2, 1]);
+    run_child(&key_dir, "accepts_old");
+
+    assert!(matches!(
+        retire_key_version(circuit, 2),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    assert!(matches!(
+        retire_key_version(circuit, 9),
+        Err(ZkpError::InvalidInput(_))
+    ));
+    retire_key_version(circuit, 1).unwrap();
+    assert_eq!(key_versions(circuit).unwrap(), vec![2]);
+    assert!(key_dir.join("equality_mimc_vk.bin.retired").exists());
+    run_child(&key_dir, "rejects_old");
+
+    // Retired numbers are not reused.
+    assert_eq!(rotate_keys(circuit).unwrap(), 3);
+    assert_eq!(key_versions(circuit).unwrap(), vec![3, 2]);
+    assert!(key_versions(SnarkCircuit::Membership).unwrap().is_empty());
+
+    // Once this process has loaded the keys, their versions are fixed for it.
+    assert!(verify_equality(prove_equality(5, 5).unwrap(), 5, 5));
+    assert!(matches!(
+        rotate_keys(circuit),
+        Err(ZkpError::ConfigError(_))
+    ));
+    let _ = std::fs::remove_dir_all(&key_dir);
+}
@@ -0,0 +1,78 @@
+//! Best-effort check that a prove call wipes the secrets it copies to the heap. Kept in its
+//! own test binary because it installs a global allocator that scans every freed block for
+//! the caller's blindings and for the blinding the prover derives from them. Stack copies
+//! are out of reach, and so are blocks freed inside the `bulletproofs` prover, whose party
+//! vectors keep copies once their elements are moved out; those are told apart by the
+//! backtrace of the free.
+
+use curve25519_dalek::scalar::Scalar;
+use libzkp::proof::inequality_proof::{prove_greater_than, verify_greater_than};
+use libzkp::utils::commitment::{commit_value_pedersen, random_pedersen_blinding};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::backtrace::Backtrace;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+struct ScanningAlloc;
+
+static ARMED: AtomicBool = AtomicBool::new(false);
+static HITS: AtomicUsize = AtomicUsize::new(0);
+static CANARIES: OnceLock<[[u8; 32]; 3]> = OnceLock::new();
+
+thread_local! {
+    // Set while a hit is being attributed, so the backtrace's own frees are not scanned.
+    static ATTRIBUTING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether the block being freed belongs to the `bulletproofs` prover rather than libzkp.
+fn freed_by_dependency_prover() -> bool {
+    ATTRIBUTING.set(true);
+    let trace = Backtrace::force_capture().to_string();
+    ATTRIBUTING.set(false);
+    trace.contains("bulletproofs::range_proof::RangeProof::prove_multiple")
+}
+
+unsafe impl GlobalAlloc for ScanningAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if ARMED.load(Ordering::SeqCst) && !ATTRIBUTING.get() {
+            if let Some(canaries) = CANARIES.get() {
+                let block = std::slice::from_raw_parts(ptr, layout.size());
+                if block
+                    .windows(32)
+                    .any(|w| canaries.iter().any(|c| w == c.as_slice()))
+                    && !freed_by_dependency_prover()
+                {
+                    HITS.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+        }
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: ScanningAlloc = ScanningAlloc;
+
+#[test]
+fn greater_than_leaves_no_blinding_in_freed_memory() {
+    let blindings = [random_pedersen_blinding(), random_pedersen_blinding()];
+    let derived =
+        Scalar::from_bytes_mod_order(blindings[0]) - Scalar::from_bytes_mod_order(blindings[1]);
+    CANARIES
+        .set([blindings[0], blindings[1], derived.to_bytes()])
+        .unwrap();
+
+    ARMED.store(true, Ordering::SeqCst);
+    let proof = prove_greater_than(900, 100, &blindings);
+    ARMED.store(false, Ordering::SeqCst);
+
+    let commit_a = commit_value_pedersen(900, &blindings[0]).unwrap();
+    let commit_b = commit_value_pedersen(100, &blindings[1]).unwrap();
+    assert!(verify_greater_than(proof.unwrap(), &commit_a, &commit_b));
+    assert_eq!(HITS.load(Ordering::SeqCst), 0);
+}